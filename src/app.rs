@@ -1,10 +1,10 @@
 use std::path::{Path, PathBuf};
 
 use crate::canvas::{self, Canvas};
-use crate::cell::{blocks, Rgb, next_primary, next_shade};
-use crate::export::{self, ColorFormat};
-use crate::history::{CellMutation, History};
-use crate::project::Project;
+use crate::cell::{blocks, Cell, Rgb, next_primary, next_shade};
+use crate::export::{self, ColorFormat, ExportTransform};
+use crate::history::{Action, CellMutation, History};
+use crate::project::{Project, ProjectSettings};
 use crate::symmetry::{self, SymmetryMode};
 use crate::palette::{self, HueGroup, PaletteItem, PaletteSection};
 use crate::theme::{Theme, THEMES};
@@ -22,6 +22,7 @@ pub enum AppMode {
     Recovery,
     ColorSliders,
     PaletteDialog,
+    PaletteDeleteConfirm,
     PaletteNameInput,
     PaletteRename,
     PaletteExport,
@@ -34,6 +35,8 @@ pub enum AppMode {
     ImportOptions,
     CommandPalette,
     GotoInput,
+    HistoryTimeline,
+    ShiftCanvas,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -61,7 +64,17 @@ pub struct App {
     pub canvas: Canvas,
     pub active_tool: ToolKind,
     pub color: Rgb,
+    /// Background color painted behind drawn blocks (None = transparent)
+    pub bg_color: Option<Rgb>,
+    /// Drawing paints the active color into the background channel and
+    /// keeps each cell's glyph and fg (false = normal foreground drawing)
+    pub paint_bg: bool,
     pub symmetry: SymmetryMode,
+    /// Custom H/V mirror axes in half-cell units (see `symmetry::center_axes`).
+    /// None follows the canvas's geometric center.
+    pub symmetry_center: Option<(usize, usize)>,
+    /// Radial symmetry: copies per stroke around the center (None = off)
+    pub radial_order: Option<usize>,
     pub history: History,
     pub cursor: Option<(usize, usize)>,
     pub zoom: u8,
@@ -73,10 +86,23 @@ pub struct App {
     pub project_name: Option<String>,
     pub project_path: Option<String>,
     pub filled_rect: bool,
+    /// Fill also spreads through cell corners (8-connected)
+    pub fill_diagonal: bool,
+    /// Dim every cell that doesn't use the active color (render-only)
+    pub isolate_color: bool,
+    /// Draw the whole-canvas overview in the corner of the canvas area
+    pub show_minimap: bool,
+    /// View-only clockwise rotation of the editor render (0, 90, 180, 270).
+    /// The canvas itself is never rotated; drawing is disabled while non-zero.
+    pub view_rotation: u16,
+    /// Quit once the pending Save As completes (from the quit prompt)
+    pub quit_after_save: bool,
+    /// Color depth used to draw the canvas, detected from the terminal at startup
+    pub color_format: crate::export::ColorFormat,
     // File dialog state
     pub file_dialog_files: Vec<String>,
     pub file_dialog_selected: usize,
-    // Export dialog state: 0=PlainText, 1=ANSI
+    // Export dialog state: 0=PlainText, 1=ANSI, 2=PNG, 3=HTML
     pub export_format: usize,
     // Export dialog state: 0=Clipboard, 1=File
     pub export_dest: usize,
@@ -84,26 +110,47 @@ pub struct App {
     pub export_cursor: usize,
     // Export color format: 0=24bit, 1=256, 2=16 (only used when ANSI)
     pub export_color_format: usize,
+    /// Export only the selection instead of the whole canvas
+    pub export_selection: bool,
+    /// Color adjustments applied, in order, to exported copies of the canvas
+    pub export_transforms: Vec<ExportTransform>,
+    /// Region chosen with the Select tool
+    pub selection: Option<Selection>,
+    /// Drawing only changes cells inside this rectangle (set from a selection)
+    pub clip: Option<Selection>,
+    /// Cells copied from a selection, indexed [y][x]
+    pub clipboard_cells: Option<Vec<Vec<Cell>>>,
     // Shared text input for SaveAs and ExportFile modes
     pub text_input: String,
     // Auto-save tick counter (increments each tick, resets on save)
     pub auto_save_ticks: u16,
     // Path of autosave file found on startup
     pub recovery_path: Option<String>,
-    // Recent colors (auto-tracked, last 8 unique)
+    // Recent colors (auto-tracked, last `recent_capacity` unique)
     pub recent_colors: Vec<Rgb>,
+    pub recent_capacity: usize,
+    // Toolbar order, favorite tools first
+    pub tool_order: Vec<ToolKind>,
     // Palette browser state
     pub hue_groups: Vec<HueGroup>,
     pub palette_scroll: usize,
     pub palette_cursor: usize,
-    // HSL slider state
+    // Color slider state (HSL, or R/G/B when `slider_rgb` is set)
     pub slider_h: u16,
     pub slider_s: u8,
     pub slider_l: u8,
-    pub slider_active: u8, // 0=H, 1=S, 2=L
+    pub slider_rgb: bool,
+    pub slider_r: u8,
+    pub slider_g: u8,
+    pub slider_b: u8,
+    pub slider_active: u8, // 0=H/R, 1=S/G, 2=L/B
+    /// Digits typed for the active slider (applied live, cleared on any other key)
+    pub slider_input: String,
     // Custom palette state
     pub custom_palette: Option<palette::CustomPalette>,
     pub palette_dialog_files: Vec<String>,
+    /// Palette awaiting delete confirmation: (file name, color count if readable)
+    pub palette_delete_pending: Option<(String, Option<usize>)>,
     pub palette_dialog_selected: usize,
     // Active block character for drawing
     pub active_block: char,
@@ -121,6 +168,12 @@ pub struct App {
     // Keyboard canvas cursor
     pub canvas_cursor: (usize, usize),
     pub canvas_cursor_active: bool,
+    /// Cells moved per WASD press (1, 5 or 10)
+    pub cursor_step: usize,
+    /// Keep the keyboard cursor centered in the viewport as it moves
+    pub follow_cursor: bool,
+    /// Last cell painted by the current mouse drag (None when not dragging)
+    pub drag_last: Option<(usize, usize)>,
     // Viewport offset and last-known dimensions for large canvases
     pub viewport_x: usize,
     pub viewport_y: usize,
@@ -138,23 +191,114 @@ pub struct App {
     pub import_normalize: bool,
     pub import_preserve_hue: bool,
     pub import_posterize: usize, // 0=off, 1=8, 2=12, 3=16, 4=24
-    pub import_options_cursor: usize, // 0=fit, 1=color, 2=charset, 3=normalize, 4=hue-preserve, 5=posterize
+    pub import_dither: usize,    // 0=off, 1=Floyd-Steinberg, 2=ordered
+    pub import_options_cursor: usize, // 0=fit, 1=color, 2=charset, 3=normalize, 4=hue-preserve, 5=posterize, 6=dither
     // Command palette state
     pub palette_query: String,
     pub palette_filtered: Vec<usize>,
     pub palette_selected_cmd: usize,
     // Reference layer
     pub reference_layer: Option<ReferenceLayer>,
+    /// Another project shown dimmed behind the canvas for tracing (render-only)
+    pub onion_skin: Option<OnionSkin>,
+    /// File dialog picks an onion skin instead of opening a project
+    pub file_dialog_for_onion: bool,
     /// Show startup guidance on blank canvas (set false on first draw or file load)
     pub show_startup_hint: bool,
     /// Text input buffer for "Go to" coordinate input
     pub goto_input: String,
+    /// Selected row in the undo history timeline (0 = before the first action)
+    pub timeline_cursor: usize,
+    /// Canvas as it was when shift mode opened; shifts are previewed from it
+    pub shift_origin: Option<Vec<Vec<Cell>>>,
+    /// Offset previewed in shift mode
+    pub shift_offset: (isize, isize),
+    /// Shift mode wraps cells around the edges instead of dropping them
+    pub shift_wrap: bool,
+    /// Normal-mode key bindings (drives both input dispatch and the help overlay)
+    pub keymap: crate::keymap::Keymap,
+    /// First visible line of the help overlay
+    pub help_scroll: u16,
+    /// Furthest the help overlay can scroll at the current screen size
+    pub help_max_scroll: u16,
     /// Paste detection buffer — accumulates rapid character input that looks like a file path
     pub paste_buffer: String,
     /// Deadline for paste buffer flush (None = not accumulating)
     pub paste_deadline: Option<std::time::Instant>,
+    /// Screen dump file from the hidden `--debug-dump` flag (F12 writes it)
+    pub debug_dump_path: Option<PathBuf>,
+    /// F12 was pressed; the next rendered frame is written to `debug_dump_path`
+    pub debug_dump_pending: bool,
 }
 
+/// Rectangular canvas region chosen with the Select tool.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Selection {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Selection {
+    /// Selection spanning two opposite corners (inclusive).
+    pub fn from_corners(a: (usize, usize), b: (usize, usize)) -> Self {
+        let (x, y) = (a.0.min(b.0), a.1.min(b.1));
+        Selection { x, y, width: a.0.max(b.0) - x + 1, height: a.1.max(b.1) - y + 1 }
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+
+    /// Selection moved out (`delta` > 0) or in (`delta` < 0) by `delta` cells
+    /// on every side. Growing clamps to the `width`x`height` canvas; shrinking
+    /// stops at one cell per axis, keeping the selection centered.
+    pub fn resized(&self, delta: isize, width: usize, height: usize) -> Selection {
+        let n = delta.unsigned_abs();
+        if delta >= 0 {
+            let x = self.x.saturating_sub(n);
+            let y = self.y.saturating_sub(n);
+            let right = (self.x + self.width - 1 + n).min(width.saturating_sub(1));
+            let bottom = (self.y + self.height - 1 + n).min(height.saturating_sub(1));
+            Selection::from_corners((x, y), (right.max(x), bottom.max(y)))
+        } else {
+            let new_w = self.width.saturating_sub(2 * n).max(1);
+            let new_h = self.height.saturating_sub(2 * n).max(1);
+            Selection {
+                x: self.x + (self.width - new_w) / 2,
+                y: self.y + (self.height - new_h) / 2,
+                width: new_w,
+                height: new_h,
+            }
+        }
+    }
+
+    /// Whether (x, y) is on the selection's outline.
+    pub fn on_border(&self, x: usize, y: usize) -> bool {
+        self.contains(x, y)
+            && (x == self.x || x == self.x + self.width - 1 || y == self.y || y == self.y + self.height - 1)
+    }
+}
+
+/// Mirror axes as columns/rows, e.g. "col 10.5, row 6" (axes in half cells).
+pub fn axes_label(axes: (usize, usize)) -> String {
+    let half = |v: usize| if v % 2 == 0 { format!("{}", v / 2) } else { format!("{}.5", v / 2) };
+    format!("col {}, row {}", half(axes.0), half(axes.1))
+}
+
+/// Recent colors kept by default, and the range `--recent-colors` accepts.
+pub const DEFAULT_RECENT_CAPACITY: usize = 8;
+pub const MIN_RECENT_CAPACITY: usize = 4;
+pub const MAX_RECENT_CAPACITY: usize = 32;
+
+/// Environment variable read when `--favorite-tools` isn't given.
+pub const FAVORITE_TOOLS_ENV: &str = "KAKUKUMA_FAVORITE_TOOLS";
+
+/// Pixels per cell for PNG export from the editor (the CLI's default `--cell-size`).
+const PNG_CELL_W: u32 = 8;
+const PNG_CELL_H: u32 = 16;
+
 // --- Reference Layer ---
 
 pub struct ReferenceLayer {
@@ -170,6 +314,31 @@ pub struct ReferenceLayer {
     pub visible: bool,
 }
 
+/// A second project's canvas drawn faintly where the working canvas is empty.
+/// Never touches the working canvas, history or saved file. Aligned at the
+/// top-left corner; anything outside the working canvas is clipped.
+pub struct OnionSkin {
+    pub canvas: Canvas,
+    /// Source file, for status messages
+    pub path: String,
+    pub visible: bool,
+}
+
+impl OnionSkin {
+    /// Non-empty onion cell at (x, y), if any.
+    pub fn cell_at(&self, x: usize, y: usize) -> Option<crate::cell::Cell> {
+        if !self.visible {
+            return None;
+        }
+        self.canvas.get(x, y).filter(|c| !c.is_empty())
+    }
+}
+
+/// Screen rows per canvas cell at a zoom level (4x draws each cell twice as tall).
+pub fn zoom_rows(zoom: u8) -> usize {
+    if zoom == 4 { 2 } else { 1 }
+}
+
 /// Dim a color by the given brightness level for reference layer rendering.
 pub fn dim_color(color: &Rgb, brightness: u8) -> Rgb {
     if brightness == 2 {
@@ -237,6 +406,15 @@ pub static COMMANDS: &[PaletteCommand] = &[
     PaletteCommand { name: "Eraser", category: "Tools", shortcut: "E", action: |app| { app.active_tool = ToolKind::Eraser; app.cancel_tool(); } },
     PaletteCommand { name: "Line", category: "Tools", shortcut: "L", action: |app| { app.active_tool = ToolKind::Line; app.cancel_tool(); } },
     PaletteCommand { name: "Rectangle", category: "Tools", shortcut: "R", action: |app| { app.active_tool = ToolKind::Rectangle; app.cancel_tool(); } },
+    PaletteCommand { name: "Ellipse", category: "Tools", shortcut: "Shift+O", action: |app| { app.active_tool = ToolKind::Ellipse; app.cancel_tool(); } },
+    PaletteCommand { name: "Select", category: "Tools", shortcut: "M", action: |app| { app.active_tool = ToolKind::Select; app.cancel_tool(); } },
+    PaletteCommand { name: "Select None", category: "Tools", shortcut: "", action: |app| { app.clear_selection(); } },
+    PaletteCommand { name: "Grow Selection", category: "Tools", shortcut: "+", action: |app| { app.resize_selection(1); } },
+    PaletteCommand { name: "Shrink Selection", category: "Tools", shortcut: "-", action: |app| { app.resize_selection(-1); } },
+    PaletteCommand { name: "Copy Selection", category: "Tools", shortcut: "Ctrl+C", action: |app| { app.copy_selection(); } },
+    PaletteCommand { name: "Paste", category: "Tools", shortcut: "Ctrl+V", action: |app| { app.paste_clipboard(); } },
+    PaletteCommand { name: "Clip to Selection", category: "Tools", shortcut: "Shift+M", action: |app| { app.toggle_clip(); } },
+    PaletteCommand { name: "Clear Clip", category: "Tools", shortcut: "", action: |app| { app.clear_clip(); } },
     PaletteCommand { name: "Fill", category: "Tools", shortcut: "F", action: |app| { app.active_tool = ToolKind::Fill; app.cancel_tool(); } },
     PaletteCommand { name: "Eyedropper", category: "Tools", shortcut: "K", action: |app| { app.active_tool = ToolKind::Eyedropper; app.cancel_tool(); } },
     // Canvas
@@ -254,6 +432,13 @@ pub static COMMANDS: &[PaletteCommand] = &[
         app.new_canvas_input = app.canvas.width.to_string();
         app.mode = AppMode::ResizeCanvas;
     }},
+    PaletteCommand { name: "Flip Horizontal", category: "Canvas", shortcut: "|", action: |app| { app.flip_canvas(true); } },
+    PaletteCommand { name: "Flip Vertical", category: "Canvas", shortcut: "_", action: |app| { app.flip_canvas(false); } },
+    PaletteCommand { name: "Rotate Canvas Clockwise", category: "Canvas", shortcut: ")", action: |app| { app.rotate_canvas(true); } },
+    PaletteCommand { name: "Rotate Canvas Counter-Clockwise", category: "Canvas", shortcut: "", action: |app| { app.rotate_canvas(false); } },
+    PaletteCommand { name: "Shift Drawing", category: "Canvas", shortcut: "[", action: |app| { app.begin_shift(); } },
+    PaletteCommand { name: "Invert Colors", category: "Canvas", shortcut: "~", action: |app| { app.invert_colors(); } },
+    PaletteCommand { name: "Crop to Content", category: "Canvas", shortcut: "", action: |app| { app.crop_to_content(); } },
     PaletteCommand { name: "Clear Canvas", category: "Canvas", shortcut: "", action: |app| {
         let w = app.canvas.width;
         let h = app.canvas.height;
@@ -265,6 +450,8 @@ pub static COMMANDS: &[PaletteCommand] = &[
         app.dirty = true;
         app.set_status("Canvas cleared");
     }},
+    PaletteCommand { name: "Remove Background", category: "Canvas", shortcut: "", action: |app| { app.remove_background(); } },
+    PaletteCommand { name: "Replace Color Under Cursor", category: "Color", shortcut: "", action: |app| { app.replace_color_under_cursor(); } },
     PaletteCommand { name: "Go to Coordinate", category: "Canvas", shortcut: "", action: |app| {
         app.goto_input = String::new();
         app.mode = AppMode::GotoInput;
@@ -291,36 +478,46 @@ pub static COMMANDS: &[PaletteCommand] = &[
         app.export_dest = 0;
         app.export_cursor = 0;
         app.export_color_format = 0;
+        app.export_selection = false;
         app.mode = AppMode::ExportDialog;
     }},
+    PaletteCommand { name: "Export Transform: Hue +30\u{00B0}", category: "File", shortcut: "", action: |app| { app.add_export_transform(ExportTransform::HueShift(30)); } },
+    PaletteCommand { name: "Export Transform: Posterize 4", category: "File", shortcut: "", action: |app| { app.add_export_transform(ExportTransform::Posterize(4)); } },
+    PaletteCommand { name: "Export Transform: Invert", category: "File", shortcut: "", action: |app| { app.add_export_transform(ExportTransform::Invert); } },
+    PaletteCommand { name: "Clear Export Transforms", category: "File", shortcut: "", action: |app| { app.clear_export_transforms(); } },
     // Edit
     PaletteCommand { name: "Undo", category: "Edit", shortcut: "Ctrl+Z", action: |app| { app.undo(); } },
     PaletteCommand { name: "Redo", category: "Edit", shortcut: "Ctrl+Y", action: |app| { app.redo(); } },
+    PaletteCommand { name: "Undo History", category: "Edit", shortcut: "U", action: |app| { app.open_history_timeline(); } },
     PaletteCommand { name: "Toggle Filled Rect", category: "Edit", shortcut: "T", action: |app| {
         app.filled_rect = !app.filled_rect;
         app.set_status(if app.filled_rect { "Rect: Filled" } else { "Rect: Outline" });
     }},
+    PaletteCommand { name: "Toggle Diagonal Fill", category: "Edit", shortcut: "", action: |app| {
+        app.fill_diagonal = !app.fill_diagonal;
+        app.set_status(if app.fill_diagonal { "Fill: 8-connected (diagonals)" } else { "Fill: 4-connected" });
+    }},
     // View
     PaletteCommand { name: "Cycle Zoom", category: "View", shortcut: "Z", action: |app| { app.cycle_zoom(); } },
+    PaletteCommand { name: "Toggle Minimap", category: "View", shortcut: "Shift+Z", action: |app| { app.toggle_minimap(); } },
+    PaletteCommand { name: "Follow Cursor", category: "View", shortcut: "Ctrl+F", action: |app| { app.toggle_follow_cursor(); } },
     PaletteCommand { name: "Cycle Theme", category: "View", shortcut: "Ctrl+T", action: |app| { app.cycle_theme(); } },
-    PaletteCommand { name: "Help", category: "View", shortcut: "?", action: |app| { app.mode = AppMode::Help; } },
+    PaletteCommand { name: "Isolate Color", category: "View", shortcut: "O", action: |app| { app.toggle_isolate_color(); } },
+    PaletteCommand { name: "Rotate View", category: "View", shortcut: "J", action: |app| { app.cycle_view_rotation(); } },
+    PaletteCommand { name: "Help", category: "View", shortcut: "?", action: |app| { app.help_scroll = 0; app.mode = AppMode::Help; } },
     // Character
     PaletteCommand { name: "Block Picker", category: "Character", shortcut: "Shift+B", action: |app| { app.open_block_picker(); } },
     PaletteCommand { name: "Cycle Block", category: "Character", shortcut: "B", action: |app| { app.cycle_block(); } },
     PaletteCommand { name: "Cycle Shade", category: "Character", shortcut: "G", action: |app| { app.cycle_shade(); } },
     // Color
-    PaletteCommand { name: "HSL Sliders", category: "Color", shortcut: "S", action: |app| {
-        let (h, s, l) = crate::palette::rgb_to_hsl(app.color.r, app.color.g, app.color.b);
-        app.slider_h = h;
-        app.slider_s = s;
-        app.slider_l = l;
-        app.slider_active = 0;
-        app.mode = AppMode::ColorSliders;
-    }},
+    PaletteCommand { name: "HSL Sliders", category: "Color", shortcut: "S", action: |app| { app.open_color_sliders(); } },
     PaletteCommand { name: "Hex Color Input", category: "Color", shortcut: "X", action: |app| {
         app.text_input = String::new();
         app.mode = AppMode::HexColorInput;
     }},
+    PaletteCommand { name: "Swap Colors", category: "Color", shortcut: "Shift+X", action: |app| { app.swap_colors(); } },
+    PaletteCommand { name: "Clear Background Color", category: "Color", shortcut: "", action: |app| { app.clear_bg_color(); } },
+    PaletteCommand { name: "Toggle Paint Channel (fg/bg)", category: "Color", shortcut: "N", action: |app| { app.toggle_paint_channel(); } },
     PaletteCommand { name: "Palette Manager", category: "Color", shortcut: "C", action: |app| { app.open_palette_dialog(); } },
     PaletteCommand { name: "Add to Palette", category: "Color", shortcut: "A", action: |app| { app.add_color_to_custom_palette(); } },
     // Symmetry
@@ -332,8 +529,23 @@ pub static COMMANDS: &[PaletteCommand] = &[
         app.symmetry = app.symmetry.toggle_vertical();
         app.set_status(&format!("Symmetry: {}", app.symmetry.label()));
     }},
+    PaletteCommand { name: "Symmetry Diagonal", category: "Symmetry", shortcut: "", action: |app| {
+        app.symmetry = app.symmetry.toggle_diagonal();
+        app.set_status(&format!("Symmetry: {}", app.symmetry.label()));
+    }},
+    PaletteCommand { name: "Symmetry Anti-Diagonal", category: "Symmetry", shortcut: "", action: |app| {
+        app.symmetry = app.symmetry.toggle_anti_diagonal();
+        app.set_status(&format!("Symmetry: {}", app.symmetry.label()));
+    }},
+    PaletteCommand { name: "Radial Symmetry (3/4/6/8)", category: "Symmetry", shortcut: "*", action: |app| { app.cycle_radial_order(); } },
+    PaletteCommand { name: "Symmetry Center at Cursor", category: "Symmetry", shortcut: "", action: |app| { app.set_symmetry_center_at_cursor(); } },
+    PaletteCommand { name: "Symmetry Axis Left", category: "Symmetry", shortcut: "<", action: |app| { app.nudge_symmetry_axis(-1, 0); } },
+    PaletteCommand { name: "Symmetry Axis Right", category: "Symmetry", shortcut: ">", action: |app| { app.nudge_symmetry_axis(1, 0); } },
+    PaletteCommand { name: "Symmetry Axis Up", category: "Symmetry", shortcut: "{", action: |app| { app.nudge_symmetry_axis(0, -1); } },
+    PaletteCommand { name: "Symmetry Axis Down", category: "Symmetry", shortcut: "}", action: |app| { app.nudge_symmetry_axis(0, 1); } },
+    PaletteCommand { name: "Symmetry Center Reset", category: "Symmetry", shortcut: "", action: |app| { app.reset_symmetry_center(); } },
     PaletteCommand { name: "Symmetry Off", category: "Symmetry", shortcut: "", action: |app| {
-        app.symmetry = SymmetryMode::Off;
+        app.symmetry = SymmetryMode::empty();
         app.set_status("Symmetry: Off");
     }},
     // Reference
@@ -359,6 +571,9 @@ pub static COMMANDS: &[PaletteCommand] = &[
         };
         app.set_status(msg);
     }},
+    PaletteCommand { name: "Onion Skin Load", category: "Reference", shortcut: "", action: |app| { app.open_onion_skin_dialog(); } },
+    PaletteCommand { name: "Onion Skin Toggle", category: "Reference", shortcut: "", action: |app| { app.toggle_onion_skin(); } },
+    PaletteCommand { name: "Onion Skin Clear", category: "Reference", shortcut: "", action: |app| { app.clear_onion_skin(); } },
 ];
 
 impl App {
@@ -367,7 +582,11 @@ impl App {
             canvas: Canvas::new(),
             active_tool: ToolKind::Pencil,
             color: Rgb::WHITE,
-            symmetry: SymmetryMode::Off,
+            bg_color: None,
+            paint_bg: false,
+            symmetry: SymmetryMode::empty(),
+            symmetry_center: None,
+            radial_order: None,
             history: History::new(),
             cursor: None,
             zoom: 1,
@@ -379,25 +598,44 @@ impl App {
             project_name: None,
             project_path: None,
             filled_rect: false,
+            fill_diagonal: false,
+            isolate_color: false,
+            show_minimap: false,
+            view_rotation: 0,
+            quit_after_save: false,
+            color_format: crate::export::detect_terminal_colors(),
             file_dialog_files: Vec::new(),
             file_dialog_selected: 0,
             export_format: 0,
             export_dest: 0,
             export_cursor: 0,
             export_color_format: 0,
+            export_selection: false,
+            export_transforms: Vec::new(),
+            selection: None,
+            clip: None,
+            clipboard_cells: None,
             text_input: String::new(),
             auto_save_ticks: 0,
             recovery_path: None,
             recent_colors: Vec::new(),
+            recent_capacity: DEFAULT_RECENT_CAPACITY,
+            tool_order: ToolKind::ALL.to_vec(),
             hue_groups: palette::build_hue_groups(),
             palette_scroll: 0,
             palette_cursor: 0,
             slider_h: 0,
             slider_s: 0,
             slider_l: 50,
+            slider_rgb: false,
+            slider_r: 0,
+            slider_g: 0,
+            slider_b: 0,
             slider_active: 0,
+            slider_input: String::new(),
             custom_palette: None,
             palette_dialog_files: Vec::new(),
+            palette_delete_pending: None,
             palette_dialog_selected: 0,
             active_block: blocks::FULL,
             palette_sections: PaletteSectionState {
@@ -414,6 +652,9 @@ impl App {
             new_canvas_input: String::new(),
             canvas_cursor: (0, 0),
             canvas_cursor_active: false,
+            cursor_step: 1,
+            follow_cursor: false,
+            drag_last: None,
             viewport_x: 0,
             viewport_y: 0,
             viewport_w: 48,
@@ -428,15 +669,27 @@ impl App {
             import_normalize: true,
             import_preserve_hue: true,
             import_posterize: 2, // Default to 12 colors
+            import_dither: 0,
             import_options_cursor: 0,
             palette_query: String::new(),
             palette_filtered: (0..COMMANDS.len()).collect(),
             palette_selected_cmd: 0,
             reference_layer: None,
+            onion_skin: None,
+            file_dialog_for_onion: false,
             show_startup_hint: true,
             goto_input: String::new(),
+            timeline_cursor: 0,
+            shift_origin: None,
+            shift_offset: (0, 0),
+            shift_wrap: false,
+            keymap: crate::keymap::Keymap::default(),
+            help_scroll: 0,
+            help_max_scroll: 0,
             paste_buffer: String::new(),
             paste_deadline: None,
+            debug_dump_path: None,
+            debug_dump_pending: false,
         };
         app.rebuild_palette_layout();
         app
@@ -497,6 +750,16 @@ impl App {
         self.palette_layout = layout;
     }
 
+    /// The curated palette color (custom palette, else the default one)
+    /// closest to `color`, with its Euclidean RGB distance.
+    pub fn nearest_palette_color(&self, color: Rgb) -> Option<(Rgb, f64)> {
+        let colors: &[Rgb] = match &self.custom_palette {
+            Some(cp) => &cp.colors,
+            None => &palette::DEFAULT_PALETTE,
+        };
+        palette::nearest_in(colors, color)
+    }
+
     pub fn theme(&self) -> &Theme {
         &THEMES[self.theme_index]
     }
@@ -507,14 +770,298 @@ impl App {
     }
 
     pub fn cycle_zoom(&mut self) {
-        self.zoom = match self.zoom {
+        let zoom = match self.zoom {
             1 => 2,
             2 => 4,
             _ => 1,
         };
+        self.set_zoom(zoom);
+        self.set_status(&format!("Zoom: {}x", self.zoom));
+    }
+
+    /// Step the zoom one level in or out (no wrap) around a hovered canvas
+    /// cell, which stays `offset` screen cells from the canvas corner.
+    pub fn zoom_around(&mut self, zoom_in: bool, cell: (usize, usize), offset: (u16, u16)) {
+        let zoom = match (zoom_in, self.zoom) {
+            (true, 1) => 2,
+            (true, _) => 4,
+            (false, 4) => 2,
+            (false, _) => 1,
+        };
+        if zoom == self.zoom {
+            return;
+        }
+        self.set_zoom(zoom);
+        self.viewport_x = cell.0.saturating_sub(offset.0 as usize / zoom as usize);
+        self.viewport_y = cell.1.saturating_sub(offset.1 as usize / zoom_rows(zoom));
+        self.clamp_viewport();
         self.set_status(&format!("Zoom: {}x", self.zoom));
     }
 
+    /// Change the zoom level, rescaling the viewport size (in canvas cells)
+    /// to the same screen area until the next render measures it.
+    fn set_zoom(&mut self, zoom: u8) {
+        let screen_w = self.viewport_w * self.zoom as usize;
+        let screen_h = self.viewport_h * zoom_rows(self.zoom);
+        self.zoom = zoom;
+        self.viewport_w = screen_w / zoom as usize;
+        self.viewport_h = screen_h / zoom_rows(zoom);
+        self.clamp_viewport();
+    }
+
+    /// Keep the viewport from scrolling past the canvas's right and bottom edges.
+    pub fn clamp_viewport(&mut self) {
+        self.viewport_x = self.viewport_x.min(self.canvas.width.saturating_sub(self.viewport_w));
+        self.viewport_y = self.viewport_y.min(self.canvas.height.saturating_sub(self.viewport_h));
+    }
+
+    /// Scroll the palette sections panel by one line.
+    pub fn scroll_palette(&mut self, up: bool) {
+        if up {
+            self.palette_scroll = self.palette_scroll.saturating_sub(1);
+        } else {
+            // Same rows estimate as ensure_palette_cursor_visible
+            self.palette_scroll = (self.palette_scroll + 1).min(self.palette_layout.len() / 6);
+        }
+    }
+
+    /// Open the slider dialog on the current color, in the last-used mode.
+    pub fn open_color_sliders(&mut self) {
+        self.set_sliders_from(self.color);
+        self.slider_active = 0;
+        self.slider_input.clear();
+        self.mode = AppMode::ColorSliders;
+    }
+
+    fn set_sliders_from(&mut self, color: Rgb) {
+        let (h, s, l) = crate::palette::rgb_to_hsl(color.r, color.g, color.b);
+        self.slider_h = h;
+        self.slider_s = s;
+        self.slider_l = l;
+        self.slider_r = color.r;
+        self.slider_g = color.g;
+        self.slider_b = color.b;
+    }
+
+    /// Color the sliders currently describe.
+    pub fn slider_color(&self) -> Rgb {
+        if self.slider_rgb {
+            Rgb::new(self.slider_r, self.slider_g, self.slider_b)
+        } else {
+            let (r, g, b) = crate::palette::hsl_to_rgb(self.slider_h, self.slider_s, self.slider_l);
+            Rgb::new(r, g, b)
+        }
+    }
+
+    /// Switch between HSL and RGB sliders, keeping the color.
+    pub fn toggle_slider_mode(&mut self) {
+        let color = self.slider_color();
+        self.slider_rgb = !self.slider_rgb;
+        self.set_sliders_from(color);
+    }
+
+    /// (label, value, max) of slider `i` in the current mode.
+    pub fn slider(&self, i: u8) -> (&'static str, u16, u16) {
+        match (self.slider_rgb, i) {
+            (false, 0) => ("H", self.slider_h, 359),
+            (false, 1) => ("S", self.slider_s as u16, 100),
+            (false, _) => ("L", self.slider_l as u16, 100),
+            (true, 0) => ("R", self.slider_r as u16, 255),
+            (true, 1) => ("G", self.slider_g as u16, 255),
+            (true, _) => ("B", self.slider_b as u16, 255),
+        }
+    }
+
+    /// Set the active slider, clamped to its range.
+    pub fn set_active_slider(&mut self, value: i32) {
+        let (_, _, max) = self.slider(self.slider_active);
+        let v = value.clamp(0, max as i32);
+        match (self.slider_rgb, self.slider_active) {
+            (false, 0) => self.slider_h = v as u16,
+            (false, 1) => self.slider_s = v as u8,
+            (false, _) => self.slider_l = v as u8,
+            (true, 0) => self.slider_r = v as u8,
+            (true, 1) => self.slider_g = v as u8,
+            (true, _) => self.slider_b = v as u8,
+        }
+    }
+
+    /// Show or hide the minimap overlay in the canvas corner.
+    pub fn toggle_minimap(&mut self) {
+        self.show_minimap = !self.show_minimap;
+        self.set_status(if self.show_minimap { "Minimap: On" } else { "Minimap: Off" });
+    }
+
+    /// Toggle the isolate-color view, which dims cells not using the active color.
+    pub fn toggle_isolate_color(&mut self) {
+        self.isolate_color = !self.isolate_color;
+        if self.isolate_color {
+            self.set_status(&format!("Isolate: {}", self.color.name()));
+        } else {
+            self.set_status("Isolate: Off");
+        }
+    }
+
+    /// Clear the border-connected background (exact match on the corner
+    /// color) as a single undo step.
+    pub fn remove_background(&mut self) {
+        let mutations = tools::remove_background(&self.canvas, 0);
+        if mutations.is_empty() {
+            self.set_status_with_level("Remove background: nothing to clear", MessageLevel::Warning);
+            return;
+        }
+        for m in &mutations {
+            self.canvas.set(m.x, m.y, m.new);
+        }
+        self.set_status(&format!("Removed background: {} cells", mutations.len()));
+        self.history.commit_labeled("remove bg".to_string(), Action::CellChange(mutations));
+        self.dirty = true;
+    }
+
+    /// Recolor the whole canvas: every fg/bg exactly matching the color shown
+    /// under the cursor becomes the active color, as a single undo step.
+    pub fn replace_color_under_cursor(&mut self) {
+        let from = self
+            .effective_cursor()
+            .and_then(|(x, y)| self.canvas.get(x, y))
+            .and_then(|c| if c.is_empty() { c.bg } else { c.fg.or(c.bg) });
+        let Some(from) = from else {
+            self.set_status_with_level("Replace color: no color under cursor", MessageLevel::Warning);
+            return;
+        };
+        let mutations = tools::replace_color(&self.canvas, from, self.color, 0);
+        if mutations.is_empty() {
+            self.set_status_with_level("Replace color: nothing to change", MessageLevel::Warning);
+            return;
+        }
+        for m in &mutations {
+            self.canvas.set(m.x, m.y, m.new);
+        }
+        self.set_status(&format!("Replaced {} with {}: {} cells", from.name(), self.color.name(), mutations.len()));
+        self.history.commit_labeled("replace color".to_string(), Action::CellChange(mutations));
+        self.dirty = true;
+    }
+
+    /// Cells a Pencil/Eraser dab at the hover cursor would touch, including
+    /// symmetry images (clipped to the canvas). Empty when there is no
+    /// cursor, another tool is active, or drawing is disabled. Display only.
+    pub fn brush_footprint(&self) -> Vec<(usize, usize)> {
+        if self.view_rotation != 0 || !matches!(self.active_tool, ToolKind::Pencil | ToolKind::Eraser) {
+            return Vec::new();
+        }
+        let Some((x, y)) = self.effective_cursor() else {
+            return Vec::new();
+        };
+        if x >= self.canvas.width || y >= self.canvas.height {
+            return Vec::new();
+        }
+        let dab = CellMutation { x, y, old: crate::cell::Cell::default(), new: crate::cell::Cell::default() };
+        self.symmetrize(vec![dab])
+        .into_iter()
+        .map(|m| (m.x, m.y))
+        .collect()
+    }
+
+    /// Mirror `mutations` with the active symmetry axes, then replicate them
+    /// around the center when radial symmetry is on.
+    fn symmetrize(&self, mutations: Vec<CellMutation>) -> Vec<CellMutation> {
+        let (w, h, axes) = (self.canvas.width, self.canvas.height, self.symmetry_axes());
+        let mirrored = symmetry::apply_symmetry_around(mutations, self.symmetry, w, h, axes);
+        match self.radial_order {
+            Some(order) => symmetry::apply_radial(mirrored, order, w, h, axes),
+            None => mirrored,
+        }
+    }
+
+    /// Step radial symmetry through its orders and back to off.
+    pub fn cycle_radial_order(&mut self) {
+        let orders = symmetry::RADIAL_ORDERS;
+        self.radial_order = match self.radial_order {
+            None => Some(orders[0]),
+            Some(n) => orders.iter().position(|&o| o == n).and_then(|i| orders.get(i + 1)).copied(),
+        };
+        match self.radial_order {
+            Some(n) => self.set_status(&format!("Radial symmetry: {}-fold", n)),
+            None => self.set_status("Radial symmetry: Off"),
+        }
+    }
+
+    /// Mirror axes in effect, in half-cell units.
+    pub fn symmetry_axes(&self) -> (usize, usize) {
+        self.symmetry_center
+            .unwrap_or_else(|| symmetry::center_axes(self.canvas.width, self.canvas.height))
+    }
+
+    /// Put the H/V mirror axes through the cell under the cursor.
+    pub fn set_symmetry_center_at_cursor(&mut self) {
+        let Some((x, y)) = self.effective_cursor() else {
+            self.set_status_with_level("Symmetry center: move the cursor onto the canvas first", MessageLevel::Warning);
+            return;
+        };
+        self.symmetry_center = Some((x * 2, y * 2));
+        self.set_status(&format!("Symmetry center: col {}, row {}", x, y));
+    }
+
+    /// Move the mirror axes by (dx, dy) half cells, keeping them on the canvas.
+    pub fn nudge_symmetry_axis(&mut self, dx: isize, dy: isize) {
+        let (ax, ay) = self.symmetry_axes();
+        let max_x = 2 * self.canvas.width.saturating_sub(1);
+        let max_y = 2 * self.canvas.height.saturating_sub(1);
+        let ax = ax.saturating_add_signed(dx).min(max_x);
+        let ay = ay.saturating_add_signed(dy).min(max_y);
+        self.symmetry_center = Some((ax, ay));
+        self.set_status(&format!("Symmetry axes: {}", axes_label((ax, ay))));
+    }
+
+    /// Return the mirror axes to the geometric center.
+    pub fn reset_symmetry_center(&mut self) {
+        self.symmetry_center = None;
+        self.set_status("Symmetry center: canvas center");
+    }
+
+    /// Step the rotated view 90° clockwise; four steps return to normal.
+    pub fn cycle_view_rotation(&mut self) {
+        self.view_rotation = (self.view_rotation + 90) % 360;
+        self.cancel_tool();
+        if self.view_rotation == 0 {
+            self.set_status("View: Normal");
+        } else {
+            self.set_status(&format!("View: Rotated {}\u{00B0} (read-only, Esc to reset)", self.view_rotation));
+        }
+    }
+
+    /// Return to the unrotated view.
+    pub fn reset_view_rotation(&mut self) {
+        self.view_rotation = 0;
+        self.set_status("View: Normal");
+    }
+
+    /// Cycle the WASD cursor step: 1 → 5 → 10 → 1 cells.
+    pub fn cycle_cursor_step(&mut self) {
+        self.cursor_step = match self.cursor_step {
+            1 => 5,
+            5 => 10,
+            _ => 1,
+        };
+        self.set_status(&format!("Cursor step: {} cell{}", self.cursor_step, if self.cursor_step == 1 { "" } else { "s" }));
+    }
+
+    /// Move the keyboard canvas cursor `cursor_step` cells in direction
+    /// (dx, dy), stopping at the canvas edges, and keep it in view.
+    pub fn move_canvas_cursor(&mut self, dx: isize, dy: isize) {
+        let step = self.cursor_step as isize;
+        let clamp = |v: usize, d: isize, len: usize| {
+            (v as isize + d * step).clamp(0, len.saturating_sub(1) as isize) as usize
+        };
+        self.canvas_cursor = (
+            clamp(self.canvas_cursor.0, dx, self.canvas.width),
+            clamp(self.canvas_cursor.1, dy, self.canvas.height),
+        );
+        self.canvas_cursor_active = true;
+        let (cx, cy) = self.canvas_cursor;
+        self.ensure_cursor_in_viewport(cx, cy, self.viewport_w, self.viewport_h);
+    }
+
     /// Returns the effective cursor position: keyboard canvas cursor if active,
     /// otherwise the mouse hover cursor.
     pub fn effective_cursor(&self) -> Option<(usize, usize)> {
@@ -525,9 +1072,27 @@ impl App {
         }
     }
 
+    /// Toggle follow mode, centering the view on the canvas cursor when turned on.
+    pub fn toggle_follow_cursor(&mut self) {
+        self.follow_cursor = !self.follow_cursor;
+        if self.follow_cursor && self.canvas_cursor_active {
+            let (cx, cy) = self.canvas_cursor;
+            self.ensure_cursor_in_viewport(cx, cy, self.viewport_w, self.viewport_h);
+        }
+        self.set_status(if self.follow_cursor { "Follow cursor: ON (view stays centered)" } else { "Follow cursor: OFF" });
+    }
+
     /// Adjusts viewport so that the given canvas coordinate is visible.
-    /// `vw` and `vh` are the viewport dimensions in canvas cells.
+    /// `vw` and `vh` are the viewport dimensions in canvas cells. In follow
+    /// mode the coordinate is centered instead, except where that would
+    /// scroll past a canvas edge.
     pub fn ensure_cursor_in_viewport(&mut self, cx: usize, cy: usize, vw: usize, vh: usize) {
+        if self.follow_cursor {
+            let center = |c: usize, view: usize, len: usize| c.saturating_sub(view / 2).min(len.saturating_sub(view));
+            self.viewport_x = center(cx, vw, self.canvas.width);
+            self.viewport_y = center(cy, vh, self.canvas.height);
+            return;
+        }
         if cx < self.viewport_x {
             self.viewport_x = cx;
         } else if cx >= self.viewport_x + vw {
@@ -629,23 +1194,96 @@ impl App {
         self.mode = AppMode::BlockPicker;
     }
 
+    /// Exchange the foreground and background colors. With no background
+    /// set, the foreground becomes the background and also stays the
+    /// foreground, so nothing is lost; "Clear Background Color" undoes it.
+    pub fn swap_colors(&mut self) {
+        let fg = self.color;
+        if let Some(bg) = self.bg_color {
+            self.color = bg;
+        }
+        self.bg_color = Some(fg);
+        self.set_status(&format!("Colors: fg {} / bg {}", self.color.name(), fg.name()));
+    }
+
+    /// Switch drawing between the foreground and background channel.
+    pub fn toggle_paint_channel(&mut self) {
+        self.paint_bg = !self.paint_bg;
+        if self.paint_bg {
+            self.set_status("Painting background (glyphs kept)");
+        } else {
+            self.set_status("Painting foreground");
+        }
+    }
+
+    /// Go back to drawing with a transparent background.
+    pub fn clear_bg_color(&mut self) {
+        self.bg_color = None;
+        self.set_status("Background color: none");
+    }
+
     /// Track a color in the recent colors list.
     fn track_recent_color(&mut self, color: Rgb) {
         // Remove if already present (to move it to front)
         self.recent_colors.retain(|&c| c != color);
         // Push to front
         self.recent_colors.insert(0, color);
-        // Cap at 8
-        self.recent_colors.truncate(8);
+        self.recent_colors.truncate(self.recent_capacity);
         // Rebuild palette layout to reflect updated recent section
         self.rebuild_palette_layout();
     }
 
+    /// Put the named tools first in the toolbar. Unknown names are ignored
+    /// and returned so the caller can report them.
+    pub fn set_favorite_tools(&mut self, names: &[String]) -> Vec<String> {
+        let mut favorites = Vec::new();
+        let mut unknown = Vec::new();
+        for name in names.iter().filter(|n| !n.trim().is_empty()) {
+            match ToolKind::from_name(name) {
+                Some(tool) => favorites.push(tool),
+                None => unknown.push(name.clone()),
+            }
+        }
+        self.tool_order = ToolKind::ordered(&favorites);
+        unknown
+    }
+
+    /// Change how many recent colors are kept (clamped to 4–32). Shrinking
+    /// drops only the oldest entries; the palette cursor stays on the same
+    /// item, or on the last kept recent color if its entry was dropped.
+    pub fn set_recent_capacity(&mut self, capacity: usize) {
+        self.recent_capacity = capacity.clamp(MIN_RECENT_CAPACITY, MAX_RECENT_CAPACITY);
+        let old_len = self.recent_colors.len();
+        self.recent_colors.truncate(self.recent_capacity);
+        let removed = old_len - self.recent_colors.len();
+        if removed == 0 {
+            return;
+        }
+
+        // Shift the cursor past the shrunken (expanded) recent section
+        let recent_start = self.palette_layout.iter()
+            .position(|item| matches!(item, PaletteItem::SectionHeader(PaletteSection::Recent)))
+            .map(|header| header + 1);
+        if let Some(start) = recent_start.filter(|_| self.palette_sections.recent_expanded) {
+            let kept_end = start + self.recent_colors.len();
+            if self.palette_cursor >= start + old_len {
+                self.palette_cursor -= removed;
+            } else if self.palette_cursor >= kept_end {
+                self.palette_cursor = kept_end.saturating_sub(1).max(start);
+            }
+        }
+        self.rebuild_palette_layout();
+    }
+
     /// Apply a tool action at (x, y), handling symmetry and history.
     pub fn apply_tool(&mut self, x: usize, y: usize) {
+        if self.view_rotation != 0 {
+            self.set_status_with_level("Rotated view is read-only (Esc to reset)", MessageLevel::Warning);
+            return;
+        }
         self.show_startup_hint = false;
         let fg = Some(self.color);
-        let bg = None;
+        let bg = self.bg_color;
         let mutations = match self.active_tool {
             ToolKind::Pencil => {
                 self.track_recent_color(self.color);
@@ -654,7 +1292,11 @@ impl App {
             ToolKind::Eraser => tools::eraser(&self.canvas, x, y),
             ToolKind::Fill => {
                 self.track_recent_color(self.color);
-                tools::flood_fill(&self.canvas, x, y, self.active_block, fg, bg)
+                if self.paint_bg {
+                    tools::flood_fill(&self.canvas, x, y, self.active_block, fg, fg, tools::FillTarget::BgOnly, self.fill_diagonal)
+                } else {
+                    tools::flood_fill(&self.canvas, x, y, self.active_block, fg, bg, tools::FillTarget::Full, self.fill_diagonal)
+                }
             }
             ToolKind::Eyedropper => {
                 if let Some((picked_fg, _bg, ch)) = tools::eyedropper(&self.canvas, x, y) {
@@ -663,7 +1305,7 @@ impl App {
                         self.track_recent_color(picked);
                         self.set_status(&format!("Picked: {} {}", picked.name(), ch));
                     }
-                    if ch != ' ' {
+                    if ch != ' ' && crate::cell::fits_cell(ch) {
                         self.active_block = ch;
                     }
                 }
@@ -684,6 +1326,24 @@ impl App {
                     _ => return,
                 }
             }
+            ToolKind::Select => {
+                match self.tool_state.clone() {
+                    ToolState::Idle => {
+                        self.tool_state = ToolState::SelectStart { x, y };
+                        self.set_status("Select: click second corner");
+                    }
+                    ToolState::SelectStart { x: x0, y: y0 } => {
+                        self.tool_state = ToolState::Idle;
+                        let sel = Selection::from_corners((x0, y0), (x, y));
+                        self.selection = Some(sel);
+                        self.set_status(&format!(
+                            "Selected {}x{} at ({}, {})", sel.width, sel.height, sel.x, sel.y
+                        ));
+                    }
+                    _ => {}
+                }
+                return;
+            }
             ToolKind::Rectangle => {
                 match self.tool_state.clone() {
                     ToolState::Idle => {
@@ -702,10 +1362,28 @@ impl App {
                     _ => return,
                 }
             }
+            ToolKind::Ellipse => {
+                match self.tool_state.clone() {
+                    ToolState::Idle => {
+                        self.tool_state = ToolState::EllipseStart { x, y };
+                        self.set_status("Ellipse: click opposite corner");
+                        return;
+                    }
+                    ToolState::EllipseStart { x: x0, y: y0 } => {
+                        self.tool_state = ToolState::Idle;
+                        self.track_recent_color(self.color);
+                        tools::ellipse(
+                            &self.canvas, x0, y0, x, y, self.active_block, fg, bg,
+                            self.filled_rect,
+                        )
+                    }
+                    _ => return,
+                }
+            }
         };
 
         // Apply symmetry
-        let mutations = symmetry::apply_symmetry(mutations, self.symmetry, self.canvas.width, self.canvas.height);
+        let mutations = self.symmetrize(mutations);
 
         if mutations.is_empty() {
             return;
@@ -714,17 +1392,24 @@ impl App {
         // Read actual old values and composite half-block draws onto existing cells.
         // Symmetry mutations have wrong `old` values since they were cloned from
         // the original mutation, so we always re-read the canvas here.
+        // In background mode only the bg changes (the eraser clears it).
+        let bg_paint = (self.active_tool != ToolKind::Eraser).then_some(self.color);
         let mutations: Vec<CellMutation> = mutations
             .into_iter()
             .filter_map(|mut m| {
                 if let Some(actual_old) = self.canvas.get(m.x, m.y) {
                     m.old = actual_old;
-                    m.new = tools::compose_cell(actual_old, m.new.ch, m.new.fg, m.new.bg);
+                    m.new = if self.paint_bg {
+                        crate::cell::Cell { bg: bg_paint, ..actual_old }
+                    } else {
+                        tools::compose_cell(actual_old, m.new.ch, m.new.fg, m.new.bg)
+                    };
                     if m.old != m.new { Some(m) } else { None }
                 } else {
                     None
                 }
             })
+            .filter(|m| self.clip.is_none_or(|clip| clip.contains(m.x, m.y)))
             .collect();
 
         // Apply to canvas
@@ -733,6 +1418,7 @@ impl App {
         }
 
         // Record in history
+        self.history.set_label_hint(&self.active_tool.name().to_lowercase());
         for m in mutations {
             self.history.push_mutation(m);
         }
@@ -748,22 +1434,306 @@ impl App {
         self.history.end_stroke();
     }
 
-    pub fn undo(&mut self) {
-        if self.history.undo(&mut self.canvas) {
-            self.dirty = true;
-            self.set_status("Undo");
-        }
+    pub fn undo(&mut self) {
+        if self.history.undo(&mut self.canvas) {
+            self.dirty = true;
+            self.set_status("Undo");
+        }
+    }
+
+    pub fn redo(&mut self) {
+        if self.history.redo(&mut self.canvas) {
+            self.dirty = true;
+            self.set_status("Redo");
+        }
+    }
+
+    /// Open the undo history timeline with the cursor on the current step.
+    pub fn open_history_timeline(&mut self) {
+        self.timeline_cursor = self.history.position();
+        self.mode = AppMode::HistoryTimeline;
+    }
+
+    /// Enter shift mode: arrows move the whole drawing until Enter or Esc.
+    pub fn begin_shift(&mut self) {
+        self.shift_origin = Some(self.canvas.cells());
+        self.shift_offset = (0, 0);
+        self.mode = AppMode::ShiftCanvas;
+    }
+
+    /// Move the shift preview by (dx, dy) more cells.
+    pub fn nudge_shift(&mut self, dx: isize, dy: isize) {
+        self.shift_offset = (self.shift_offset.0 + dx, self.shift_offset.1 + dy);
+        self.preview_shift();
+    }
+
+    pub fn toggle_shift_wrap(&mut self) {
+        self.shift_wrap = !self.shift_wrap;
+        self.preview_shift();
+    }
+
+    /// Redraw the canvas as the origin shifted by the current offset.
+    fn preview_shift(&mut self) {
+        let Some(origin) = &self.shift_origin else { return };
+        let (w, h) = (self.canvas.width, self.canvas.height);
+        self.canvas.replace(origin.clone(), w, h);
+        let (dx, dy) = self.shift_offset;
+        self.canvas.shift(dx, dy, self.shift_wrap);
+    }
+
+    /// Leave shift mode, keeping the shifted drawing as a single undo step.
+    pub fn apply_shift(&mut self) {
+        self.mode = AppMode::Normal;
+        let Some(old_cells) = self.shift_origin.take() else { return };
+        let new_cells = self.canvas.cells();
+        if new_cells == old_cells {
+            self.set_status("Shift: nothing moved");
+            return;
+        }
+        let (w, h) = (self.canvas.width, self.canvas.height);
+        let (dx, dy) = self.shift_offset;
+        self.history.commit_labeled(format!("shift {:+},{:+}", dx, dy), Action::CanvasSnapshot {
+            old_cells, old_w: w, old_h: h,
+            new_cells, new_w: w, new_h: h,
+        });
+        self.dirty = true;
+        self.set_status(&format!("Shifted by {:+},{:+}", dx, dy));
+    }
+
+    /// Leave shift mode and put the drawing back where it was.
+    pub fn cancel_shift(&mut self) {
+        self.mode = AppMode::Normal;
+        if let Some(origin) = self.shift_origin.take() {
+            let (w, h) = (self.canvas.width, self.canvas.height);
+            self.canvas.replace(origin, w, h);
+        }
+        self.set_status("Shift cancelled");
+    }
+
+    /// Undo/redo until the history sits at `target` on the timeline.
+    pub fn jump_to_history(&mut self, target: usize) {
+        if self.history.jump_to(&mut self.canvas, target) > 0 {
+            self.dirty = true;
+        }
+        self.set_status(&format!(
+            "History: step {}/{}",
+            self.history.position(),
+            self.history.timeline().len()
+        ));
+    }
+
+    pub fn cancel_tool(&mut self) {
+        self.tool_state = ToolState::Idle;
+    }
+
+    /// Restrict drawing to the current selection, or lift an active clip.
+    pub fn toggle_clip(&mut self) {
+        if self.clip.is_some() {
+            self.clear_clip();
+        } else if let Some(sel) = self.selection {
+            self.clip = Some(sel);
+            self.set_status(&format!("Clip: drawing limited to {}x{} at ({}, {})", sel.width, sel.height, sel.x, sel.y));
+        } else {
+            self.set_status_with_level("Clip: select a region first (M)", MessageLevel::Warning);
+        }
+    }
+
+    pub fn clear_clip(&mut self) {
+        self.clip = None;
+        self.set_status("Clip cleared");
+    }
+
+    /// Grow (`delta` > 0) or shrink the selection by `delta` cells per side.
+    pub fn resize_selection(&mut self, delta: isize) {
+        let Some(sel) = self.selection else {
+            self.set_status_with_level("Select a region first (M)", MessageLevel::Warning);
+            return;
+        };
+        let resized = sel.resized(delta, self.canvas.width, self.canvas.height);
+        if resized == sel {
+            let why = if delta < 0 { "can't shrink below 1 cell" } else { "already at the canvas edges" };
+            self.set_status_with_level(&format!("Selection {}", why), MessageLevel::Warning);
+            return;
+        }
+        self.selection = Some(resized);
+        self.set_status(&format!(
+            "Selection: {}x{} at ({}, {})",
+            resized.width, resized.height, resized.x, resized.y
+        ));
+    }
+
+    /// Mirror the whole canvas (left-right when `horizontal`, else
+    /// top-bottom) as a single undo step.
+    pub fn flip_canvas(&mut self, horizontal: bool) {
+        let old_cells = self.canvas.cells();
+        if horizontal {
+            self.canvas.flip_horizontal();
+        } else {
+            self.canvas.flip_vertical();
+        }
+        let (w, h) = (self.canvas.width, self.canvas.height);
+        let label = if horizontal { "flip h" } else { "flip v" };
+        self.history.commit_labeled(label.to_string(), Action::CanvasSnapshot {
+            old_cells, old_w: w, old_h: h,
+            new_cells: self.canvas.cells(), new_w: w, new_h: h,
+        });
+        self.dirty = true;
+        self.set_status(if horizontal { "Flipped left-right" } else { "Flipped top-bottom" });
+    }
+
+    /// Negate every color on the canvas (snapped to the 256-color palette)
+    /// as a single undo step.
+    pub fn invert_colors(&mut self) {
+        let old_cells = self.canvas.cells();
+        self.canvas.invert_colors();
+        let (w, h) = (self.canvas.width, self.canvas.height);
+        self.history.commit_labeled("invert".to_string(), Action::CanvasSnapshot {
+            old_cells, old_w: w, old_h: h,
+            new_cells: self.canvas.cells(), new_w: w, new_h: h,
+        });
+        self.dirty = true;
+        self.set_status("Inverted colors");
+    }
+
+    /// Turn the whole canvas a quarter (clockwise when `cw`) as a single
+    /// undo step. Width and height swap, so the view returns to the origin.
+    pub fn rotate_canvas(&mut self, cw: bool) {
+        let old_cells = self.canvas.cells();
+        let (old_w, old_h) = (self.canvas.width, self.canvas.height);
+        if cw {
+            self.canvas.rotate_cw();
+        } else {
+            self.canvas.rotate_ccw();
+        }
+        let (new_w, new_h) = (self.canvas.width, self.canvas.height);
+        let label = if cw { "rotate cw" } else { "rotate ccw" };
+        self.history.commit_labeled(label.to_string(), Action::CanvasSnapshot {
+            old_cells, old_w, old_h,
+            new_cells: self.canvas.cells(), new_w, new_h,
+        });
+        self.viewport_x = 0;
+        self.viewport_y = 0;
+        self.canvas_cursor = (self.canvas_cursor.0.min(new_w - 1), self.canvas_cursor.1.min(new_h - 1));
+        self.dirty = true;
+        self.set_status(&format!("Rotated {}: {}x{}", if cw { "clockwise" } else { "counter-clockwise" }, new_w, new_h));
+    }
+
+    /// Shrink the canvas to its drawn content as a single undo step.
+    pub fn crop_to_content(&mut self) {
+        let old_cells = self.canvas.cells();
+        let (old_w, old_h) = (self.canvas.width, self.canvas.height);
+        if self.canvas.crop_to_content().is_none() {
+            self.set_status_with_level("Crop: canvas is empty", MessageLevel::Warning);
+            return;
+        }
+        let (new_w, new_h) = (self.canvas.width, self.canvas.height);
+        let new_cells = self.canvas.cells();
+        if new_cells == old_cells {
+            self.set_status("Crop: content already fills the canvas");
+            return;
+        }
+        self.history.commit_labeled("crop".to_string(), Action::CanvasSnapshot {
+            old_cells, old_w, old_h,
+            new_cells, new_w, new_h,
+        });
+        self.viewport_x = 0;
+        self.viewport_y = 0;
+        self.canvas_cursor = (self.canvas_cursor.0.min(new_w - 1), self.canvas_cursor.1.min(new_h - 1));
+        self.dirty = true;
+        self.set_status_with_level(&format!("Cropped to {}x{}", new_w, new_h), MessageLevel::Success);
+    }
+
+    /// Copy the selected cells into the paste buffer.
+    pub fn copy_selection(&mut self) {
+        let Some(sel) = self.selection else {
+            self.set_status_with_level("Select a region first (M)", MessageLevel::Warning);
+            return;
+        };
+        let cells = (sel.y..sel.y + sel.height)
+            .map(|y| (sel.x..sel.x + sel.width).map(|x| self.canvas.get(x, y).unwrap_or_default()).collect())
+            .collect();
+        self.clipboard_cells = Some(cells);
+        self.set_status(&format!("Copied {}x{}", sel.width, sel.height));
+    }
+
+    /// Stamp the paste buffer with its top-left at the canvas cursor, clipped
+    /// to the canvas and any active clip region, as a single undo step.
+    pub fn paste_clipboard(&mut self) {
+        let Some(cells) = &self.clipboard_cells else {
+            self.set_status_with_level("Nothing to paste (Ctrl+C copies a selection)", MessageLevel::Warning);
+            return;
+        };
+        if !self.canvas_cursor_active {
+            self.set_status_with_level("Paste: place the cursor first (WASD)", MessageLevel::Warning);
+            return;
+        }
+        let (x0, y0) = self.canvas_cursor;
+        let mut mutations = Vec::new();
+        for (dy, row) in cells.iter().enumerate() {
+            for (dx, &new) in row.iter().enumerate() {
+                let (x, y) = (x0 + dx, y0 + dy);
+                if self.clip.is_some_and(|clip| !clip.contains(x, y)) {
+                    continue;
+                }
+                if let Some(old) = self.canvas.get(x, y) {
+                    if old != new {
+                        mutations.push(CellMutation { x, y, old, new });
+                    }
+                }
+            }
+        }
+        for m in &mutations {
+            self.canvas.set(m.x, m.y, m.new);
+        }
+        self.set_status(&format!("Pasted at ({}, {}): {} cells changed", x0, y0, mutations.len()));
+        if !mutations.is_empty() {
+            self.history.commit_labeled("paste".to_string(), Action::CellChange(mutations));
+            self.dirty = true;
+        }
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selection = None;
+        self.export_selection = false;
+        self.set_status("Selection cleared");
+    }
+
+    /// Export dialog: switch between whole canvas and selection only.
+    /// Unavailable (stays off) without a selection.
+    pub fn toggle_export_selection(&mut self) {
+        if self.selection.is_none() {
+            self.export_selection = false;
+            self.set_status_with_level("No selection (M to select a region)", MessageLevel::Warning);
+            return;
+        }
+        self.export_selection = !self.export_selection;
+    }
+
+    /// Canvas the export dialog writes: the selected region when
+    /// "selection only" is on, otherwise the whole canvas, with the export
+    /// transforms applied to the copy.
+    pub fn export_canvas(&self) -> Canvas {
+        let canvas = match (self.export_selection, self.selection) {
+            (true, Some(sel)) => self.canvas.subcanvas(sel.x, sel.y, sel.width, sel.height),
+            _ => self.canvas.clone(),
+        };
+        export::apply_transforms(&canvas, &self.export_transforms)
+    }
+
+    /// Stack another export-time color adjustment after the existing ones.
+    pub fn add_export_transform(&mut self, transform: ExportTransform) {
+        self.export_transforms.push(transform);
+        self.dirty = true;
+        let specs: Vec<String> = self.export_transforms.iter().map(|t| t.spec()).collect();
+        self.set_status(&format!("Export transforms: {}", specs.join(" \u{2192} ")));
     }
 
-    pub fn redo(&mut self) {
-        if self.history.redo(&mut self.canvas) {
+    pub fn clear_export_transforms(&mut self) {
+        if !self.export_transforms.is_empty() {
+            self.export_transforms.clear();
             self.dirty = true;
-            self.set_status("Redo");
         }
-    }
-
-    pub fn cancel_tool(&mut self) {
-        self.tool_state = ToolState::Idle;
+        self.set_status("Export transforms cleared");
     }
 
     /// Open the custom palette dialog, scanning for .palette files.
@@ -790,6 +1760,24 @@ impl App {
         }
     }
 
+    /// Ask before deleting the selected palette (deletion can't be undone).
+    pub fn request_palette_delete(&mut self) {
+        if let Some(filename) = self.palette_dialog_files.get(self.palette_dialog_selected).cloned() {
+            let count = palette::load_palette(Path::new(&filename)).ok().map(|p| p.colors.len());
+            self.palette_delete_pending = Some((filename, count));
+            self.mode = AppMode::PaletteDeleteConfirm;
+        }
+    }
+
+    /// Answer the delete prompt: delete on `confirm`, then return to the
+    /// palette dialog either way.
+    pub fn resolve_palette_delete(&mut self, confirm: bool) {
+        self.mode = AppMode::PaletteDialog;
+        if self.palette_delete_pending.take().is_some() && confirm {
+            self.delete_selected_palette();
+        }
+    }
+
     /// Delete the currently selected palette file.
     pub fn delete_selected_palette(&mut self) {
         if let Some(filename) = self.palette_dialog_files.get(self.palette_dialog_selected).cloned() {
@@ -946,6 +1934,8 @@ impl App {
             self.color,
             self.symmetry,
         );
+        project.settings = Some(self.project_settings());
+        project.transforms = self.export_transforms.clone();
         match project.save_to_file(&path) {
             Ok(()) => {
                 self.dirty = false;
@@ -963,16 +1953,56 @@ impl App {
         }
     }
 
-    /// Save with a specific name (from SaveAs dialog).
-    pub fn save_as(&mut self, name: &str) {
-        let filename = if name.ends_with(".kaku") {
+    /// Editor setup to store with the project.
+    pub fn project_settings(&self) -> ProjectSettings {
+        ProjectSettings {
+            tool: Some(self.active_tool.name().to_string()),
+            block: Some(self.active_block),
+            filled_rect: Some(self.filled_rect),
+        }
+    }
+
+    /// Restore a saved editor setup; unset fields and unknown tool names
+    /// leave the current state alone.
+    pub fn apply_project_settings(&mut self, settings: &ProjectSettings) {
+        if let Some(tool) = settings.tool.as_deref().and_then(ToolKind::from_name) {
+            self.active_tool = tool;
+            self.tool_state = ToolState::Idle;
+        }
+        if let Some(block) = settings.block.filter(|&ch| crate::cell::fits_cell(ch)) {
+            self.active_block = block;
+        }
+        if let Some(filled) = settings.filled_rect {
+            self.filled_rect = filled;
+        }
+    }
+
+    /// Save and exit from the quit prompt. Without a project path this opens
+    /// Save As and quits once that save succeeds.
+    pub fn save_and_quit(&mut self) {
+        if self.project_path.is_none() {
+            self.quit_after_save = true;
+            self.text_input = self.project_name.clone().unwrap_or_else(|| "untitled".to_string());
+            self.mode = AppMode::SaveAs;
+            return;
+        }
+        self.mode = AppMode::Normal;
+        if self.save_project() {
+            self.running = false;
+        }
+    }
+
+    /// Save with a specific name (from SaveAs dialog). Returns true on success.
+    pub fn save_as(&mut self, name: &str) -> bool {
+        // .kakb keeps the binary format; anything else is saved as .kaku JSON
+        let filename = if name.ends_with(".kaku") || name.ends_with(".kakb") {
             name.to_string()
         } else {
             format!("{}.kaku", name)
         };
-        self.project_name = Some(name.trim_end_matches(".kaku").to_string());
+        self.project_name = Some(name.trim_end_matches(".kaku").trim_end_matches(".kakb").to_string());
         self.project_path = Some(filename);
-        self.save_project();
+        self.save_project()
     }
 
     /// Load a project from a .kaku file.
@@ -983,12 +2013,18 @@ impl App {
                 self.canvas = project.canvas;
                 self.color = project.color;
                 self.symmetry = project.symmetry;
+                if let Some(ref settings) = project.settings {
+                    self.apply_project_settings(settings);
+                }
+                self.export_transforms = project.transforms;
                 self.project_name = Some(project.name);
                 self.project_path = Some(filename.to_string());
                 self.dirty = false;
                 self.history = History::new();
                 self.auto_save_ticks = 0;
                 self.show_startup_hint = false;
+                self.selection = None;
+                self.clip = None;
                 // Load reference image if present
                 self.reference_layer = None;
                 if let Some(ref ref_path) = project.reference_image {
@@ -1041,8 +2077,60 @@ impl App {
         Ok(())
     }
 
+    /// Load another project as an onion skin behind the working canvas.
+    pub fn load_onion_skin(&mut self, filename: &str) {
+        match Project::load_from_file(Path::new(filename)) {
+            Ok(project) => {
+                let clipped = project.canvas.width > self.canvas.width
+                    || project.canvas.height > self.canvas.height;
+                self.onion_skin = Some(OnionSkin {
+                    canvas: project.canvas,
+                    path: filename.to_string(),
+                    visible: true,
+                });
+                if clipped {
+                    self.set_status_with_level(
+                        &format!("Onion skin: {} (larger than canvas, clipped)", filename),
+                        MessageLevel::Warning,
+                    );
+                } else {
+                    self.set_status_with_level(&format!("Onion skin: {}", filename), MessageLevel::Success);
+                }
+            }
+            Err(e) => {
+                self.set_status_with_level(&format!("Onion skin failed: {}", e), MessageLevel::Error);
+            }
+        }
+    }
+
+    pub fn toggle_onion_skin(&mut self) {
+        let msg = match self.onion_skin {
+            Some(ref mut onion) => {
+                onion.visible = !onion.visible;
+                if onion.visible { "Onion skin: Visible" } else { "Onion skin: Hidden" }
+            }
+            None => "No onion skin loaded",
+        };
+        self.set_status(msg);
+    }
+
+    pub fn clear_onion_skin(&mut self) {
+        if self.onion_skin.take().is_some() {
+            self.set_status("Onion skin cleared");
+        } else {
+            self.set_status_with_level("No onion skin loaded", MessageLevel::Warning);
+        }
+    }
+
+    /// File dialog that loads the chosen project as an onion skin.
+    pub fn open_onion_skin_dialog(&mut self) {
+        self.open_file_dialog();
+        self.file_dialog_for_onion = self.mode == AppMode::FileDialog;
+    }
+
     /// Populate file dialog with .kaku files from current directory.
     pub fn open_file_dialog(&mut self) {
+        self.file_dialog_for_onion = false;
         let cwd = std::env::current_dir().unwrap_or_default();
         self.file_dialog_files = crate::project::list_kaku_files(&cwd);
         self.file_dialog_selected = 0;
@@ -1064,11 +2152,15 @@ impl App {
 
     /// Execute the current export dialog selection.
     pub fn do_export(&mut self) {
-        let content = if self.export_format == 0 {
-            export::to_plain_text(&self.canvas)
-        } else {
-            export::to_ansi(&self.canvas, self.color_format())
-        };
+        if self.export_format == 2 {
+            // PNG is binary, so it always goes to a file
+            let base = self.project_name.as_deref().unwrap_or("untitled");
+            self.text_input = format!("{}.png", base);
+            self.mode = AppMode::ExportFile;
+            return;
+        }
+
+        let content = self.export_text();
 
         if self.export_dest == 0 {
             // Clipboard
@@ -1090,7 +2182,11 @@ impl App {
             }
         } else {
             // File — switch to text input for filename
-            let ext = if self.export_format == 0 { "txt" } else { "ans" };
+            let ext = match self.export_format {
+                1 => "ans",
+                3 => "html",
+                _ => "txt",
+            };
             let base = self
                 .project_name
                 .as_deref()
@@ -1100,13 +2196,32 @@ impl App {
         }
     }
 
+    /// Text content for the selected export format (plain, ANSI or HTML).
+    fn export_text(&self) -> String {
+        let canvas = self.export_canvas();
+        match self.export_format {
+            1 => export::to_ansi(&canvas, self.color_format()),
+            3 => export::to_html(&canvas),
+            _ => export::to_plain_text(&canvas),
+        }
+    }
+
     /// Write export content to a file.
     pub fn export_to_file(&mut self, filename: &str) {
-        let content = if self.export_format == 0 {
-            export::to_plain_text(&self.canvas)
-        } else {
-            export::to_ansi(&self.canvas, self.color_format())
-        };
+        let canvas = self.export_canvas();
+        if self.export_format == 2 {
+            let img = export::to_png(&canvas, PNG_CELL_W, PNG_CELL_H, 1, false);
+            match img.save(filename) {
+                Ok(()) => self.set_status_with_level(
+                    &format!("Exported {}x{} PNG to {}", img.width(), img.height(), filename),
+                    MessageLevel::Success,
+                ),
+                Err(e) => self.set_status_with_level(&format!("Export failed: {}", e), MessageLevel::Error),
+            }
+            self.mode = AppMode::Normal;
+            return;
+        }
+        let content = self.export_text();
         match std::fs::write(filename, &content) {
             Ok(()) => self.set_status_with_level(&format!("Exported to {}", filename), MessageLevel::Success),
             Err(e) => self.set_status_with_level(&format!("Export failed: {}", e), MessageLevel::Error),
@@ -1139,8 +2254,11 @@ impl App {
             self.color,
             self.symmetry,
         );
-        if project.save_to_file(Path::new(&path)).is_ok() {
-            self.set_status("Auto-saved");
+        project.settings = Some(self.project_settings());
+        project.transforms = self.export_transforms.clone();
+        match project.save_to_file(Path::new(&path)) {
+            Ok(()) => self.set_status("Auto-saved"),
+            Err(e) => self.set_status_with_level(&format!("Auto-save failed: {}", e), MessageLevel::Error),
         }
     }
 
@@ -1162,6 +2280,7 @@ impl App {
                     self.canvas = project.canvas;
                     self.color = project.color;
                     self.symmetry = project.symmetry;
+                    self.export_transforms = project.transforms;
                     self.project_name = Some(project.name);
                     // Derive the real save path from autosave name
                     let real_path = autosave.trim_end_matches(".autosave");
@@ -1203,6 +2322,295 @@ mod tests {
         assert_eq!(app.zoom, 1);
     }
 
+    #[test]
+    fn test_symmetry_center_defaults_to_geometric_center() {
+        let mut app = App::new();
+        let (w, h) = (app.canvas.width, app.canvas.height);
+        assert_eq!(app.symmetry_axes(), (w - 1, h - 1));
+
+        app.symmetry = SymmetryMode::HORIZONTAL;
+        app.canvas_cursor = (10, 3);
+        app.canvas_cursor_active = true;
+        app.set_symmetry_center_at_cursor();
+        assert_eq!(app.symmetry_axes(), (20, 6));
+        app.canvas_cursor_active = false;
+        app.apply_tool(7, 0);
+        assert!(!app.canvas.get(13, 0).unwrap().is_empty(), "mirrored about column 10");
+        assert!(app.canvas.get(w - 8, 0).unwrap().is_empty());
+
+        app.reset_symmetry_center();
+        assert_eq!(app.symmetry_axes(), (w - 1, h - 1));
+    }
+
+    #[test]
+    fn test_nudge_symmetry_axis_moves_in_half_cells() {
+        let mut app = App::new();
+        let (w, h) = (app.canvas.width, app.canvas.height);
+        app.nudge_symmetry_axis(-3, 0);
+        assert_eq!(app.symmetry_axes(), (w - 4, h - 1));
+        assert_eq!(axes_label(app.symmetry_axes()), format!("col {}, row {}.5", (w - 4) / 2, (h - 1) / 2));
+
+        app.nudge_symmetry_axis(-1000, 1000);
+        assert_eq!(app.symmetry_axes(), (0, 2 * (h - 1)), "clamped to the canvas");
+
+        app.symmetry = SymmetryMode::HORIZONTAL;
+        app.nudge_symmetry_axis(6, 0);
+        app.apply_tool(1, 0);
+        assert!(!app.canvas.get(5, 0).unwrap().is_empty(), "mirrored about column 3");
+    }
+
+    #[test]
+    fn test_brush_footprint_follows_cursor_and_symmetry() {
+        let mut app = App::new();
+        assert!(app.brush_footprint().is_empty(), "no cursor, no preview");
+
+        app.cursor = Some((2, 3));
+        assert_eq!(app.brush_footprint(), vec![(2, 3)]);
+
+        app.symmetry = SymmetryMode::QUAD;
+        let (w, h) = (app.canvas.width, app.canvas.height);
+        assert_eq!(app.brush_footprint().len(), 4);
+        assert!(app.brush_footprint().contains(&(w - 3, h - 4)));
+
+        // Off-center axis: images that fall off the canvas are clipped
+        app.symmetry_center = Some((2, 2));
+        assert_eq!(app.brush_footprint(), vec![(2, 3), (0, 3)]);
+
+        // Preview never touches the canvas
+        assert!(app.canvas.is_empty());
+
+        app.active_tool = ToolKind::Line;
+        assert!(app.brush_footprint().is_empty());
+        app.active_tool = ToolKind::Pencil;
+        app.cursor = None;
+        assert!(app.brush_footprint().is_empty());
+    }
+
+    #[test]
+    fn test_clip_limits_drawing_to_selection() {
+        let mut app = App::new();
+        app.toggle_clip();
+        assert!(app.clip.is_none(), "no selection, no clip");
+
+        app.selection = Some(Selection::from_corners((2, 2), (4, 4)));
+        app.toggle_clip();
+        app.active_tool = ToolKind::Line;
+        app.apply_tool(0, 3);
+        app.apply_tool(10, 3);
+        for x in 0..=10 {
+            let painted = !app.canvas.get(x, 3).unwrap().is_empty();
+            assert_eq!(painted, (2..=4).contains(&x), "x={}", x);
+        }
+        // One undo step, covering only the clipped cells
+        app.undo();
+        assert!((0..=10).all(|x| app.canvas.get(x, 3).unwrap().is_empty()));
+
+        app.toggle_clip();
+        assert!(app.clip.is_none());
+        app.active_tool = ToolKind::Pencil;
+        app.apply_tool(0, 0);
+        assert!(!app.canvas.get(0, 0).unwrap().is_empty(), "no clip, unchanged behavior");
+    }
+
+    #[test]
+    fn test_swap_colors() {
+        let red = Rgb::new(255, 0, 0);
+        let blue = Rgb::new(0, 0, 255);
+        let mut app = App::new();
+        app.color = red;
+        // No background yet: fg is kept and copied to bg
+        app.swap_colors();
+        assert_eq!((app.color, app.bg_color), (red, Some(red)));
+        app.color = blue;
+        app.swap_colors();
+        assert_eq!((app.color, app.bg_color), (red, Some(blue)));
+
+        app.active_tool = ToolKind::Pencil;
+        app.apply_tool(2, 2);
+        assert_eq!(app.canvas.get(2, 2).unwrap().bg, Some(blue));
+        app.clear_bg_color();
+        app.apply_tool(3, 2);
+        assert_eq!(app.canvas.get(3, 2).unwrap().bg, None);
+    }
+
+    #[test]
+    fn test_nearest_palette_color() {
+        let mut app = App::new();
+        let first = palette::DEFAULT_PALETTE[0];
+        assert_eq!(app.nearest_palette_color(first), Some((first, 0.0)));
+
+        app.custom_palette = Some(palette::CustomPalette {
+            name: "two".to_string(),
+            colors: vec![Rgb::new(0, 0, 0), Rgb::new(200, 0, 0)],
+        });
+        let (nearest, distance) = app.nearest_palette_color(Rgb::new(203, 4, 0)).unwrap();
+        assert_eq!(nearest, Rgb::new(200, 0, 0));
+        assert_eq!(distance, 5.0);
+
+        app.custom_palette.as_mut().unwrap().colors.clear();
+        assert_eq!(app.nearest_palette_color(first), None);
+    }
+
+    #[test]
+    fn test_paint_bg_channel_keeps_glyph() {
+        let red = Rgb::new(255, 0, 0);
+        let blue = Rgb::new(0, 0, 255);
+        let mut app = App::new();
+        assert!(!app.paint_bg);
+        app.active_tool = ToolKind::Pencil;
+        app.active_block = blocks::UPPER_HALF;
+        app.color = red;
+        app.apply_tool(2, 2);
+        let drawn = app.canvas.get(2, 2).unwrap();
+
+        app.toggle_paint_channel();
+        app.color = blue;
+        app.active_block = blocks::FULL;
+        app.apply_tool(2, 2);
+        let cell = app.canvas.get(2, 2).unwrap();
+        assert_eq!((cell.ch, cell.fg, cell.bg), (drawn.ch, drawn.fg, Some(blue)));
+
+        // Undo only takes back the background paint
+        app.undo();
+        assert_eq!(app.canvas.get(2, 2).unwrap(), drawn);
+
+        app.apply_tool(2, 2);
+        app.active_tool = ToolKind::Eraser;
+        app.apply_tool(2, 2);
+        assert_eq!(app.canvas.get(2, 2).unwrap(), drawn);
+
+        app.toggle_paint_channel();
+        app.active_tool = ToolKind::Pencil;
+        app.apply_tool(4, 4);
+        assert_eq!(app.canvas.get(4, 4).unwrap().fg, Some(blue));
+    }
+
+    #[test]
+    fn test_remove_background_is_one_undo_step() {
+        let mut app = App::new();
+        let bg = crate::cell::Cell { ch: blocks::FULL, fg: Some(Rgb::new(0, 0, 255)), bg: None };
+        for y in 0..app.canvas.height {
+            for x in 0..app.canvas.width {
+                app.canvas.set(x, y, bg);
+            }
+        }
+        app.canvas.set(5, 5, crate::cell::Cell { ch: blocks::FULL, fg: Some(Rgb::new(255, 0, 0)), bg: None });
+
+        app.remove_background();
+        assert!(app.canvas.get(0, 0).unwrap().is_empty());
+        assert!(!app.canvas.get(5, 5).unwrap().is_empty());
+        assert_eq!(app.history.position(), 1);
+
+        app.undo();
+        assert_eq!(app.canvas.get(0, 0).unwrap(), bg);
+    }
+
+    #[test]
+    fn test_replace_color_under_cursor_is_one_undo_step() {
+        let mut app = App::new();
+        let red = crate::cell::Cell { ch: blocks::FULL, fg: Some(Rgb::new(255, 0, 0)), bg: None };
+        app.canvas.set(1, 1, red);
+        app.canvas.set(6, 3, red);
+        app.color = Rgb::new(0, 0, 255);
+        app.cursor = Some((1, 1));
+
+        app.replace_color_under_cursor();
+        assert_eq!(app.canvas.get(6, 3).unwrap().fg, Some(Rgb::new(0, 0, 255)));
+        assert_eq!(app.history.position(), 1);
+
+        app.undo();
+        assert_eq!(app.canvas.get(6, 3).unwrap(), red);
+    }
+
+    #[test]
+    fn test_view_rotation_is_view_only() {
+        let mut app = App::new();
+        app.apply_tool(1, 1);
+        let before = app.canvas.cells();
+        let position = app.history.position();
+
+        app.cycle_view_rotation();
+        assert_eq!(app.view_rotation, 90);
+        app.apply_tool(2, 2);
+        assert!(app.canvas.get(2, 2).unwrap().is_empty(), "drawing is disabled while rotated");
+
+        for _ in 0..3 {
+            app.cycle_view_rotation();
+        }
+        assert_eq!(app.view_rotation, 0);
+        assert_eq!(app.canvas.cells(), before);
+        assert_eq!(app.history.position(), position);
+    }
+
+    #[test]
+    fn test_toggle_isolate_color() {
+        let mut app = App::new();
+        assert!(!app.isolate_color);
+        app.toggle_isolate_color();
+        assert!(app.isolate_color);
+        app.toggle_isolate_color();
+        assert!(!app.isolate_color);
+    }
+
+    #[test]
+    fn test_isolate_color_does_not_touch_canvas() {
+        let mut app = App::new();
+        app.apply_tool(1, 1);
+        let before = app.canvas.cells();
+        app.toggle_isolate_color();
+        app.toggle_isolate_color();
+        assert_eq!(app.canvas.cells(), before);
+        // Drawing still works normally once disabled
+        app.apply_tool(2, 2);
+        assert_eq!(app.canvas.get(2, 2).unwrap().fg, Some(app.color));
+    }
+
+    #[test]
+    fn test_truecolor_survives_open_save_roundtrip() {
+        let path = std::env::temp_dir().join(format!("kaku_app_truecolor_{}.kaku", std::process::id()));
+        let teal = Rgb::new(0x12, 0x34, 0x56);
+        let mut canvas = Canvas::new();
+        canvas.set(3, 4, crate::cell::Cell { ch: blocks::FULL, fg: Some(teal), bg: Some(teal) });
+        let mut project = Project::new("tc", canvas, teal, SymmetryMode::empty());
+        project.save_to_file(&path).unwrap();
+        let before: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+
+        let mut app = App::new();
+        app.load_project(path.to_str().unwrap());
+        assert_eq!(app.canvas.get(3, 4).unwrap().fg, Some(teal));
+        assert!(app.save_project());
+
+        let after: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(before["canvas"], after["canvas"]);
+        assert_eq!(before["color"], after["color"]);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_project_settings_roundtrip() {
+        let path = std::env::temp_dir().join(format!("kaku_app_settings_{}.kaku", std::process::id()));
+        let mut app = App::new();
+        app.active_tool = ToolKind::Rectangle;
+        app.active_block = blocks::SHADE_DARK;
+        app.filled_rect = true;
+        app.project_path = Some(path.to_string_lossy().to_string());
+        assert!(app.save_project());
+
+        let mut reopened = App::new();
+        reopened.load_project(path.to_str().unwrap());
+        assert_eq!(reopened.active_tool, ToolKind::Rectangle);
+        assert_eq!(reopened.active_block, blocks::SHADE_DARK);
+        assert!(reopened.filled_rect);
+
+        // Older files without settings, and unknown tools, keep the defaults
+        let mut fresh = App::new();
+        fresh.apply_project_settings(&ProjectSettings { tool: Some("Lasso".to_string()), ..Default::default() });
+        assert_eq!(fresh.active_tool, ToolKind::Pencil);
+        let _ = std::fs::remove_file(&path);
+    }
+
     #[test]
     fn test_recent_colors_tracking() {
         let mut app = App::new();
@@ -1236,6 +2644,49 @@ mod tests {
         assert_eq!(app.recent_colors[0], Rgb::new(225, 0, 0));
     }
 
+    #[test]
+    fn test_recent_capacity_configurable() {
+        let mut app = App::new();
+        app.set_recent_capacity(20);
+        for i in 0..25u8 {
+            app.track_recent_color(Rgb::new(i, 0, 0));
+        }
+        assert_eq!(app.recent_colors.len(), 20);
+
+        app.set_recent_capacity(1);
+        assert_eq!(app.recent_capacity, MIN_RECENT_CAPACITY);
+        // Shrinking keeps the newest entries in order
+        assert_eq!(app.recent_colors, (21..25u8).rev().map(|i| Rgb::new(i, 0, 0)).collect::<Vec<_>>());
+        app.set_recent_capacity(100);
+        assert_eq!(app.recent_capacity, MAX_RECENT_CAPACITY);
+        assert_eq!(app.recent_colors.len(), 4);
+    }
+
+    #[test]
+    fn test_recent_capacity_shrink_keeps_cursor_item() {
+        let mut app = App::new();
+        app.set_recent_capacity(16);
+        for i in 0..16u8 {
+            app.track_recent_color(Rgb::new(i, 0, 0));
+        }
+        // Cursor on the Standard header, below the recent section
+        let standard = |app: &App| app.palette_layout.iter()
+            .position(|item| matches!(item, PaletteItem::SectionHeader(PaletteSection::Standard)))
+            .unwrap();
+        app.palette_cursor = standard(&app);
+        app.set_recent_capacity(6);
+        assert_eq!(app.palette_cursor, standard(&app));
+
+        // Cursor on a dropped recent color lands on the last kept one
+        app.set_recent_capacity(16);
+        for i in 0..16u8 {
+            app.track_recent_color(Rgb::new(i, 1, 0));
+        }
+        app.palette_cursor = standard(&app) - 1;
+        app.set_recent_capacity(4);
+        assert!(matches!(app.palette_layout[app.palette_cursor], PaletteItem::Color(c) if c == app.recent_colors[3]));
+    }
+
     #[test]
     fn test_recent_colors_palette_layout() {
         let mut app = App::new();
@@ -1402,6 +2853,289 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_onion_skin_is_render_only() {
+        let dir = std::env::temp_dir().join(format!("kaku_onion_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("ghost.kaku");
+        let mut ghost = Canvas::new_with_size(80, 10);
+        ghost.set(1, 1, crate::cell::Cell { ch: blocks::FULL, fg: Some(Rgb::new(255, 0, 0)), bg: None });
+        ghost.set(70, 5, crate::cell::Cell { ch: blocks::FULL, fg: Some(Rgb::new(0, 255, 0)), bg: None });
+        Project::new("ghost", ghost, Rgb::WHITE, SymmetryMode::empty())
+            .save_to_file(&path)
+            .unwrap();
+
+        let mut app = App::new();
+        app.load_onion_skin(path.to_str().unwrap());
+        let onion = app.onion_skin.as_ref().expect("onion skin loaded");
+        assert!(onion.cell_at(1, 1).is_some());
+        assert!(onion.cell_at(0, 0).is_none());
+        assert_eq!(
+            app.status_message.as_ref().unwrap().level,
+            MessageLevel::Warning,
+            "wider skin is reported as clipped"
+        );
+        assert!(app.canvas.get(1, 1).unwrap().is_empty());
+        assert!(!app.dirty);
+        assert!(!app.history.can_undo());
+
+        app.toggle_onion_skin();
+        assert!(app.onion_skin.as_ref().unwrap().cell_at(1, 1).is_none());
+        app.clear_onion_skin();
+        assert!(app.onion_skin.is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_select_tool_sets_selection_without_drawing() {
+        let mut app = App::new();
+        app.active_tool = ToolKind::Select;
+        app.apply_tool(6, 4);
+        app.apply_tool(2, 3);
+        assert_eq!(app.selection, Some(Selection { x: 2, y: 3, width: 5, height: 2 }));
+        assert!(!app.history.can_undo());
+        assert!(app.canvas.get(2, 3).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_flip_canvas_is_one_undo_step() {
+        let mut app = App::new();
+        let upper = crate::cell::Cell { ch: blocks::UPPER_HALF, fg: Some(Rgb::new(255, 0, 0)), bg: None };
+        app.canvas.set(1, 0, upper);
+        let (w, h) = (app.canvas.width, app.canvas.height);
+        app.flip_canvas(false);
+        assert_eq!(app.canvas.get(1, h - 1).unwrap().ch, blocks::LOWER_HALF);
+        app.flip_canvas(true);
+        assert_eq!(app.canvas.get(w - 2, h - 1).unwrap().ch, blocks::LOWER_HALF);
+        app.undo();
+        app.undo();
+        assert_eq!(app.canvas.get(1, 0), Some(upper));
+    }
+
+    #[test]
+    fn test_radial_symmetry_cycles_and_replicates_dabs() {
+        let mut app = App::new();
+        let seen: Vec<Option<usize>> = (0..5).map(|_| { app.cycle_radial_order(); app.radial_order }).collect();
+        assert_eq!(seen, vec![Some(3), Some(4), Some(6), Some(8), None]);
+
+        app.radial_order = Some(4);
+        app.active_tool = ToolKind::Pencil;
+        app.apply_tool(20, 10);
+        let drawn = app.canvas.cells().iter().flatten().filter(|c| !c.is_empty()).count();
+        assert_eq!(drawn, 4);
+    }
+
+    #[test]
+    fn test_shift_mode_commits_one_step_or_cancels() {
+        let mut app = App::new();
+        let red = crate::cell::Cell { ch: blocks::FULL, fg: Some(Rgb::new(255, 0, 0)), bg: None };
+        let (w, h) = (app.canvas.width, app.canvas.height);
+        app.canvas.set(0, 0, red);
+
+        app.begin_shift();
+        app.nudge_shift(-1, 0);
+        assert!(app.canvas.is_empty(), "dropped off the left edge");
+        app.toggle_shift_wrap();
+        assert_eq!(app.canvas.get(w - 1, 0), Some(red));
+        app.nudge_shift(0, -1);
+        app.apply_shift();
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.canvas.get(w - 1, h - 1), Some(red));
+        app.undo();
+        assert_eq!(app.canvas.get(0, 0), Some(red));
+
+        app.begin_shift();
+        app.nudge_shift(3, 3);
+        app.cancel_shift();
+        assert_eq!(app.canvas.get(0, 0), Some(red));
+        assert!(app.history.can_redo(), "cancel commits nothing, so the undone shift is still redoable");
+    }
+
+    #[test]
+    fn test_crop_to_content_is_one_undo_step() {
+        let mut app = App::new();
+        app.crop_to_content();
+        assert!(!app.history.can_undo(), "empty canvas: no-op");
+
+        let (w, h) = (app.canvas.width, app.canvas.height);
+        let red = crate::cell::Cell { ch: blocks::FULL, fg: Some(Rgb::new(255, 0, 0)), bg: None };
+        app.canvas.set(12, 9, red);
+        app.crop_to_content();
+        assert_eq!((app.canvas.width, app.canvas.height), (8, 8));
+        assert_eq!(app.canvas.get(0, 0), Some(red));
+        app.undo();
+        assert_eq!((app.canvas.width, app.canvas.height), (w, h));
+        assert_eq!(app.canvas.get(12, 9), Some(red));
+    }
+
+    #[test]
+    fn test_rotate_canvas_swaps_size_and_undoes() {
+        let mut app = App::new();
+        let (w, h) = (app.canvas.width, app.canvas.height);
+        let red = crate::cell::Cell { ch: blocks::FULL, fg: Some(Rgb::new(255, 0, 0)), bg: None };
+        app.canvas.set(0, 0, red);
+        app.viewport_x = 3;
+        app.rotate_canvas(true);
+        assert_eq!((app.canvas.width, app.canvas.height), (h, w));
+        assert_eq!(app.canvas.get(h - 1, 0), Some(red));
+        assert_eq!(app.viewport_x, 0);
+        app.undo();
+        assert_eq!((app.canvas.width, app.canvas.height), (w, h));
+        assert_eq!(app.canvas.get(0, 0), Some(red));
+    }
+
+    #[test]
+    fn test_paste_is_one_undo_step_clipped_to_canvas() {
+        let mut app = App::new();
+        let red = crate::cell::Cell { ch: blocks::FULL, fg: Some(Rgb::new(255, 0, 0)), bg: None };
+        for (x, y) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+            app.canvas.set(x, y, red);
+        }
+        app.selection = Some(Selection::from_corners((0, 0), (2, 1)));
+        app.copy_selection();
+        assert_eq!(app.clipboard_cells.as_ref().map(|c| (c[0].len(), c.len())), Some((3, 2)));
+
+        let (w, h) = (app.canvas.width, app.canvas.height);
+        app.canvas_cursor = (w - 1, h - 1);
+        app.canvas_cursor_active = true;
+        app.paste_clipboard();
+        assert_eq!(app.canvas.get(w - 1, h - 1), Some(red));
+
+        app.canvas_cursor = (4, 4);
+        app.paste_clipboard();
+        assert_eq!(app.canvas.get(5, 5), Some(red));
+        assert!(app.canvas.get(6, 4).unwrap().is_empty());
+        app.undo();
+        assert!(app.canvas.get(4, 4).unwrap().is_empty());
+        assert!(app.canvas.get(5, 5).unwrap().is_empty());
+        assert_eq!(app.canvas.get(w - 1, h - 1), Some(red));
+    }
+
+    #[test]
+    fn test_paste_respects_clip() {
+        let mut app = App::new();
+        let red = crate::cell::Cell { ch: blocks::FULL, fg: Some(Rgb::new(255, 0, 0)), bg: None };
+        for (x, y) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+            app.canvas.set(x, y, red);
+        }
+        app.selection = Some(Selection::from_corners((0, 0), (1, 1)));
+        app.copy_selection();
+        app.selection = Some(Selection::from_corners((5, 5), (9, 9)));
+        app.toggle_clip();
+
+        app.canvas_cursor = (4, 4);
+        app.canvas_cursor_active = true;
+        app.paste_clipboard();
+        assert_eq!(app.canvas.get(5, 5), Some(red));
+        for (x, y) in [(4, 4), (5, 4), (4, 5)] {
+            assert!(app.canvas.get(x, y).unwrap().is_empty(), "({}, {}) is outside the clip", x, y);
+        }
+    }
+
+    #[test]
+    fn test_export_transforms_leave_canvas_alone() {
+        let mut app = App::new();
+        let red = crate::cell::Cell { ch: blocks::FULL, fg: Some(Rgb::new(255, 0, 0)), bg: None };
+        app.canvas.set(1, 1, red);
+        app.add_export_transform(ExportTransform::Invert);
+        app.add_export_transform(ExportTransform::HueShift(180));
+
+        assert_eq!(app.export_canvas().get(1, 1).unwrap().fg, Some(Rgb::new(255, 0, 0)));
+        app.export_transforms.pop();
+        assert_eq!(app.export_canvas().get(1, 1).unwrap().fg, Some(Rgb::new(0, 255, 255)));
+        assert_eq!(app.canvas.get(1, 1), Some(red));
+
+        app.clear_export_transforms();
+        assert_eq!(app.export_canvas().get(1, 1), Some(red));
+    }
+
+    #[test]
+    fn test_export_selection_matches_selection_size() {
+        let mut app = App::new();
+        app.toggle_export_selection();
+        assert!(!app.export_selection, "disabled without a selection");
+
+        app.selection = Some(Selection::from_corners((1, 1), (3, 2)));
+        app.toggle_export_selection();
+        assert!(app.export_selection);
+        let canvas = app.export_canvas();
+        assert_eq!((canvas.width, canvas.height), (3, 2));
+
+        app.clear_selection();
+        assert!(!app.export_selection);
+        assert_eq!(app.export_canvas().width, app.canvas.width);
+    }
+
+    #[test]
+    fn test_selection_grow_clamps_and_shrink_stops_at_one_cell() {
+        let sel = Selection::from_corners((1, 2), (4, 4));
+        assert_eq!(sel.resized(1, 48, 32), Selection::from_corners((0, 1), (5, 5)));
+        // Past the edges it clamps instead of wrapping
+        assert_eq!(sel.resized(10, 8, 8), Selection::from_corners((0, 0), (7, 7)));
+
+        assert_eq!(sel.resized(-1, 48, 32), Selection { x: 2, y: 3, width: 2, height: 1 });
+        let tiny = sel.resized(-5, 48, 32);
+        assert_eq!((tiny.width, tiny.height), (1, 1));
+        assert_eq!(tiny.resized(-1, 48, 32), tiny);
+
+        let mut app = App::new();
+        app.resize_selection(1);
+        assert!(app.selection.is_none());
+        app.selection = Some(sel);
+        app.resize_selection(2);
+        assert_eq!(app.selection, Some(Selection::from_corners((0, 0), (6, 6))));
+    }
+
+    #[test]
+    fn test_cursor_step_moves_and_clamps_at_edges() {
+        let mut app = App::new();
+        app.cycle_cursor_step();
+        app.cycle_cursor_step();
+        assert_eq!(app.cursor_step, 10);
+
+        app.canvas_cursor = (3, 3);
+        app.move_canvas_cursor(-1, 0);
+        assert_eq!(app.canvas_cursor, (0, 3), "clamps at the left edge");
+        app.move_canvas_cursor(0, -1);
+        assert_eq!(app.canvas_cursor, (0, 0));
+        for _ in 0..10 {
+            app.move_canvas_cursor(1, 1);
+        }
+        assert_eq!(app.canvas_cursor, (app.canvas.width - 1, app.canvas.height - 1));
+        assert!(app.canvas_cursor_active);
+
+        app.cycle_cursor_step();
+        assert_eq!(app.cursor_step, 1);
+    }
+
+    #[test]
+    fn test_follow_cursor_centers_until_the_edge() {
+        let mut app = App::new();
+        app.canvas = Canvas::new_with_size(100, 80);
+        app.viewport_w = 20;
+        app.viewport_h = 10;
+        assert!(!app.follow_cursor, "off by default");
+
+        app.toggle_follow_cursor();
+        app.canvas_cursor = (49, 40);
+        app.move_canvas_cursor(1, 0);
+        assert_eq!((app.viewport_x, app.viewport_y), (40, 35), "cursor at the center");
+
+        // Near the edges the view stops at the boundary
+        app.canvas_cursor = (1, 1);
+        app.move_canvas_cursor(-1, -1);
+        assert_eq!((app.viewport_x, app.viewport_y), (0, 0));
+        app.canvas_cursor = (98, 78);
+        app.move_canvas_cursor(1, 1);
+        assert_eq!((app.viewport_x, app.viewport_y), (80, 70));
+
+        // Without follow the view only moves once the cursor leaves it
+        app.toggle_follow_cursor();
+        app.move_canvas_cursor(-1, 0);
+        assert_eq!(app.viewport_x, 80);
+    }
+
     #[test]
     fn test_startup_hint_true_on_new() {
         let app = App::new();
@@ -1422,4 +3156,39 @@ mod tests {
             "Go to Coordinate not found in COMMANDS"
         );
     }
+
+    #[test]
+    fn test_history_timeline_labels_tools() {
+        let mut app = App::new();
+        app.active_tool = ToolKind::Pencil;
+        app.begin_stroke();
+        app.apply_tool(0, 0);
+        app.apply_tool(1, 0);
+        app.end_stroke();
+        app.active_tool = ToolKind::Fill;
+        app.apply_tool(5, 5);
+        let timeline = app.history.timeline();
+        assert_eq!(timeline[0], "pencil x2");
+        assert!(timeline[1].starts_with("fill x"));
+    }
+
+    #[test]
+    fn test_jump_to_history() {
+        let mut app = App::new();
+        app.active_tool = ToolKind::Pencil;
+        for x in 0..3 {
+            app.apply_tool(x, 0);
+        }
+        app.open_history_timeline();
+        assert_eq!(app.mode, AppMode::HistoryTimeline);
+        assert_eq!(app.timeline_cursor, 3);
+
+        app.jump_to_history(0);
+        assert!(app.canvas.get(0, 0).unwrap().is_empty());
+        assert!(app.history.can_redo());
+
+        app.jump_to_history(2);
+        assert!(!app.canvas.get(1, 0).unwrap().is_empty());
+        assert!(app.canvas.get(2, 0).unwrap().is_empty());
+    }
 }
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::cell::Cell;
+use crate::cell::{Cell, Rgb};
 
 pub const DEFAULT_WIDTH: usize = 48;
 pub const DEFAULT_HEIGHT: usize = 32;
@@ -10,6 +10,17 @@ pub const MAX_DIMENSION: usize = 128;
 fn default_width() -> usize { DEFAULT_WIDTH }
 fn default_height() -> usize { DEFAULT_HEIGHT }
 
+/// How `Canvas::scaled` computes each output cell.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Interpolation {
+    /// Copy the nearest source cell unchanged (keeps pixel art crisp)
+    #[default]
+    Nearest,
+    /// Blend neighboring cells' colors in RGB (averaging when shrinking),
+    /// then snap each blend to the closest palette color
+    Bilinear,
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Canvas {
     cells: Vec<Vec<Cell>>,
@@ -70,6 +81,133 @@ impl Canvas {
         self.height = height;
     }
 
+    /// Copy of the `width`x`height` region at (x, y), clipped to the canvas.
+    /// Unlike `new_with_size`, the result keeps the exact region size, even
+    /// below MIN_DIMENSION, so exports of small regions aren't padded.
+    pub fn subcanvas(&self, x: usize, y: usize, width: usize, height: usize) -> Canvas {
+        let x = x.min(self.width);
+        let y = y.min(self.height);
+        let w = width.min(self.width - x);
+        let h = height.min(self.height - y);
+        let cells = self.cells[y..y + h].iter().map(|row| row[x..x + w].to_vec()).collect();
+        Canvas { cells, width: w, height: h }
+    }
+
+    /// Stamp `src` onto this canvas with its top-left corner at (x, y).
+    /// Cells falling outside the canvas are dropped.
+    pub fn blit(&mut self, src: &Canvas, x: usize, y: usize) {
+        for (sy, row) in src.cells.iter().enumerate() {
+            for (sx, &cell) in row.iter().enumerate() {
+                self.set(x + sx, y + sy, cell);
+            }
+        }
+    }
+
+    /// Mirror the canvas left-to-right, mirroring each glyph too.
+    pub fn flip_horizontal(&mut self) {
+        for row in &mut self.cells {
+            row.reverse();
+            for cell in row.iter_mut() {
+                *cell = cell.flipped_horizontal();
+            }
+        }
+    }
+
+    /// Replace every color with its negative, snapped to the nearest
+    /// xterm-256 entry. Empty cells stay empty.
+    pub fn invert_colors(&mut self) {
+        let invert = |c: Option<Rgb>| c.map(|c| crate::palette::nearest_color(255 - c.r, 255 - c.g, 255 - c.b));
+        for cell in self.cells.iter_mut().flatten() {
+            if !cell.is_empty() {
+                *cell = Cell { fg: invert(cell.fg), bg: invert(cell.bg), ..*cell };
+            }
+        }
+    }
+
+    /// Blend every color `amount` percent (0-100) toward its luminance gray,
+    /// snapped to the xterm grayscale ramp (232-255).
+    pub fn desaturate(&mut self, amount: u8) {
+        let t = amount.min(100) as f32 / 100.0;
+        let desaturate = |c: Option<Rgb>| {
+            c.map(|c| {
+                let step = ((c.luminance() - 8.0) / 10.0).round().clamp(0.0, 23.0) as u8;
+                let gray = crate::cell::color256_to_rgb(232 + step);
+                let mix = |v: u8, g: u8| (v as f32 + (g as f32 - v as f32) * t).round() as u8;
+                Rgb::new(mix(c.r, gray.r), mix(c.g, gray.g), mix(c.b, gray.b))
+            })
+        };
+        for cell in self.cells.iter_mut().flatten() {
+            if !cell.is_empty() {
+                *cell = Cell { fg: desaturate(cell.fg), bg: desaturate(cell.bg), ..*cell };
+            }
+        }
+    }
+
+    /// Mirror the canvas top-to-bottom, mirroring each glyph too.
+    pub fn flip_vertical(&mut self) {
+        self.cells.reverse();
+        for cell in self.cells.iter_mut().flatten() {
+            *cell = cell.flipped_vertical();
+        }
+    }
+
+    /// Shrink the canvas to the bounding box of its drawn cells, moved to the
+    /// origin. Sizes below MIN_DIMENSION are padded on the right and bottom.
+    /// Returns the box's old top-left corner, or None (unchanged) when empty.
+    pub fn crop_to_content(&mut self) -> Option<(usize, usize)> {
+        let (min_x, min_y, max_x, max_y) = crate::export::bounding_box(self)?;
+        let content = self.subcanvas(min_x, min_y, max_x - min_x + 1, max_y - min_y + 1);
+        *self = Canvas::new_with_size(content.width, content.height);
+        self.blit(&content, 0, 0);
+        Some((min_x, min_y))
+    }
+
+    /// Move every cell by (dx, dy). With `wrap`, cells pushed off one edge
+    /// come back on the opposite one; otherwise they are dropped and the
+    /// vacated cells are blank.
+    pub fn shift(&mut self, dx: isize, dy: isize, wrap: bool) {
+        let (w, h) = (self.width as isize, self.height as isize);
+        let source = |v: isize, d: isize, len: isize| {
+            let s = v - d;
+            if wrap {
+                Some(s.rem_euclid(len) as usize)
+            } else {
+                (0..len).contains(&s).then_some(s as usize)
+            }
+        };
+        self.cells = (0..h)
+            .map(|y| {
+                (0..w)
+                    .map(|x| match (source(x, dx, w), source(y, dy, h)) {
+                        (Some(sx), Some(sy)) => self.cells[sy][sx],
+                        _ => Cell::default(),
+                    })
+                    .collect()
+            })
+            .collect();
+    }
+
+    /// Turn the canvas a quarter clockwise; width and height swap.
+    pub fn rotate_cw(&mut self) {
+        let (w, h) = (self.width, self.height);
+        self.cells = (0..w)
+            .map(|x| (0..h).rev().map(|y| self.cells[y][x].rotated_cw()).collect())
+            .collect();
+        self.width = h;
+        self.height = w;
+    }
+
+    /// Turn the canvas a quarter counter-clockwise; width and height swap.
+    pub fn rotate_ccw(&mut self) {
+        let (w, h) = (self.width, self.height);
+        self.cells = (0..w)
+            .rev()
+            .map(|x| (0..h).map(|y| self.cells[y][x].rotated_ccw()).collect())
+            .collect();
+        self.width = h;
+        self.height = w;
+    }
+
     /// Resize the canvas, preserving existing content where it overlaps.
     pub fn resize(&mut self, new_width: usize, new_height: usize) {
         let w = new_width.clamp(MIN_DIMENSION, MAX_DIMENSION);
@@ -86,6 +224,118 @@ impl Canvas {
     }
 }
 
+impl Canvas {
+    /// A copy scaled to `width`x`height` (clamped like `new_with_size`).
+    /// Glyphs always come from the nearest source cell; `interp` decides
+    /// whether colors are copied or blended. Blends snap to `palette`, or to
+    /// the colors already on this canvas when it's `None`.
+    pub fn scaled(
+        &self,
+        width: usize,
+        height: usize,
+        interp: Interpolation,
+        palette: Option<&[Rgb]>,
+    ) -> Canvas {
+        let mut out = Canvas::new_with_size(width, height);
+        let own;
+        let palette = match palette {
+            Some(p) => p,
+            None => {
+                own = self.colors();
+                &own[..]
+            }
+        };
+        for oy in 0..out.height {
+            let y_weights = axis_weights(oy, self.height, out.height);
+            for ox in 0..out.width {
+                let base = self.cells[nearest_index(oy, self.height, out.height)]
+                    [nearest_index(ox, self.width, out.width)];
+                let cell = match interp {
+                    Interpolation::Nearest => base,
+                    Interpolation::Bilinear => {
+                        let x_weights = axis_weights(ox, self.width, out.width);
+                        let blend = |channel: fn(&Cell) -> Option<Rgb>| {
+                            let mut sum = [0.0f64; 3];
+                            let mut total = 0.0;
+                            for &(sy, wy) in &y_weights {
+                                for &(sx, wx) in &x_weights {
+                                    if let Some(c) = channel(&self.cells[sy][sx]) {
+                                        let w = wx * wy;
+                                        sum[0] += c.r as f64 * w;
+                                        sum[1] += c.g as f64 * w;
+                                        sum[2] += c.b as f64 * w;
+                                        total += w;
+                                    }
+                                }
+                            }
+                            if total == 0.0 {
+                                return None;
+                            }
+                            let mix = Rgb::new(
+                                (sum[0] / total).round() as u8,
+                                (sum[1] / total).round() as u8,
+                                (sum[2] / total).round() as u8,
+                            );
+                            Some(crate::palette::nearest_in(palette, mix).map_or(mix, |(c, _)| c))
+                        };
+                        // Only blend the channels the base cell shows, and
+                        // ignore fg of blank cells (it isn't visible)
+                        let fg_of = |c: &Cell| if c.is_empty() { None } else { c.fg };
+                        Cell {
+                            fg: base.fg.and(blend(fg_of).or(base.fg)),
+                            bg: base.bg.and(blend(|c| c.bg).or(base.bg)),
+                            ..base
+                        }
+                    }
+                };
+                out.cells[oy][ox] = cell;
+            }
+        }
+        out
+    }
+
+    /// Every distinct visible color: fg of drawn cells and any bg.
+    fn colors(&self) -> Vec<Rgb> {
+        let mut colors = Vec::new();
+        for cell in self.cells.iter().flatten() {
+            let fg = if cell.is_empty() { None } else { cell.fg };
+            for c in [fg, cell.bg].into_iter().flatten() {
+                if !colors.contains(&c) {
+                    colors.push(c);
+                }
+            }
+        }
+        colors
+    }
+}
+
+/// Source index whose center is closest to output cell `o`'s center.
+fn nearest_index(o: usize, src: usize, dst: usize) -> usize {
+    ((2 * o + 1) * src / (2 * dst)).min(src - 1)
+}
+
+/// Source cells and weights for output cell `o` along one axis: linear
+/// interpolation between the two nearest centers when enlarging, the
+/// covered cells weighted by overlap when shrinking.
+fn axis_weights(o: usize, src: usize, dst: usize) -> Vec<(usize, f64)> {
+    let ratio = src as f64 / dst as f64;
+    if dst >= src {
+        let pos = ((o as f64 + 0.5) * ratio - 0.5).clamp(0.0, (src - 1) as f64);
+        let i = pos.floor() as usize;
+        let t = pos - i as f64;
+        if t == 0.0 {
+            vec![(i, 1.0)]
+        } else {
+            vec![(i, 1.0 - t), (i + 1, t)]
+        }
+    } else {
+        let (start, end) = (o as f64 * ratio, (o + 1) as f64 * ratio);
+        (start.floor() as usize..(end.ceil() as usize).min(src))
+            .map(|i| (i, end.min(i as f64 + 1.0) - start.max(i as f64)))
+            .collect()
+    }
+}
+
 impl Default for Canvas {
     fn default() -> Self {
         Self::new()
@@ -100,6 +350,198 @@ mod tests {
     const RED: Option<Rgb> = Some(Rgb { r: 205, g: 0, b: 0 });
     const BLUE: Option<Rgb> = Some(Rgb { r: 0, g: 0, b: 238 });
 
+    #[test]
+    fn test_flip_vertical_swaps_half_blocks() {
+        let mut canvas = Canvas::new_with_size(8, 8);
+        let upper = Cell { ch: blocks::UPPER_HALF, fg: RED, bg: BLUE };
+        let lower = Cell { ch: blocks::LOWER_HALF, fg: BLUE, bg: None };
+        canvas.set(2, 0, upper);
+        canvas.set(5, 6, lower);
+        canvas.flip_vertical();
+        assert_eq!(canvas.get(2, 7), Some(Cell { ch: blocks::LOWER_HALF, fg: RED, bg: BLUE }));
+        assert_eq!(canvas.get(5, 1), Some(Cell { ch: blocks::UPPER_HALF, fg: BLUE, bg: None }));
+        assert!(canvas.get(2, 0).unwrap().is_empty());
+
+        canvas.flip_vertical();
+        assert_eq!(canvas.get(2, 0), Some(upper));
+        assert_eq!(canvas.get(5, 6), Some(lower));
+    }
+
+    #[test]
+    fn test_invert_colors_white_becomes_black() {
+        let mut canvas = Canvas::new_with_size(4, 4);
+        canvas.set(1, 1, Cell { ch: blocks::FULL, fg: Some(Rgb::new(255, 255, 255)), bg: None });
+        canvas.invert_colors();
+        assert_eq!(canvas.get(1, 1), Some(Cell { ch: blocks::FULL, fg: Some(Rgb::BLACK), bg: None }));
+        assert!(canvas.get(0, 0).unwrap().is_empty(), "empty cells stay empty");
+    }
+
+    #[test]
+    fn test_desaturate_red_to_ramp_gray() {
+        let mut canvas = Canvas::new_with_size(4, 4);
+        canvas.set(1, 1, Cell { ch: blocks::FULL, fg: Some(Rgb::new(255, 0, 0)), bg: None });
+        let mut half = canvas.clone();
+
+        canvas.desaturate(100);
+        let gray = canvas.get(1, 1).unwrap().fg.unwrap();
+        assert_eq!((gray.r, gray.g, gray.b), (78, 78, 78), "ramp entry 239");
+
+        half.desaturate(50);
+        assert_eq!(half.get(1, 1).unwrap().fg, Some(Rgb::new(167, 39, 39)), "halfway to the ramp gray");
+        let mut none = half.clone();
+        none.desaturate(0);
+        assert_eq!(none.cells(), half.cells());
+    }
+
+    #[test]
+    fn test_flip_horizontal_mirrors_glyphs() {
+        let mut canvas = Canvas::new_with_size(8, 8);
+        let upper = Cell { ch: blocks::UPPER_HALF, fg: RED, bg: BLUE };
+        canvas.set(0, 3, Cell { ch: blocks::LEFT_HALF, fg: RED, bg: None });
+        canvas.set(1, 3, upper);
+        canvas.set(2, 3, Cell { ch: blocks::LEFT_7_8, fg: RED, bg: BLUE });
+        canvas.set(3, 3, Cell { ch: blocks::LEFT_3_8, fg: RED, bg: None });
+        canvas.flip_horizontal();
+        assert_eq!(canvas.get(7, 3), Some(Cell { ch: blocks::RIGHT_HALF, fg: RED, bg: None }));
+        assert_eq!(canvas.get(6, 3), Some(upper), "vertical halves unchanged");
+        assert_eq!(canvas.get(5, 3), Some(Cell { ch: blocks::LEFT_1_8, fg: BLUE, bg: RED }));
+        assert_eq!(canvas.get(4, 3).unwrap().ch, blocks::LEFT_3_8, "no bg to swap in: kept");
+    }
+
+    #[test]
+    fn test_crop_to_content_moves_box_to_origin() {
+        let mut canvas = Canvas::new_with_size(40, 30);
+        assert_eq!(canvas.crop_to_content(), None);
+        assert_eq!((canvas.width, canvas.height), (40, 30));
+
+        let red = Cell { ch: blocks::FULL, fg: RED, bg: None };
+        canvas.set(10, 5, red);
+        canvas.set(21, 16, red);
+        assert_eq!(canvas.crop_to_content(), Some((10, 5)));
+        assert_eq!((canvas.width, canvas.height), (12, 12));
+        assert_eq!(canvas.get(0, 0), Some(red));
+        assert_eq!(canvas.get(11, 11), Some(red));
+
+        let mut small = Canvas::new_with_size(40, 30);
+        small.set(30, 20, red);
+        small.set(32, 21, red);
+        assert_eq!(small.crop_to_content(), Some((30, 20)));
+        assert_eq!((small.width, small.height), (MIN_DIMENSION, MIN_DIMENSION));
+        assert_eq!(small.get(2, 1), Some(red));
+        assert_eq!(small.cells().iter().flatten().filter(|c| !c.is_empty()).count(), 2);
+    }
+
+    /// 8x8 canvas with red at (0, 0) and blue at (7, 7).
+    fn corner_pattern() -> Canvas {
+        let mut canvas = Canvas::new_with_size(8, 8);
+        canvas.set(0, 0, Cell { ch: blocks::FULL, fg: RED, bg: None });
+        canvas.set(7, 7, Cell { ch: blocks::FULL, fg: BLUE, bg: None });
+        canvas
+    }
+
+    #[test]
+    fn test_shift_without_wrap_drops_cells() {
+        let mut canvas = corner_pattern();
+        canvas.shift(2, 1, false);
+        assert_eq!(canvas.get(2, 1).unwrap().fg, RED);
+        assert!(canvas.get(0, 0).unwrap().is_empty());
+        assert_eq!(canvas.cells().iter().flatten().filter(|c| !c.is_empty()).count(), 1, "blue fell off");
+
+        canvas.shift(-3, 0, false);
+        assert!(canvas.is_empty());
+    }
+
+    #[test]
+    fn test_shift_with_wrap_keeps_every_cell() {
+        let mut canvas = corner_pattern();
+        canvas.shift(2, 1, true);
+        assert_eq!(canvas.get(2, 1).unwrap().fg, RED);
+        assert_eq!(canvas.get(1, 0).unwrap().fg, BLUE, "(7,7) wraps to (1,0)");
+
+        canvas.shift(-3, -2, true);
+        assert_eq!(canvas.get(7, 7).unwrap().fg, RED);
+        assert_eq!(canvas.get(6, 6).unwrap().fg, BLUE);
+
+        canvas.shift(17, 9, true);
+        assert_eq!(canvas.get(0, 0).unwrap().fg, RED, "offsets beyond the size wrap again");
+    }
+
+    #[test]
+    fn test_rotate_four_times_returns_to_origin() {
+        let mut canvas = Canvas::new_with_size(16, 8);
+        let corner = Cell { ch: blocks::FULL, fg: RED, bg: None };
+        canvas.set(15, 0, corner);
+
+        canvas.rotate_cw();
+        assert_eq!((canvas.width, canvas.height), (8, 16));
+        assert_eq!(canvas.get(7, 15), Some(corner), "top-right goes to bottom-right");
+        for _ in 0..3 {
+            canvas.rotate_cw();
+        }
+        assert_eq!((canvas.width, canvas.height), (16, 8));
+        assert_eq!(canvas.get(15, 0), Some(corner));
+
+        canvas.rotate_ccw();
+        assert_eq!(canvas.get(0, 0), Some(corner), "top-right goes to top-left");
+        for _ in 0..3 {
+            canvas.rotate_ccw();
+        }
+        assert_eq!(canvas.get(15, 0), Some(corner));
+        assert_eq!(canvas.cells().iter().flatten().filter(|c| !c.is_empty()).count(), 1);
+    }
+
+    #[test]
+    fn test_rotate_turns_directional_glyphs() {
+        let mut canvas = Canvas::new_with_size(8, 8);
+        canvas.set(0, 0, Cell { ch: blocks::UPPER_HALF, fg: RED, bg: BLUE });
+        canvas.set(1, 0, Cell { ch: blocks::LOWER_1_4, fg: RED, bg: None });
+        canvas.set(2, 0, Cell { ch: blocks::LEFT_3_8, fg: RED, bg: BLUE });
+        canvas.rotate_cw();
+        assert_eq!(canvas.get(7, 0), Some(Cell { ch: blocks::RIGHT_HALF, fg: RED, bg: BLUE }));
+        assert_eq!(canvas.get(7, 1), Some(Cell { ch: blocks::LEFT_1_4, fg: RED, bg: None }));
+        assert_eq!(canvas.get(7, 2), Some(Cell { ch: blocks::LOWER_5_8, fg: BLUE, bg: RED }));
+
+        canvas.rotate_ccw();
+        assert_eq!(canvas.get(0, 0), Some(Cell { ch: blocks::UPPER_HALF, fg: RED, bg: BLUE }));
+        assert_eq!(canvas.get(1, 0), Some(Cell { ch: blocks::LOWER_1_4, fg: RED, bg: None }));
+        assert_eq!(canvas.get(2, 0), Some(Cell { ch: blocks::LEFT_3_8, fg: RED, bg: BLUE }));
+    }
+
+    #[test]
+    fn test_flip_vertical_complements_lower_fills() {
+        let mut canvas = Canvas::new_with_size(8, 8);
+        canvas.set(0, 0, Cell { ch: blocks::LOWER_1_4, fg: RED, bg: BLUE });
+        canvas.flip_vertical();
+        assert_eq!(canvas.get(0, 7), Some(Cell { ch: blocks::LOWER_3_4, fg: BLUE, bg: RED }));
+    }
+
+    #[test]
+    fn test_subcanvas_exact_size() {
+        let mut canvas = Canvas::new();
+        canvas.set(5, 6, Cell { ch: blocks::FULL, fg: RED, bg: None });
+        let sub = canvas.subcanvas(4, 5, 3, 2);
+        assert_eq!((sub.width, sub.height), (3, 2));
+        assert_eq!(sub.get(1, 1).unwrap().fg, RED);
+        assert_eq!(sub.get(0, 0), Some(Cell::default()));
+
+        // Clipped at the canvas edge
+        let edge = canvas.subcanvas(DEFAULT_WIDTH - 2, 0, 10, 1);
+        assert_eq!((edge.width, edge.height), (2, 1));
+    }
+
+    #[test]
+    fn test_blit_round_trips_subcanvas() {
+        let mut canvas = Canvas::new();
+        canvas.set(5, 6, Cell { ch: blocks::FULL, fg: RED, bg: None });
+        let sub = canvas.subcanvas(4, 5, 3, 2);
+
+        let mut target = Canvas::new_with_size(8, 8);
+        target.blit(&sub, 6, 0);
+        assert_eq!(target.get(7, 1).unwrap().fg, RED);
+        // The third column falls off the right edge
+        assert_eq!((target.width, target.height), (8, 8));
+    }
+
     #[test]
     fn test_new_canvas_is_empty() {
         let canvas = Canvas::new();
@@ -234,4 +676,64 @@ mod tests {
         canvas.clear();
         assert!(canvas.is_empty(), "Canvas after clear should be empty");
     }
+
+    fn fill(canvas: &mut Canvas, x0: usize, x1: usize, color: Rgb) {
+        for y in 0..canvas.height {
+            for x in x0..x1 {
+                canvas.set(x, y, Cell { ch: blocks::FULL, fg: Some(color), bg: None });
+            }
+        }
+    }
+
+    #[test]
+    fn test_scaled_nearest_copies_cells() {
+        let mut canvas = Canvas::new_with_size(8, 8);
+        fill(&mut canvas, 0, 4, Rgb::new(255, 0, 0));
+        fill(&mut canvas, 4, 8, Rgb::new(0, 0, 255));
+        let big = canvas.scaled(16, 16, Interpolation::default(), None);
+        assert_eq!((big.width, big.height), (16, 16));
+        for x in 0..16 {
+            let expected = canvas.get(x / 2, 0);
+            assert_eq!(big.get(x, 5), expected);
+        }
+    }
+
+    #[test]
+    fn test_scaled_bilinear_blends_then_snaps_to_palette() {
+        let (red, blue) = (Rgb::new(255, 0, 0), Rgb::new(0, 0, 255));
+        let purple = Rgb::new(128, 0, 128);
+        let mut canvas = Canvas::new_with_size(8, 8);
+        fill(&mut canvas, 0, 4, red);
+        fill(&mut canvas, 4, 8, blue);
+
+        // Across the seam, blends land on the in-between palette entry
+        let palette = [red, purple, blue];
+        let big = canvas.scaled(32, 8, Interpolation::Bilinear, Some(&palette));
+        assert_eq!(big.get(0, 0).unwrap().fg, Some(red));
+        assert_eq!(big.get(16, 0).unwrap().fg, Some(purple));
+        assert_eq!(big.get(31, 0).unwrap().fg, Some(blue));
+
+        // Without a palette every blend is one of the canvas's own colors
+        let own = canvas.scaled(32, 8, Interpolation::Bilinear, None);
+        for x in 0..32 {
+            let fg = own.get(x, 0).unwrap().fg.unwrap();
+            assert!(fg == red || fg == blue);
+        }
+    }
+
+    #[test]
+    fn test_scaled_bilinear_downscale_averages() {
+        let (red, blue) = (Rgb::new(255, 0, 0), Rgb::new(0, 0, 255));
+        let purple = Rgb::new(128, 0, 128);
+        let mut canvas = Canvas::new_with_size(16, 8);
+        // Alternating columns average to purple when halved
+        for x in 0..16 {
+            fill(&mut canvas, x, x + 1, if x % 2 == 0 { red } else { blue });
+        }
+        let small = canvas.scaled(8, 8, Interpolation::Bilinear, Some(&[red, purple, blue]));
+        assert!((0..8).all(|x| small.get(x, 3).unwrap().fg == Some(purple)));
+        // Empty cells stay empty
+        let empty = Canvas::new_with_size(16, 16).scaled(8, 8, Interpolation::Bilinear, None);
+        assert!(empty.is_empty());
+    }
 }
@@ -1,5 +1,6 @@
 use ratatui::style::Color;
 use serde::Serialize;
+use unicode_width::UnicodeWidthChar;
 
 /// Block element constants (U+2580–259F) for readability.
 pub mod blocks {
@@ -92,10 +93,11 @@ pub mod blocks {
     ];
 
     /// Resolve a character alias to a char. Returns None if not found.
-    /// Single-char input returns the char directly (backward compat).
+    /// Single-char input returns the char directly (backward compat), as
+    /// long as it fits one cell (see [`super::fits_cell`]).
     pub fn resolve_char_alias(input: &str) -> Option<char> {
         if input.chars().count() == 1 {
-            return Some(input.chars().next().unwrap());
+            return input.chars().next().filter(|&ch| super::fits_cell(ch));
         }
         let lower = input.to_lowercase();
         CHAR_INFO.iter().find(|info| {
@@ -112,6 +114,18 @@ pub mod blocks {
     pub const CATEGORIES: [&str; 4] = ["primary", "shade", "vertical-fill", "horizontal-fill"];
 }
 
+/// Terminal columns `ch` occupies: 1 for ASCII and block art, 2 for wide
+/// CJK/emoji glyphs, 0 for control and combining characters.
+pub fn char_width(ch: char) -> usize {
+    ch.width().unwrap_or(0)
+}
+
+/// Whether `ch` can be stored in a cell: exactly one terminal column, so
+/// the grid stays aligned with the screen and mouse mapping.
+pub fn fits_cell(ch: char) -> bool {
+    char_width(ch) == 1
+}
+
 /// Classification helpers for rendering.
 pub fn is_vertical_half(ch: char) -> bool {
     ch == blocks::UPPER_HALF || ch == blocks::LOWER_HALF
@@ -185,19 +199,200 @@ pub fn next_shade(ch: char) -> char {
     }
 }
 
-/// Parse a hex color string into an Rgb value.
-/// Accepts "#RRGGBB", "RRGGBB", case-insensitive.
+/// Accepted color spellings, for error messages.
+pub const COLOR_FORMS: &str = "#RRGGBB, #RGB or a CSS color name (e.g. #FF0000, #F00, red)";
+
+/// Parse a color string into an Rgb value.
+/// Accepts "#RRGGBB", "RRGGBB", "#RGB", "RGB" (each digit doubled) and CSS
+/// color names like "cornflowerblue", all case-insensitive.
 pub fn parse_hex_color(input: &str) -> Option<Rgb> {
     let hex = input.strip_prefix('#').unwrap_or(input);
-    if hex.len() != 6 {
+    if hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok();
+        match hex.len() {
+            6 => {
+                let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+                let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+                let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+                return Some(Rgb::new(r, g, b));
+            }
+            3 => return Some(Rgb::new(digit(0)? * 17, digit(1)? * 17, digit(2)? * 17)),
+            _ => {}
+        }
+    }
+    if input.starts_with('#') {
         return None;
     }
-    let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
-    let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
-    let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
-    Some(Rgb::new(r, g, b))
+    css_color(input)
+}
+
+/// Look up a CSS color name, case-insensitive.
+pub fn css_color(name: &str) -> Option<Rgb> {
+    let name = name.to_ascii_lowercase();
+    CSS_COLORS
+        .binary_search_by(|(n, _)| n.cmp(&name.as_str()))
+        .ok()
+        .map(|i| CSS_COLORS[i].1)
+}
+
+/// CSS name of exactly this color, if it has one (first alphabetically,
+/// so "aqua" rather than "cyan").
+pub fn css_name(color: Rgb) -> Option<&'static str> {
+    CSS_COLORS.iter().find(|(_, c)| *c == color).map(|(n, _)| *n)
 }
 
+/// CSS named colors (CSS Color Module Level 4), sorted by name.
+pub const CSS_COLORS: &[(&str, Rgb)] = &[
+    ("aliceblue", Rgb::new(240, 248, 255)),
+    ("antiquewhite", Rgb::new(250, 235, 215)),
+    ("aqua", Rgb::new(0, 255, 255)),
+    ("aquamarine", Rgb::new(127, 255, 212)),
+    ("azure", Rgb::new(240, 255, 255)),
+    ("beige", Rgb::new(245, 245, 220)),
+    ("bisque", Rgb::new(255, 228, 196)),
+    ("black", Rgb::new(0, 0, 0)),
+    ("blanchedalmond", Rgb::new(255, 235, 205)),
+    ("blue", Rgb::new(0, 0, 255)),
+    ("blueviolet", Rgb::new(138, 43, 226)),
+    ("brown", Rgb::new(165, 42, 42)),
+    ("burlywood", Rgb::new(222, 184, 135)),
+    ("cadetblue", Rgb::new(95, 158, 160)),
+    ("chartreuse", Rgb::new(127, 255, 0)),
+    ("chocolate", Rgb::new(210, 105, 30)),
+    ("coral", Rgb::new(255, 127, 80)),
+    ("cornflowerblue", Rgb::new(100, 149, 237)),
+    ("cornsilk", Rgb::new(255, 248, 220)),
+    ("crimson", Rgb::new(220, 20, 60)),
+    ("cyan", Rgb::new(0, 255, 255)),
+    ("darkblue", Rgb::new(0, 0, 139)),
+    ("darkcyan", Rgb::new(0, 139, 139)),
+    ("darkgoldenrod", Rgb::new(184, 134, 11)),
+    ("darkgray", Rgb::new(169, 169, 169)),
+    ("darkgreen", Rgb::new(0, 100, 0)),
+    ("darkgrey", Rgb::new(169, 169, 169)),
+    ("darkkhaki", Rgb::new(189, 183, 107)),
+    ("darkmagenta", Rgb::new(139, 0, 139)),
+    ("darkolivegreen", Rgb::new(85, 107, 47)),
+    ("darkorange", Rgb::new(255, 140, 0)),
+    ("darkorchid", Rgb::new(153, 50, 204)),
+    ("darkred", Rgb::new(139, 0, 0)),
+    ("darksalmon", Rgb::new(233, 150, 122)),
+    ("darkseagreen", Rgb::new(143, 188, 143)),
+    ("darkslateblue", Rgb::new(72, 61, 139)),
+    ("darkslategray", Rgb::new(47, 79, 79)),
+    ("darkslategrey", Rgb::new(47, 79, 79)),
+    ("darkturquoise", Rgb::new(0, 206, 209)),
+    ("darkviolet", Rgb::new(148, 0, 211)),
+    ("deeppink", Rgb::new(255, 20, 147)),
+    ("deepskyblue", Rgb::new(0, 191, 255)),
+    ("dimgray", Rgb::new(105, 105, 105)),
+    ("dimgrey", Rgb::new(105, 105, 105)),
+    ("dodgerblue", Rgb::new(30, 144, 255)),
+    ("firebrick", Rgb::new(178, 34, 34)),
+    ("floralwhite", Rgb::new(255, 250, 240)),
+    ("forestgreen", Rgb::new(34, 139, 34)),
+    ("fuchsia", Rgb::new(255, 0, 255)),
+    ("gainsboro", Rgb::new(220, 220, 220)),
+    ("ghostwhite", Rgb::new(248, 248, 255)),
+    ("gold", Rgb::new(255, 215, 0)),
+    ("goldenrod", Rgb::new(218, 165, 32)),
+    ("gray", Rgb::new(128, 128, 128)),
+    ("green", Rgb::new(0, 128, 0)),
+    ("greenyellow", Rgb::new(173, 255, 47)),
+    ("grey", Rgb::new(128, 128, 128)),
+    ("honeydew", Rgb::new(240, 255, 240)),
+    ("hotpink", Rgb::new(255, 105, 180)),
+    ("indianred", Rgb::new(205, 92, 92)),
+    ("indigo", Rgb::new(75, 0, 130)),
+    ("ivory", Rgb::new(255, 255, 240)),
+    ("khaki", Rgb::new(240, 230, 140)),
+    ("lavender", Rgb::new(230, 230, 250)),
+    ("lavenderblush", Rgb::new(255, 240, 245)),
+    ("lawngreen", Rgb::new(124, 252, 0)),
+    ("lemonchiffon", Rgb::new(255, 250, 205)),
+    ("lightblue", Rgb::new(173, 216, 230)),
+    ("lightcoral", Rgb::new(240, 128, 128)),
+    ("lightcyan", Rgb::new(224, 255, 255)),
+    ("lightgoldenrodyellow", Rgb::new(250, 250, 210)),
+    ("lightgray", Rgb::new(211, 211, 211)),
+    ("lightgreen", Rgb::new(144, 238, 144)),
+    ("lightgrey", Rgb::new(211, 211, 211)),
+    ("lightpink", Rgb::new(255, 182, 193)),
+    ("lightsalmon", Rgb::new(255, 160, 122)),
+    ("lightseagreen", Rgb::new(32, 178, 170)),
+    ("lightskyblue", Rgb::new(135, 206, 250)),
+    ("lightslategray", Rgb::new(119, 136, 153)),
+    ("lightslategrey", Rgb::new(119, 136, 153)),
+    ("lightsteelblue", Rgb::new(176, 196, 222)),
+    ("lightyellow", Rgb::new(255, 255, 224)),
+    ("lime", Rgb::new(0, 255, 0)),
+    ("limegreen", Rgb::new(50, 205, 50)),
+    ("linen", Rgb::new(250, 240, 230)),
+    ("magenta", Rgb::new(255, 0, 255)),
+    ("maroon", Rgb::new(128, 0, 0)),
+    ("mediumaquamarine", Rgb::new(102, 205, 170)),
+    ("mediumblue", Rgb::new(0, 0, 205)),
+    ("mediumorchid", Rgb::new(186, 85, 211)),
+    ("mediumpurple", Rgb::new(147, 112, 219)),
+    ("mediumseagreen", Rgb::new(60, 179, 113)),
+    ("mediumslateblue", Rgb::new(123, 104, 238)),
+    ("mediumspringgreen", Rgb::new(0, 250, 154)),
+    ("mediumturquoise", Rgb::new(72, 209, 204)),
+    ("mediumvioletred", Rgb::new(199, 21, 133)),
+    ("midnightblue", Rgb::new(25, 25, 112)),
+    ("mintcream", Rgb::new(245, 255, 250)),
+    ("mistyrose", Rgb::new(255, 228, 225)),
+    ("moccasin", Rgb::new(255, 228, 181)),
+    ("navajowhite", Rgb::new(255, 222, 173)),
+    ("navy", Rgb::new(0, 0, 128)),
+    ("oldlace", Rgb::new(253, 245, 230)),
+    ("olive", Rgb::new(128, 128, 0)),
+    ("olivedrab", Rgb::new(107, 142, 35)),
+    ("orange", Rgb::new(255, 165, 0)),
+    ("orangered", Rgb::new(255, 69, 0)),
+    ("orchid", Rgb::new(218, 112, 214)),
+    ("palegoldenrod", Rgb::new(238, 232, 170)),
+    ("palegreen", Rgb::new(152, 251, 152)),
+    ("paleturquoise", Rgb::new(175, 238, 238)),
+    ("palevioletred", Rgb::new(219, 112, 147)),
+    ("papayawhip", Rgb::new(255, 239, 213)),
+    ("peachpuff", Rgb::new(255, 218, 185)),
+    ("peru", Rgb::new(205, 133, 63)),
+    ("pink", Rgb::new(255, 192, 203)),
+    ("plum", Rgb::new(221, 160, 221)),
+    ("powderblue", Rgb::new(176, 224, 230)),
+    ("purple", Rgb::new(128, 0, 128)),
+    ("rebeccapurple", Rgb::new(102, 51, 153)),
+    ("red", Rgb::new(255, 0, 0)),
+    ("rosybrown", Rgb::new(188, 143, 143)),
+    ("royalblue", Rgb::new(65, 105, 225)),
+    ("saddlebrown", Rgb::new(139, 69, 19)),
+    ("salmon", Rgb::new(250, 128, 114)),
+    ("sandybrown", Rgb::new(244, 164, 96)),
+    ("seagreen", Rgb::new(46, 139, 87)),
+    ("seashell", Rgb::new(255, 245, 238)),
+    ("sienna", Rgb::new(160, 82, 45)),
+    ("silver", Rgb::new(192, 192, 192)),
+    ("skyblue", Rgb::new(135, 206, 235)),
+    ("slateblue", Rgb::new(106, 90, 205)),
+    ("slategray", Rgb::new(112, 128, 144)),
+    ("slategrey", Rgb::new(112, 128, 144)),
+    ("snow", Rgb::new(255, 250, 250)),
+    ("springgreen", Rgb::new(0, 255, 127)),
+    ("steelblue", Rgb::new(70, 130, 180)),
+    ("tan", Rgb::new(210, 180, 140)),
+    ("teal", Rgb::new(0, 128, 128)),
+    ("thistle", Rgb::new(216, 191, 216)),
+    ("tomato", Rgb::new(255, 99, 71)),
+    ("turquoise", Rgb::new(64, 224, 208)),
+    ("violet", Rgb::new(238, 130, 238)),
+    ("wheat", Rgb::new(245, 222, 179)),
+    ("white", Rgb::new(255, 255, 255)),
+    ("whitesmoke", Rgb::new(245, 245, 245)),
+    ("yellow", Rgb::new(255, 255, 0)),
+    ("yellowgreen", Rgb::new(154, 205, 50)),
+];
+
 /// Convert a legacy BlockChar name to a char.
 fn legacy_block_to_char(name: &str) -> char {
     match name {
@@ -224,7 +419,7 @@ impl Rgb {
     #[allow(dead_code)] // Used in tests and palette defaults
     pub const BLACK: Rgb = Rgb { r: 0, g: 0, b: 0 };
 
-    pub fn new(r: u8, g: u8, b: u8) -> Self {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
         Rgb { r, g, b }
     }
 
@@ -232,6 +427,11 @@ impl Rgb {
         Color::Indexed(nearest_256(&self))
     }
 
+    /// Perceived brightness (0-255) with the Rec. 601 weights.
+    pub fn luminance(self) -> f32 {
+        0.299 * self.r as f32 + 0.587 * self.g as f32 + 0.114 * self.b as f32
+    }
+
     /// Human-readable name. Returns hex string like "#FF0000".
     pub fn name(self) -> String {
         format!("#{:02X}{:02X}{:02X}", self.r, self.g, self.b)
@@ -295,26 +495,11 @@ impl<'de> serde::Deserialize<'de> for Rgb {
             where
                 E: de::Error,
             {
-                let idx = match value {
-                    "Black" => 0,
-                    "Red" => 1,
-                    "Green" => 2,
-                    "Yellow" => 3,
-                    "Blue" => 4,
-                    "Magenta" => 5,
-                    "Cyan" => 6,
-                    "White" => 7,
-                    "BrightBlack" => 8,
-                    "BrightRed" => 9,
-                    "BrightGreen" => 10,
-                    "BrightYellow" => 11,
-                    "BrightBlue" => 12,
-                    "BrightMagenta" => 13,
-                    "BrightCyan" => 14,
-                    "BrightWhite" => 15,
-                    _ => return Err(E::custom(format!("unknown color name: {}", value))),
-                };
-                Ok(color256_to_rgb(idx))
+                let idx = ANSI_16_NAMES
+                    .iter()
+                    .position(|&name| name == value)
+                    .ok_or_else(|| E::custom(format!("unknown color name: {}", value)))?;
+                Ok(color256_to_rgb(idx as u8))
             }
         }
 
@@ -342,6 +527,14 @@ pub const ANSI_16_RGB: [(u8, u8, u8); 16] = [
     (255, 255, 255), // 15 BrightWhite
 ];
 
+/// Names of the 16 standard ANSI colors (indices 0–15), as used by legacy
+/// project files.
+pub const ANSI_16_NAMES: [&str; 16] = [
+    "Black", "Red", "Green", "Yellow", "Blue", "Magenta", "Cyan", "White",
+    "BrightBlack", "BrightRed", "BrightGreen", "BrightYellow",
+    "BrightBlue", "BrightMagenta", "BrightCyan", "BrightWhite",
+];
+
 /// Convert a xterm-256 color index to an Rgb value.
 pub fn color256_to_rgb(idx: u8) -> Rgb {
     let (r, g, b) = match idx {
@@ -441,8 +634,141 @@ impl Cell {
         Cell { ch: ' ', fg: None, bg: None }
     }
 
+    /// Nothing visible: a space with no background. A space with a bg (as
+    /// block-mode imports produce) is a solid colored cell, not empty.
     pub fn is_empty(&self) -> bool {
-        self.ch == ' '
+        self.ch == ' ' && self.bg.is_none()
+    }
+
+    /// Canonical form of this cell. Equivalent representations collapse to one:
+    /// - blank cells become `Cell::default()`; a space with a bg becomes a full block
+    /// - any glyph whose fg and bg are the same color becomes a full block
+    /// - full blocks drop their (hidden) bg
+    /// - lower/right halves become upper/left halves with fg and bg swapped
+    /// - half blocks with both halves transparent become blank
+    ///
+    /// Normalizing is idempotent: `c.normalized().normalized() == c.normalized()`.
+    pub fn normalized(self) -> Cell {
+        if self.ch == ' ' {
+            return match self.bg {
+                Some(c) => Cell { ch: blocks::FULL, fg: Some(c), bg: None },
+                None => Cell::default(),
+            };
+        }
+        if let (Some(f), Some(b)) = (self.fg, self.bg) {
+            if f == b {
+                return Cell { ch: blocks::FULL, fg: Some(f), bg: None };
+            }
+        }
+        let cell = match self.ch {
+            blocks::FULL => Cell { bg: None, ..self },
+            blocks::LOWER_HALF => Cell { ch: blocks::UPPER_HALF, fg: self.bg, bg: self.fg },
+            blocks::RIGHT_HALF => Cell { ch: blocks::LEFT_HALF, fg: self.bg, bg: self.fg },
+            _ => self,
+        };
+        if is_half_block(cell.ch) && cell.fg.is_none() && cell.bg.is_none() {
+            return Cell::default();
+        }
+        cell
+    }
+}
+
+impl Cell {
+    /// This cell as it looks mirrored left-to-right: left and right halves
+    /// swap, and a left fractional fill becomes the complementary fill with
+    /// fg and bg swapped (only when it has a bg to swap into).
+    pub fn flipped_horizontal(self) -> Cell {
+        match self.ch {
+            blocks::LEFT_HALF => Cell { ch: blocks::RIGHT_HALF, ..self },
+            blocks::RIGHT_HALF => Cell { ch: blocks::LEFT_HALF, ..self },
+            ch => match fill_eighths(ch) {
+                Some((false, n)) => complement_fill(self, false, n),
+                _ => self,
+            },
+        }
+    }
+
+    /// This cell as it looks mirrored top-to-bottom: `▀` and `▄` swap with
+    /// the same colors, and lower fractional fills complement like
+    /// [`Cell::flipped_horizontal`].
+    pub fn flipped_vertical(self) -> Cell {
+        match self.ch {
+            blocks::UPPER_HALF => Cell { ch: blocks::LOWER_HALF, ..self },
+            blocks::LOWER_HALF => Cell { ch: blocks::UPPER_HALF, ..self },
+            ch => match fill_eighths(ch) {
+                Some((true, n)) => complement_fill(self, true, n),
+                _ => self,
+            },
+        }
+    }
+
+    /// This cell turned a quarter clockwise: half blocks follow their filled
+    /// side (`▀` becomes `▐`), lower fills become left fills, and left fills
+    /// become the complementary lower fill with colors swapped. A left fill
+    /// without a bg has no such form and is kept, so rotating it is approximate.
+    pub fn rotated_cw(self) -> Cell {
+        let ch = match self.ch {
+            blocks::UPPER_HALF => blocks::RIGHT_HALF,
+            blocks::RIGHT_HALF => blocks::LOWER_HALF,
+            blocks::LOWER_HALF => blocks::LEFT_HALF,
+            blocks::LEFT_HALF => blocks::UPPER_HALF,
+            ch => {
+                return match fill_eighths(ch) {
+                    Some((true, n)) => Cell { ch: fill_char(false, n), ..self },
+                    Some((false, n)) => complement_fill(self, true, n),
+                    None => self,
+                };
+            }
+        };
+        Cell { ch, ..self }
+    }
+
+    /// This cell turned a quarter counter-clockwise (see [`Cell::rotated_cw`]).
+    pub fn rotated_ccw(self) -> Cell {
+        let ch = match self.ch {
+            blocks::UPPER_HALF => blocks::LEFT_HALF,
+            blocks::LEFT_HALF => blocks::LOWER_HALF,
+            blocks::LOWER_HALF => blocks::RIGHT_HALF,
+            blocks::RIGHT_HALF => blocks::UPPER_HALF,
+            ch => {
+                return match fill_eighths(ch) {
+                    Some((false, n)) => Cell { ch: fill_char(true, n), ..self },
+                    Some((true, n)) => complement_fill(self, false, n),
+                    None => self,
+                };
+            }
+        };
+        Cell { ch, ..self }
+    }
+}
+
+/// Eighths covered by each entry of `blocks::VERTICAL_FILLS`
+/// (`HORIZONTAL_FILLS` lists the same amounts in reverse).
+const FILL_EIGHTHS: [usize; 6] = [1, 2, 3, 5, 6, 7];
+
+/// Whether `ch` is a lower (true) or left (false) fractional fill, and how
+/// many eighths of the cell it covers.
+fn fill_eighths(ch: char) -> Option<(bool, usize)> {
+    if let Some(i) = blocks::VERTICAL_FILLS.iter().position(|&c| c == ch) {
+        return Some((true, FILL_EIGHTHS[i]));
+    }
+    let i = blocks::HORIZONTAL_FILLS.iter().position(|&c| c == ch)?;
+    Some((false, FILL_EIGHTHS[5 - i]))
+}
+
+/// Lower (`vertical`) or left fill covering `eighths` of the cell.
+fn fill_char(vertical: bool, eighths: usize) -> char {
+    let i = FILL_EIGHTHS.iter().position(|&e| e == eighths).unwrap_or(0);
+    if vertical { blocks::VERTICAL_FILLS[i] } else { blocks::HORIZONTAL_FILLS[5 - i] }
+}
+
+/// The fill covering the other `8 - eighths` of the cell with colors swapped,
+/// i.e. the same picture drawn from the opposite edge. Kept as is when the
+/// cell has no bg to become the new fg.
+fn complement_fill(cell: Cell, vertical: bool, eighths: usize) -> Cell {
+    match cell.bg {
+        Some(bg) => Cell { ch: fill_char(vertical, 8 - eighths), fg: Some(bg), bg: cell.fg },
+        None => cell,
     }
 }
 
@@ -751,6 +1077,7 @@ mod tests {
     fn test_cell_is_empty() {
         assert!(Cell::default().is_empty());
         assert!(!Cell { ch: blocks::FULL, fg: Some(Rgb::new(205, 0, 0)), bg: None }.is_empty());
+        assert!(!Cell { ch: ' ', fg: None, bg: Some(Rgb::new(0, 0, 238)) }.is_empty());
     }
 
     // --- resolve_half_block tests ---
@@ -884,7 +1211,43 @@ mod tests {
 
     #[test]
     fn parse_hex_too_short() {
-        assert_eq!(parse_hex_color("#FFF"), None);
+        assert_eq!(parse_hex_color("#FFFF"), None);
+        assert_eq!(parse_hex_color("#FF"), None);
+    }
+
+    #[test]
+    fn parse_short_hex_expands_nibbles() {
+        assert_eq!(parse_hex_color("#FFF"), Some(Rgb::new(255, 255, 255)));
+        assert_eq!(parse_hex_color("#f00").map(Rgb::name).as_deref(), Some("#FF0000"));
+        assert_eq!(parse_hex_color("f80"), Some(Rgb::new(255, 136, 0)));
+        assert_eq!(parse_hex_color("#1a2"), Some(Rgb::new(0x11, 0xAA, 0x22)));
+    }
+
+    #[test]
+    fn parse_css_names() {
+        assert_eq!(parse_hex_color("red"), Some(Rgb::new(255, 0, 0)));
+        assert_eq!(parse_hex_color("CornflowerBlue"), Some(Rgb::new(100, 149, 237)));
+        // Anything spelled in hex digits parses as hex, never as a name
+        assert_eq!(parse_hex_color("bad"), Some(Rgb::new(0xBB, 0xAA, 0xDD)));
+        assert_eq!(parse_hex_color("#red"), None);
+        assert_eq!(parse_hex_color("notacolor"), None);
+    }
+
+    #[test]
+    fn css_name_reverse_lookup() {
+        assert_eq!(css_name(Rgb::new(100, 149, 237)), Some("cornflowerblue"));
+        assert_eq!(css_name(Rgb::new(1, 2, 3)), None);
+    }
+
+    #[test]
+    fn css_colors_sorted_for_lookup() {
+        assert!(CSS_COLORS.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn parse_hex_rejects_signs_and_non_ascii() {
+        assert_eq!(parse_hex_color("#+F+F+F"), None);
+        assert_eq!(parse_hex_color("\u{e9}\u{e9}\u{e9}"), None);
     }
 
     #[test]
@@ -919,6 +1282,18 @@ mod tests {
         assert_eq!(blocks::resolve_char_alias("a"), Some('a'));
     }
 
+    #[test]
+    fn test_wide_and_zero_width_chars_rejected() {
+        assert_eq!(char_width('a'), 1);
+        assert_eq!(char_width(blocks::FULL), 1);
+        assert_eq!(char_width('\u{5B57}'), 2); // 字
+        assert_eq!(char_width('\u{1F600}'), 2); // 😀
+        assert_eq!(char_width('\u{0301}'), 0); // combining acute
+        assert!(blocks::CHAR_INFO.iter().all(|info| fits_cell(info.ch)));
+        assert_eq!(blocks::resolve_char_alias("\u{5B57}"), None);
+        assert_eq!(blocks::resolve_char_alias("\u{0301}"), None);
+    }
+
     #[test]
     fn test_resolve_alias_case_insensitive() {
         assert_eq!(blocks::resolve_char_alias("FULL"), Some(blocks::FULL));
@@ -951,4 +1326,67 @@ mod tests {
             assert!(blocks::char_info(ch).is_some(), "Missing CharInfo for {:?}", ch);
         }
     }
+
+    // --- Cell::normalized ---
+
+    #[test]
+    fn normalize_space_with_bg_becomes_full_block() {
+        let c = Cell { ch: ' ', fg: None, bg: Some(Rgb::new(1, 2, 3)) };
+        assert_eq!(c.normalized(), Cell { ch: blocks::FULL, fg: Some(Rgb::new(1, 2, 3)), bg: None });
+    }
+
+    #[test]
+    fn normalize_blank_becomes_default() {
+        assert_eq!(Cell::empty().normalized(), Cell::default());
+    }
+
+    #[test]
+    fn normalize_same_fg_bg_becomes_full_block() {
+        let red = Rgb::new(205, 0, 0);
+        let c = Cell { ch: blocks::SHADE_MEDIUM, fg: Some(red), bg: Some(red) };
+        assert_eq!(c.normalized(), Cell { ch: blocks::FULL, fg: Some(red), bg: None });
+    }
+
+    #[test]
+    fn normalize_lower_half_swaps_to_upper() {
+        let red = Rgb::new(205, 0, 0);
+        let c = Cell { ch: blocks::LOWER_HALF, fg: Some(red), bg: None };
+        assert_eq!(c.normalized(), Cell { ch: blocks::UPPER_HALF, fg: None, bg: Some(red) });
+        // Visual resolution is unchanged
+        assert_eq!(resolve_half_block(&c), resolve_half_block(&c.normalized()));
+    }
+
+    #[test]
+    fn normalize_right_half_swaps_to_left() {
+        let red = Rgb::new(205, 0, 0);
+        let blue = Rgb::new(0, 0, 238);
+        let c = Cell { ch: blocks::RIGHT_HALF, fg: Some(red), bg: Some(blue) };
+        assert_eq!(c.normalized(), Cell { ch: blocks::LEFT_HALF, fg: Some(blue), bg: Some(red) });
+    }
+
+    #[test]
+    fn normalize_transparent_half_becomes_blank() {
+        let c = Cell { ch: blocks::UPPER_HALF, fg: None, bg: None };
+        assert_eq!(c.normalized(), Cell::default());
+    }
+
+    #[test]
+    fn normalize_is_idempotent() {
+        let red = Rgb::new(205, 0, 0);
+        let blue = Rgb::new(0, 0, 238);
+        let samples = [
+            Cell::empty(),
+            Cell::default(),
+            Cell { ch: ' ', fg: Some(red), bg: Some(blue) },
+            Cell { ch: blocks::FULL, fg: Some(red), bg: Some(blue) },
+            Cell { ch: blocks::LOWER_HALF, fg: None, bg: Some(blue) },
+            Cell { ch: blocks::RIGHT_HALF, fg: Some(red), bg: Some(red) },
+            Cell { ch: blocks::SHADE_DARK, fg: Some(red), bg: None },
+            Cell { ch: 'A', fg: Some(blue), bg: None },
+        ];
+        for c in samples {
+            let once = c.normalized();
+            assert_eq!(once.normalized(), once, "not idempotent for {:?}", c);
+        }
+    }
 }
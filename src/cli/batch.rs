@@ -4,8 +4,9 @@ use std::path::Path;
 use serde::Deserialize;
 
 use crate::canvas::Canvas;
-use crate::cell::{blocks, parse_hex_color, Cell, Rgb};
-use crate::tools;
+use crate::cell::{blocks, parse_hex_color, Cell, Rgb, COLOR_FORMS};
+use crate::cli::print_json;
+use crate::tools::{self, FillTarget};
 
 // --- Batch JSON types ---
 
@@ -30,6 +31,8 @@ pub enum BatchOp {
         fg: Option<String>,
         bg: Option<String>,
         filled: Option<bool>,
+        /// Flood fill channel: "full" (default), "fg" or "bg"
+        target: Option<String>,
     },
     #[serde(alias = "set_cell")]
     SetCell {
@@ -57,16 +60,14 @@ fn parse_optional_color(s: &Option<String>) -> Result<Option<Rgb>, String> {
         None => Ok(None),
         Some(hex) => parse_hex_color(hex)
             .map(Some)
-            .ok_or_else(|| format!("Invalid color: '{}'", hex)),
+            .ok_or_else(|| format!("Invalid color: '{}'. Expected {}", hex, COLOR_FORMS)),
     }
 }
 
 fn parse_char(s: &Option<String>) -> Result<char, String> {
     match s {
         Some(ref c) if !c.is_empty() => {
-            blocks::resolve_char_alias(c).ok_or_else(|| {
-                format!("Unknown character '{}'. Run 'kakukuma chars' for available characters.", c)
-            })
+            blocks::resolve_char_alias(c).ok_or_else(|| super::chars::unknown_char_message(c))
         }
         _ => Ok(blocks::FULL),
     }
@@ -79,6 +80,15 @@ fn require_xy(x: Option<usize>, y: Option<usize>) -> Result<(usize, usize), Stri
     }
 }
 
+fn parse_fill_target(s: &Option<String>) -> Result<FillTarget, String> {
+    match s.as_deref() {
+        None | Some("full") => Ok(FillTarget::Full),
+        Some("fg") => Ok(FillTarget::FgOnly),
+        Some("bg") => Ok(FillTarget::BgOnly),
+        Some(other) => Err(format!("Invalid fill target: '{}'. Expected full, fg or bg", other)),
+    }
+}
+
 fn require_rect_coords(
     x1: Option<usize>, y1: Option<usize>,
     x2: Option<usize>, y2: Option<usize>,
@@ -91,9 +101,9 @@ fn require_rect_coords(
 
 // --- Executor ---
 
-fn execute_op(canvas: &mut Canvas, op: &BatchOp) -> Result<usize, String> {
+pub(super) fn execute_op(canvas: &mut Canvas, op: &BatchOp) -> Result<usize, String> {
     match op {
-        BatchOp::Draw { tool, x, y, x1, y1, x2, y2, ch, fg, bg, filled } => {
+        BatchOp::Draw { tool, x, y, x1, y1, x2, y2, ch, fg, bg, filled, target } => {
             let character = parse_char(ch)?;
             let fg_rgb = parse_optional_color(fg)?;
             let bg_rgb = parse_optional_color(bg)?;
@@ -122,9 +132,13 @@ fn execute_op(canvas: &mut Canvas, op: &BatchOp) -> Result<usize, String> {
                     let (a, b, c, d) = require_rect_coords(*x1, *y1, *x2, *y2)?;
                     tools::rectangle(canvas, a, b, c, d, character, fg_rgb, bg_rgb, filled.unwrap_or(false))
                 }
+                "ellipse" => {
+                    let (a, b, c, d) = require_rect_coords(*x1, *y1, *x2, *y2)?;
+                    tools::ellipse(canvas, a, b, c, d, character, fg_rgb, bg_rgb, filled.unwrap_or(false))
+                }
                 "fill" | "flood_fill" => {
                     let (px, py) = require_xy(*x, *y)?;
-                    tools::flood_fill(canvas, px, py, character, fg_rgb, bg_rgb)
+                    tools::flood_fill(canvas, px, py, character, fg_rgb, bg_rgb, parse_fill_target(target)?, false)
                 }
                 unknown => return Err(format!("Unknown tool: '{}'", unknown)),
             };
@@ -208,7 +222,7 @@ pub fn run_batch(file: &str, commands_path: &str, dry_run: bool) -> io::Result<(
             "operations": op_count,
             "file": file,
         });
-        println!("{}", serde_json::to_string(&json).unwrap());
+        print_json(&json, false);
         return Ok(());
     }
 
@@ -244,7 +258,7 @@ pub fn run_batch(file: &str, commands_path: &str, dry_run: bool) -> io::Result<(
         result["error_details"] = serde_json::Value::Array(error_details);
     }
 
-    println!("{}", serde_json::to_string(&result).unwrap());
+    print_json(&result, false);
     Ok(())
 }
 
@@ -345,6 +359,7 @@ mod tests {
             x1: None, y1: None, x2: None, y2: None,
             ch: None, fg: Some("#FF0000".to_string()), bg: None,
             filled: None,
+            target: None,
         };
         let count = execute_op(&mut canvas, &op).unwrap();
         assert_eq!(count, 1);
@@ -362,6 +377,7 @@ mod tests {
             x1: Some(0), y1: Some(0), x2: Some(3), y2: Some(3),
             ch: None, fg: Some("#FFFFFF".to_string()), bg: None,
             filled: None,
+            target: None,
         };
         let count = execute_op(&mut canvas, &op).unwrap();
         // 4x4 outline = 12 cells (perimeter of 4x4)
@@ -377,6 +393,7 @@ mod tests {
             x1: None, y1: None, x2: None, y2: None,
             ch: None, fg: Some("#00FF00".to_string()), bg: None,
             filled: None,
+            target: None,
         };
         let count = execute_op(&mut canvas, &op).unwrap();
         // Flood fills entire 16x16 empty canvas = 256
@@ -392,6 +409,7 @@ mod tests {
             x1: Some(0), y1: Some(0), x2: Some(5), y2: Some(0),
             ch: None, fg: Some("#FFFFFF".to_string()), bg: None,
             filled: None,
+            target: None,
         };
         let count = execute_op(&mut canvas, &op).unwrap();
         assert_eq!(count, 6); // Horizontal line 0..=5
@@ -454,7 +472,7 @@ mod tests {
             tool: "magic".to_string(),
             x: Some(0), y: Some(0),
             x1: None, y1: None, x2: None, y2: None,
-            ch: None, fg: None, bg: None, filled: None,
+            ch: None, fg: None, bg: None, filled: None, target: None,
         };
         let result = execute_op(&mut canvas, &op);
         assert!(result.is_err());
@@ -471,6 +489,7 @@ mod tests {
             x1: None, y1: None, x2: None, y2: None,
             ch: None, fg: Some("#FF0000".to_string()), bg: None,
             filled: None,
+            target: None,
         };
         execute_op(&mut canvas, &op1).unwrap();
 
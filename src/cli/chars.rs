@@ -1,6 +1,7 @@
 use std::io;
 
 use crate::cell::blocks;
+use crate::cli::print_json;
 
 pub fn run_chars(category: Option<&str>, plain: bool) -> io::Result<()> {
     let chars: Vec<&blocks::CharInfo> = match category {
@@ -30,12 +31,30 @@ pub fn run_chars(category: Option<&str>, plain: bool) -> io::Result<()> {
     if plain {
         print_plain_table(&chars);
     } else {
-        print_json(&chars, category);
+        print_chars_json(&chars, category);
     }
     Ok(())
 }
 
-fn print_json(chars: &[&blocks::CharInfo], category: Option<&str>) {
+/// Error message for an unrecognized `--char` token, listing every valid name.
+pub fn unknown_char_message(input: &str) -> String {
+    let mut chars = input.chars();
+    if let (Some(ch), None) = (chars.next(), chars.next()) {
+        return format!(
+            "Character '{}' is {} columns wide; cells hold single-width characters only.",
+            input,
+            crate::cell::char_width(ch)
+        );
+    }
+    let names: Vec<&str> = blocks::CHAR_INFO.iter().map(|c| c.name).collect();
+    format!(
+        "Unknown character '{}'. Valid names: {}. Run 'kakukuma chars' for details.",
+        input,
+        names.join(", ")
+    )
+}
+
+fn print_chars_json(chars: &[&blocks::CharInfo], category: Option<&str>) {
     let characters: Vec<serde_json::Value> = chars
         .iter()
         .map(|c| {
@@ -69,7 +88,7 @@ fn print_json(chars: &[&blocks::CharInfo], category: Option<&str>) {
         "total": chars.len(),
     });
 
-    println!("{}", serde_json::to_string_pretty(&output).unwrap());
+    print_json(&output, true);
 }
 
 fn print_plain_table(chars: &[&blocks::CharInfo]) {
@@ -94,7 +113,7 @@ mod tests {
 
     #[test]
     fn test_json_output_structure() {
-        // Capture what print_json would produce by building it directly
+        // Capture what print_chars_json would produce by building it directly
         let chars: Vec<&blocks::CharInfo> = blocks::CHAR_INFO.iter().collect();
         let characters: Vec<serde_json::Value> = chars
             .iter()
@@ -133,6 +152,30 @@ mod tests {
         assert_eq!(shade_chars[0].name, "shade-light");
     }
 
+    #[test]
+    fn test_order_matches_block_picker() {
+        // Listing order must mirror the TUI picker rows (blocks::ALL / CATEGORY_SIZES)
+        let listed: Vec<char> = blocks::CHAR_INFO.iter().map(|c| c.ch).collect();
+        assert_eq!(listed, blocks::ALL.to_vec());
+        let mut offset = 0;
+        for (cat, &size) in blocks::CATEGORIES.iter().zip(blocks::CATEGORY_SIZES.iter()) {
+            for info in &blocks::CHAR_INFO[offset..offset + size] {
+                assert_eq!(info.category, *cat);
+                assert!(info.codepoint.starts_with("U+"));
+            }
+            offset += size;
+        }
+    }
+
+    #[test]
+    fn test_unknown_char_message_lists_tokens() {
+        let msg = unknown_char_message("sparkle");
+        assert!(msg.contains("'sparkle'"));
+        for info in &blocks::CHAR_INFO {
+            assert!(msg.contains(info.name), "missing {}", info.name);
+        }
+    }
+
     #[test]
     fn test_all_categories_covered() {
         for cat in &blocks::CATEGORIES {
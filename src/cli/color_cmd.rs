@@ -0,0 +1,37 @@
+use std::io;
+
+use crate::cell::{
+    color256_to_rgb, css_name, nearest_16, nearest_256, parse_hex_color, ANSI_16_NAMES, COLOR_FORMS,
+};
+use crate::cli::{cli_error, print_json};
+use crate::palette::rgb_to_hsl;
+
+/// Describe one color in every form the editor and exporters use.
+pub fn run(input: &str) -> io::Result<()> {
+    let color = match parse_hex_color(input) {
+        Some(c) => c,
+        None => cli_error(&format!("Invalid color '{}'. Expected {}", input, COLOR_FORMS)),
+    };
+    let (h, s, l) = rgb_to_hsl(color.r, color.g, color.b);
+    let idx256 = nearest_256(&color);
+    let idx16 = nearest_16(&color);
+
+    let json = serde_json::json!({
+        "input": input,
+        "hex": color.name(),
+        "rgb": {"r": color.r, "g": color.g, "b": color.b},
+        "hsl": {"h": h, "s": s, "l": l},
+        "css_name": css_name(color),
+        "xterm256": {
+            "index": idx256,
+            "hex": color256_to_rgb(idx256).name(),
+        },
+        "ansi16": {
+            "index": idx16,
+            "name": ANSI_16_NAMES[idx16 as usize],
+            "hex": color256_to_rgb(idx16).name(),
+        },
+    });
+    print_json(&json, false);
+    Ok(())
+}
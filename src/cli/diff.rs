@@ -1,17 +1,19 @@
 use std::io;
 
+use crate::canvas::Canvas;
 use crate::cell::Cell;
-use crate::cli::load_project;
+use crate::cli::{load_project, print_json};
 use crate::oplog;
-use crate::project::Project;
 
-pub fn run(file1: &str, file2: Option<&str>, before: bool) -> io::Result<()> {
-    if before {
+pub fn run(file1: &str, file2: Option<&str>, before: bool, at: Option<usize>) -> io::Result<()> {
+    if let Some(step) = at {
+        cmd_diff_at(file1, step)
+    } else if before {
         cmd_diff_before(file1)
     } else if let Some(f2) = file2 {
         cmd_diff_files(file1, f2)
     } else {
-        eprintln!("Error: Specify a second file or use --before");
+        eprintln!("Error: Specify a second file or use --before / --at N");
         std::process::exit(1);
     }
 }
@@ -19,8 +21,40 @@ pub fn run(file1: &str, file2: Option<&str>, before: bool) -> io::Result<()> {
 fn cmd_diff_files(file1: &str, file2: &str) -> io::Result<()> {
     let p1 = load_project(file1);
     let p2 = load_project(file2);
-    let result = diff_canvases(&p1, &p2);
-    println!("{}", serde_json::to_string_pretty(&result).unwrap());
+    let result = diff_canvases(&p1.canvas, &p2.canvas);
+    print_json(&result, true);
+    Ok(())
+}
+
+/// Diff the canvas as it was at log pointer `step` against the current file.
+/// The log has no base snapshot, so the earlier state is rebuilt by undoing
+/// active entries `step..pointer` from the current canvas, newest first.
+fn cmd_diff_at(file: &str, step: usize) -> io::Result<()> {
+    let project = load_project(file);
+    let log_path = oplog::log_path(std::path::Path::new(file));
+    let entries = oplog::active_entries(&log_path)?;
+
+    let pointer = entries.len();
+    let step = step.min(pointer);
+    let mut canvas = project.canvas.clone();
+    for entry in entries[step..].iter().rev() {
+        if let Some(r) = entry.resize {
+            let (w, h) = r.union();
+            canvas.resize(w, h);
+        }
+        for m in entry.mutations.iter().rev() {
+            canvas.set(m.x, m.y, m.old.to_cell());
+        }
+        if let Some(r) = entry.resize {
+            canvas.resize(r.old_width, r.old_height);
+        }
+    }
+
+    let mut result = diff_canvases(&canvas, &project.canvas);
+    result["at"] = serde_json::json!(step);
+    result["pointer"] = serde_json::json!(pointer);
+    result["operations"] = serde_json::json!(pointer - step);
+    print_json(&result, true);
     Ok(())
 }
 
@@ -67,13 +101,11 @@ fn cmd_diff_before(file: &str) -> io::Result<()> {
         "modified": modified,
         "unchanged": unchanged,
     });
-    println!("{}", serde_json::to_string_pretty(&result).unwrap());
+    print_json(&result, true);
     Ok(())
 }
 
-fn diff_canvases(p1: &Project, p2: &Project) -> serde_json::Value {
-    let c1 = &p1.canvas;
-    let c2 = &p2.canvas;
+fn diff_canvases(c1: &Canvas, c2: &Canvas) -> serde_json::Value {
     let w = c1.width.max(c2.width);
     let h = c1.height.max(c2.height);
 
@@ -2,19 +2,17 @@ use std::io;
 use std::path::Path;
 
 use crate::cell::blocks;
-use crate::cli::{DrawOpts, DrawTool, atomic_save, cli_error, load_project, resolve_colors, to_symmetry_mode};
+use crate::cli::{CliFillTarget, DrawOpts, DrawTool, atomic_save, cli_error, load_project, print_json, resolve_colors, to_fill_target, to_symmetry_mode};
 use crate::history::CellMutation;
 use crate::oplog;
-use crate::symmetry::apply_symmetry;
+use crate::symmetry::{apply_symmetry_around, center_axes};
 use crate::tools;
 
 /// Resolve the --ch option: alias name, raw char, or default to FULL block.
 fn resolve_ch(opts: &DrawOpts) -> char {
     match &opts.ch {
         Some(s) => blocks::resolve_char_alias(s).unwrap_or_else(|| {
-            cli_error(&format!(
-                "Unknown character '{}'. Run 'kakukuma chars' for available characters.", s
-            ));
+            cli_error(&super::chars::unknown_char_message(s));
         }),
         None => blocks::FULL,
     }
@@ -23,10 +21,12 @@ fn resolve_ch(opts: &DrawOpts) -> char {
 pub fn run(tool: DrawTool) -> io::Result<()> {
     match tool {
         DrawTool::Pencil { file, coord, opts } => cmd_pencil(&file, coord, &opts),
-        DrawTool::Eraser { file, coord, region } => cmd_eraser(&file, coord, region),
+        DrawTool::Eraser { file, coord, region, verbose } => cmd_eraser(&file, coord, region, verbose),
         DrawTool::Line { file, from, to, opts } => cmd_line(&file, from, to, &opts),
         DrawTool::Rect { file, from, to, filled, opts } => cmd_rect(&file, from, to, filled, &opts),
-        DrawTool::Fill { file, coord, opts } => cmd_fill(&file, coord, &opts),
+        DrawTool::Ellipse { file, from, to, filled, opts } => cmd_ellipse(&file, from, to, filled, &opts),
+        DrawTool::Fill { file, coord, target, diagonal, opts } => cmd_fill(&file, coord, &target, diagonal, &opts),
+        DrawTool::Stipple { file, region, spacing, opts } => cmd_stipple(&file, region, spacing, &opts),
         DrawTool::Eyedropper { file, coord } => cmd_eyedropper(&file, coord),
     }
 }
@@ -36,19 +36,29 @@ fn apply_and_save(
     tool_name: &str,
     mutations: Vec<CellMutation>,
     opts: Option<&DrawOpts>,
+    verbose: bool,
 ) -> io::Result<()> {
     let path = Path::new(file);
     let mut project = load_project(file);
 
     let sym_mode = opts.map(|o| to_symmetry_mode(&o.symmetry))
-        .unwrap_or(crate::symmetry::SymmetryMode::Off);
+        .unwrap_or(crate::symmetry::SymmetryMode::empty());
 
-    let mutations = apply_symmetry(
-        mutations,
-        sym_mode,
-        project.canvas.width,
-        project.canvas.height,
-    );
+    let (width, height) = (project.canvas.width, project.canvas.height);
+    let center = opts.and_then(|o| o.center);
+    if let Some((cx, cy)) = center {
+        if cx >= width || cy >= height {
+            cli_error(&format!(
+                "Symmetry center {},{} exceeds canvas {}x{}",
+                cx, cy, width, height
+            ));
+        }
+    }
+    let axes = center
+        .map(|(cx, cy)| (cx * 2, cy * 2))
+        .unwrap_or_else(|| center_axes(width, height));
+
+    let mutations = apply_symmetry_around(mutations, sym_mode, width, height, axes);
 
     // Apply mutations to canvas
     for m in &mutations {
@@ -72,16 +82,42 @@ fn apply_and_save(
         .map(|o| format!("{:?}", o.symmetry).to_lowercase())
         .unwrap_or_else(|| "off".to_string());
 
-    let json = serde_json::json!({
+    let mut json = serde_json::json!({
         "ok": true,
         "cells_modified": cells_modified,
         "tool": tool_name,
         "symmetry": sym_label,
     });
-    println!("{}", serde_json::to_string(&json).unwrap());
+    if let Some((cx, cy)) = center {
+        json["center"] = serde_json::json!({"x": cx, "y": cy});
+    }
+    if verbose {
+        json["mutations"] = serde_json::Value::Array(changed_cells(&project.canvas, &mutations));
+    }
+    print_json(&json, false);
     Ok(())
 }
 
+/// Final state of each mutated cell, in the same shape `inspect` reports.
+/// Read back from the canvas so symmetry overlaps report the cell as saved.
+fn changed_cells(canvas: &crate::canvas::Canvas, mutations: &[CellMutation]) -> Vec<serde_json::Value> {
+    let mut seen = std::collections::HashSet::new();
+    mutations
+        .iter()
+        .filter(|m| seen.insert((m.x, m.y)))
+        .filter_map(|m| {
+            canvas.get(m.x, m.y).map(|cell| serde_json::json!({
+                "x": m.x,
+                "y": m.y,
+                "fg": cell.fg.map(|c| c.name()),
+                "bg": cell.bg.map(|c| c.name()),
+                "char": cell.ch.to_string(),
+                "empty": cell.is_empty(),
+            }))
+        })
+        .collect()
+}
+
 fn cmd_pencil(file: &str, coord: (usize, usize), opts: &DrawOpts) -> io::Result<()> {
     let project = load_project(file);
     let (fg, bg) = resolve_colors(opts);
@@ -93,10 +129,15 @@ fn cmd_pencil(file: &str, coord: (usize, usize), opts: &DrawOpts) -> io::Result<
     let mutations = tools::pencil(&project.canvas, x, y, ch, fg, bg);
     drop(project); // Release the loaded project before apply_and_save reloads
 
-    apply_and_save(file, "pencil", mutations, Some(opts))
+    apply_and_save(file, "pencil", mutations, Some(opts), opts.verbose)
 }
 
-fn cmd_eraser(file: &str, coord: (usize, usize), region: Option<(usize, usize, usize, usize)>) -> io::Result<()> {
+fn cmd_eraser(
+    file: &str,
+    coord: (usize, usize),
+    region: Option<(usize, usize, usize, usize)>,
+    verbose: bool,
+) -> io::Result<()> {
     let project = load_project(file);
     let (x, y) = coord;
 
@@ -114,7 +155,7 @@ fn cmd_eraser(file: &str, coord: (usize, usize), region: Option<(usize, usize, u
     };
     drop(project);
 
-    apply_and_save(file, "eraser", mutations, None)
+    apply_and_save(file, "eraser", mutations, None, verbose)
 }
 
 fn cmd_line(file: &str, from: (usize, usize), to: (usize, usize), opts: &DrawOpts) -> io::Result<()> {
@@ -125,7 +166,7 @@ fn cmd_line(file: &str, from: (usize, usize), to: (usize, usize), opts: &DrawOpt
     let mutations = tools::line(&project.canvas, from.0, from.1, to.0, to.1, ch, fg, bg);
     drop(project);
 
-    apply_and_save(file, "line", mutations, Some(opts))
+    apply_and_save(file, "line", mutations, Some(opts), opts.verbose)
 }
 
 fn cmd_rect(file: &str, from: (usize, usize), to: (usize, usize), filled: bool, opts: &DrawOpts) -> io::Result<()> {
@@ -136,10 +177,27 @@ fn cmd_rect(file: &str, from: (usize, usize), to: (usize, usize), filled: bool,
     let mutations = tools::rectangle(&project.canvas, from.0, from.1, to.0, to.1, ch, fg, bg, filled);
     drop(project);
 
-    apply_and_save(file, "rect", mutations, Some(opts))
+    apply_and_save(file, "rect", mutations, Some(opts), opts.verbose)
+}
+
+fn cmd_ellipse(file: &str, from: (usize, usize), to: (usize, usize), filled: bool, opts: &DrawOpts) -> io::Result<()> {
+    let project = load_project(file);
+    let (fg, bg) = resolve_colors(opts);
+    let ch = resolve_ch(opts);
+
+    let mutations = tools::ellipse(&project.canvas, from.0, from.1, to.0, to.1, ch, fg, bg, filled);
+    drop(project);
+
+    apply_and_save(file, "ellipse", mutations, Some(opts), opts.verbose)
 }
 
-fn cmd_fill(file: &str, coord: (usize, usize), opts: &DrawOpts) -> io::Result<()> {
+fn cmd_fill(
+    file: &str,
+    coord: (usize, usize),
+    target: &CliFillTarget,
+    diagonal: bool,
+    opts: &DrawOpts,
+) -> io::Result<()> {
     let project = load_project(file);
     let (fg, bg) = resolve_colors(opts);
     let ch = resolve_ch(opts);
@@ -147,10 +205,30 @@ fn cmd_fill(file: &str, coord: (usize, usize), opts: &DrawOpts) -> io::Result<()
     let (x, y) = coord;
     validate_coords(x, y, &project.canvas);
 
-    let mutations = tools::flood_fill(&project.canvas, x, y, ch, fg, bg);
+    let mutations = tools::flood_fill(&project.canvas, x, y, ch, fg, bg, to_fill_target(target), diagonal);
+    drop(project);
+
+    apply_and_save(file, "fill", mutations, Some(opts), opts.verbose)
+}
+
+fn cmd_stipple(
+    file: &str,
+    region: (usize, usize, usize, usize),
+    spacing: usize,
+    opts: &DrawOpts,
+) -> io::Result<()> {
+    if spacing == 0 {
+        cli_error("--spacing must be at least 1");
+    }
+    let project = load_project(file);
+    let (fg, bg) = resolve_colors(opts);
+    let ch = resolve_ch(opts);
+
+    let (x1, y1, x2, y2) = region;
+    let mutations = tools::stipple(&project.canvas, x1, y1, x2, y2, spacing, ch, fg, bg);
     drop(project);
 
-    apply_and_save(file, "fill", mutations, Some(opts))
+    apply_and_save(file, "stipple", mutations, Some(opts), opts.verbose)
 }
 
 fn cmd_eyedropper(file: &str, coord: (usize, usize)) -> io::Result<()> {
@@ -167,7 +245,7 @@ fn cmd_eyedropper(file: &str, coord: (usize, usize)) -> io::Result<()> {
                 "bg": bg.map(|c| c.name()),
                 "char": ch.to_string(),
             });
-            println!("{}", serde_json::to_string(&json).unwrap());
+            print_json(&json, false);
             Ok(())
         }
         None => {
@@ -197,7 +275,9 @@ mod tests {
             bg: None,
             ch: ch.map(|s| s.to_string()),
             symmetry: CliSymmetry::Off,
+            center: None,
             no_log: false,
+            verbose: false,
         }
     }
 
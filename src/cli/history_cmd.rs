@@ -1,10 +1,18 @@
 use std::io;
 use std::path::Path;
 
-use crate::cli::{atomic_save, load_project};
+use crate::cli::{atomic_save, cli_error, load_project, print_json};
 use crate::oplog;
 
+/// A project streamed through stdin/stdout keeps no history.
+fn require_log(file: &str) {
+    if file == oplog::STDIO {
+        cli_error("A project on stdin has no history");
+    }
+}
+
 pub fn undo(file: &str, count: usize) -> io::Result<()> {
+    require_log(file);
     let log_path = oplog::log_path(Path::new(file));
     let undone = oplog::pop_for_undo(&log_path, count)?;
 
@@ -12,13 +20,21 @@ pub fn undo(file: &str, count: usize) -> io::Result<()> {
     let path = Path::new(file);
     let mut project = load_project(file);
 
+    // Newest first, so a cell changed by several entries ends at its oldest state
     let mut cells_restored = 0usize;
-    for entry in &undone {
+    for entry in undone.iter().rev() {
+        if let Some(r) = entry.resize {
+            let (w, h) = r.union();
+            project.canvas.resize(w, h);
+        }
         for m in &entry.mutations {
             let old_cell = m.old.to_cell();
             project.canvas.set(m.x, m.y, old_cell);
             cells_restored += 1;
         }
+        if let Some(r) = entry.resize {
+            project.canvas.resize(r.old_width, r.old_height);
+        }
     }
 
     atomic_save(&mut project, path)?;
@@ -28,11 +44,12 @@ pub fn undo(file: &str, count: usize) -> io::Result<()> {
         "undone": undone.len(),
         "cells_restored": cells_restored,
     });
-    println!("{}", serde_json::to_string(&json).unwrap());
+    print_json(&json, false);
     Ok(())
 }
 
 pub fn redo(file: &str, count: usize) -> io::Result<()> {
+    require_log(file);
     let log_path = oplog::log_path(Path::new(file));
     let redone = oplog::push_for_redo(&log_path, count)?;
 
@@ -42,11 +59,18 @@ pub fn redo(file: &str, count: usize) -> io::Result<()> {
 
     let mut cells_applied = 0usize;
     for entry in &redone {
+        if let Some(r) = entry.resize {
+            let (w, h) = r.union();
+            project.canvas.resize(w, h);
+        }
         for m in &entry.mutations {
             let new_cell = m.new.to_cell();
             project.canvas.set(m.x, m.y, new_cell);
             cells_applied += 1;
         }
+        if let Some(r) = entry.resize {
+            project.canvas.resize(r.new_width, r.new_height);
+        }
     }
 
     atomic_save(&mut project, path)?;
@@ -56,11 +80,12 @@ pub fn redo(file: &str, count: usize) -> io::Result<()> {
         "redone": redone.len(),
         "cells_applied": cells_applied,
     });
-    println!("{}", serde_json::to_string(&json).unwrap());
+    print_json(&json, false);
     Ok(())
 }
 
 pub fn history(file: &str, full: bool) -> io::Result<()> {
+    require_log(file);
     let log_path = oplog::log_path(Path::new(file));
     let (header, entries) = oplog::read_log(&log_path)?;
 
@@ -71,7 +96,7 @@ pub fn history(file: &str, full: bool) -> io::Result<()> {
             "entries": [],
             "message": "No operations recorded",
         });
-        println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        print_json(&json, true);
         return Ok(());
     }
 
@@ -110,6 +135,6 @@ pub fn history(file: &str, full: bool) -> io::Result<()> {
         "total": header.total,
         "entries": entries_json,
     });
-    println!("{}", serde_json::to_string_pretty(&json).unwrap());
+    print_json(&json, true);
     Ok(())
 }
@@ -0,0 +1,74 @@
+use std::io;
+use std::path::Path;
+
+use crate::cli::{cli_error, internal_error, print_json, stdin_project};
+use crate::kakb;
+use crate::oplog;
+use crate::project::ProjectHeader;
+
+/// Quick overview of a project: header fields and sidecar status. Only the
+/// header is parsed; cell data is skipped, so it stays cheap on large files.
+pub fn run(file: &str) -> io::Result<()> {
+    let path = Path::new(file);
+    let read;
+    let data = if file == oplog::STDIO {
+        stdin_project()
+    } else {
+        if !path.exists() {
+            cli_error(&format!("File not found: '{}'", file));
+        }
+        read = std::fs::read(path)?;
+        &read
+    };
+    let format = if kakb::is_binary(data) { "binary" } else { "json" };
+    let project = ProjectHeader::from_bytes(data).unwrap_or_else(|e| {
+        internal_error(&format!("Failed to load '{}': {}", file, e));
+    });
+
+    let log_path = oplog::log_path(path);
+    let log = match oplog::read_header(&log_path) {
+        Ok(Some(header)) => serde_json::json!({
+            "path": log_path.display().to_string(),
+            "exists": true,
+            "pointer": header.pointer,
+            "total": header.total,
+        }),
+        Ok(None) => serde_json::json!({
+            "path": log_path.display().to_string(),
+            "exists": log_path.exists(),
+            "pointer": null,
+            "total": null,
+        }),
+        Err(e) => serde_json::json!({
+            "path": log_path.display().to_string(),
+            "exists": true,
+            "pointer": null,
+            "total": null,
+            "error": e.to_string(),
+        }),
+    };
+
+    let autosave_path = format!("{}.autosave", file);
+
+    let json = serde_json::json!({
+        "file": file,
+        "name": project.name,
+        "version": project.version,
+        "format": format,
+        "size_bytes": data.len(),
+        "width": project.width,
+        "height": project.height,
+        "symmetry": serde_json::to_value(project.symmetry).unwrap_or(serde_json::Value::Null),
+        "color": project.color.name(),
+        "created_at": project.created_at,
+        "modified_at": project.modified_at,
+        "reference_image": project.reference_image,
+        "log": log,
+        "autosave": {
+            "path": autosave_path,
+            "exists": Path::new(&autosave_path).exists(),
+        },
+    });
+    print_json(&json, false);
+    Ok(())
+}
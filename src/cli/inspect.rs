@@ -1,6 +1,6 @@
 use std::io;
 
-use crate::cli::load_project;
+use crate::cli::{load_project, print_json};
 
 pub fn run(
     file: &str,
@@ -27,7 +27,7 @@ pub fn run(
             "char": cell.ch.to_string(),
             "empty": cell.is_empty(),
         });
-        println!("{}", serde_json::to_string(&json).unwrap());
+        print_json(&json, false);
     } else if let Some((x1, y1, x2, y2)) = region {
         // Region inspection — non-empty cells only
         let mut cells = Vec::new();
@@ -49,7 +49,7 @@ pub fn run(
                 }
             }
         }
-        println!("{}", serde_json::to_string(&cells).unwrap());
+        print_json(&cells, false);
     } else if let Some(r) = row {
         // Row inspection
         if r >= canvas.height {
@@ -69,7 +69,7 @@ pub fn run(
                 }));
             }
         }
-        println!("{}", serde_json::to_string(&cells).unwrap());
+        print_json(&cells, false);
     } else if let Some(c) = col {
         // Column inspection
         if c >= canvas.width {
@@ -89,7 +89,7 @@ pub fn run(
                 }));
             }
         }
-        println!("{}", serde_json::to_string(&cells).unwrap());
+        print_json(&cells, false);
     } else {
         eprintln!("Error: Specify a coordinate, --region, --row, or --col");
         std::process::exit(1);
@@ -1,24 +1,30 @@
 pub mod batch;
+pub mod script;
 pub mod chars;
 pub mod draw;
 pub mod preview;
 pub mod inspect;
 pub mod diff;
 pub mod stats;
+pub mod info;
 pub mod history_cmd;
 pub mod palette_cmd;
+pub mod color_cmd;
 
-use std::io;
-use std::path::Path;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::OnceLock;
 
 use clap::{Parser, Subcommand, ValueEnum};
 
-use crate::canvas::Canvas;
-use crate::cell::{parse_hex_color, Cell, Rgb};
-use crate::export::ColorFormat;
-use crate::import::{ImportOptions, FitMode, ImportColorMode};
-use crate::project::Project;
+use crate::canvas::{Canvas, Interpolation};
+use crate::cell::{parse_hex_color, Cell, Rgb, COLOR_FORMS};
+use crate::export::{ColorFormat, ExportTransform};
+use crate::import::{DitherMode, ImportOptions, FitMode, ImportColorMode};
+use crate::project::{Project, ProjectFormat};
 use crate::symmetry::SymmetryMode;
+use crate::tools::{FillTarget, FrameStyle};
 
 #[derive(Parser)]
 #[command(name = "kakukuma", about = "Terminal ANSI art editor")]
@@ -26,6 +32,52 @@ pub struct Cli {
     /// Open .kaku file in TUI editor
     pub file: Option<String>,
 
+    /// Editor color depth (default: auto-detect from $COLORTERM/$TERM)
+    #[arg(long)]
+    pub color_format: Option<CliColorFormat>,
+
+    /// Keep operation logs in this directory instead of next to each file
+    /// (same as setting KAKUKUMA_LOG_DIR)
+    #[arg(long, global = true)]
+    pub log_dir: Option<String>,
+
+    /// Operation-log entries kept per project, oldest pruned first
+    /// (8-65536, default 256; same as setting KAKUKUMA_MAX_LOG_ENTRIES)
+    #[arg(long, global = true, value_parser = clap::value_parser!(u32).range(8..=65536))]
+    pub max_entries: Option<u32>,
+
+    /// Merge consecutive operation-log entries of the same command made
+    /// within this many seconds into one undo step (off by default; same as
+    /// setting KAKUKUMA_OPLOG_COALESCE_SECS)
+    #[arg(long, global = true, value_parser = clap::value_parser!(u32).range(1..=3600))]
+    pub coalesce_secs: Option<u32>,
+
+    /// Print success JSON compactly on one line, whatever the command's default
+    #[arg(long, global = true, conflicts_with_all = ["pretty", "quiet"])]
+    pub json: bool,
+
+    /// Print success JSON pretty-printed, whatever the command's default
+    #[arg(long, global = true, conflicts_with = "quiet")]
+    pub pretty: bool,
+
+    /// Print no success JSON; check the exit code (errors still go to stderr)
+    #[arg(long, short, global = true)]
+    pub quiet: bool,
+
+    /// Number of recent colors the editor keeps (4-32, default 8)
+    #[arg(long, value_parser = clap::value_parser!(u8).range(4..=32))]
+    pub recent_colors: Option<u8>,
+
+    /// Tools to list first in the toolbar, comma-separated (e.g. fill,pencil;
+    /// same as setting KAKUKUMA_FAVORITE_TOOLS)
+    #[arg(long, value_delimiter = ',')]
+    pub favorite_tools: Option<Vec<String>>,
+
+    /// Debug: in the editor, F12 writes the rendered screen (glyphs plus
+    /// per-cell fg/bg) to this file
+    #[arg(long, hide = true)]
+    pub debug_dump: Option<PathBuf>,
+
     #[command(subcommand)]
     pub command: Option<Command>,
 }
@@ -45,7 +97,7 @@ pub enum Command {
         /// Canvas size as WxH (e.g., 32x24)
         #[arg(long, value_parser = parse_size)]
         size: Option<(usize, usize)>,
-        /// Overwrite existing file
+        /// Overwrite existing file (also removes its stale .autosave/.bak sidecars)
         #[arg(long)]
         force: bool,
     },
@@ -69,6 +121,23 @@ pub enum Command {
         /// Color depth for ANSI output (auto-detects terminal support)
         #[arg(long, default_value = "auto")]
         color_format: CliColorFormat,
+        /// JSON: emit a flat list of drawn cells only
+        #[arg(long)]
+        non_empty_only: bool,
+        /// JSON: print on a single line
+        #[arg(long)]
+        compact: bool,
+        /// Never consult the environment ($COLORTERM, $TERM, NO_COLOR); `auto`
+        /// pins to 256-color. Identical input gives identical bytes (for CI snapshots).
+        #[arg(long)]
+        deterministic: bool,
+        /// Print a true-pixel bitmap instead of cells (sixel or kitty protocol).
+        /// The escape sequence is printed whether or not the terminal supports it.
+        #[arg(long, conflicts_with_all = ["non_empty_only", "compact"])]
+        graphics: Option<CliGraphics>,
+        /// Cell size for --graphics (WxH pixels)
+        #[arg(long, default_value = "8x16", requires = "graphics")]
+        cell_size: String,
     },
 
     /// Query canvas cell data
@@ -113,6 +182,22 @@ pub enum Command {
         /// Export full canvas (skip auto-crop)
         #[arg(long)]
         no_crop: bool,
+        /// ANSI: emit a reset before every cell (larger, but no color bleed)
+        #[arg(long)]
+        reset_each_cell: bool,
+        /// Refuse to replace an existing output file (default: overwrite)
+        #[arg(long)]
+        no_overwrite: bool,
+        /// PNG: composite over this color so the image has no transparency
+        #[arg(long)]
+        background: Option<String>,
+        /// Plain: ASCII art, picking characters from this ramp (darkest first)
+        /// by each cell's luminance. Bare `--ramp` uses " .:-=+*#%@".
+        #[arg(long, num_args = 0..=1, default_missing_value = crate::export::DEFAULT_RAMP)]
+        ramp: Option<String>,
+        /// Plain: reverse the --ramp so bright cells get the lightest characters
+        #[arg(long, requires = "ramp")]
+        invert: bool,
     },
 
     /// Compare two canvas files
@@ -124,6 +209,10 @@ pub enum Command {
         /// Compare current state vs before last operation
         #[arg(long)]
         before: bool,
+        /// Compare current state vs the state at oplog step N
+        /// (0 = before the first logged operation; clamped to the log pointer)
+        #[arg(long, value_name = "N", conflicts_with_all = ["file2", "before"])]
+        at: Option<usize>,
     },
 
     /// Canvas statistics
@@ -132,6 +221,12 @@ pub enum Command {
         file: String,
     },
 
+    /// Quick project overview (no cell scan)
+    Info {
+        /// Path to .kaku file
+        file: String,
+    },
+
     /// Undo last CLI operation.
     ///
     /// Uses a linear model: new operations discard redo history.
@@ -179,6 +274,23 @@ pub enum Command {
         size: Option<(usize, usize)>,
     },
 
+    /// Scale the artwork to a new canvas size (resize crops or pads instead)
+    Scale {
+        /// Path to .kaku file
+        file: String,
+        /// New size as WxH (8-128 each)
+        #[arg(value_parser = parse_size)]
+        size: (usize, usize),
+        /// nearest keeps pixel art crisp; bilinear blends colors in RGB
+        /// (averaging when shrinking) and snaps the result to a palette
+        #[arg(long, default_value = "nearest")]
+        interp: CliInterpolation,
+        /// Palette bilinear blends snap to (name, or "default"; default:
+        /// the colors already on the canvas)
+        #[arg(long)]
+        palette: Option<String>,
+    },
+
     /// Clear canvas (reset all cells to default).
     ///
     /// Warning: clear is destructive. If clear overlaps with prior
@@ -192,6 +304,117 @@ pub enum Command {
         region: Option<(usize, usize, usize, usize)>,
     },
 
+    /// Normalize cells into one canonical form (appearance is unchanged).
+    ///
+    /// Grid and symmetry guides are render-only and never stored, so there
+    /// is nothing to bake out; this only collapses equivalent encodings
+    /// (e.g. a space with a bg vs. a full block) so diffs and hashes are stable.
+    Normalize {
+        /// Path to .kaku file
+        file: String,
+        /// Skip operation log (no undo for this operation)
+        #[arg(long)]
+        no_log: bool,
+    },
+
+    /// Clear the background connected to the canvas border (magic wand)
+    ///
+    /// Uses the top-left corner's color as the background. Same-colored
+    /// areas enclosed by the artwork are kept.
+    RemoveBg {
+        /// Path to .kaku file
+        file: String,
+        /// Max per-channel difference from the corner color (0-255)
+        #[arg(long, default_value_t = 0)]
+        tolerance: u8,
+        /// Skip operation log (no undo for this operation)
+        #[arg(long)]
+        no_log: bool,
+    },
+
+    /// Recolor: swap every fg/bg matching one color to another
+    ///
+    /// Scans the whole canvas; a color matches when each channel is within
+    /// --tolerance of --from. Recorded as a single undo step.
+    Replace {
+        /// Path to .kaku file
+        file: String,
+        /// Color to replace (#RRGGBB, #RGB or CSS name)
+        #[arg(long)]
+        from: String,
+        /// Replacement color
+        #[arg(long)]
+        to: String,
+        /// Max per-channel difference from --from (0-255)
+        #[arg(long, default_value_t = 0)]
+        tolerance: u8,
+    },
+
+    /// Fill a region with an ordered (Bayer) dither of two colors
+    ///
+    /// RATIO percent of the cells take color B (0 = solid A, 100 = solid B),
+    /// giving in-between shades with a limited palette.
+    Dither2 {
+        /// Path to .kaku file
+        file: String,
+        /// Region (x1,y1,x2,y2)
+        #[arg(value_parser = parse_region)]
+        region: (usize, usize, usize, usize),
+        /// First color (#RRGGBB, #RGB or CSS name)
+        a: String,
+        /// Second color
+        b: String,
+        /// Percent of cells using color B (0-100)
+        #[arg(long, value_parser = clap::value_parser!(u8).range(0..=100))]
+        ratio: u8,
+    },
+
+    /// Print which cells are filled, ignoring color (e.g. for collision maps)
+    ///
+    /// Prints {"width", "height", "filled", "mask"} where mask is one array of
+    /// booleans per row. With --output FILE.png, writes a 1-bit PNG instead,
+    /// one pixel per cell (white = filled). Filled means the same as in stats.
+    Mask {
+        /// Path to .kaku file
+        file: String,
+        /// Write the mask as a 1-bit PNG (must end in .png)
+        #[arg(long)]
+        output: Option<String>,
+    },
+
+    /// Snap every stored color to the nearest 256- or 16-color palette entry
+    ///
+    /// Unlike export --color-format this rewrites the project itself, so the
+    /// editor and every export agree. Undoable like any other operation.
+    Quantize {
+        /// Path to .kaku file
+        file: String,
+        /// Target color depth (truecolor leaves colors unchanged)
+        #[arg(long)]
+        depth: CliColorFormat,
+    },
+
+    /// Draw a box-drawing border around the canvas edge
+    ///
+    /// Only the outermost ring of cells is written; each keeps its background.
+    Frame {
+        /// Path to .kaku file
+        file: String,
+        /// Border color (#RRGGBB, #RGB or CSS name)
+        color: String,
+        /// Line style: single, double or rounded
+        #[arg(long)]
+        style: String,
+    },
+
+    /// Shrink the canvas to the bounding box of its drawn cells
+    ///
+    /// Content moves to the top-left; results smaller than 8x8 are padded.
+    Crop {
+        /// Path to .kaku file
+        file: String,
+    },
+
     /// Import image file onto canvas
     Import {
         /// Path to image file (PNG, JPEG, etc.)
@@ -222,9 +445,15 @@ pub enum Command {
         /// Reduce to N distinct colors via k-means (2-64). Makes art cleaner and more readable.
         #[arg(long)]
         posterize: Option<usize>,
+        /// Dither gradients when quantizing to 256 or 16 colors (ignored for truecolor and --mosaic)
+        #[arg(long, default_value = "none")]
+        dither: CliDither,
         /// Use mosaic mode: average each grid region instead of per-pixel sampling.
         #[arg(long)]
         mosaic: bool,
+        /// Import every frame of an animated GIF, one numbered file per frame (art-001.kaku, ...)
+        #[arg(long, conflicts_with = "mosaic")]
+        frames: bool,
     },
 
     /// Convert an image directly to ANSI art on stdout (no intermediate file)
@@ -271,7 +500,18 @@ pub enum Command {
         dry_run: bool,
     },
 
+    /// Apply a text script of draw operations as one undoable step
+    Script {
+        /// Path to .kaku file
+        file: String,
+        /// Script file: one operation per line, e.g. `pencil 5,5 #FF0000`,
+        /// `line 0,0 10,10 #00FF00`, `rect 1,1 4,4 red filled`, `fill 2,2 #0000FF`
+        #[arg(long)]
+        commands: String,
+    },
+
     /// List available block characters with metadata
+    #[command(visible_alias = "blocks")]
     Chars {
         /// Filter by category (primary, shade, vertical-fill, horizontal-fill)
         #[arg(long)]
@@ -281,6 +521,12 @@ pub enum Command {
         plain: bool,
     },
 
+    /// Show a color as hex, RGB, HSL and its nearest xterm-256 / ANSI-16 entries
+    Color {
+        /// Color as #RRGGBB, #RGB or a CSS name
+        input: String,
+    },
+
     /// Set or clear reference image for a project
     Reference {
         /// Path to .kaku file
@@ -291,6 +537,71 @@ pub enum Command {
         #[arg(long)]
         clear: bool,
     },
+
+    /// Cut a canvas into a grid of tiles, saved as PREFIX_ROW_COL.kaku
+    ///
+    /// The canvas must divide evenly by the tile size unless --partial is
+    /// given, which keeps the smaller tiles along the right and bottom edges.
+    Slice {
+        /// Path to .kaku file
+        file: String,
+        /// Tile size as WxH (e.g., 16x16)
+        #[arg(value_parser = parse_size)]
+        tile: (usize, usize),
+        /// Output path prefix for the tile files
+        #[arg(long)]
+        prefix: String,
+        /// Keep smaller edge tiles when the canvas isn't evenly divisible
+        #[arg(long)]
+        partial: bool,
+        /// Overwrite tile files that already exist
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Whole-canvas transforms
+    Transform {
+        #[command(subcommand)]
+        action: TransformAction,
+    },
+
+    /// Build one canvas from a grid of equally sized tiles (inverse of slice)
+    ///
+    /// Tiles are placed in row-major order, COLS per row; a short last row
+    /// leaves the remaining cells empty.
+    Assemble {
+        /// Path to output .kaku file
+        output: String,
+        /// Number of tiles per row
+        #[arg(long)]
+        cols: usize,
+        /// Tile .kaku files, in row-major order
+        #[arg(required = true)]
+        tiles: Vec<String>,
+        /// Overwrite the output if it already exists
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Stamp one project's drawn cells onto another (empty overlay cells leave the base alone)
+    Merge {
+        /// Base .kaku file
+        base: String,
+        /// Overlay .kaku file
+        overlay: String,
+        /// Output .kaku file; the base itself merges in place (one undo step)
+        #[arg(long)]
+        output: String,
+        /// Where the overlay's top-left lands on the base (x,y)
+        #[arg(long, value_parser = parse_coord, default_value = "0,0")]
+        at: (usize, usize),
+        /// How overlay colors combine with the base
+        #[arg(long, value_enum, default_value = "over")]
+        mode: CliBlendMode,
+        /// Overwrite an existing output other than the base
+        #[arg(long)]
+        force: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -315,6 +626,9 @@ pub enum DrawTool {
         /// Erase region (x1,y1,x2,y2)
         #[arg(long, value_parser = parse_region)]
         region: Option<(usize, usize, usize, usize)>,
+        /// Include every changed cell in the JSON output
+        #[arg(long)]
+        verbose: bool,
     },
     /// Draw a line between two points
     Line {
@@ -345,6 +659,22 @@ pub enum DrawTool {
         #[command(flatten)]
         opts: DrawOpts,
     },
+    /// Draw an ellipse inscribed in a box
+    Ellipse {
+        /// Path to .kaku file
+        file: String,
+        /// One corner of the bounding box (x,y)
+        #[arg(value_parser = parse_coord)]
+        from: (usize, usize),
+        /// Opposite corner (x,y)
+        #[arg(value_parser = parse_coord)]
+        to: (usize, usize),
+        /// Fill the ellipse
+        #[arg(long)]
+        filled: bool,
+        #[command(flatten)]
+        opts: DrawOpts,
+    },
     /// Flood fill from a point
     Fill {
         /// Path to .kaku file
@@ -352,6 +682,26 @@ pub enum DrawTool {
         /// Start coordinate (x,y)
         #[arg(value_parser = parse_coord)]
         coord: (usize, usize),
+        /// Channel to match and repaint: whole cells, fg only, or bg only
+        #[arg(long, value_enum, default_value = "full")]
+        target: CliFillTarget,
+        /// Also spread through cell corners (8-connected); leaks through
+        /// one-cell diagonal gaps in outlines
+        #[arg(long)]
+        diagonal: bool,
+        #[command(flatten)]
+        opts: DrawOpts,
+    },
+    /// Place a cell every N cells across a region (dot grid / halftone)
+    Stipple {
+        /// Path to .kaku file
+        file: String,
+        /// Region (x1,y1,x2,y2); the pattern starts at its top-left corner
+        #[arg(value_parser = parse_region)]
+        region: (usize, usize, usize, usize),
+        /// Distance between dots in both axes (1 = solid)
+        #[arg(long, default_value_t = 2)]
+        spacing: usize,
         #[command(flatten)]
         opts: DrawOpts,
     },
@@ -382,9 +732,15 @@ pub struct DrawOpts {
     /// Apply symmetry
     #[arg(long, default_value = "off")]
     pub symmetry: CliSymmetry,
+    /// Mirror horizontal/vertical symmetry through cell X,Y instead of the canvas center
+    #[arg(long, value_parser = parse_coord)]
+    pub center: Option<(usize, usize)>,
     /// Skip operation log (no undo for this operation)
     #[arg(long)]
     pub no_log: bool,
+    /// Include every changed cell in the JSON output
+    #[arg(long)]
+    pub verbose: bool,
 }
 
 #[derive(ValueEnum, Clone, Debug, PartialEq)]
@@ -394,6 +750,24 @@ pub enum PreviewFormat {
     Json,
     Plain,
     Png,
+    Svg,
+    Html,
+    /// Monochrome Unicode Braille, 2x4 cells per glyph (colors dropped)
+    Braille,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum PaletteFormat {
+    /// kakukuma `.palette` JSON (name + colors)
+    Palette,
+    /// One #RRGGBB per line
+    Hex,
+    /// JSON array of "#RRGGBB" strings
+    Json,
+    /// GIMP palette (.gpl)
+    Gpl,
+    /// Adobe Swatch Exchange (.ase)
+    Ase,
 }
 
 #[derive(ValueEnum, Clone, Debug)]
@@ -410,12 +784,158 @@ pub enum CliColorFormat {
     Color16,
 }
 
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum CliBlendMode {
+    /// Overlay cells replace the base
+    Over,
+    /// Multiply colors (darken)
+    Multiply,
+    /// Screen colors (lighten)
+    Screen,
+}
+
+impl From<CliBlendMode> for crate::tools::BlendMode {
+    fn from(m: CliBlendMode) -> Self {
+        match m {
+            CliBlendMode::Over => crate::tools::BlendMode::Over,
+            CliBlendMode::Multiply => crate::tools::BlendMode::Multiply,
+            CliBlendMode::Screen => crate::tools::BlendMode::Screen,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum CliGraphics {
+    /// DEC sixel (xterm -ti vt340, foot, WezTerm, mlterm, ...)
+    Sixel,
+    /// Kitty graphics protocol (kitty, WezTerm, Ghostty, ...)
+    Kitty,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum CliDither {
+    None,
+    /// Error diffusion: smooth gradients, organic noise
+    FloydSteinberg,
+    /// 4x4 Bayer pattern: regular crosshatch texture
+    Ordered,
+}
+
+impl From<CliDither> for DitherMode {
+    fn from(d: CliDither) -> Self {
+        match d {
+            CliDither::None => DitherMode::None,
+            CliDither::FloydSteinberg => DitherMode::FloydSteinberg,
+            CliDither::Ordered => DitherMode::Ordered,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum CliInterpolation {
+    Nearest,
+    Bilinear,
+}
+
 #[derive(ValueEnum, Clone, Debug)]
 pub enum CliSymmetry {
     Off,
     Horizontal,
     Vertical,
     Quad,
+    Diagonal,
+    AntiDiagonal,
+    /// All four axes (8-fold)
+    Kaleidoscope,
+}
+
+#[derive(ValueEnum, Clone, Debug)]
+pub enum CliFillTarget {
+    /// Character, fg and bg
+    Full,
+    /// Foreground color only (glyphs and bg kept)
+    Fg,
+    /// Background color only (glyphs and fg kept)
+    Bg,
+}
+
+#[derive(Subcommand)]
+pub enum TransformAction {
+    /// List or edit the export transforms stored in a project
+    ///
+    /// Transforms (hue:DEGREES, posterize:LEVELS, invert) adjust colors in
+    /// order on every preview and export; the canvas itself is never changed.
+    Export {
+        /// Path to .kaku file
+        file: String,
+        /// Append a transform (repeatable, applied in the given order)
+        #[arg(long, value_parser = ExportTransform::parse)]
+        add: Vec<ExportTransform>,
+        /// Remove all transforms (before any --add)
+        #[arg(long)]
+        clear: bool,
+    },
+    /// Mirror the canvas left-to-right and/or top-to-bottom
+    ///
+    /// Half blocks are mirrored too, so `▀` becomes `▄` on a vertical flip.
+    Flip {
+        /// Path to .kaku file
+        file: String,
+        /// Mirror left-to-right
+        #[arg(long)]
+        horizontal: bool,
+        /// Mirror top-to-bottom
+        #[arg(long)]
+        vertical: bool,
+    },
+    /// Turn the canvas a quarter clockwise or counter-clockwise
+    ///
+    /// Width and height swap. Half blocks turn with the canvas (`▀` becomes `▐`).
+    Rotate {
+        /// Path to .kaku file
+        file: String,
+        /// Rotate clockwise
+        #[arg(long, conflicts_with = "ccw", required_unless_present = "ccw")]
+        cw: bool,
+        /// Rotate counter-clockwise
+        #[arg(long)]
+        ccw: bool,
+    },
+    /// Move the whole drawing by an offset
+    ///
+    /// Cells pushed past an edge are dropped, or come back on the opposite
+    /// edge with --wrap.
+    Shift {
+        /// Path to .kaku file
+        file: String,
+        /// Columns to move right (negative: left)
+        #[arg(long, default_value_t = 0, allow_negative_numbers = true)]
+        dx: isize,
+        /// Rows to move down (negative: up)
+        #[arg(long, default_value_t = 0, allow_negative_numbers = true)]
+        dy: isize,
+        /// Wrap cells around the edges instead of dropping them
+        #[arg(long)]
+        wrap: bool,
+    },
+    /// Replace every color with its negative (snapped to the 256-color palette)
+    ///
+    /// Empty cells stay empty. Undoable like any other operation.
+    Invert {
+        /// Path to .kaku file
+        file: String,
+    },
+    /// Convert colors to their luminance gray (the 232-255 grayscale ramp)
+    ///
+    /// --amount below 100 blends only partway toward that gray.
+    /// Empty cells stay empty. Undoable like any other operation.
+    Grayscale {
+        /// Path to .kaku file
+        file: String,
+        /// How far to desaturate, in percent (0-100)
+        #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(u8).range(0..=100))]
+        amount: u8,
+    },
 }
 
 #[derive(Subcommand)]
@@ -425,15 +945,47 @@ pub enum PaletteAction {
     /// Show colors in a palette
     Show { name: String },
     /// Create palette from canvas colors
-    Create { name: String, file: String },
-    /// Export palette to file
+    Create {
+        name: String,
+        file: String,
+        /// Merge into the existing palette of that name instead of replacing it
+        #[arg(long)]
+        append: bool,
+    },
+    /// Export palette to file (`default` = built-in palette)
     Export {
         name: String,
         /// Output file path
-        output: String,
+        #[arg(long)]
+        output: Option<String>,
+        /// Output file path (older positional form of --output)
+        #[arg(hide = true)]
+        output_pos: Option<String>,
+        /// Output format
+        #[arg(long, value_enum, default_value = "palette")]
+        format: PaletteFormat,
     },
     /// Add color to palette
     Add { name: String, color: String },
+    /// Compare two palettes (`default` = built-in palette)
+    Diff { a: String, b: String },
+    /// Merge palettes into a new one, dropping duplicate colors
+    Merge {
+        /// Name of the palette to write
+        output: String,
+        /// Palettes to combine, in priority order (`default` = built-in palette)
+        #[arg(required = true)]
+        inputs: Vec<String>,
+    },
+    /// Render a palette as a PNG strip of labeled swatches (`default` = built-in palette)
+    Swatches {
+        name: String,
+        /// Output PNG path
+        output: String,
+        /// Swatches per row before wrapping
+        #[arg(long, default_value_t = 8)]
+        columns: usize,
+    },
     /// List available themes
     Themes,
     /// Show colors in a theme
@@ -467,7 +1019,9 @@ pub fn parse_region(s: &str) -> Result<(usize, usize, usize, usize), String> {
         .map_err(|_| format!("Invalid X2: '{}'", parts[2]))?;
     let y2 = parts[3].trim().parse::<usize>()
         .map_err(|_| format!("Invalid Y2: '{}'", parts[3]))?;
-    Ok((x1, y1, x2, y2))
+    // Corners may come in any order; a region with x1 == x2 and/or
+    // y1 == y2 is a single column, row, or cell.
+    Ok((x1.min(x2), y1.min(y2), x1.max(x2), y1.max(y2)))
 }
 
 pub fn parse_size(s: &str) -> Result<(usize, usize), String> {
@@ -490,7 +1044,7 @@ pub fn resolve_colors(opts: &DrawOpts) -> (Option<Rgb>, Option<Rgb>) {
         Some(s) => match parse_hex_color(s) {
             Some(c) => Some(c),
             None => cli_error(&format!(
-                "Invalid hex color '{}'. Expected format: #RRGGBB (e.g. #FF0000)", s
+                "Invalid color '{}'. Expected {}", s, COLOR_FORMS
             )),
         },
         None => Some(Rgb::WHITE),
@@ -499,7 +1053,7 @@ pub fn resolve_colors(opts: &DrawOpts) -> (Option<Rgb>, Option<Rgb>) {
         Some(s) => match parse_hex_color(s) {
             Some(c) => Some(c),
             None => cli_error(&format!(
-                "Invalid hex color '{}'. Expected format: #RRGGBB (e.g. #FF0000)", s
+                "Invalid color '{}'. Expected {}", s, COLOR_FORMS
             )),
         },
         None => None,
@@ -509,10 +1063,13 @@ pub fn resolve_colors(opts: &DrawOpts) -> (Option<Rgb>, Option<Rgb>) {
 
 pub fn to_symmetry_mode(s: &CliSymmetry) -> SymmetryMode {
     match s {
-        CliSymmetry::Off => SymmetryMode::Off,
-        CliSymmetry::Horizontal => SymmetryMode::Horizontal,
-        CliSymmetry::Vertical => SymmetryMode::Vertical,
-        CliSymmetry::Quad => SymmetryMode::Quad,
+        CliSymmetry::Off => SymmetryMode::empty(),
+        CliSymmetry::Horizontal => SymmetryMode::HORIZONTAL,
+        CliSymmetry::Vertical => SymmetryMode::VERTICAL,
+        CliSymmetry::Quad => SymmetryMode::QUAD,
+        CliSymmetry::Diagonal => SymmetryMode::DIAGONAL,
+        CliSymmetry::AntiDiagonal => SymmetryMode::ANTI_DIAGONAL,
+        CliSymmetry::Kaleidoscope => SymmetryMode::KALEIDOSCOPE,
     }
 }
 
@@ -526,11 +1083,81 @@ pub fn to_color_format(f: &CliColorFormat) -> ColorFormat {
     }
 }
 
+/// How commands print their success JSON, from the global output flags.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OutputStyle {
+    /// Each command's own choice (compact or pretty)
+    Default = 0,
+    Compact = 1,
+    Pretty = 2,
+    Quiet = 3,
+}
+
+static OUTPUT_STYLE: AtomicU8 = AtomicU8::new(OutputStyle::Default as u8);
+
+/// Pick the output style from `--json` / `--pretty` / `--quiet`.
+pub fn set_output_style(style: OutputStyle) {
+    OUTPUT_STYLE.store(style as u8, Ordering::Relaxed);
+}
+
+/// Set once a project (or export content) has been written to stdout, so
+/// status JSON moves to stderr and the stream stays clean for the next command.
+static PROJECT_ON_STDOUT: AtomicBool = AtomicBool::new(false);
+
+/// Stdin is read once and kept: some commands load the project twice.
+static STDIN_PROJECT: OnceLock<Vec<u8>> = OnceLock::new();
+
+fn output_style() -> OutputStyle {
+    match OUTPUT_STYLE.load(Ordering::Relaxed) {
+        1 => OutputStyle::Compact,
+        2 => OutputStyle::Pretty,
+        3 => OutputStyle::Quiet,
+        _ => OutputStyle::Default,
+    }
+}
+
+/// Print a command's success JSON to stdout. `pretty` is the command's
+/// default, overridden by the global output flags.
+pub fn print_json<T: serde::Serialize + ?Sized>(value: &T, pretty: bool) {
+    let pretty = match output_style() {
+        OutputStyle::Quiet => return,
+        OutputStyle::Compact => false,
+        OutputStyle::Pretty => true,
+        OutputStyle::Default => pretty,
+    };
+    let text = if pretty {
+        serde_json::to_string_pretty(value)
+    } else {
+        serde_json::to_string(value)
+    };
+    if PROJECT_ON_STDOUT.load(Ordering::Relaxed) {
+        eprintln!("{}", text.unwrap());
+    } else {
+        println!("{}", text.unwrap());
+    }
+}
+
+pub fn to_fill_target(t: &CliFillTarget) -> FillTarget {
+    match t {
+        CliFillTarget::Full => FillTarget::Full,
+        CliFillTarget::Fg => FillTarget::FgOnly,
+        CliFillTarget::Bg => FillTarget::BgOnly,
+    }
+}
+
 fn cli_error(msg: &str) -> ! {
-    let json = serde_json::json!({
+    cli_error_with(msg, serde_json::json!({}))
+}
+
+/// Like `cli_error`, with the fields of `extra` added to the error JSON.
+fn cli_error_with(msg: &str, extra: serde_json::Value) -> ! {
+    let mut json = serde_json::json!({
         "error": msg,
         "code": "USER_ERROR"
     });
+    if let (Some(obj), serde_json::Value::Object(fields)) = (json.as_object_mut(), extra) {
+        obj.extend(fields);
+    }
     eprintln!("{}", json);
     std::process::exit(1)
 }
@@ -544,7 +1171,27 @@ fn internal_error(msg: &str) -> ! {
     std::process::exit(2)
 }
 
+/// The project piped to stdin, read on first use.
+fn stdin_project() -> &'static [u8] {
+    let data = STDIN_PROJECT.get_or_init(|| {
+        let mut data = Vec::new();
+        if let Err(e) = io::stdin().read_to_end(&mut data) {
+            internal_error(&format!("Failed to read stdin: {}", e));
+        }
+        data
+    });
+    if data.is_empty() {
+        cli_error("No project on stdin");
+    }
+    data
+}
+
 fn load_project(path: &str) -> Project {
+    if path == crate::oplog::STDIO {
+        return Project::from_bytes(stdin_project().to_vec()).unwrap_or_else(|e| {
+            internal_error(&format!("Failed to load stdin: {}", e));
+        });
+    }
     let p = Path::new(path);
     if !p.exists() {
         cli_error(&format!("File not found: '{}'", path));
@@ -554,9 +1201,24 @@ fn load_project(path: &str) -> Project {
     })
 }
 
+/// Write command output to stdout; later status JSON goes to stderr.
+fn write_stdout(data: &[u8]) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    out.write_all(data)?;
+    out.flush()?;
+    PROJECT_ON_STDOUT.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
 fn atomic_save(project: &mut Project, path: &Path) -> io::Result<()> {
+    if path == Path::new(crate::oplog::STDIO) {
+        let mut data = project.to_bytes(ProjectFormat::Json)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        data.push(b'\n');
+        return write_stdout(&data);
+    }
     let tmp = path.with_extension("kaku.tmp");
-    project.save_to_file(&tmp)
+    project.save_to_file_as(&tmp, ProjectFormat::for_path(path))
         .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
     std::fs::rename(&tmp, path)
 }
@@ -569,40 +1231,73 @@ pub fn run(cmd: Command) -> io::Result<()> {
             cmd_new(&file, w, h, force)
         }
         Command::Draw { tool } => draw::run(tool),
-        Command::Preview { file, format, region, color_format } => {
-            preview::run(&file, &format, region, &color_format)
+        Command::Preview { file, format, region, color_format, non_empty_only, compact, deterministic, graphics, cell_size } => {
+            match graphics {
+                Some(protocol) => preview::run_graphics(&file, protocol, region, &cell_size),
+                None => preview::run(&file, &format, region, &color_format, non_empty_only, compact, deterministic),
+            }
         }
         Command::Inspect { file, coord, region, row, col } => {
             inspect::run(&file, coord, region, row, col)
         }
-        Command::Diff { file1, file2, before } => {
-            diff::run(&file1, file2.as_deref(), before)
+        Command::Diff { file1, file2, before, at } => {
+            diff::run(&file1, file2.as_deref(), before, at)
         }
         Command::Stats { file } => stats::run(&file),
+        Command::Info { file } => info::run(&file),
         Command::Undo { file, count } => history_cmd::undo(&file, count),
         Command::Redo { file, count } => history_cmd::redo(&file, count),
         Command::History { file, full } => history_cmd::history(&file, full),
-        Command::Export { file, output, output_flag, format, color_format, cell_size, scale, no_crop } => {
+        Command::Export { file, output, output_flag, format, color_format, cell_size, scale, no_crop, reset_each_cell, no_overwrite, background, ramp, invert } => {
             let out = output.or(output_flag)
                 .unwrap_or_else(|| cli_error("Output path required. Usage: kakukuma export <FILE> <OUTPUT>"));
-            preview::export_to_file(&file, &out, &format, &color_format, &cell_size, scale, no_crop)
+            let background = background.map(|s| parse_hex_color(&s).unwrap_or_else(|| {
+                cli_error(&format!("Invalid background color '{}'. Expected {}", s, COLOR_FORMS))
+            }));
+            preview::export_to_file(&file, &out, &format, &color_format, &cell_size, scale, no_crop, reset_each_cell, no_overwrite, background, ramp.as_deref(), invert)
         }
         Command::Resize { file, width, height, size } => {
             cmd_resize(&file, width, height, size)
         }
+        Command::Scale { file, size, interp, palette } => cmd_scale(&file, size, interp, palette.as_deref()),
         Command::Clear { file, region } => cmd_clear(&file, region),
-        Command::Import { image, output, output_flag, width, height, quantize, boost, no_preserve_hue, no_normalize, posterize, mosaic } => {
+        Command::Normalize { file, no_log } => cmd_normalize(&file, no_log),
+        Command::RemoveBg { file, tolerance, no_log } => cmd_remove_bg(&file, tolerance, no_log),
+        Command::Replace { file, from, to, tolerance } => cmd_replace(&file, &from, &to, tolerance),
+        Command::Frame { file, color, style } => cmd_frame(&file, &color, &style),
+        Command::Crop { file } => cmd_crop(&file),
+        Command::Quantize { file, depth } => cmd_quantize(&file, &depth),
+        Command::Mask { file, output } => cmd_mask(&file, output.as_deref()),
+        Command::Dither2 { file, region, a, b, ratio } => cmd_dither2(&file, region, &a, &b, ratio),
+        Command::Import { image, output, output_flag, width, height, quantize, boost, no_preserve_hue, no_normalize, posterize, dither, mosaic, frames } => {
             let out = output.or(output_flag)
                 .unwrap_or_else(|| cli_error("Output path required. Usage: kakukuma import <IMAGE> <OUTPUT>"));
-            cmd_import(&image, &out, width, height, &quantize, boost, !no_preserve_hue, !no_normalize, posterize, mosaic)
+            if frames {
+                cmd_import_frames(&image, &out, width, height, &quantize, boost, !no_preserve_hue, !no_normalize, posterize, dither)
+            } else {
+                cmd_import(&image, &out, width, height, &quantize, boost, !no_preserve_hue, !no_normalize, posterize, dither, mosaic)
+            }
         }
         Command::Render { image, width, height, color_format, no_normalize, no_preserve_hue, boost, posterize } => {
             cmd_render(&image, width, height, &color_format, !no_normalize, !no_preserve_hue, boost, posterize)
         }
         Command::Palette { action } => palette_cmd::run(action),
         Command::Batch { file, commands, dry_run } => batch::run_batch(&file, &commands, dry_run),
+        Command::Script { file, commands } => script::run_script(&file, &commands),
         Command::Chars { category, plain } => chars::run_chars(category.as_deref(), plain),
+        Command::Color { input } => color_cmd::run(&input),
         Command::Reference { file, image, clear } => cmd_reference(&file, image.as_deref(), clear),
+        Command::Slice { file, tile, prefix, partial, force } => cmd_slice(&file, tile, &prefix, partial, force),
+        Command::Assemble { output, cols, tiles, force } => cmd_assemble(&output, cols, &tiles, force),
+        Command::Merge { base, overlay, output, at, mode, force } => cmd_merge(&base, &overlay, &output, at, mode, force),
+        Command::Transform { action } => match action {
+            TransformAction::Export { file, add, clear } => cmd_transform(&file, &add, clear),
+            TransformAction::Flip { file, horizontal, vertical } => cmd_flip(&file, horizontal, vertical),
+            TransformAction::Rotate { file, cw, .. } => cmd_rotate(&file, cw),
+            TransformAction::Shift { file, dx, dy, wrap } => cmd_shift(&file, dx, dy, wrap),
+            TransformAction::Invert { file } => cmd_invert(&file),
+            TransformAction::Grayscale { file, amount } => cmd_grayscale(&file, amount),
+        },
     }
 }
 
@@ -626,25 +1321,76 @@ fn cmd_resize(
 
     let old_w = project.canvas.width;
     let old_h = project.canvas.height;
-    project.canvas.resize(new_w, new_h);
-    let actual_w = project.canvas.width;
-    let actual_h = project.canvas.height;
+    let actual_w = new_w.clamp(crate::canvas::MIN_DIMENSION, crate::canvas::MAX_DIMENSION);
+    let actual_h = new_h.clamp(crate::canvas::MIN_DIMENSION, crate::canvas::MAX_DIMENSION);
+    if (actual_w, actual_h) == (old_w, old_h) {
+        cli_error(&format!("Canvas is already {}x{}", old_w, old_h));
+    }
     let clamped = actual_w != new_w || actual_h != new_h;
+
+    let old_canvas = project.canvas.clone();
+    project.canvas.resize(actual_w, actual_h);
+    // Only drawn cells the shrink cropped away differ
+    let entry = size_change_entry("resize", &old_canvas, &project.canvas);
+    let cropped = !entry.mutations.is_empty();
+    crate::oplog::append(&crate::oplog::log_path(path), entry)?;
     atomic_save(&mut project, path)?;
 
     let mut json = serde_json::json!({
-        "resized": file,
-        "old_width": old_w,
-        "old_height": old_h,
-        "new_width": actual_w,
-        "new_height": actual_h,
+        "resized": true,
+        "file": file,
+        "old": format!("{}x{}", old_w, old_h),
+        "new": format!("{}x{}", actual_w, actual_h),
+        "cropped": cropped,
     });
     if clamped {
         json["clamped"] = serde_json::json!(true);
         json["requested_width"] = serde_json::json!(new_w);
         json["requested_height"] = serde_json::json!(new_h);
     }
-    println!("{}", serde_json::to_string(&json).unwrap());
+    print_json(&json, false);
+    Ok(())
+}
+
+fn cmd_scale(
+    file: &str,
+    (width, height): (usize, usize),
+    interp: CliInterpolation,
+    palette: Option<&str>,
+) -> io::Result<()> {
+    let path = Path::new(file);
+    let range = crate::canvas::MIN_DIMENSION..=crate::canvas::MAX_DIMENSION;
+    if !range.contains(&width) || !range.contains(&height) {
+        cli_error(&format!(
+            "Size {}x{} is out of range; width and height must be {}-{}",
+            width, height, crate::canvas::MIN_DIMENSION, crate::canvas::MAX_DIMENSION
+        ));
+    }
+    let palette = palette.map(|name| palette_cmd::load_colors(name).unwrap_or_else(|e| cli_error(&e)));
+    let mut project = load_project(file);
+
+    let (old_w, old_h) = (project.canvas.width, project.canvas.height);
+    let interp = match interp {
+        CliInterpolation::Nearest => Interpolation::Nearest,
+        CliInterpolation::Bilinear => Interpolation::Bilinear,
+    };
+    let scaled = project.canvas.scaled(width, height, interp, palette.as_deref());
+    let old_canvas = std::mem::replace(&mut project.canvas, scaled);
+    crate::oplog::append(&crate::oplog::log_path(path), size_change_entry("scale", &old_canvas, &project.canvas))?;
+    atomic_save(&mut project, path)?;
+
+    let json = serde_json::json!({
+        "scaled": file,
+        "old_width": old_w,
+        "old_height": old_h,
+        "new_width": width,
+        "new_height": height,
+        "interp": match interp {
+            Interpolation::Nearest => "nearest",
+            Interpolation::Bilinear => "bilinear",
+        },
+    });
+    print_json(&json, false);
     Ok(())
 }
 
@@ -684,20 +1430,455 @@ fn cmd_clear(file: &str, region: Option<(usize, usize, usize, usize)>) -> io::Re
             "x1": x1, "y1": y1, "x2": x2, "y2": y2
         })),
     });
-    println!("{}", serde_json::to_string(&json).unwrap());
+    print_json(&json, false);
     Ok(())
 }
 
-fn cmd_import(
-    image: &str,
-    output: &str,
-    width: usize,
-    height: usize,
-    quantize: &CliColorFormat,
-    boost: f32,
+fn cmd_normalize(file: &str, no_log: bool) -> io::Result<()> {
+    let path = Path::new(file);
+    let mut project = load_project(file);
+
+    let mut mutations = Vec::new();
+    for y in 0..project.canvas.height {
+        for x in 0..project.canvas.width {
+            if let Some(old) = project.canvas.get(x, y) {
+                let new = old.normalized();
+                if new != old {
+                    mutations.push(crate::history::CellMutation { x, y, old, new });
+                }
+            }
+        }
+    }
+
+    for m in &mutations {
+        project.canvas.set(m.x, m.y, m.new);
+    }
+
+    if !no_log && !mutations.is_empty() {
+        let log_path = crate::oplog::log_path(path);
+        crate::oplog::append(&log_path, crate::oplog::make_entry("normalize", &mutations))?;
+    }
+
+    atomic_save(&mut project, path)?;
+
+    let json = serde_json::json!({
+        "normalized": file,
+        "cells_modified": mutations.len(),
+    });
+    print_json(&json, false);
+    Ok(())
+}
+
+fn cmd_remove_bg(file: &str, tolerance: u8, no_log: bool) -> io::Result<()> {
+    let path = Path::new(file);
+    let mut project = load_project(file);
+
+    let mutations = crate::tools::remove_background(&project.canvas, tolerance);
+    for m in &mutations {
+        project.canvas.set(m.x, m.y, m.new);
+    }
+
+    if !no_log && !mutations.is_empty() {
+        let log_path = crate::oplog::log_path(path);
+        crate::oplog::append(&log_path, crate::oplog::make_entry("remove-bg", &mutations))?;
+    }
+
+    atomic_save(&mut project, path)?;
+
+    let json = serde_json::json!({
+        "ok": true,
+        "file": file,
+        "tolerance": tolerance,
+        "cells_cleared": mutations.len(),
+    });
+    print_json(&json, false);
+    Ok(())
+}
+
+fn cmd_replace(file: &str, from: &str, to: &str, tolerance: u8) -> io::Result<()> {
+    let path = Path::new(file);
+    let parse = |s: &str| {
+        parse_hex_color(s)
+            .unwrap_or_else(|| cli_error(&format!("Invalid color '{}'. Expected {}", s, COLOR_FORMS)))
+    };
+    let (from_color, to_color) = (parse(from), parse(to));
+    let mut project = load_project(file);
+
+    let mutations = crate::tools::replace_color(&project.canvas, from_color, to_color, tolerance);
+    for m in &mutations {
+        project.canvas.set(m.x, m.y, m.new);
+    }
+
+    if !mutations.is_empty() {
+        let log_path = crate::oplog::log_path(path);
+        crate::oplog::append(&log_path, crate::oplog::make_entry("replace", &mutations))?;
+    }
+
+    atomic_save(&mut project, path)?;
+
+    let json = serde_json::json!({
+        "replaced": file,
+        "from": from_color.name(),
+        "to": to_color.name(),
+        "tolerance": tolerance,
+        "cells_modified": mutations.len(),
+    });
+    print_json(&json, false);
+    Ok(())
+}
+
+fn cmd_dither2(
+    file: &str,
+    (x1, y1, x2, y2): (usize, usize, usize, usize),
+    a: &str,
+    b: &str,
+    ratio: u8,
+) -> io::Result<()> {
+    let path = Path::new(file);
+    let parse = |s: &str| {
+        parse_hex_color(s)
+            .unwrap_or_else(|| cli_error(&format!("Invalid color '{}'. Expected {}", s, COLOR_FORMS)))
+    };
+    let (color_a, color_b) = (parse(a), parse(b));
+    let mut project = load_project(file);
+    if x1 >= project.canvas.width || y1 >= project.canvas.height {
+        cli_error(&format!(
+            "Region starts at ({}, {}), outside the {}x{} canvas",
+            x1, y1, project.canvas.width, project.canvas.height
+        ));
+    }
+
+    let mutations = crate::tools::dither2(&project.canvas, x1, y1, x2, y2, color_a, color_b, ratio);
+    for m in &mutations {
+        project.canvas.set(m.x, m.y, m.new);
+    }
+
+    if !mutations.is_empty() {
+        let log_path = crate::oplog::log_path(path);
+        crate::oplog::append(&log_path, crate::oplog::make_entry("dither2", &mutations))?;
+    }
+
+    atomic_save(&mut project, path)?;
+
+    let json = serde_json::json!({
+        "dithered": file,
+        "a": color_a.name(),
+        "b": color_b.name(),
+        "ratio": ratio,
+        "cells_modified": mutations.len(),
+    });
+    print_json(&json, false);
+    Ok(())
+}
+
+fn cmd_mask(file: &str, output: Option<&str>) -> io::Result<()> {
+    if let Some(out) = output {
+        if !out.to_ascii_lowercase().ends_with(".png") {
+            cli_error(&format!("Mask output must be a .png file, got '{}'", out));
+        }
+    }
+    let project = load_project(file);
+    let mask = crate::export::mask(&project.canvas);
+    let filled = mask.iter().flatten().filter(|&&set| set).count();
+
+    let mut json = serde_json::json!({
+        "width": project.canvas.width,
+        "height": project.canvas.height,
+        "filled": filled,
+    });
+    match output {
+        Some(out) => {
+            crate::export::write_mask_png(&mask, Path::new(out))?;
+            json["exported"] = serde_json::json!(out);
+            json["format"] = serde_json::json!("png");
+        }
+        None => json["mask"] = serde_json::json!(mask),
+    }
+    print_json(&json, false);
+    Ok(())
+}
+
+fn cmd_quantize(file: &str, depth: &CliColorFormat) -> io::Result<()> {
+    let path = Path::new(file);
+    let snap: Option<fn(&Rgb) -> u8> = match depth {
+        CliColorFormat::Auto => cli_error("--depth auto is ambiguous here; use truecolor, 256, 256-hue or 16."),
+        CliColorFormat::Truecolor => None,
+        CliColorFormat::Color256 => Some(crate::cell::nearest_256),
+        CliColorFormat::Color256Hue => Some(crate::cell::nearest_256_hue),
+        CliColorFormat::Color16 => Some(crate::cell::nearest_16),
+    };
+    let mut project = load_project(file);
+
+    let mut mutations = Vec::new();
+    if let Some(snap) = snap {
+        let quantize = |c: Option<Rgb>| c.map(|c| crate::cell::color256_to_rgb(snap(&c)));
+        for y in 0..project.canvas.height {
+            for x in 0..project.canvas.width {
+                if let Some(old) = project.canvas.get(x, y) {
+                    let new = Cell { fg: quantize(old.fg), bg: quantize(old.bg), ..old };
+                    if new != old {
+                        mutations.push(crate::history::CellMutation { x, y, old, new });
+                    }
+                }
+            }
+        }
+    }
+
+    for m in &mutations {
+        project.canvas.set(m.x, m.y, m.new);
+    }
+
+    if !mutations.is_empty() {
+        let log_path = crate::oplog::log_path(path);
+        crate::oplog::append(&log_path, crate::oplog::make_entry("quantize", &mutations))?;
+        atomic_save(&mut project, path)?;
+    }
+
+    let json = serde_json::json!({
+        "quantized": file,
+        "depth": depth.to_possible_value().map(|v| v.get_name().to_string()),
+        "cells_modified": mutations.len(),
+    });
+    print_json(&json, false);
+    Ok(())
+}
+
+fn cmd_frame(file: &str, color: &str, style: &str) -> io::Result<()> {
+    let path = Path::new(file);
+    let style = FrameStyle::from_name(style).unwrap_or_else(|| {
+        let names: Vec<&str> = FrameStyle::ALL.iter().map(|s| s.name()).collect();
+        cli_error(&format!("Unknown frame style '{}'. Expected one of: {}", style, names.join(", ")))
+    });
+    let fg = parse_hex_color(color).unwrap_or_else(|| {
+        cli_error(&format!("Invalid color '{}'. Expected {}", color, COLOR_FORMS))
+    });
+    let mut project = load_project(file);
+    if project.canvas.width < 2 || project.canvas.height < 2 {
+        cli_error(&format!(
+            "Canvas is {}x{}; a frame needs at least 2x2.",
+            project.canvas.width, project.canvas.height
+        ));
+    }
+
+    let mutations = crate::tools::frame(&project.canvas, style, Some(fg));
+    for m in &mutations {
+        project.canvas.set(m.x, m.y, m.new);
+    }
+
+    if !mutations.is_empty() {
+        let log_path = crate::oplog::log_path(path);
+        crate::oplog::append(&log_path, crate::oplog::make_entry("frame", &mutations))?;
+    }
+
+    atomic_save(&mut project, path)?;
+
+    let json = serde_json::json!({
+        "framed": file,
+        "style": style.name(),
+        "color": fg.name(),
+        "cells_modified": mutations.len(),
+    });
+    print_json(&json, false);
+    Ok(())
+}
+
+fn cmd_flip(file: &str, horizontal: bool, vertical: bool) -> io::Result<()> {
+    if !horizontal && !vertical {
+        cli_error("Nothing to flip. Pass --horizontal and/or --vertical.");
+    }
+    let path = Path::new(file);
+    let mut project = load_project(file);
+
+    let mut flipped = project.canvas.clone();
+    if horizontal {
+        flipped.flip_horizontal();
+    }
+    if vertical {
+        flipped.flip_vertical();
+    }
+    let mutations = canvas_mutations(&project.canvas, &flipped);
+    project.canvas = flipped;
+
+    if !mutations.is_empty() {
+        let log_path = crate::oplog::log_path(path);
+        crate::oplog::append(&log_path, crate::oplog::make_entry("flip", &mutations))?;
+    }
+    atomic_save(&mut project, path)?;
+
+    let json = serde_json::json!({
+        "flipped": file,
+        "horizontal": horizontal,
+        "vertical": vertical,
+        "cells_modified": mutations.len(),
+    });
+    print_json(&json, false);
+    Ok(())
+}
+
+fn cmd_invert(file: &str) -> io::Result<()> {
+    let path = Path::new(file);
+    let mut project = load_project(file);
+
+    let mut inverted = project.canvas.clone();
+    inverted.invert_colors();
+    let mutations = canvas_mutations(&project.canvas, &inverted);
+    project.canvas = inverted;
+
+    if !mutations.is_empty() {
+        let log_path = crate::oplog::log_path(path);
+        crate::oplog::append(&log_path, crate::oplog::make_entry("invert", &mutations))?;
+    }
+    atomic_save(&mut project, path)?;
+
+    let json = serde_json::json!({
+        "inverted": file,
+        "cells_modified": mutations.len(),
+    });
+    print_json(&json, false);
+    Ok(())
+}
+
+fn cmd_grayscale(file: &str, amount: u8) -> io::Result<()> {
+    let path = Path::new(file);
+    let mut project = load_project(file);
+
+    let mut gray = project.canvas.clone();
+    gray.desaturate(amount);
+    let mutations = canvas_mutations(&project.canvas, &gray);
+    project.canvas = gray;
+
+    if !mutations.is_empty() {
+        let log_path = crate::oplog::log_path(path);
+        crate::oplog::append(&log_path, crate::oplog::make_entry("grayscale", &mutations))?;
+    }
+    atomic_save(&mut project, path)?;
+
+    let json = serde_json::json!({
+        "grayscaled": file,
+        "amount": amount,
+        "cells_modified": mutations.len(),
+    });
+    print_json(&json, false);
+    Ok(())
+}
+
+/// Cells that differ between `old` and `new`, over the area of both (cells
+/// past the edge of either canvas count as blank).
+fn canvas_mutations(old: &Canvas, new: &Canvas) -> Vec<crate::history::CellMutation> {
+    let mut mutations = Vec::new();
+    for y in 0..old.height.max(new.height) {
+        for x in 0..old.width.max(new.width) {
+            let before = old.get(x, y).unwrap_or_default();
+            let after = new.get(x, y).unwrap_or_default();
+            if before != after {
+                mutations.push(crate::history::CellMutation { x, y, old: before, new: after });
+            }
+        }
+    }
+    mutations
+}
+
+/// Log entry for an operation that changed the canvas size from `old` to
+/// `new`. Cells outside either canvas are logged against blank, so undo and
+/// redo can resize and restore both sides.
+fn size_change_entry(command: &str, old: &Canvas, new: &Canvas) -> crate::oplog::LogEntry {
+    let mut entry = crate::oplog::make_entry(command, &canvas_mutations(old, new));
+    entry.resize = Some(crate::oplog::LogResize {
+        old_width: old.width,
+        old_height: old.height,
+        new_width: new.width,
+        new_height: new.height,
+    });
+    entry
+}
+
+fn cmd_shift(file: &str, dx: isize, dy: isize, wrap: bool) -> io::Result<()> {
+    let path = Path::new(file);
+    let mut project = load_project(file);
+    let mut shifted = project.canvas.clone();
+    shifted.shift(dx, dy, wrap);
+    let mutations = canvas_mutations(&project.canvas, &shifted);
+    project.canvas = shifted;
+
+    if !mutations.is_empty() {
+        let log_path = crate::oplog::log_path(path);
+        crate::oplog::append(&log_path, crate::oplog::make_entry("shift", &mutations))?;
+    }
+    atomic_save(&mut project, path)?;
+
+    let json = serde_json::json!({
+        "shifted": file,
+        "dx": dx,
+        "dy": dy,
+        "wrap": wrap,
+        "cells_modified": mutations.len(),
+    });
+    print_json(&json, false);
+    Ok(())
+}
+
+fn cmd_crop(file: &str) -> io::Result<()> {
+    let path = Path::new(file);
+    let mut project = load_project(file);
+    let old_canvas = project.canvas.clone();
+    let (old_w, old_h) = (old_canvas.width, old_canvas.height);
+    let Some((x, y)) = project.canvas.crop_to_content() else {
+        cli_error("Canvas is empty; nothing to crop to.");
+    };
+    let (new_w, new_h) = (project.canvas.width, project.canvas.height);
+    if (x, y, new_w, new_h) == (0, 0, old_w, old_h) {
+        cli_error(&format!("Content already fills the {}x{} canvas", old_w, old_h));
+    }
+
+    crate::oplog::append(&crate::oplog::log_path(path), size_change_entry("crop", &old_canvas, &project.canvas))?;
+    atomic_save(&mut project, path)?;
+
+    let json = serde_json::json!({
+        "cropped": file,
+        "offset": {"x": x, "y": y},
+        "old": format!("{}x{}", old_w, old_h),
+        "new": format!("{}x{}", new_w, new_h),
+    });
+    print_json(&json, false);
+    Ok(())
+}
+
+fn cmd_rotate(file: &str, cw: bool) -> io::Result<()> {
+    let path = Path::new(file);
+    let mut project = load_project(file);
+    let (old_w, old_h) = (project.canvas.width, project.canvas.height);
+    let old_canvas = project.canvas.clone();
+    if cw {
+        project.canvas.rotate_cw();
+    } else {
+        project.canvas.rotate_ccw();
+    }
+    crate::oplog::append(&crate::oplog::log_path(path), size_change_entry("rotate", &old_canvas, &project.canvas))?;
+    atomic_save(&mut project, path)?;
+
+    let json = serde_json::json!({
+        "rotated": file,
+        "direction": if cw { "cw" } else { "ccw" },
+        "old_width": old_w,
+        "old_height": old_h,
+        "new_width": project.canvas.width,
+        "new_height": project.canvas.height,
+    });
+    print_json(&json, false);
+    Ok(())
+}
+
+fn cmd_import(
+    image: &str,
+    output: &str,
+    width: usize,
+    height: usize,
+    quantize: &CliColorFormat,
+    boost: f32,
     preserve_hue: bool,
     normalize: bool,
     posterize: Option<usize>,
+    dither: CliDither,
     mosaic: bool,
 ) -> io::Result<()> {
     let img_path = Path::new(image);
@@ -707,21 +1888,8 @@ fn cmd_import(
 
     let out_path = Path::new(output);
 
-    let color_mode = match quantize {
-        CliColorFormat::Auto | CliColorFormat::Truecolor => ImportColorMode::TrueColor,
-        CliColorFormat::Color256 | CliColorFormat::Color256Hue => ImportColorMode::Color256,
-        CliColorFormat::Color16 => ImportColorMode::Color16,
-    };
-
-    let options = ImportOptions {
-        fit_mode: FitMode::FitToCanvas,
-        color_mode,
-        color_boost: boost,
-        preserve_hue,
-        normalize,
-        posterize,
-        ..ImportOptions::default()
-    };
+    let options = cli_import_options(quantize, boost, preserve_hue, normalize, posterize, dither);
+    let color_mode = options.color_mode;
 
     let w = width.clamp(crate::canvas::MIN_DIMENSION, crate::canvas::MAX_DIMENSION);
     let h = height.clamp(crate::canvas::MIN_DIMENSION, crate::canvas::MAX_DIMENSION);
@@ -736,30 +1904,115 @@ fn cmd_import(
         })
         .unwrap();
 
+    let mut project = imported_project(out_path, &cells, w, h);
+    atomic_save(&mut project, out_path)?;
+
+    let json = serde_json::json!({
+        "imported": image,
+        "output": output,
+        "width": w,
+        "height": h,
+        "color_mode": format!("{:?}", color_mode),
+    });
+    print_json(&json, false);
+    Ok(())
+}
+
+/// Map the shared import/render flags onto `ImportOptions`.
+fn cli_import_options(
+    quantize: &CliColorFormat,
+    boost: f32,
+    preserve_hue: bool,
+    normalize: bool,
+    posterize: Option<usize>,
+    dither: CliDither,
+) -> ImportOptions {
+    let color_mode = match quantize {
+        CliColorFormat::Auto | CliColorFormat::Truecolor => ImportColorMode::TrueColor,
+        CliColorFormat::Color256 | CliColorFormat::Color256Hue => ImportColorMode::Color256,
+        CliColorFormat::Color16 => ImportColorMode::Color16,
+    };
+    ImportOptions {
+        fit_mode: FitMode::FitToCanvas,
+        color_mode,
+        color_boost: boost,
+        preserve_hue,
+        normalize,
+        posterize,
+        dither: dither.into(),
+        ..ImportOptions::default()
+    }
+}
+
+/// Wrap an imported cell grid in a fresh project named after `out_path`.
+fn imported_project(out_path: &Path, cells: &[Vec<Cell>], w: usize, h: usize) -> Project {
     let mut canvas = Canvas::new_with_size(w, h);
     for (y, row) in cells.iter().enumerate() {
         for (x, cell) in row.iter().enumerate() {
             canvas.set(x, y, *cell);
         }
     }
-
-    let mut project = Project::new(
+    Project::new(
         out_path.file_stem().and_then(|s| s.to_str()).unwrap_or("imported"),
         canvas,
         Rgb::WHITE,
-        SymmetryMode::Off,
-    );
+        SymmetryMode::empty(),
+    )
+}
 
-    atomic_save(&mut project, out_path)?;
+/// Import every GIF frame, writing `<stem>-001.kaku`, `<stem>-002.kaku`, ...
+/// next to `output`.
+#[allow(clippy::too_many_arguments)]
+fn cmd_import_frames(
+    image: &str,
+    output: &str,
+    width: usize,
+    height: usize,
+    quantize: &CliColorFormat,
+    boost: f32,
+    preserve_hue: bool,
+    normalize: bool,
+    posterize: Option<usize>,
+    dither: CliDither,
+) -> io::Result<()> {
+    let img_path = Path::new(image);
+    if !img_path.exists() {
+        cli_error(&format!("Image not found: '{}'", image));
+    }
+    if output == crate::oplog::STDIO {
+        cli_error("--frames writes one file per frame and can't write to stdout");
+    }
+
+    let options = cli_import_options(quantize, boost, preserve_hue, normalize, posterize, dither);
+    let w = width.clamp(crate::canvas::MIN_DIMENSION, crate::canvas::MAX_DIMENSION);
+    let h = height.clamp(crate::canvas::MIN_DIMENSION, crate::canvas::MAX_DIMENSION);
+
+    let frames = crate::import::import_gif_frames(img_path, w, h, &options)
+        .unwrap_or_else(|e| cli_error(&format!("Import failed: {}", e)));
+
+    let out_path = Path::new(output);
+    let stem = out_path.file_stem().and_then(|s| s.to_str()).unwrap_or("frame");
+    let ext = out_path.extension().and_then(|s| s.to_str()).unwrap_or("kaku");
+
+    let mut written = Vec::with_capacity(frames.len());
+    for (i, frame) in frames.iter().enumerate() {
+        let frame_path = out_path.with_file_name(format!("{}-{:03}.{}", stem, i + 1, ext));
+        let mut project = imported_project(&frame_path, &frame.cells, w, h);
+        atomic_save(&mut project, &frame_path)?;
+        written.push(serde_json::json!({
+            "file": frame_path.to_string_lossy(),
+            "delay_ms": frame.delay_ms,
+        }));
+    }
 
     let json = serde_json::json!({
         "imported": image,
-        "output": output,
+        "frames": written,
         "width": w,
         "height": h,
-        "color_mode": format!("{:?}", color_mode),
+        "color_mode": format!("{:?}", options.color_mode),
     });
-    println!("{}", serde_json::to_string(&json).unwrap());
+    print_json(&json, false);
     Ok(())
 }
 
@@ -827,9 +2080,19 @@ fn cmd_render(
 
 fn cmd_new(file: &str, width: usize, height: usize, force: bool) -> io::Result<()> {
     let path = Path::new(file);
-    if path.exists() && !force {
+    let stdio = file == crate::oplog::STDIO;
+    if !stdio && path.exists() && !force {
         cli_error(&format!("'{}' already exists. Use --force to overwrite.", file));
     }
+    // Sidecars without their project (e.g. after a crash) would leave the
+    // new canvas with someone else's history or recovery file.
+    let orphaned = if stdio || path.exists() { Vec::new() } else { existing_sidecars(file) };
+    if !orphaned.is_empty() && !force {
+        cli_error_with(
+            &format!("'{}' doesn't exist but its sidecar files do. Use --force to remove them.", file),
+            serde_json::json!({ "orphaned_sidecars": orphaned }),
+        );
+    }
 
     let w = width.clamp(crate::canvas::MIN_DIMENSION, crate::canvas::MAX_DIMENSION);
     let h = height.clamp(crate::canvas::MIN_DIMENSION, crate::canvas::MAX_DIMENSION);
@@ -837,14 +2100,22 @@ fn cmd_new(file: &str, width: usize, height: usize, force: bool) -> io::Result<(
 
     let canvas = Canvas::new_with_size(w, h);
     let mut project = Project::new(
-        path.file_stem().and_then(|s| s.to_str()).unwrap_or("untitled"),
+        path.file_stem().and_then(|s| s.to_str()).filter(|_| !stdio).unwrap_or("untitled"),
         canvas,
         Rgb::WHITE,
-        SymmetryMode::Off,
+        SymmetryMode::empty(),
     );
 
-    project.save_to_file(path)
-        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    // A forced overwrite starts a new history: drop recovery files left by
+    // the old project so they can't be mistaken for this one.
+    let removed = if force && !stdio { remove_stale_sidecars(file)? } else { Vec::new() };
+
+    if stdio {
+        atomic_save(&mut project, path)?;
+    } else {
+        project.save_to_file(path)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    }
 
     // Initialize empty log
     let log = crate::oplog::log_path(path);
@@ -855,15 +2126,49 @@ fn cmd_new(file: &str, width: usize, height: usize, force: bool) -> io::Result<(
         "width": w,
         "height": h,
     });
+    if force {
+        json["removed_sidecars"] = serde_json::json!(removed);
+    }
+    if !orphaned.is_empty() {
+        json["orphaned_sidecars"] = serde_json::json!(orphaned);
+    }
     if clamped {
         json["clamped"] = serde_json::json!(true);
         json["requested_width"] = serde_json::json!(width);
         json["requested_height"] = serde_json::json!(height);
     }
-    println!("{}", serde_json::to_string(&json).unwrap());
+    print_json(&json, false);
     Ok(())
 }
 
+/// Recovery sidecars written next to a project as `<file>.<suffix>`.
+const STALE_SIDECARS: &[&str] = &["autosave", "bak"];
+
+/// Sidecar files that exist for `file`: its operation log and recovery files.
+fn existing_sidecars(file: &str) -> Vec<String> {
+    let log = crate::oplog::log_path(Path::new(file));
+    let recovery = STALE_SIDECARS.iter().map(|suffix| PathBuf::from(format!("{}.{}", file, suffix)));
+    std::iter::once(log)
+        .chain(recovery)
+        .filter(|p| p.exists())
+        .map(|p| p.display().to_string())
+        .collect()
+}
+
+/// Delete the recovery sidecars for `file`, returning the paths removed.
+fn remove_stale_sidecars(file: &str) -> io::Result<Vec<String>> {
+    let mut removed = Vec::new();
+    for suffix in STALE_SIDECARS {
+        let sidecar = format!("{}.{}", file, suffix);
+        match std::fs::remove_file(&sidecar) {
+            Ok(()) => removed.push(sidecar),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(removed)
+}
+
 fn cmd_reference(file: &str, image: Option<&str>, clear: bool) -> io::Result<()> {
     let path = Path::new(file);
     let mut project = load_project(file);
@@ -875,7 +2180,7 @@ fn cmd_reference(file: &str, image: Option<&str>, clear: bool) -> io::Result<()>
             "reference": serde_json::Value::Null,
             "file": file,
         });
-        println!("{}", serde_json::to_string(&json).unwrap());
+        print_json(&json, false);
         return Ok(());
     }
 
@@ -905,7 +2210,217 @@ fn cmd_reference(file: &str, image: Option<&str>, clear: bool) -> io::Result<()>
         "reference": rel_path,
         "file": file,
     });
-    println!("{}", serde_json::to_string(&json).unwrap());
+    print_json(&json, false);
+    Ok(())
+}
+
+fn cmd_slice(
+    file: &str,
+    (tile_w, tile_h): (usize, usize),
+    prefix: &str,
+    partial: bool,
+    force: bool,
+) -> io::Result<()> {
+    let project = load_project(file);
+    let canvas = &project.canvas;
+    let min = crate::canvas::MIN_DIMENSION;
+    if tile_w < min || tile_h < min {
+        cli_error(&format!("Tile size must be at least {}x{}, got {}x{}", min, min, tile_w, tile_h));
+    }
+    if !partial && (canvas.width % tile_w != 0 || canvas.height % tile_h != 0) {
+        cli_error(&format!(
+            "Canvas {}x{} is not evenly divisible by tile {}x{}. Use --partial to keep smaller edge tiles (padded to {}x{} if needed).",
+            canvas.width, canvas.height, tile_w, tile_h, min, min
+        ));
+    }
+
+    let cols = canvas.width.div_ceil(tile_w);
+    let rows = canvas.height.div_ceil(tile_h);
+    let paths: Vec<String> = (0..rows)
+        .flat_map(|row| (0..cols).map(move |col| format!("{}_{}_{}.kaku", prefix, row, col)))
+        .collect();
+    if !force {
+        if let Some(existing) = paths.iter().find(|p| Path::new(p).exists()) {
+            cli_error(&format!("'{}' already exists. Use --force to overwrite.", existing));
+        }
+    }
+
+    let mut tiles = Vec::with_capacity(paths.len());
+    for (i, tile_path) in paths.iter().enumerate() {
+        let (row, col) = (i / cols, i % cols);
+        let mut sub = canvas.subcanvas(col * tile_w, row * tile_h, tile_w, tile_h);
+        // Edge tiles narrower than a canvas can be get blank padding
+        let padded = sub.width < min || sub.height < min;
+        if padded {
+            sub.resize(sub.width.max(min), sub.height.max(min));
+        }
+        let (width, height) = (sub.width, sub.height);
+        let path = Path::new(tile_path);
+        let name = path.file_stem().and_then(|s| s.to_str()).unwrap_or("tile");
+        let mut tile = Project::new(name, sub, project.color, SymmetryMode::empty());
+        atomic_save(&mut tile, path)?;
+        tiles.push(serde_json::json!({
+            "file": tile_path,
+            "row": row,
+            "col": col,
+            "width": width,
+            "height": height,
+            "padded": padded,
+        }));
+    }
+
+    let json = serde_json::json!({
+        "sliced": file,
+        "tile": { "width": tile_w, "height": tile_h },
+        "rows": rows,
+        "cols": cols,
+        "count": tiles.len(),
+        "tiles": tiles,
+    });
+    print_json(&json, false);
+    Ok(())
+}
+
+fn cmd_transform(file: &str, add: &[ExportTransform], clear: bool) -> io::Result<()> {
+    let path = Path::new(file);
+    let mut project = load_project(file);
+    let changed = clear || !add.is_empty();
+    if clear {
+        project.transforms.clear();
+    }
+    project.transforms.extend_from_slice(add);
+    if changed {
+        atomic_save(&mut project, path)?;
+    }
+
+    let json = serde_json::json!({
+        "file": file,
+        "transforms": project.transforms.iter().map(|t| t.spec()).collect::<Vec<_>>(),
+        "changed": changed,
+    });
+    print_json(&json, false);
+    Ok(())
+}
+
+fn cmd_merge(
+    base: &str,
+    overlay: &str,
+    output: &str,
+    at: (usize, usize),
+    mode: CliBlendMode,
+    force: bool,
+) -> io::Result<()> {
+    let out_path = Path::new(output);
+    let same_file = |a: &str, b: &str| {
+        a == b
+            || matches!(
+                (std::fs::canonicalize(a), std::fs::canonicalize(b)),
+                (Ok(x), Ok(y)) if x == y
+            )
+    };
+    let in_place = same_file(base, output);
+    if !in_place && output != crate::oplog::STDIO && out_path.exists() && !force {
+        cli_error(&format!("'{}' already exists. Use --force to overwrite.", output));
+    }
+
+    let mut project = load_project(base);
+    let top = load_project(overlay);
+    let mutations = crate::tools::merge(&project.canvas, &top.canvas, at.0, at.1, mode.into());
+    for m in &mutations {
+        project.canvas.set(m.x, m.y, m.new);
+    }
+
+    let mut removed = Vec::new();
+    if in_place {
+        if !mutations.is_empty() {
+            crate::oplog::append(&crate::oplog::log_path(out_path), crate::oplog::make_entry("merge", &mutations))?;
+        }
+        atomic_save(&mut project, out_path)?;
+    } else {
+        // A new file starts its own history
+        project.name = out_path.file_stem().and_then(|s| s.to_str()).unwrap_or("merged").to_string();
+        if force {
+            removed = remove_stale_sidecars(output)?;
+        }
+        atomic_save(&mut project, out_path)?;
+        crate::oplog::init_log(&crate::oplog::log_path(out_path))?;
+    }
+
+    let mut json = serde_json::json!({
+        "merged": overlay,
+        "base": base,
+        "output": output,
+        "at": {"x": at.0, "y": at.1},
+        "mode": format!("{:?}", mode).to_lowercase(),
+        "cells_modified": mutations.len(),
+    });
+    if force && !in_place {
+        json["removed_sidecars"] = serde_json::json!(removed);
+    }
+    print_json(&json, false);
+    Ok(())
+}
+
+fn cmd_assemble(output: &str, cols: usize, files: &[String], force: bool) -> io::Result<()> {
+    let path = Path::new(output);
+    if path.exists() && !force {
+        cli_error(&format!("'{}' already exists. Use --force to overwrite.", output));
+    }
+    if cols == 0 {
+        cli_error("--cols must be at least 1");
+    }
+
+    let tiles: Vec<Project> = files.iter().map(|f| load_project(f)).collect();
+    let (tile_w, tile_h) = (tiles[0].canvas.width, tiles[0].canvas.height);
+    for (file, tile) in files.iter().zip(&tiles).skip(1) {
+        if (tile.canvas.width, tile.canvas.height) != (tile_w, tile_h) {
+            cli_error(&format!(
+                "Tile '{}' is {}x{}, but '{}' is {}x{}. All tiles must be the same size.",
+                file, tile.canvas.width, tile.canvas.height, files[0], tile_w, tile_h
+            ));
+        }
+    }
+
+    let cols = cols.min(tiles.len());
+    let rows = tiles.len().div_ceil(cols);
+    let (width, height) = (tile_w * cols, tile_h * rows);
+    let range = crate::canvas::MIN_DIMENSION..=crate::canvas::MAX_DIMENSION;
+    if !range.contains(&width) || !range.contains(&height) {
+        cli_error(&format!(
+            "Assembled canvas would be {}x{} ({} cols x {} rows of {}x{}); sizes must be {}-{}",
+            width, height, cols, rows, tile_w, tile_h,
+            crate::canvas::MIN_DIMENSION, crate::canvas::MAX_DIMENSION
+        ));
+    }
+
+    let mut canvas = Canvas::new_with_size(width, height);
+    for (i, tile) in tiles.iter().enumerate() {
+        canvas.blit(&tile.canvas, (i % cols) * tile_w, (i / cols) * tile_h);
+    }
+    let mut project = Project::new(
+        path.file_stem().and_then(|s| s.to_str()).unwrap_or("untitled"),
+        canvas,
+        tiles[0].color,
+        SymmetryMode::empty(),
+    );
+
+    let removed = if force { remove_stale_sidecars(output)? } else { Vec::new() };
+    atomic_save(&mut project, path)?;
+    crate::oplog::init_log(&crate::oplog::log_path(path))?;
+
+    let mut json = serde_json::json!({
+        "assembled": output,
+        "width": width,
+        "height": height,
+        "tile": { "width": tile_w, "height": tile_h },
+        "rows": rows,
+        "cols": cols,
+        "count": tiles.len(),
+    });
+    if force {
+        json["removed_sidecars"] = serde_json::json!(removed);
+    }
+    print_json(&json, false);
     Ok(())
 }
 
@@ -936,6 +2451,8 @@ mod tests {
     #[test]
     fn test_parse_region_valid() {
         assert_eq!(parse_region("0,0,10,10"), Ok((0, 0, 10, 10)));
+        assert_eq!(parse_region("3,3,3,3"), Ok((3, 3, 3, 3)));
+        assert_eq!(parse_region("10,7,2,1"), Ok((2, 1, 10, 7)));
     }
 
     #[test]
@@ -961,7 +2478,7 @@ mod tests {
     fn test_resolve_colors_default() {
         let opts = DrawOpts {
             color: None, fg: None, bg: None,
-            ch: None, symmetry: CliSymmetry::Off, no_log: false,
+            ch: None, symmetry: CliSymmetry::Off, center: None, no_log: false, verbose: false,
         };
         let (fg, bg) = resolve_colors(&opts);
         assert_eq!(fg, Some(Rgb::WHITE));
@@ -972,7 +2489,7 @@ mod tests {
     fn test_resolve_colors_with_color() {
         let opts = DrawOpts {
             color: Some("#FF0000".to_string()), fg: None, bg: None,
-            ch: None, symmetry: CliSymmetry::Off, no_log: false,
+            ch: None, symmetry: CliSymmetry::Off, center: None, no_log: false, verbose: false,
         };
         let (fg, bg) = resolve_colors(&opts);
         assert_eq!(fg, Some(Rgb::new(255, 0, 0)));
@@ -985,7 +2502,7 @@ mod tests {
             color: Some("#FF0000".to_string()),
             fg: Some("#00FF00".to_string()),
             bg: Some("#0000FF".to_string()),
-            ch: None, symmetry: CliSymmetry::Off, no_log: false,
+            ch: None, symmetry: CliSymmetry::Off, center: None, no_log: false, verbose: false,
         };
         let (fg, bg) = resolve_colors(&opts);
         assert_eq!(fg, Some(Rgb::new(0, 255, 0)));
@@ -994,10 +2511,11 @@ mod tests {
 
     #[test]
     fn test_symmetry_mode_mapping() {
-        assert_eq!(to_symmetry_mode(&CliSymmetry::Off), SymmetryMode::Off);
-        assert_eq!(to_symmetry_mode(&CliSymmetry::Horizontal), SymmetryMode::Horizontal);
-        assert_eq!(to_symmetry_mode(&CliSymmetry::Vertical), SymmetryMode::Vertical);
-        assert_eq!(to_symmetry_mode(&CliSymmetry::Quad), SymmetryMode::Quad);
+        assert_eq!(to_symmetry_mode(&CliSymmetry::Off), SymmetryMode::empty());
+        assert_eq!(to_symmetry_mode(&CliSymmetry::Horizontal), SymmetryMode::HORIZONTAL);
+        assert_eq!(to_symmetry_mode(&CliSymmetry::Vertical), SymmetryMode::VERTICAL);
+        assert_eq!(to_symmetry_mode(&CliSymmetry::Quad), SymmetryMode::QUAD);
+        assert_eq!(to_symmetry_mode(&CliSymmetry::Kaleidoscope).bits().count_ones(), 4);
     }
 
     #[test]
@@ -1024,6 +2542,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_normalize_command_parse() {
+        let cli = Cli::try_parse_from(["kakukuma", "normalize", "art.kaku"]).unwrap();
+        match cli.command.unwrap() {
+            Command::Normalize { file, no_log } => {
+                assert_eq!(file, "art.kaku");
+                assert!(!no_log);
+            }
+            _ => panic!("Expected Normalize command"),
+        }
+    }
+
+    #[test]
+    fn test_blocks_alias_parses_as_chars() {
+        let cli = Cli::try_parse_from(["kakukuma", "blocks", "--category", "primary"]).unwrap();
+        match cli.command.unwrap() {
+            Command::Chars { category, .. } => {
+                assert_eq!(category.as_deref(), Some("primary"));
+            }
+            _ => panic!("Expected Chars command"),
+        }
+    }
+
     #[test]
     fn test_chars_command_parse_plain() {
         let cli = Cli::try_parse_from(["kakukuma", "chars", "--plain"]).unwrap();
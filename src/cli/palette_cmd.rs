@@ -1,8 +1,8 @@
 use std::io;
 use std::path::Path;
 
-use crate::cell::{parse_hex_color, Rgb};
-use crate::cli::{load_project, PaletteAction};
+use crate::cell::{parse_hex_color, Rgb, COLOR_FORMS};
+use crate::cli::{cli_error, load_project, PaletteAction, PaletteFormat, print_json};
 use crate::palette::{self, CustomPalette, DEFAULT_PALETTE};
 use crate::theme::THEMES;
 
@@ -10,9 +10,17 @@ pub fn run(action: PaletteAction) -> io::Result<()> {
     match action {
         PaletteAction::List => cmd_list(),
         PaletteAction::Show { name } => cmd_show(&name),
-        PaletteAction::Create { name, file } => cmd_create(&name, &file),
-        PaletteAction::Export { name, output } => cmd_export(&name, &output),
+        PaletteAction::Create { name, file, append } => cmd_create(&name, &file, append),
+        PaletteAction::Export { name, output, output_pos, format } => {
+            let output = output.or(output_pos).unwrap_or_else(|| {
+                cli_error("Output path required. Usage: kakukuma palette export <NAME> --output <FILE>")
+            });
+            cmd_export(&name, &output, format)
+        }
         PaletteAction::Add { name, color } => cmd_add(&name, &color),
+        PaletteAction::Diff { a, b } => cmd_diff(&a, &b),
+        PaletteAction::Merge { output, inputs } => cmd_merge(&output, &inputs),
+        PaletteAction::Swatches { name, output, columns } => cmd_swatches(&name, &output, columns),
         PaletteAction::Themes => cmd_themes(),
         PaletteAction::Theme { name } => cmd_theme(&name),
     }
@@ -38,7 +46,7 @@ fn cmd_list() -> io::Result<()> {
         },
         "custom_palettes": files,
     });
-    println!("{}", serde_json::to_string_pretty(&json).unwrap());
+    print_json(&json, true);
     Ok(())
 }
 
@@ -52,7 +60,7 @@ fn cmd_show(name: &str) -> io::Result<()> {
             "count": DEFAULT_PALETTE.len(),
             "colors": colors,
         });
-        println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        print_json(&json, true);
         return Ok(());
     }
 
@@ -67,7 +75,7 @@ fn cmd_show(name: &str) -> io::Result<()> {
                 "count": pal.colors.len(),
                 "colors": colors,
             });
-            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+            print_json(&json, true);
             Ok(())
         }
         Err(e) => {
@@ -77,7 +85,7 @@ fn cmd_show(name: &str) -> io::Result<()> {
     }
 }
 
-fn cmd_create(name: &str, file: &str) -> io::Result<()> {
+fn cmd_create(name: &str, file: &str, append: bool) -> io::Result<()> {
     let project = load_project(file);
     let canvas = &project.canvas;
 
@@ -102,37 +110,77 @@ fn cmd_create(name: &str, file: &str) -> io::Result<()> {
         }
     }
 
-    let pal = CustomPalette {
+    let path = palette_dir().join(format!("{}.palette", name));
+    let mut pal = CustomPalette {
         name: name.to_string(),
-        colors: colors.clone(),
+        colors: Vec::new(),
     };
+    if append && path.exists() {
+        pal = palette::load_palette(&path)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    }
+    let pre_existing = pal.colors.len();
+    for c in &colors {
+        if !pal.colors.contains(c) {
+            pal.colors.push(*c);
+        }
+    }
 
-    let path = palette_dir().join(format!("{}.palette", name));
     palette::save_palette(&pal, &path)
         .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
 
-    let json = serde_json::json!({
+    let mut json = serde_json::json!({
         "created": format!("{}.palette", name),
         "name": name,
         "colors_extracted": colors.len(),
     });
-    println!("{}", serde_json::to_string(&json).unwrap());
+    if append {
+        json["appended"] = serde_json::json!(true);
+        json["pre_existing"] = serde_json::json!(pre_existing);
+        json["colors_added"] = serde_json::json!(pal.colors.len() - pre_existing);
+        json["total_colors"] = serde_json::json!(pal.colors.len());
+    }
+    print_json(&json, false);
     Ok(())
 }
 
-fn cmd_export(name: &str, output: &str) -> io::Result<()> {
+fn cmd_export(name: &str, output: &str, format: PaletteFormat) -> io::Result<()> {
     let src = palette_dir().join(format!("{}.palette", name));
-    if !src.exists() {
-        eprintln!("Error: Palette '{}' not found", name);
-        std::process::exit(1);
+    let colors = load_colors(name).unwrap_or_else(|e| cli_error(&e));
+
+    match format {
+        PaletteFormat::Palette if src.exists() => {
+            std::fs::copy(&src, Path::new(output))?;
+        }
+        PaletteFormat::Palette => {
+            let pal = CustomPalette { name: name.to_string(), colors: colors.clone() };
+            palette::save_palette(&pal, Path::new(output))
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        }
+        PaletteFormat::Hex => std::fs::write(output, palette::to_hex_list(&colors))?,
+        PaletteFormat::Json => {
+            let hexes: Vec<String> = colors.iter().map(|c| c.name()).collect();
+            std::fs::write(output, serde_json::to_string_pretty(&hexes).unwrap() + "\n")?;
+        }
+        PaletteFormat::Gpl => std::fs::write(output, palette::to_gpl(name, &colors))?,
+        PaletteFormat::Ase => std::fs::write(output, palette::to_ase(&colors))?,
     }
-    std::fs::copy(&src, Path::new(output))?;
 
+    let format_name = match format {
+        PaletteFormat::Palette => "palette",
+        PaletteFormat::Hex => "hex",
+        PaletteFormat::Json => "json",
+        PaletteFormat::Gpl => "gpl",
+        PaletteFormat::Ase => "ase",
+    };
+    let source = if name == "default" { name.to_string() } else { format!("{}.palette", name) };
     let json = serde_json::json!({
         "exported": output,
-        "source": format!("{}.palette", name),
+        "source": source,
+        "format": format_name,
+        "colors": colors.len(),
     });
-    println!("{}", serde_json::to_string(&json).unwrap());
+    print_json(&json, false);
     Ok(())
 }
 
@@ -140,7 +188,7 @@ fn cmd_add(name: &str, color: &str) -> io::Result<()> {
     let rgb = match parse_hex_color(color) {
         Some(c) => c,
         None => {
-            eprintln!("Error: Invalid hex color '{}'", color);
+            eprintln!("Error: Invalid color '{}'. Expected {}", color, COLOR_FORMS);
             std::process::exit(1);
         }
     };
@@ -166,7 +214,98 @@ fn cmd_add(name: &str, color: &str) -> io::Result<()> {
         "added": rgb.name(),
         "total_colors": pal.colors.len(),
     });
-    println!("{}", serde_json::to_string(&json).unwrap());
+    print_json(&json, false);
+    Ok(())
+}
+
+/// Colors of a named palette: `default` or `<name>.palette` in the palette dir.
+pub fn load_colors(name: &str) -> Result<Vec<Rgb>, String> {
+    if name == "default" {
+        return Ok(DEFAULT_PALETTE.to_vec());
+    }
+    let path = palette_dir().join(format!("{}.palette", name));
+    if !path.exists() {
+        return Err(format!("Palette '{}' not found ({})", name, path.display()));
+    }
+    palette::load_palette(&path)
+        .map(|pal| pal.colors)
+        .map_err(|e| format!("Palette '{}': {}", name, e))
+}
+
+fn cmd_diff(a: &str, b: &str) -> io::Result<()> {
+    let load = |name: &str| {
+        load_colors(name).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        })
+    };
+    let diff = palette::diff_palettes(&load(a), &load(b));
+    let hexes = |colors: &[Rgb]| colors.iter().map(|c| c.name()).collect::<Vec<_>>();
+
+    let json = serde_json::json!({
+        "a": a,
+        "b": b,
+        "only_a": hexes(&diff.only_a),
+        "only_b": hexes(&diff.only_b),
+        "both": hexes(&diff.both),
+    });
+    print_json(&json, false);
+    Ok(())
+}
+
+fn cmd_merge(output: &str, inputs: &[String]) -> io::Result<()> {
+    // Load everything first so a missing input leaves nothing half-written
+    let mut lists = Vec::new();
+    for name in inputs {
+        match load_colors(name) {
+            Ok(colors) => lists.push(colors),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+    let colors = palette::merge_palettes(&lists);
+    let input_total: usize = lists.iter().map(Vec::len).sum();
+
+    let pal = CustomPalette {
+        name: output.to_string(),
+        colors,
+    };
+    let path = palette_dir().join(format!("{}.palette", output));
+    palette::save_palette(&pal, &path)
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+    let json = serde_json::json!({
+        "created": format!("{}.palette", output),
+        "name": output,
+        "inputs": inputs,
+        "total_colors": pal.colors.len(),
+        "duplicates_removed": input_total - pal.colors.len(),
+    });
+    print_json(&json, false);
+    Ok(())
+}
+
+fn cmd_swatches(name: &str, output: &str, columns: usize) -> io::Result<()> {
+    let colors = load_colors(name).unwrap_or_else(|e| cli_error(&e));
+    if colors.is_empty() {
+        cli_error(&format!("Palette '{}' has no colors", name));
+    }
+
+    let img = crate::export::palette_swatches(&colors, columns);
+    img.save(output).map_err(|e| {
+        io::Error::new(io::ErrorKind::Other, format!("PNG save failed: {}", e))
+    })?;
+
+    let json = serde_json::json!({
+        "exported": output,
+        "palette": name,
+        "colors": colors.len(),
+        "width": img.width(),
+        "height": img.height(),
+    });
+    print_json(&json, false);
     Ok(())
 }
 
@@ -179,7 +318,7 @@ fn cmd_themes() -> io::Result<()> {
         "themes": themes,
         "count": THEMES.len(),
     });
-    println!("{}", serde_json::to_string_pretty(&json).unwrap());
+    print_json(&json, true);
     Ok(())
 }
 
@@ -199,7 +338,7 @@ fn cmd_theme(name: &str) -> io::Result<()> {
                 "grid_even": format_color(t.grid_even),
                 "grid_odd": format_color(t.grid_odd),
             });
-            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+            print_json(&json, true);
             Ok(())
         }
         None => {
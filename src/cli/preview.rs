@@ -1,17 +1,22 @@
 use std::io;
 use std::path::Path;
 
-use crate::cli::{CliColorFormat, PreviewFormat, load_project, to_color_format};
+use crate::cli::{CliColorFormat, CliGraphics, PreviewFormat, cli_error, load_project, print_json, to_color_format, write_stdout};
 use crate::export;
+use crate::cell::Rgb;
 
 pub fn run(
     file: &str,
     format: &PreviewFormat,
     region: Option<(usize, usize, usize, usize)>,
     color_format: &CliColorFormat,
+    non_empty_only: bool,
+    compact: bool,
+    deterministic: bool,
 ) -> io::Result<()> {
-    let project = load_project(file);
+    let project = load_transformed(file);
     let cf = to_color_format(color_format);
+    let cf = if deterministic { pin_color_format(cf) } else { cf };
 
     match format {
         PreviewFormat::Ansi | PreviewFormat::Auto => {
@@ -24,7 +29,7 @@ pub fn run(
             Ok(())
         }
         PreviewFormat::Json => {
-            let output = json_preview(&project, region);
+            let output = json_preview(&project, region, non_empty_only, compact);
             println!("{}", output);
             Ok(())
         }
@@ -37,6 +42,22 @@ pub fn run(
             print!("{}", output);
             Ok(())
         }
+        PreviewFormat::Svg => {
+            print!("{}", export::to_svg(&project.canvas));
+            Ok(())
+        }
+        PreviewFormat::Html => {
+            print!("{}", export::to_html(&project.canvas));
+            Ok(())
+        }
+        PreviewFormat::Braille => {
+            let canvas = match region {
+                Some((x1, y1, x2, y2)) => region_canvas(&project, x1, y1, x2, y2),
+                None => project.canvas.clone(),
+            };
+            print!("{}", export::to_braille(&canvas));
+            Ok(())
+        }
         PreviewFormat::Png => {
             eprintln!("{{\"error\":\"PNG format not supported for preview (stdout). Use 'export' instead.\",\"code\":\"USER_ERROR\"}}");
             std::process::exit(1);
@@ -44,6 +65,27 @@ pub fn run(
     }
 }
 
+/// Print the canvas (or a region) as a bitmap for graphics-capable terminals.
+pub fn run_graphics(
+    file: &str,
+    protocol: CliGraphics,
+    region: Option<(usize, usize, usize, usize)>,
+    cell_size: &str,
+) -> io::Result<()> {
+    let (cw, ch) = parse_cell_size(cell_size).unwrap_or_else(|e| cli_error(&e));
+    let project = load_transformed(file);
+    let canvas = match region {
+        Some((x1, y1, x2, y2)) => region_canvas(&project, x1, y1, x2, y2),
+        None => project.canvas.clone(),
+    };
+    let output = match protocol {
+        CliGraphics::Sixel => export::to_sixel(&canvas, cw, ch),
+        CliGraphics::Kitty => export::to_kitty(&canvas, cw, ch),
+    };
+    println!("{}", output);
+    Ok(())
+}
+
 /// Detect export format from output file extension when format is Auto.
 fn detect_format(output: &str, explicit: &PreviewFormat) -> PreviewFormat {
     if *explicit != PreviewFormat::Auto {
@@ -51,12 +93,22 @@ fn detect_format(output: &str, explicit: &PreviewFormat) -> PreviewFormat {
     }
     match Path::new(output).extension().and_then(|e| e.to_str()) {
         Some("png") => PreviewFormat::Png,
+        Some("svg") => PreviewFormat::Svg,
+        Some("html") | Some("htm") => PreviewFormat::Html,
         Some("json") => PreviewFormat::Json,
         Some("txt") => PreviewFormat::Plain,
         _ => PreviewFormat::Ansi,
     }
 }
 
+/// Load a project for output with its export transforms applied to the
+/// in-memory canvas. Nothing is written back.
+fn load_transformed(file: &str) -> crate::project::Project {
+    let mut project = load_project(file);
+    project.canvas = export::apply_transforms(&project.canvas, &project.transforms);
+    project
+}
+
 /// Parse cell size string like "8x16" into (width, height).
 fn parse_cell_size(s: &str) -> Result<(u32, u32), String> {
     let parts: Vec<&str> = s.split('x').collect();
@@ -71,6 +123,7 @@ fn parse_cell_size(s: &str) -> Result<(u32, u32), String> {
     Ok((w, h))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn export_to_file(
     file: &str,
     output: &str,
@@ -79,44 +132,96 @@ pub fn export_to_file(
     cell_size: &str,
     scale: u32,
     no_crop: bool,
+    reset_each_cell: bool,
+    no_overwrite: bool,
+    background: Option<Rgb>,
+    ramp: Option<&str>,
+    invert: bool,
 ) -> io::Result<()> {
-    let project = load_project(file);
+    // `-` sends the exported content to stdout (status JSON then goes to stderr)
+    let to_stdout = output == crate::oplog::STDIO;
+    if no_overwrite && !to_stdout && Path::new(output).exists() {
+        cli_error(&format!(
+            "Output '{}' already exists. Remove it or drop --no-overwrite to replace it.",
+            output
+        ));
+    }
+    let project = load_transformed(file);
     let cf = to_color_format(color_format);
     let resolved_format = detect_format(output, format);
+    if ramp.is_some() && resolved_format != PreviewFormat::Plain {
+        cli_error("--ramp only applies to plain-text export (--format plain or a .txt output).");
+    }
+    if ramp.is_some_and(|r| r.is_empty()) {
+        cli_error("--ramp needs at least one character.");
+    }
 
     match resolved_format {
         PreviewFormat::Png => {
             let (cw, ch) = parse_cell_size(cell_size).map_err(|e| {
                 io::Error::new(io::ErrorKind::InvalidInput, e)
             })?;
-            let img = export::to_png(&project.canvas, cw, ch, scale, !no_crop);
+            let mut img = export::to_png(&project.canvas, cw, ch, scale, !no_crop);
+            if let Some(bg) = background {
+                export::flatten(&mut img, bg);
+            }
             let (w, h) = (img.width(), img.height());
-            img.save(output).map_err(|e| {
-                io::Error::new(io::ErrorKind::Other, format!("PNG save failed: {}", e))
-            })?;
-            let json = serde_json::json!({
+            if to_stdout {
+                let mut png = io::Cursor::new(Vec::new());
+                img.write_to(&mut png, image::ImageFormat::Png).map_err(|e| {
+                    io::Error::new(io::ErrorKind::Other, format!("PNG encode failed: {}", e))
+                })?;
+                write_stdout(&png.into_inner())?;
+            } else {
+                img.save(output).map_err(|e| {
+                    io::Error::new(io::ErrorKind::Other, format!("PNG save failed: {}", e))
+                })?;
+            }
+            let mut json = serde_json::json!({
                 "exported": output,
                 "format": "png",
                 "width": w,
                 "height": h,
                 "cell_size": format!("{}x{}", cw, ch),
             });
-            println!("{}", serde_json::to_string(&json).unwrap());
+            if let Some(bg) = background {
+                json["background"] = serde_json::json!(bg.name());
+            }
+            print_json(&json, false);
+        }
+        _ if background.is_some() => {
+            cli_error("--background only applies to PNG export.");
         }
         _ => {
             let content = match resolved_format {
+                PreviewFormat::Ansi | PreviewFormat::Auto if reset_each_cell => {
+                    export::to_ansi_reset_each_cell(&project.canvas, cf)
+                }
                 PreviewFormat::Ansi | PreviewFormat::Auto => export::to_ansi(&project.canvas, cf),
-                PreviewFormat::Plain => export::to_plain_text(&project.canvas),
-                PreviewFormat::Json => json_preview(&project, None),
+                PreviewFormat::Plain => match ramp {
+                    Some(ramp) => export::to_ascii_ramp(&project.canvas, ramp, invert),
+                    None => export::to_plain_text(&project.canvas),
+                },
+                PreviewFormat::Json => json_preview(&project, None, false, false),
+                PreviewFormat::Svg => export::to_svg(&project.canvas),
+                PreviewFormat::Html => export::to_html(&project.canvas),
+                PreviewFormat::Braille => export::to_braille(&project.canvas),
                 PreviewFormat::Png => unreachable!(),
             };
 
-            std::fs::write(output, &content)?;
+            if to_stdout {
+                write_stdout(content.as_bytes())?;
+            } else {
+                std::fs::write(output, &content)?;
+            }
 
             let format_str = match resolved_format {
                 PreviewFormat::Ansi | PreviewFormat::Auto => "ansi",
                 PreviewFormat::Plain => "plain",
                 PreviewFormat::Json => "json",
+                PreviewFormat::Svg => "svg",
+                PreviewFormat::Html => "html",
+                PreviewFormat::Braille => "braille",
                 PreviewFormat::Png => unreachable!(),
             };
             let cf_str = match color_format {
@@ -127,18 +232,33 @@ pub fn export_to_file(
                 CliColorFormat::Color16 => "16",
             };
 
-            let json = serde_json::json!({
+            let mut json = serde_json::json!({
                 "exported": output,
                 "format": format_str,
                 "color_format": cf_str,
             });
-            println!("{}", serde_json::to_string(&json).unwrap());
+            if reset_each_cell && format_str == "ansi" {
+                json["reset_each_cell"] = serde_json::json!(true);
+            }
+            if let Some(ramp) = ramp {
+                json["ramp"] = serde_json::json!(ramp);
+                json["invert"] = serde_json::json!(invert);
+            }
+            print_json(&json, false);
         }
     }
     Ok(())
 }
 
-fn json_preview(project: &crate::project::Project, region: Option<(usize, usize, usize, usize)>) -> String {
+/// JSON dump of the canvas (or a region). Cells are nested per row unless
+/// `non_empty_only`, which emits a flat list of drawn cells only.
+/// `compact` prints a single line instead of pretty JSON.
+fn json_preview(
+    project: &crate::project::Project,
+    region: Option<(usize, usize, usize, usize)>,
+    non_empty_only: bool,
+    compact: bool,
+) -> String {
     let canvas = &project.canvas;
     let (x_start, y_start, x_end, y_end) = region
         .unwrap_or((0, 0, canvas.width.saturating_sub(1), canvas.height.saturating_sub(1)));
@@ -146,35 +266,63 @@ fn json_preview(project: &crate::project::Project, region: Option<(usize, usize,
     let x_end = x_end.min(canvas.width.saturating_sub(1));
     let y_end = y_end.min(canvas.height.saturating_sub(1));
 
-    let mut cells = Vec::new();
+    let mut rows = Vec::new();
+    let mut drawn = Vec::new();
     let mut non_empty_count = 0;
 
     for y in y_start..=y_end {
         let mut row = Vec::new();
         for x in x_start..=x_end {
             if let Some(cell) = canvas.get(x, y) {
-                if !cell.is_empty() {
-                    non_empty_count += 1;
-                }
-                row.push(serde_json::json!({
+                let value = serde_json::json!({
                     "x": x,
                     "y": y,
                     "fg": cell.fg.map(|c| c.name()),
                     "bg": cell.bg.map(|c| c.name()),
                     "char": cell.ch.to_string(),
-                }));
+                });
+                if !cell.is_empty() {
+                    non_empty_count += 1;
+                    if non_empty_only {
+                        drawn.push(value);
+                        continue;
+                    }
+                }
+                if !non_empty_only {
+                    row.push(value);
+                }
             }
         }
-        cells.push(row);
+        if !non_empty_only {
+            rows.push(row);
+        }
     }
 
+    let cells = if non_empty_only {
+        serde_json::Value::Array(drawn)
+    } else {
+        serde_json::json!(rows)
+    };
     let json = serde_json::json!({
         "width": canvas.width,
         "height": canvas.height,
         "cells": cells,
         "non_empty_count": non_empty_count,
     });
-    serde_json::to_string_pretty(&json).unwrap()
+    if compact {
+        serde_json::to_string(&json).unwrap()
+    } else {
+        serde_json::to_string_pretty(&json).unwrap()
+    }
+}
+
+/// The inclusive region (x1,y1)-(x2,y2) as its own canvas, exactly as large
+/// as the region (clipped to the canvas), so a single cell stays 1x1.
+fn region_canvas(
+    project: &crate::project::Project,
+    x1: usize, y1: usize, x2: usize, y2: usize,
+) -> crate::canvas::Canvas {
+    project.canvas.subcanvas(x1, y1, x2.saturating_sub(x1) + 1, y2.saturating_sub(y1) + 1)
 }
 
 fn ansi_region(
@@ -182,45 +330,36 @@ fn ansi_region(
     x1: usize, y1: usize, x2: usize, y2: usize,
     format: crate::export::ColorFormat,
 ) -> String {
-    // Create a sub-canvas from the region
-    let canvas = &project.canvas;
-    let mut sub = crate::canvas::Canvas::new_with_size(
-        (x2 - x1 + 1).max(8),
-        (y2 - y1 + 1).max(8),
-    );
-    for y in y1..=y2.min(canvas.height.saturating_sub(1)) {
-        for x in x1..=x2.min(canvas.width.saturating_sub(1)) {
-            if let Some(cell) = canvas.get(x, y) {
-                sub.set(x - x1, y - y1, cell);
-            }
-        }
-    }
-    export::to_ansi(&sub, format)
+    export::to_ansi(&region_canvas(project, x1, y1, x2, y2), format)
 }
 
 fn plain_region(
     project: &crate::project::Project,
     x1: usize, y1: usize, x2: usize, y2: usize,
 ) -> String {
-    let canvas = &project.canvas;
-    let mut sub = crate::canvas::Canvas::new_with_size(
-        (x2 - x1 + 1).max(8),
-        (y2 - y1 + 1).max(8),
-    );
-    for y in y1..=y2.min(canvas.height.saturating_sub(1)) {
-        for x in x1..=x2.min(canvas.width.saturating_sub(1)) {
-            if let Some(cell) = canvas.get(x, y) {
-                sub.set(x - x1, y - y1, cell);
-            }
-        }
+    export::to_plain_text(&region_canvas(project, x1, y1, x2, y2))
+}
+
+/// Replace `Auto` with a fixed depth so output never depends on the terminal.
+fn pin_color_format(format: export::ColorFormat) -> export::ColorFormat {
+    match format {
+        export::ColorFormat::Auto => export::ColorFormat::Color256,
+        other => other,
     }
-    export::to_plain_text(&sub)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_pin_color_format_only_replaces_auto() {
+        use export::ColorFormat;
+        assert_eq!(pin_color_format(ColorFormat::Auto), ColorFormat::Color256);
+        assert_eq!(pin_color_format(ColorFormat::TrueColor), ColorFormat::TrueColor);
+        assert_eq!(pin_color_format(ColorFormat::Color16), ColorFormat::Color16);
+    }
+
     #[test]
     fn test_detect_format_png() {
         assert_eq!(detect_format("out.png", &PreviewFormat::Auto), PreviewFormat::Png);
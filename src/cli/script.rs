@@ -0,0 +1,174 @@
+use std::io;
+use std::path::Path;
+
+use crate::cli::batch::{execute_op, BatchOp};
+use crate::cli::{cli_error, parse_coord, print_json};
+
+/// Parse one script line into a draw operation.
+///
+/// Grammar: `<tool> <X,Y> [X2,Y2] [COLOR] [bg=COLOR] [ch=CHAR] [target=full|fg|bg] [filled]`.
+/// Tools and how many coordinates they take:
+/// `pencil`/`eraser`/`fill` one, `line`/`rect`/`ellipse` two. A bare color is the fg.
+fn parse_line(line: &str) -> Result<BatchOp, String> {
+    let mut words = line.split_whitespace();
+    let tool = words.next().ok_or("empty line")?.to_lowercase();
+    let coord_count = match tool.as_str() {
+        "pencil" | "eraser" | "fill" => 1,
+        "line" | "rect" | "ellipse" => 2,
+        other => {
+            return Err(format!(
+                "Unknown tool '{}'. Expected pencil, eraser, line, rect, ellipse or fill",
+                other
+            ))
+        }
+    };
+
+    let mut coords = Vec::new();
+    let (mut ch, mut fg, mut bg, mut target) = (None, None, None, None);
+    let mut filled = false;
+    for word in words {
+        if let Some(value) = word.strip_prefix("ch=") {
+            ch = Some(value.to_string());
+        } else if let Some(value) = word.strip_prefix("fg=") {
+            fg = Some(value.to_string());
+        } else if let Some(value) = word.strip_prefix("bg=") {
+            bg = Some(value.to_string());
+        } else if let Some(value) = word.strip_prefix("target=") {
+            target = Some(value.to_string());
+        } else if word == "filled" {
+            filled = true;
+        } else if word.contains(',') {
+            coords.push(parse_coord(word)?);
+        } else if fg.is_none() {
+            fg = Some(word.to_string());
+        } else {
+            return Err(format!("Unexpected '{}'", word));
+        }
+    }
+
+    if coords.len() != coord_count {
+        return Err(format!(
+            "'{}' takes {} coordinate{} (X,Y), got {}",
+            tool,
+            coord_count,
+            if coord_count == 1 { "" } else { "s" },
+            coords.len()
+        ));
+    }
+    if filled && tool != "rect" && tool != "ellipse" {
+        return Err("'filled' only applies to rect and ellipse".to_string());
+    }
+
+    let (x, y) = coords[0];
+    let (x2, y2) = coords.get(1).map_or((None, None), |&(a, b)| (Some(a), Some(b)));
+    Ok(BatchOp::Draw {
+        tool,
+        x: Some(x),
+        y: Some(y),
+        x1: Some(x),
+        y1: Some(y),
+        x2,
+        y2,
+        ch,
+        fg,
+        bg,
+        filled: Some(filled),
+        target,
+    })
+}
+
+/// Run a text draw script against a project as one logged operation: a
+/// single `undo` reverts the whole script. Any bad line aborts with its
+/// line number before anything is saved.
+pub fn run_script(file: &str, commands_path: &str) -> io::Result<()> {
+    let path = Path::new(file);
+    let mut project = super::load_project(file);
+
+    let text = std::fs::read_to_string(commands_path).unwrap_or_else(|e| {
+        cli_error(&format!("Cannot read script '{}': {}", commands_path, e))
+    });
+
+    let mut canvas = project.canvas.clone();
+    let mut operations = 0usize;
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        parse_line(line)
+            .and_then(|op| execute_op(&mut canvas, &op))
+            .unwrap_or_else(|e| cli_error(&format!("{}:{}: {}", commands_path, i + 1, e)));
+        operations += 1;
+    }
+
+    let mutations = super::canvas_mutations(&project.canvas, &canvas);
+    if !mutations.is_empty() {
+        crate::oplog::append(
+            &crate::oplog::log_path(path),
+            crate::oplog::make_entry("script", &mutations),
+        )?;
+    }
+    project.canvas = canvas;
+    super::atomic_save(&mut project, path)?;
+
+    print_json(
+        &serde_json::json!({
+            "file": file,
+            "operations": operations,
+            "cells_modified": mutations.len(),
+        }),
+        false,
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::canvas::Canvas;
+    use crate::cell::Rgb;
+
+    #[test]
+    fn parse_pencil_with_color() {
+        match parse_line("pencil 5,6 #FF0000").unwrap() {
+            BatchOp::Draw { tool, x, y, fg, .. } => {
+                assert_eq!(tool, "pencil");
+                assert_eq!((x, y), (Some(5), Some(6)));
+                assert_eq!(fg.as_deref(), Some("#FF0000"));
+            }
+            _ => panic!("Expected Draw"),
+        }
+    }
+
+    #[test]
+    fn parse_rect_with_options() {
+        match parse_line("rect 0,0 3,3 red bg=#000000 ch=shade-light filled").unwrap() {
+            BatchOp::Draw { tool, x1, y1, x2, y2, fg, bg, ch, filled, .. } => {
+                assert_eq!(tool, "rect");
+                assert_eq!((x1, y1, x2, y2), (Some(0), Some(0), Some(3), Some(3)));
+                assert_eq!(fg.as_deref(), Some("red"));
+                assert_eq!(bg.as_deref(), Some("#000000"));
+                assert_eq!(ch.as_deref(), Some("shade-light"));
+                assert_eq!(filled, Some(true));
+            }
+            _ => panic!("Expected Draw"),
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(parse_line("spray 1,1").unwrap_err().contains("Unknown tool"));
+        assert!(parse_line("line 1,1 #FF0000").unwrap_err().contains("2 coordinates"));
+        assert!(parse_line("pencil 1,1 red blue").unwrap_err().contains("Unexpected"));
+        assert!(parse_line("pencil x,1").is_err());
+        assert!(parse_line("fill 1,1 filled").is_err());
+    }
+
+    #[test]
+    fn parsed_line_draws() {
+        let mut canvas = Canvas::new_with_size(8, 8);
+        let op = parse_line("line 0,0 3,0 #00FF00").unwrap();
+        assert_eq!(execute_op(&mut canvas, &op).unwrap(), 4);
+        assert_eq!(canvas.get(3, 0).unwrap().fg, Some(Rgb::new(0, 255, 0)));
+    }
+}
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::io;
 
-use crate::cli::load_project;
+use crate::cli::{load_project, print_json};
 
 pub fn run(file: &str) -> io::Result<()> {
     let project = load_project(file);
@@ -114,7 +114,7 @@ pub fn run(file: &str) -> io::Result<()> {
             "vertical": round2(v_score),
         },
     });
-    println!("{}", serde_json::to_string_pretty(&json).unwrap());
+    print_json(&json, true);
     Ok(())
 }
 
@@ -1,4 +1,5 @@
 use image::{Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
 
 use crate::canvas::Canvas;
 use crate::cell::{blocks, is_half_block, nearest_16, nearest_256, nearest_256_hue, resolve_half_block, ResolvedHalfBlock, Rgb};
@@ -19,13 +20,34 @@ pub enum ColorFormat {
 }
 
 /// Detect terminal color capabilities from environment variables.
-/// Returns TrueColor if COLORTERM indicates support, otherwise Color256Hue (safe default).
+/// See `detect_color_format` for the rules.
 pub fn detect_terminal_colors() -> ColorFormat {
-    if let Ok(ct) = std::env::var("COLORTERM") {
+    detect_color_format(
+        std::env::var("COLORTERM").ok().as_deref(),
+        std::env::var("TERM").ok().as_deref(),
+    )
+}
+
+/// Terminals known to only handle the basic 16 ANSI colors.
+const BASIC_TERMS: &[&str] = &["dumb", "linux", "vt100", "vt102", "vt220", "ansi", "cons25"];
+
+/// Pick a color format from `$COLORTERM` and `$TERM`. Conservative: truecolor
+/// only when explicitly advertised, 16 colors only for known basic terminals,
+/// and Color256Hue (safe default) whenever unsure.
+pub fn detect_color_format(colorterm: Option<&str>, term: Option<&str>) -> ColorFormat {
+    if let Some(ct) = colorterm {
         if ct == "truecolor" || ct == "24bit" {
             return ColorFormat::TrueColor;
         }
     }
+    if let Some(term) = term {
+        if term.ends_with("-direct") {
+            return ColorFormat::TrueColor;
+        }
+        if BASIC_TERMS.contains(&term) {
+            return ColorFormat::Color16;
+        }
+    }
     ColorFormat::Color256Hue
 }
 
@@ -37,6 +59,18 @@ pub fn resolve_color_format(format: ColorFormat) -> ColorFormat {
     }
 }
 
+/// Map a color to what the terminal can display at the given depth.
+/// Auto is treated as plain 256-color; resolve it first to detect.
+pub fn to_display_color(color: Rgb, format: ColorFormat) -> ratatui::style::Color {
+    use ratatui::style::Color;
+    match format {
+        ColorFormat::TrueColor => Color::Rgb(color.r, color.g, color.b),
+        ColorFormat::Auto | ColorFormat::Color256 => Color::Indexed(nearest_256(&color)),
+        ColorFormat::Color256Hue => Color::Indexed(nearest_256_hue(&color)),
+        ColorFormat::Color16 => Color::Indexed(nearest_16(&color)),
+    }
+}
+
 /// Returns the bounding box of all non-empty cells as (min_x, min_y, max_x, max_y),
 /// or None if the canvas is entirely empty.
 pub fn bounding_box(canvas: &Canvas) -> Option<(usize, usize, usize, usize)> {
@@ -92,6 +126,79 @@ pub fn to_plain_text(canvas: &Canvas) -> String {
     output
 }
 
+/// Default ASCII-art ramp, darkest to densest.
+pub const DEFAULT_RAMP: &str = " .:-=+*#%@";
+
+/// Export canvas as colorless ASCII art: each drawn cell becomes a `ramp`
+/// character picked by the luminance of its visible color (fg for glyphs,
+/// bg for spaces). Brighter cells take later, denser characters, which
+/// suits a dark background; `invert` flips that for light backgrounds.
+/// Empty cells stay spaces. Auto-crops to bounding box.
+pub fn to_ascii_ramp(canvas: &Canvas, ramp: &str, invert: bool) -> String {
+    let ramp: Vec<char> = ramp.chars().collect();
+    let (min_x, min_y, max_x, max_y) = match bounding_box(canvas) {
+        Some(bb) if !ramp.is_empty() => bb,
+        _ => return String::new(),
+    };
+
+    let mut lines = Vec::new();
+    for y in min_y..=max_y {
+        let mut row = String::new();
+        for x in min_x..=max_x {
+            let Some(cell) = canvas.get(x, y) else { continue };
+            let color = if cell.ch == ' ' { cell.bg } else { cell.fg.or(cell.bg) };
+            match color {
+                Some(c) => {
+                    let mut t = c.luminance() / 255.0;
+                    if invert {
+                        t = 1.0 - t;
+                    }
+                    let idx = (t * (ramp.len() - 1) as f32).round() as usize;
+                    row.push(ramp[idx.min(ramp.len() - 1)]);
+                }
+                None => row.push(' '),
+            }
+        }
+        lines.push(row.trim_end().to_string());
+    }
+
+    lines.join("\n")
+}
+
+/// Braille dot bits for the cell at (dx, dy) within a 2x4 block.
+const BRAILLE_DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// Export canvas as Unicode Braille patterns: every 2x4 block of cells
+/// becomes one glyph with a dot per non-empty cell. Monochrome, so colors
+/// and glyph shapes are dropped. Auto-crops to bounding box.
+pub fn to_braille(canvas: &Canvas) -> String {
+    let (min_x, min_y, max_x, max_y) = match bounding_box(canvas) {
+        Some(bb) => bb,
+        None => return String::new(),
+    };
+
+    let mut lines = Vec::new();
+    for by in (min_y..=max_y).step_by(4) {
+        let mut row = String::new();
+        for bx in (min_x..=max_x).step_by(2) {
+            let mut bits = 0;
+            for (dy, dots) in BRAILLE_DOTS.iter().enumerate() {
+                for (dx, dot) in dots.iter().enumerate() {
+                    let (x, y) = (bx + dx, by + dy);
+                    if x <= max_x && y <= max_y && canvas.get(x, y).is_some_and(|c| !c.is_empty()) {
+                        bits |= dot;
+                    }
+                }
+            }
+            row.push(char::from_u32(0x2800 + bits).unwrap_or(' '));
+        }
+        // Strip trailing blank patterns
+        lines.push(row.trim_end_matches('\u{2800}').to_string());
+    }
+
+    lines.join("\n")
+}
+
 /// Emit ANSI fg escape code for a color in the given format.
 fn emit_fg(color: &Rgb, format: ColorFormat) -> String {
     match format {
@@ -179,6 +286,20 @@ fn emit_cell_colors(
 /// Auto-crops to bounding box. Applies half-block resolution for export fidelity.
 /// Color format determines escape sequence type (24-bit, 256-color, or 16-color).
 pub fn to_ansi(canvas: &Canvas, format: ColorFormat) -> String {
+    render_ansi(canvas, format, false, true)
+}
+
+/// Like `to_ansi`, but emits a full reset before every cell instead of only
+/// emitting color changes. Larger output, but no color can bleed past a cell
+/// even when a viewer drops or mangles some of the resets.
+pub fn to_ansi_reset_each_cell(canvas: &Canvas, format: ColorFormat) -> String {
+    render_ansi(canvas, format, true, true)
+}
+
+/// `skip_blank_tails` drops the blank cells after a row's last drawn cell
+/// when no background is active, since they'd only print invisible spaces.
+/// Sparse canvases shrink a lot; dense rows stop the scan at their last cell.
+fn render_ansi(canvas: &Canvas, format: ColorFormat, reset_each_cell: bool, skip_blank_tails: bool) -> String {
     let format = resolve_color_format(format);
     let (min_x, min_y, max_x, max_y) = match bounding_box(canvas) {
         Some(bb) => bb,
@@ -190,9 +311,21 @@ pub fn to_ansi(canvas: &Canvas, format: ColorFormat) -> String {
     for y in min_y..=max_y {
         let mut prev_fg: Option<Rgb> = None;
         let mut prev_bg: Option<Rgb> = None;
+        let last_drawn = (min_x..=max_x)
+            .rev()
+            .find(|&x| canvas.get(x, y).is_some_and(|c| !c.is_empty()));
 
         for x in min_x..=max_x {
+            let blank_tail = last_drawn.is_none_or(|last| x > last);
+            if skip_blank_tails && blank_tail && (reset_each_cell || prev_bg.is_none()) {
+                break;
+            }
             if let Some(cell) = canvas.get(x, y) {
+                if reset_each_cell {
+                    output.push_str("\x1b[0m");
+                    prev_fg = None;
+                    prev_bg = None;
+                }
                 if cell.is_empty() {
                     output.push(' ');
                     continue;
@@ -230,6 +363,90 @@ pub fn to_ansi(canvas: &Canvas, format: ColorFormat) -> String {
     output
 }
 
+// --- HTML Export ---
+
+/// Export canvas as an HTML `<pre>` block with inline-styled `<span>` runs.
+/// Auto-crops to bounding box and applies half-block resolution like `to_ansi`.
+/// Consecutive cells sharing fg/bg collapse into one span; uncolored cells
+/// are bare text, and blank row tails are trimmed.
+pub fn to_html(canvas: &Canvas) -> String {
+    let mut output = String::from("<pre style=\"font-family:monospace;line-height:1\">");
+    let (min_x, min_y, max_x, max_y) = match bounding_box(canvas) {
+        Some(bb) => bb,
+        None => {
+            output.push_str("</pre>\n");
+            return output;
+        }
+    };
+
+    for y in min_y..=max_y {
+        // Runs of (fg, bg, text) for this row
+        let mut runs: Vec<(Option<Rgb>, Option<Rgb>, String)> = Vec::new();
+        for x in min_x..=max_x {
+            let Some(cell) = canvas.get(x, y) else { continue };
+            let (ch, fg, bg) = if cell.is_empty() {
+                (' ', None, None)
+            } else if is_half_block(cell.ch) {
+                match resolve_half_block(&cell) {
+                    Some(r) => (r.ch, r.fg, r.bg),
+                    None => (cell.ch, cell.fg, cell.bg),
+                }
+            } else {
+                (cell.ch, cell.fg, cell.bg)
+            };
+            // A fg color on a space is invisible, so don't let it split runs
+            let fg = if ch == ' ' { None } else { fg };
+            match runs.last_mut() {
+                Some((rfg, rbg, text)) if *rfg == fg && *rbg == bg => text.push(ch),
+                _ => runs.push((fg, bg, ch.to_string())),
+            }
+        }
+
+        // Uncolored trailing spaces add nothing
+        if let Some((None, None, text)) = runs.last_mut() {
+            let trimmed = text.trim_end_matches(' ').len();
+            text.truncate(trimmed);
+        }
+
+        for (fg, bg, text) in &runs {
+            let escaped = html_escape(text);
+            let mut style = Vec::new();
+            if let Some(f) = fg {
+                style.push(format!("color:{}", f.name()));
+            }
+            if let Some(b) = bg {
+                style.push(format!("background:{}", b.name()));
+            }
+            if style.is_empty() {
+                output.push_str(&escaped);
+            } else {
+                output.push_str(&format!("<span style=\"{}\">{}</span>", style.join(";"), escaped));
+            }
+        }
+        if y < max_y {
+            output.push('\n');
+        }
+    }
+
+    output.push_str("</pre>\n");
+    output
+}
+
+/// Escape the characters HTML treats as markup.
+fn html_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
 // --- PNG Export ---
 
 /// Convert an Rgb color to an opaque RGBA pixel.
@@ -422,6 +639,469 @@ pub fn to_png(
     }
 }
 
+// --- Terminal graphics (sixel / kitty) ---
+
+/// Encode the canvas as a DEC sixel image, rasterized like `to_png` (cropped).
+///
+/// Uses the exact colors when there are at most 256, otherwise their
+/// xterm-256 approximations. Transparent pixels are left unpainted. The
+/// escape sequence is emitted as-is; terminals without sixel support
+/// will show garbage or nothing.
+pub fn to_sixel(canvas: &Canvas, cell_w: u32, cell_h: u32) -> String {
+    let img = to_png(canvas, cell_w, cell_h, 1, true);
+    let (w, h) = img.dimensions();
+
+    let mut distinct: std::collections::HashSet<(u8, u8, u8)> = std::collections::HashSet::new();
+    for p in img.pixels() {
+        if p[3] >= 128 {
+            distinct.insert((p[0], p[1], p[2]));
+        }
+    }
+    let reduce = distinct.len() > 256;
+
+    // Palette register for every pixel (None = transparent)
+    let mut palette: Vec<(u8, u8, u8)> = Vec::new();
+    let mut registers: std::collections::HashMap<(u8, u8, u8), usize> = std::collections::HashMap::new();
+    let indices: Vec<Option<usize>> = img
+        .pixels()
+        .map(|p| {
+            if p[3] < 128 {
+                return None;
+            }
+            let mut c = (p[0], p[1], p[2]);
+            if reduce {
+                let q = crate::cell::color256_to_rgb(nearest_256(&Rgb::new(c.0, c.1, c.2)));
+                c = (q.r, q.g, q.b);
+            }
+            Some(*registers.entry(c).or_insert_with(|| {
+                palette.push(c);
+                palette.len() - 1
+            }))
+        })
+        .collect();
+
+    let mut out = format!("\x1bP0;1;0q\"1;1;{};{}", w, h);
+    let pct = |v: u8| v as u32 * 100 / 255;
+    for (i, &(r, g, b)) in palette.iter().enumerate() {
+        out.push_str(&format!("#{};2;{};{};{}", i, pct(r), pct(g), pct(b)));
+    }
+
+    for top in (0..h).step_by(6) {
+        // One row of sixel bits per color present in this band
+        let mut rows: std::collections::BTreeMap<usize, Vec<u8>> = std::collections::BTreeMap::new();
+        for dy in 0..6u32.min(h - top) {
+            let y = top + dy;
+            for x in 0..w {
+                if let Some(i) = indices[(y * w + x) as usize] {
+                    rows.entry(i).or_insert_with(|| vec![0; w as usize])[x as usize] |= 1 << dy;
+                }
+            }
+        }
+        for (n, (i, bits)) in rows.iter().enumerate() {
+            if n > 0 {
+                out.push('$'); // back to the band's start for the next color
+            }
+            out.push_str(&format!("#{}", i));
+            push_sixel_runs(&mut out, bits);
+        }
+        out.push('-');
+    }
+
+    out.push_str("\x1b\\");
+    out
+}
+
+/// Append sixel data bytes, run-length encoding repeats (`!<count><char>`).
+fn push_sixel_runs(out: &mut String, bits: &[u8]) {
+    let mut i = 0;
+    while i < bits.len() {
+        let run = bits[i..].iter().take_while(|&&b| b == bits[i]).count();
+        let ch = (63 + bits[i]) as char;
+        if run > 3 {
+            out.push_str(&format!("!{}{}", run, ch));
+        } else {
+            for _ in 0..run {
+                out.push(ch);
+            }
+        }
+        i += run;
+    }
+}
+
+/// Encode the canvas for the kitty graphics protocol: a PNG (rasterized like
+/// `to_png`, cropped) sent base64-encoded in 4096-byte chunks. Like
+/// `to_sixel`, the escape sequence is printed whether or not the terminal
+/// understands it.
+pub fn to_kitty(canvas: &Canvas, cell_w: u32, cell_h: u32) -> String {
+    let img = to_png(canvas, cell_w, cell_h, 1, true);
+    let mut png = std::io::Cursor::new(Vec::new());
+    img.write_to(&mut png, image::ImageFormat::Png)
+        .expect("encoding PNG into memory cannot fail");
+    let payload = base64_encode(&png.into_inner());
+
+    let chunks: Vec<&[u8]> = payload.as_bytes().chunks(4096).collect();
+    let mut out = String::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = if i + 1 < chunks.len() { 1 } else { 0 };
+        let chunk = std::str::from_utf8(chunk).expect("base64 is ASCII");
+        if i == 0 {
+            out.push_str(&format!("\x1b_Ga=T,f=100,m={};{}\x1b\\", more, chunk));
+        } else {
+            out.push_str(&format!("\x1b_Gm={};{}\x1b\\", more, chunk));
+        }
+    }
+    out
+}
+
+/// Standard base64 with padding.
+fn base64_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | (b[2] as u32);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((n >> (18 - 6 * i)) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Composite a PNG export over an opaque `background`, so cells without a
+/// fg or bg (and the halves of half blocks they leave unset) show the
+/// background instead of transparency. Opaque pixels are unchanged.
+pub fn flatten(img: &mut RgbaImage, background: Rgb) {
+    let back = [background.r, background.g, background.b];
+    for pixel in img.pixels_mut() {
+        let alpha = pixel[3] as u32;
+        for (c, &b) in pixel.0.iter_mut().zip(back.iter()) {
+            *c = ((*c as u32 * alpha + b as u32 * (255 - alpha) + 127) / 255) as u8;
+        }
+        pixel[3] = 255;
+    }
+}
+
+// --- SVG Export ---
+
+/// SVG units per cell; matches the 1:2 aspect of a terminal cell.
+const SVG_CELL_W: u32 = 8;
+const SVG_CELL_H: u32 = 16;
+
+/// Export canvas as an SVG document with one `<rect>` per colored cell part.
+///
+/// Geometry mirrors `to_png`: full blocks fill the cell, half and
+/// fractional blocks split it into an fg and a bg rect, and shades lay a
+/// semi-transparent fg rect over the bg. Missing colors emit no rect, so
+/// empty cells stay transparent.
+pub fn to_svg(canvas: &Canvas) -> String {
+    let width = canvas.width as u32 * SVG_CELL_W;
+    let height = canvas.height as u32 * SVG_CELL_H;
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" shape-rendering=\"crispEdges\">\n",
+        w = width,
+        h = height,
+    );
+    for y in 0..canvas.height {
+        for x in 0..canvas.width {
+            if let Some(cell) = canvas.get(x, y) {
+                let px = x as u32 * SVG_CELL_W;
+                let py = y as u32 * SVG_CELL_H;
+                for (rx, ry, rw, rh, color, opacity) in svg_cell_rects(&cell) {
+                    let Some(color) = color else { continue };
+                    if rw == 0 || rh == 0 {
+                        continue;
+                    }
+                    out.push_str(&format!(
+                        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"",
+                        px + rx,
+                        py + ry,
+                        rw,
+                        rh,
+                        color.name()
+                    ));
+                    if opacity < 1.0 {
+                        out.push_str(&format!(" fill-opacity=\"{}\"", opacity));
+                    }
+                    out.push_str("/>\n");
+                }
+            }
+        }
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+/// Rects `(x, y, w, h, color, opacity)` making up one cell, relative to its
+/// top-left corner, back to front.
+fn svg_cell_rects(cell: &crate::cell::Cell) -> Vec<(u32, u32, u32, u32, Option<Rgb>, f32)> {
+    let (w, h) = (SVG_CELL_W, SVG_CELL_H);
+    let (fg, bg) = (cell.fg, cell.bg);
+    let ch = cell.ch;
+
+    if ch == blocks::FULL {
+        return vec![(0, 0, w, h, fg, 1.0)];
+    }
+    if ch == blocks::UPPER_HALF {
+        return vec![(0, 0, w, h / 2, fg, 1.0), (0, h / 2, w, h - h / 2, bg, 1.0)];
+    }
+    if ch == blocks::LOWER_HALF {
+        return vec![(0, 0, w, h / 2, bg, 1.0), (0, h / 2, w, h - h / 2, fg, 1.0)];
+    }
+    if ch == blocks::LEFT_HALF {
+        return vec![(0, 0, w / 2, h, fg, 1.0), (w / 2, 0, w - w / 2, h, bg, 1.0)];
+    }
+    if ch == blocks::RIGHT_HALF {
+        return vec![(0, 0, w / 2, h, bg, 1.0), (w / 2, 0, w - w / 2, h, fg, 1.0)];
+    }
+    let shade = match ch {
+        blocks::SHADE_LIGHT => Some(0.25),
+        blocks::SHADE_MEDIUM => Some(0.5),
+        blocks::SHADE_DARK => Some(0.75),
+        _ => None,
+    };
+    if let Some(opacity) = shade {
+        return vec![(0, 0, w, h, bg, 1.0), (0, 0, w, h, fg, opacity)];
+    }
+    if let Some(frac) = vertical_fraction(ch) {
+        let fg_rows = (h as f32 * frac).round() as u32;
+        return vec![(0, 0, w, h - fg_rows, bg, 1.0), (0, h - fg_rows, w, fg_rows, fg, 1.0)];
+    }
+    if let Some(frac) = horizontal_fraction(ch) {
+        let fg_cols = (w as f32 * frac).round() as u32;
+        return vec![(0, 0, fg_cols, h, fg, 1.0), (fg_cols, 0, w - fg_cols, h, bg, 1.0)];
+    }
+    if ch == ' ' {
+        return vec![(0, 0, w, h, bg, 1.0)];
+    }
+    // Any other printable character: fill with fg, as in PNG export
+    vec![(0, 0, w, h, fg, 1.0)]
+}
+
+// --- Cell mask ---
+
+/// Which cells hold something visible (`!Cell::is_empty`), as rows of
+/// booleans. Colors are ignored.
+pub fn mask(canvas: &Canvas) -> Vec<Vec<bool>> {
+    (0..canvas.height)
+        .map(|y| {
+            (0..canvas.width)
+                .map(|x| canvas.get(x, y).is_some_and(|c| !c.is_empty()))
+                .collect()
+        })
+        .collect()
+}
+
+/// Pack mask rows for a 1-bit PNG: 8 cells per byte, most significant bit
+/// first, each row padded to a whole byte. Set bits are filled cells.
+pub fn pack_mask(mask: &[Vec<bool>]) -> Vec<u8> {
+    let mut data = Vec::new();
+    for row in mask {
+        for chunk in row.chunks(8) {
+            let byte = chunk
+                .iter()
+                .enumerate()
+                .fold(0u8, |acc, (i, &set)| if set { acc | (0x80 >> i) } else { acc });
+            data.push(byte);
+        }
+    }
+    data
+}
+
+/// Write `mask` as a 1-bit grayscale PNG, one pixel per cell: white where
+/// the cell is filled, black where it's empty.
+pub fn write_mask_png(mask: &[Vec<bool>], path: &std::path::Path) -> std::io::Result<()> {
+    let height = mask.len() as u32;
+    let width = mask.first().map_or(0, |row| row.len()) as u32;
+    let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    let mut encoder = png::Encoder::new(file, width, height);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::One);
+    let to_io = |e: png::EncodingError| std::io::Error::other(e.to_string());
+    let mut writer = encoder.write_header().map_err(to_io)?;
+    writer.write_image_data(&pack_mask(mask)).map_err(to_io)?;
+    writer.finish().map_err(to_io)
+}
+
+// --- Export transforms ---
+
+/// A color adjustment applied to a copy of the canvas when exporting or
+/// previewing. The stored canvas is never changed.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExportTransform {
+    /// Rotate every color's hue by this many degrees
+    HueShift(i16),
+    /// Reduce each RGB channel to this many evenly spaced levels (2-255)
+    Posterize(u8),
+    /// Replace each channel with 255 minus its value
+    Invert,
+}
+
+impl ExportTransform {
+    /// Parse `hue:DEGREES`, `posterize:LEVELS` or `invert`.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let (kind, arg) = match s.split_once(':') {
+            Some((kind, arg)) => (kind.trim(), Some(arg.trim())),
+            None => (s.trim(), None),
+        };
+        match (kind.to_ascii_lowercase().as_str(), arg) {
+            ("invert", None) => Ok(ExportTransform::Invert),
+            ("hue" | "hue-shift", Some(deg)) => deg
+                .parse::<i16>()
+                .map(|d| ExportTransform::HueShift(d.rem_euclid(360)))
+                .map_err(|_| format!("Invalid hue shift '{}', expected degrees", deg)),
+            ("posterize", Some(levels)) => match levels.parse::<u8>() {
+                Ok(n) if n >= 2 => Ok(ExportTransform::Posterize(n)),
+                _ => Err(format!("Invalid posterize levels '{}', expected 2-255", levels)),
+            },
+            _ => Err(format!(
+                "Unknown transform '{}'. Expected hue:DEGREES, posterize:LEVELS or invert",
+                s
+            )),
+        }
+    }
+
+    /// The spec `parse` accepts for this transform.
+    pub fn spec(&self) -> String {
+        match self {
+            ExportTransform::HueShift(deg) => format!("hue:{}", deg),
+            ExportTransform::Posterize(levels) => format!("posterize:{}", levels),
+            ExportTransform::Invert => "invert".to_string(),
+        }
+    }
+
+    fn apply(&self, c: Rgb) -> Rgb {
+        match *self {
+            ExportTransform::HueShift(deg) => {
+                let (h, s, l) = crate::palette::rgb_to_hsl(c.r, c.g, c.b);
+                // Grays have no hue; converting them back would only add rounding drift
+                if s == 0 || deg.rem_euclid(360) == 0 {
+                    return c;
+                }
+                let h = (h as i32 + deg as i32).rem_euclid(360) as u16;
+                let (r, g, b) = crate::palette::hsl_to_rgb(h, s, l);
+                Rgb::new(r, g, b)
+            }
+            ExportTransform::Posterize(levels) => {
+                let steps = levels.max(2) as f32 - 1.0;
+                let q = |v: u8| ((v as f32 / 255.0 * steps).round() * 255.0 / steps).round() as u8;
+                Rgb::new(q(c.r), q(c.g), q(c.b))
+            }
+            ExportTransform::Invert => Rgb::new(255 - c.r, 255 - c.g, 255 - c.b),
+        }
+    }
+}
+
+/// A copy of `canvas` with `transforms` applied in order to every fg and bg
+/// color. Glyphs and transparency are kept.
+pub fn apply_transforms(canvas: &Canvas, transforms: &[ExportTransform]) -> Canvas {
+    let mut out = canvas.clone();
+    if transforms.is_empty() {
+        return out;
+    }
+    let adjust = |c: Rgb| transforms.iter().fold(c, |c, t| t.apply(c));
+    for y in 0..canvas.height {
+        for x in 0..canvas.width {
+            if let Some(cell) = canvas.get(x, y) {
+                out.set(x, y, crate::cell::Cell {
+                    fg: cell.fg.map(adjust),
+                    bg: cell.bg.map(adjust),
+                    ..cell
+                });
+            }
+        }
+    }
+    out
+}
+
+// --- Palette swatches ---
+
+const SWATCH_W: u32 = 64;
+const SWATCH_H: u32 = 40;
+const LABEL_SCALE: u32 = 2;
+
+/// 3x5 glyphs for hex labels; each row's low 3 bits are pixels, MSB left.
+const HEX_GLYPHS: [(char, [u8; 5]); 17] = [
+    ('#', [0b101, 0b111, 0b101, 0b111, 0b101]),
+    ('0', [0b111, 0b101, 0b101, 0b101, 0b111]),
+    ('1', [0b010, 0b110, 0b010, 0b010, 0b111]),
+    ('2', [0b111, 0b001, 0b111, 0b100, 0b111]),
+    ('3', [0b111, 0b001, 0b111, 0b001, 0b111]),
+    ('4', [0b101, 0b101, 0b111, 0b001, 0b001]),
+    ('5', [0b111, 0b100, 0b111, 0b001, 0b111]),
+    ('6', [0b111, 0b100, 0b111, 0b101, 0b111]),
+    ('7', [0b111, 0b001, 0b010, 0b010, 0b010]),
+    ('8', [0b111, 0b101, 0b111, 0b101, 0b111]),
+    ('9', [0b111, 0b101, 0b111, 0b001, 0b111]),
+    ('A', [0b010, 0b101, 0b111, 0b101, 0b101]),
+    ('B', [0b110, 0b101, 0b110, 0b101, 0b110]),
+    ('C', [0b011, 0b100, 0b100, 0b100, 0b011]),
+    ('D', [0b110, 0b101, 0b101, 0b101, 0b110]),
+    ('E', [0b111, 0b100, 0b110, 0b100, 0b111]),
+    ('F', [0b111, 0b100, 0b110, 0b100, 0b100]),
+];
+
+/// Black or white, whichever reads better on `c` (Rec. 601 luma).
+fn label_color(c: &Rgb) -> Rgba<u8> {
+    let luma = 299 * c.r as u32 + 587 * c.g as u32 + 114 * c.b as u32;
+    if luma > 128_000 {
+        Rgba([0, 0, 0, 255])
+    } else {
+        Rgba([255, 255, 255, 255])
+    }
+}
+
+/// Draw `text` with the hex glyphs, top-left at (x, y). Unknown chars are skipped.
+fn draw_label(img: &mut RgbaImage, text: &str, x: u32, y: u32, color: Rgba<u8>) {
+    for (i, ch) in text.chars().enumerate() {
+        let Some((_, rows)) = HEX_GLYPHS.iter().find(|(g, _)| *g == ch) else {
+            continue;
+        };
+        let gx = x + i as u32 * 4 * LABEL_SCALE;
+        for (row, bits) in rows.iter().enumerate() {
+            for col in 0..3 {
+                if bits & (0b100 >> col) != 0 {
+                    let px = gx + col * LABEL_SCALE;
+                    let py = y + row as u32 * LABEL_SCALE;
+                    fill_rect(img, px, py, LABEL_SCALE, LABEL_SCALE, color);
+                }
+            }
+        }
+    }
+}
+
+/// Render palette colors as a strip of labeled swatches, wrapping after
+/// `per_row` swatches. Each swatch shows its `#RRGGBB` in black or white,
+/// depending on the swatch's brightness.
+pub fn palette_swatches(colors: &[Rgb], per_row: usize) -> RgbaImage {
+    if colors.is_empty() {
+        return RgbaImage::new(1, 1);
+    }
+    let cols = per_row.clamp(1, colors.len()) as u32;
+    let rows = colors.len().div_ceil(cols as usize) as u32;
+    let mut img = RgbaImage::new(cols * SWATCH_W, rows * SWATCH_H);
+
+    let label_w = (7 * 4 - 1) * LABEL_SCALE;
+    let label_h = 5 * LABEL_SCALE;
+    for (i, color) in colors.iter().enumerate() {
+        let x = (i as u32 % cols) * SWATCH_W;
+        let y = (i as u32 / cols) * SWATCH_H;
+        fill_rect(&mut img, x, y, SWATCH_W, SWATCH_H, rgb_to_rgba(color));
+        draw_label(
+            &mut img,
+            &color.name(),
+            x + (SWATCH_W - label_w) / 2,
+            y + SWATCH_H - label_h - 4,
+            label_color(color),
+        );
+    }
+    img
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -933,6 +1613,31 @@ mod tests {
         assert_eq!(img.get_pixel(0, CH - 1), &Rgba([0, 0, 255, 255]));
     }
 
+    #[test]
+    fn test_flatten_fills_transparent_half_and_keeps_opaque_pixels() {
+        let mut canvas = Canvas::new();
+        canvas.set(0, 0, Cell { ch: blocks::UPPER_HALF, fg: Some(red_rgb()), bg: None });
+        canvas.set(1, 0, Cell { ch: blocks::FULL, fg: Some(blue_rgb()), bg: None });
+        let mut img = to_png(&canvas, CW, CH, 1, true);
+        flatten(&mut img, Rgb::new(10, 20, 30));
+        let half = CH / 2;
+        assert_eq!(img.get_pixel(0, 0), &Rgba([255, 0, 0, 255]));
+        assert_eq!(img.get_pixel(0, half), &Rgba([10, 20, 30, 255]));
+        assert_eq!(img.get_pixel(CW, CH - 1), &Rgba([0, 0, 255, 255]));
+
+        // A canvas with no transparent pixels comes out identical
+        let mut opaque = Canvas::new_with_size(8, 8);
+        for y in 0..8 {
+            for x in 0..8 {
+                opaque.set(x, y, Cell { ch: blocks::LOWER_HALF, fg: Some(red_rgb()), bg: Some(blue_rgb()) });
+            }
+        }
+        let before = to_png(&opaque, CW, CH, 1, false);
+        let mut after = before.clone();
+        flatten(&mut after, Rgb::new(10, 20, 30));
+        assert_eq!(before, after);
+    }
+
     #[test]
     fn test_png_lower_half() {
         let mut canvas = Canvas::new();
@@ -1104,6 +1809,191 @@ mod tests {
         assert_eq!(img.height(), 16 * CH);
     }
 
+    // --- Terminal graphics Tests ---
+
+    #[test]
+    fn test_sixel_framing_and_palette() {
+        let mut canvas = Canvas::new_with_size(8, 8);
+        canvas.set(0, 0, Cell { ch: blocks::FULL, fg: Some(red_rgb()), bg: None });
+        canvas.set(1, 0, Cell { ch: blocks::FULL, fg: Some(blue_rgb()), bg: None });
+        let sixel = to_sixel(&canvas, 2, 6);
+
+        assert!(sixel.starts_with("\x1bP0;1;0q\"1;1;4;6"), "raster size is the cropped 2x1 cells: {:?}", sixel);
+        assert!(sixel.ends_with("\x1b\\"));
+        assert!(sixel.contains("#0;2;100;0;0"));
+        assert!(sixel.contains("#1;2;0;0;100"));
+        // One band of 6 rows: red fills columns 0-1 ('~' = all six bits), blue 2-3
+        assert!(sixel.contains("#0~~??$#1??~~-"), "{:?}", sixel);
+    }
+
+    #[test]
+    fn test_sixel_run_length() {
+        let mut out = String::new();
+        push_sixel_runs(&mut out, &[63, 63, 63, 63, 63, 0, 1]);
+        assert_eq!(out, "!5~?@");
+    }
+
+    #[test]
+    fn test_base64_encode() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn test_kitty_chunks_png_payload() {
+        let mut canvas = Canvas::new_with_size(64, 64);
+        for y in 0..64 {
+            for x in 0..64 {
+                let c = Rgb::new((x * 4) as u8, (y * 4) as u8, ((x * y) % 256) as u8);
+                canvas.set(x, y, Cell { ch: blocks::FULL, fg: Some(c), bg: None });
+            }
+        }
+        let kitty = to_kitty(&canvas, 8, 16);
+        assert!(kitty.starts_with("\x1b_Ga=T,f=100,m=1;iVBORw0KGgo"), "PNG signature in base64");
+        assert!(kitty.contains("\x1b_Gm=0;"), "last chunk closes the transfer");
+        for chunk in kitty.split("\x1b\\").filter(|c| !c.is_empty()) {
+            let data = chunk.split(';').nth(1).unwrap();
+            assert!(data.len() <= 4096);
+        }
+    }
+
+    // --- ASCII Ramp Tests ---
+
+    #[test]
+    fn test_ascii_ramp_white_is_densest() {
+        let mut canvas = Canvas::new_with_size(8, 8);
+        for y in 0..8 {
+            for x in 0..8 {
+                canvas.set(x, y, Cell { ch: blocks::FULL, fg: Some(Rgb::new(255, 255, 255)), bg: None });
+            }
+        }
+        let art = to_ascii_ramp(&canvas, DEFAULT_RAMP, false);
+        let lines: Vec<&str> = art.lines().collect();
+        assert_eq!(lines.len(), 8);
+        assert!(lines.iter().all(|l| *l == "@@@@@@@@"), "{:?}", lines);
+
+        // Inverted, white maps to the lightest character (a space, trimmed away)
+        assert!(to_ascii_ramp(&canvas, DEFAULT_RAMP, true).trim().is_empty());
+    }
+
+    #[test]
+    fn test_ascii_ramp_uses_luminance_weights() {
+        let mut canvas = Canvas::new_with_size(8, 8);
+        // Pure green (lum ~150) is brighter than pure blue (lum ~29)
+        canvas.set(0, 0, Cell { ch: blocks::FULL, fg: Some(Rgb::new(0, 255, 0)), bg: None });
+        canvas.set(1, 0, Cell { ch: blocks::FULL, fg: Some(Rgb::new(0, 0, 255)), bg: None });
+        // Space shows its bg; empty cell stays blank
+        canvas.set(2, 0, Cell { ch: ' ', fg: None, bg: Some(Rgb::new(255, 255, 255)) });
+        canvas.set(4, 0, Cell { ch: blocks::FULL, fg: Some(Rgb::BLACK), bg: None });
+        assert_eq!(to_ascii_ramp(&canvas, "0123456789", false), "519 0");
+    }
+
+    // --- Braille Export Tests ---
+
+    #[test]
+    fn test_braille_known_pattern() {
+        let mut canvas = Canvas::new_with_size(8, 8);
+        let on = Cell { ch: blocks::FULL, fg: Some(red_rgb()), bg: None };
+        // Left column fully on, right column only on the bottom row:
+        // dots 1, 2, 3, 7 + dot 8 = 0x01|0x02|0x04|0x40|0x80 = 0xC7
+        for y in 0..4 {
+            canvas.set(0, y, on);
+        }
+        canvas.set(1, 3, on);
+        assert_eq!(to_braille(&canvas), "\u{28C7}");
+    }
+
+    #[test]
+    fn test_braille_packs_blocks_and_crops() {
+        let mut canvas = Canvas::new_with_size(16, 16);
+        let on = Cell { ch: ' ', fg: None, bg: Some(blue_rgb()) };
+        canvas.set(4, 4, on); // dot 1 of the first glyph
+        canvas.set(7, 9, on); // second glyph row, second glyph, right column, row 1: dot 5
+        assert_eq!(to_braille(&canvas), "\u{2801}\n\u{2800}\u{2810}");
+    }
+
+    #[test]
+    fn test_braille_empty_canvas() {
+        assert_eq!(to_braille(&Canvas::new()), "");
+    }
+
+    // --- HTML Export Tests ---
+
+    #[test]
+    fn test_html_merges_identical_runs() {
+        let mut canvas = Canvas::new_with_size(8, 8);
+        for x in 0..4 {
+            canvas.set(x, 0, Cell { ch: blocks::FULL, fg: Some(red_rgb()), bg: None });
+        }
+        canvas.set(4, 0, Cell { ch: blocks::FULL, fg: Some(blue_rgb()), bg: None });
+
+        let html = to_html(&canvas);
+        assert_eq!(html.matches("<span").count(), 2, "4 red cells + 1 blue cell = 2 runs: {}", html);
+        assert!(html.contains("<span style=\"color:#FF0000\">\u{2588}\u{2588}\u{2588}\u{2588}</span>"));
+        assert!(html.contains("<span style=\"color:#0000FF\">\u{2588}</span>"));
+        assert!(html.starts_with("<pre"));
+        assert!(html.trim_end().ends_with("</pre>"));
+    }
+
+    #[test]
+    fn test_html_background_and_escaping() {
+        let mut canvas = Canvas::new_with_size(8, 8);
+        canvas.set(0, 0, Cell { ch: '<', fg: Some(red_rgb()), bg: Some(blue_rgb()) });
+        canvas.set(2, 1, Cell { ch: '&', fg: None, bg: None });
+        let html = to_html(&canvas);
+        assert!(html.contains("<span style=\"color:#FF0000;background:#0000FF\">&lt;</span>"));
+        assert!(html.contains("&amp;"));
+        assert!(!html.contains("<<"));
+    }
+
+    #[test]
+    fn test_html_empty_canvas() {
+        assert_eq!(to_html(&Canvas::new()).matches("<span").count(), 0);
+    }
+
+    // --- SVG Export Tests ---
+
+    #[test]
+    fn test_svg_rect_count() {
+        let mut canvas = Canvas::new_with_size(8, 8);
+        // Full block: 1 rect
+        canvas.set(0, 0, Cell { ch: blocks::FULL, fg: Some(red_rgb()), bg: None });
+        // Half block with both colors: 2 stacked rects
+        canvas.set(1, 0, Cell { ch: blocks::UPPER_HALF, fg: Some(red_rgb()), bg: Some(blue_rgb()) });
+        // Half block without bg: 1 rect, the lower half stays transparent
+        canvas.set(2, 0, Cell { ch: blocks::LOWER_HALF, fg: Some(blue_rgb()), bg: None });
+        // Space with bg: 1 rect
+        canvas.set(3, 0, Cell { ch: ' ', fg: None, bg: Some(blue_rgb()) });
+
+        let svg = to_svg(&canvas);
+        assert_eq!(svg.matches("<rect").count(), 5);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("width=\"64\" height=\"128\""));
+        assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"8\" height=\"16\" fill=\"#FF0000\"/>"));
+        // Upper half: fg on top, bg below
+        assert!(svg.contains("<rect x=\"8\" y=\"0\" width=\"8\" height=\"8\" fill=\"#FF0000\"/>"));
+        assert!(svg.contains("<rect x=\"8\" y=\"8\" width=\"8\" height=\"8\" fill=\"#0000FF\"/>"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+
+    #[test]
+    fn test_svg_empty_canvas_has_no_rects() {
+        let svg = to_svg(&Canvas::new());
+        assert_eq!(svg.matches("<rect").count(), 0);
+    }
+
+    #[test]
+    fn test_svg_shade_blends_with_opacity() {
+        let mut canvas = Canvas::new_with_size(8, 8);
+        canvas.set(0, 0, Cell { ch: blocks::SHADE_MEDIUM, fg: Some(red_rgb()), bg: Some(blue_rgb()) });
+        let svg = to_svg(&canvas);
+        assert_eq!(svg.matches("<rect").count(), 2);
+        assert!(svg.contains("fill=\"#FF0000\" fill-opacity=\"0.5\""));
+    }
+
     #[test]
     fn test_png_scale_2x() {
         let mut canvas = Canvas::new();
@@ -1169,6 +2059,77 @@ mod tests {
         assert!(ansi.contains("\x1b["));
     }
 
+    #[test]
+    fn test_detect_color_format_truecolor() {
+        assert_eq!(detect_color_format(Some("truecolor"), Some("xterm-256color")), ColorFormat::TrueColor);
+        assert_eq!(detect_color_format(Some("24bit"), None), ColorFormat::TrueColor);
+        assert_eq!(detect_color_format(None, Some("xterm-direct")), ColorFormat::TrueColor);
+    }
+
+    #[test]
+    fn test_detect_color_format_basic_terms() {
+        assert_eq!(detect_color_format(None, Some("linux")), ColorFormat::Color16);
+        assert_eq!(detect_color_format(None, Some("vt100")), ColorFormat::Color16);
+        // COLORTERM wins over a basic TERM
+        assert_eq!(detect_color_format(Some("truecolor"), Some("linux")), ColorFormat::TrueColor);
+    }
+
+    #[test]
+    fn test_detect_color_format_falls_back_to_256() {
+        assert_eq!(detect_color_format(None, None), ColorFormat::Color256Hue);
+        assert_eq!(detect_color_format(None, Some("xterm")), ColorFormat::Color256Hue);
+        assert_eq!(detect_color_format(Some("yes"), Some("screen")), ColorFormat::Color256Hue);
+    }
+
+    #[test]
+    fn test_to_display_color_depths() {
+        use ratatui::style::Color;
+        let c = Rgb::new(0x12, 0x34, 0x56);
+        assert_eq!(to_display_color(c, ColorFormat::TrueColor), Color::Rgb(0x12, 0x34, 0x56));
+        assert_eq!(to_display_color(c, ColorFormat::Color256), c.to_ratatui());
+        match to_display_color(c, ColorFormat::Color16) {
+            Color::Indexed(i) => assert!(i < 16),
+            other => panic!("expected indexed color, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_ansi_reset_each_cell() {
+        let mut canvas = Canvas::new_with_size(8, 8);
+        canvas.set(0, 0, Cell { ch: blocks::FULL, fg: RED, bg: None });
+        canvas.set(1, 0, Cell { ch: blocks::FULL, fg: RED, bg: None });
+        canvas.set(3, 0, Cell { ch: blocks::UPPER_HALF, fg: RED, bg: Some(blue_rgb()) });
+        canvas.set(0, 1, Cell { ch: blocks::SHADE_LIGHT, fg: Some(blue_rgb()), bg: None });
+
+        let plain = to_ansi(&canvas, ColorFormat::TrueColor);
+        let robust = to_ansi_reset_each_cell(&canvas, ColorFormat::TrueColor);
+        assert!(robust.len() > plain.len());
+
+        // Every rendered cell (glyph or blank) must follow a reset, with
+        // only SGR sequences allowed in between.
+        let mut reset_seen = false;
+        let mut cells = 0;
+        let mut rest = robust.as_str();
+        while let Some(c) = rest.chars().next() {
+            if c == '\x1b' {
+                let end = rest.find('m').unwrap();
+                if &rest[..=end] == "\x1b[0m" {
+                    reset_seen = true;
+                }
+                rest = &rest[end + 1..];
+                continue;
+            }
+            if c != '\n' {
+                assert!(reset_seen, "cell {:?} not preceded by a reset", c);
+                reset_seen = false;
+                cells += 1;
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+        // Bounding box is 4x2
+        assert_eq!(cells, 8);
+    }
+
     #[test]
     fn test_render_auto_256() {
         // When COLORTERM is not set to truecolor/24bit, Auto resolves to Color256Hue
@@ -1189,14 +2150,167 @@ mod tests {
         assert!(ansi.contains("\x1b[0m"), "Should end with reset");
     }
 
+    #[test]
+    fn test_mask_marks_visible_cells_and_packs_msb_first() {
+        let mut canvas = Canvas::new_with_size(10, 8);
+        canvas.set(0, 0, Cell { ch: blocks::FULL, fg: RED, bg: None });
+        canvas.set(9, 0, Cell { ch: ' ', fg: None, bg: Some(blue_rgb()) });
+        canvas.set(2, 1, Cell { ch: blocks::UPPER_HALF, fg: RED, bg: None });
+
+        let m = mask(&canvas);
+        assert_eq!((m[0].len(), m.len()), (10, 8));
+        assert_eq!(m.iter().flatten().filter(|&&b| b).count(), 3);
+        assert!(m[0][0] && m[0][9] && m[1][2]);
+
+        let packed = pack_mask(&m);
+        // Two bytes per 10-cell row
+        assert_eq!(packed.len(), 2 * 8);
+        assert_eq!(&packed[..4], &[0b1000_0000, 0b0100_0000, 0b0010_0000, 0]);
+    }
+
     #[test]
     fn test_png_space_fills_bg() {
         let mut canvas = Canvas::new_with_size(2, 2);
         canvas.set(0, 0, Cell { ch: ' ', fg: None, bg: Some(blue_rgb()) });
-        // Cell::is_empty only checks ch==' ', so space+bg is "empty" for bounding box.
-        // Use crop=false to test space rendering directly.
         let img = to_png(&canvas, CW, CH, 1, false);
         // Space should fill with bg color
         assert_eq!(img.get_pixel(0, 0), &Rgba([0, 0, 255, 255]));
+        // A space with a bg is visible, so cropping keeps it
+        let cropped = to_png(&canvas, CW, CH, 1, true);
+        assert_eq!((cropped.width(), cropped.height()), (CW, CH));
+        assert_eq!(cropped.get_pixel(0, 0), &Rgba([0, 0, 255, 255]));
+    }
+
+    #[test]
+    fn test_palette_swatches_wrap_rows() {
+        let colors: Vec<Rgb> = (0..10).map(|i| Rgb::new(i * 20, 0, 0)).collect();
+        let img = palette_swatches(&colors, 4);
+        assert_eq!(img.dimensions(), (4 * SWATCH_W, 3 * SWATCH_H));
+        // Swatch 5 starts the second row
+        assert_eq!(img.get_pixel(SWATCH_W + 1, SWATCH_H + 1), &Rgba([100, 0, 0, 255]));
+        // Unused slots on the last row stay transparent
+        assert_eq!(img.get_pixel(3 * SWATCH_W + 1, 2 * SWATCH_H + 1), &TRANSPARENT);
+    }
+
+    #[test]
+    fn test_palette_swatch_label_contrasts() {
+        let img = palette_swatches(&[Rgb::new(255, 255, 0), Rgb::new(0, 0, 128)], 8);
+        let label_pixels = |x0: u32| {
+            (x0..x0 + SWATCH_W)
+                .flat_map(|x| (0..SWATCH_H).map(move |y| (x, y)))
+                .map(|(x, y)| *img.get_pixel(x, y))
+                .collect::<Vec<_>>()
+        };
+        assert!(label_pixels(0).contains(&Rgba([0, 0, 0, 255])));
+        assert!(label_pixels(SWATCH_W).contains(&Rgba([255, 255, 255, 255])));
+    }
+
+    #[test]
+    fn test_export_transforms_compose_in_order() {
+        let mut canvas = Canvas::new_with_size(8, 8);
+        canvas.set(0, 0, Cell { ch: blocks::FULL, fg: Some(Rgb::new(255, 0, 0)), bg: Some(Rgb::new(100, 100, 100)) });
+
+        let hue = apply_transforms(&canvas, &[ExportTransform::HueShift(120)]);
+        assert_eq!(hue.get(0, 0).unwrap().fg, Some(Rgb::new(0, 255, 0)));
+        // Grays keep their value
+        assert_eq!(hue.get(0, 0).unwrap().bg, Some(Rgb::new(100, 100, 100)));
+
+        let inverted = apply_transforms(&canvas, &[ExportTransform::Invert, ExportTransform::Posterize(2)]);
+        assert_eq!(inverted.get(0, 0).unwrap().fg, Some(Rgb::new(0, 255, 255)));
+        assert_eq!(inverted.get(0, 0).unwrap().bg, Some(Rgb::new(255, 255, 255)));
+
+        // Order matters: posterizing first snaps orange to red before the shift
+        canvas.set(1, 0, Cell { ch: blocks::FULL, fg: Some(Rgb::new(200, 100, 0)), bg: None });
+        let original = canvas.cells();
+        let steps = [ExportTransform::Posterize(2), ExportTransform::HueShift(60)];
+        let forward = apply_transforms(&canvas, &steps);
+        let reversed = apply_transforms(&canvas, &[steps[1], steps[0]]);
+        assert_eq!(forward.get(1, 0).unwrap().fg, Some(Rgb::new(255, 255, 0)));
+        assert_eq!(reversed.get(1, 0).unwrap().fg, Some(Rgb::new(0, 255, 0)));
+
+        // Glyphs, transparency and the source canvas are untouched
+        assert_eq!(hue.get(0, 0).unwrap().ch, blocks::FULL);
+        assert_eq!(hue.get(1, 1), canvas.get(1, 1));
+        assert_eq!(canvas.cells(), original);
+    }
+
+    #[test]
+    fn test_export_transform_parse() {
+        assert_eq!(ExportTransform::parse("invert"), Ok(ExportTransform::Invert));
+        assert_eq!(ExportTransform::parse("hue:-30"), Ok(ExportTransform::HueShift(330)));
+        assert_eq!(ExportTransform::parse("posterize:4"), Ok(ExportTransform::Posterize(4)));
+        assert!(ExportTransform::parse("posterize:1").is_err());
+        assert!(ExportTransform::parse("hue").is_err());
+        assert!(ExportTransform::parse("blur:3").is_err());
+        for t in [ExportTransform::Invert, ExportTransform::HueShift(45), ExportTransform::Posterize(3)] {
+            assert_eq!(ExportTransform::parse(&t.spec()), Ok(t));
+        }
+    }
+
+    /// What a terminal would show for ANSI output: per row, each cell's
+    /// glyph with its truecolor fg/bg, minus invisible trailing blanks.
+    fn screen(ansi: &str) -> Vec<Vec<(char, Option<String>, Option<String>)>> {
+        let mut rows = Vec::new();
+        for line in ansi.split('\n') {
+            let (mut fg, mut bg): (Option<String>, Option<String>) = (None, None);
+            let mut row = Vec::new();
+            let mut rest = line;
+            while let Some(ch) = rest.chars().next() {
+                if let Some(seq) = rest.strip_prefix("\x1b[") {
+                    let end = seq.find('m').unwrap();
+                    let params: Vec<&str> = seq[..end].split(';').collect();
+                    let mut i = 0;
+                    while i < params.len() {
+                        match params[i] {
+                            "0" => (fg, bg) = (None, None),
+                            "39" => fg = None,
+                            "49" => bg = None,
+                            "38" => { fg = Some(params[i + 2..i + 5].join(";")); i += 4; }
+                            "48" => { bg = Some(params[i + 2..i + 5].join(";")); i += 4; }
+                            other => panic!("unexpected SGR {}", other),
+                        }
+                        i += 1;
+                    }
+                    rest = &seq[end + 1..];
+                    continue;
+                }
+                let shown_fg = if ch == ' ' { None } else { fg.clone() };
+                row.push((ch, shown_fg, bg.clone()));
+                rest = &rest[ch.len_utf8()..];
+            }
+            while row.last().is_some_and(|(ch, _, bg)| *ch == ' ' && bg.is_none()) {
+                row.pop();
+            }
+            rows.push(row);
+        }
+        rows
+    }
+
+    #[test]
+    fn test_ansi_skips_blank_tails_without_visual_change() {
+        let mut canvas = Canvas::new_with_size(96, 48);
+        canvas.set(2, 1, Cell { ch: blocks::FULL, fg: RED, bg: None });
+        canvas.set(90, 3, Cell { ch: blocks::UPPER_HALF, fg: RED, bg: None });
+        canvas.set(10, 20, Cell { ch: blocks::FULL, fg: RED, bg: Some(blue_rgb()) });
+        canvas.set(40, 40, Cell { ch: blocks::LOWER_HALF, fg: None, bg: Some(blue_rgb()) });
+
+        for reset_each_cell in [false, true] {
+            let fast = render_ansi(&canvas, ColorFormat::TrueColor, reset_each_cell, true);
+            let full = render_ansi(&canvas, ColorFormat::TrueColor, reset_each_cell, false);
+            assert_eq!(screen(&fast), screen(&full), "reset_each_cell={}", reset_each_cell);
+            assert!(fast.len() * 4 < full.len(), "{} vs {}", fast.len(), full.len());
+        }
+
+        // Dense rows are unchanged
+        let mut dense = Canvas::new_with_size(8, 8);
+        for y in 0..8 {
+            for x in 0..8 {
+                dense.set(x, y, Cell { ch: blocks::FULL, fg: RED, bg: None });
+            }
+        }
+        assert_eq!(
+            render_ansi(&dense, ColorFormat::TrueColor, false, true),
+            render_ansi(&dense, ColorFormat::TrueColor, false, false)
+        );
     }
 }
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use crate::canvas::Canvas;
 use crate::cell::Cell;
 
@@ -26,10 +28,40 @@ pub enum Action {
     },
 }
 
+impl Action {
+    /// Short human-readable label, e.g. "pencil x12" or "resize 48x32→64x32".
+    /// `hint` names the tool that produced a cell change.
+    pub fn label(&self, hint: Option<&str>) -> String {
+        match self {
+            Action::CellChange(mutations) => {
+                format!("{} x{}", hint.unwrap_or("edit"), mutations.len())
+            }
+            Action::CanvasSnapshot { old_w, old_h, new_w, new_h, .. } => {
+                if old_w == new_w && old_h == new_h {
+                    hint.unwrap_or("canvas").to_string()
+                } else {
+                    format!(
+                        "{} {}x{}\u{2192}{}x{}",
+                        hint.unwrap_or("resize"), old_w, old_h, new_w, new_h
+                    )
+                }
+            }
+        }
+    }
+}
+
+/// An action on one of the stacks, with its timeline label.
+struct Entry {
+    label: String,
+    action: Action,
+}
+
 pub struct History {
-    undo_stack: Vec<Action>,
-    redo_stack: Vec<Action>,
+    undo_stack: Vec<Entry>,
+    redo_stack: Vec<Entry>,
     pending: Option<Vec<CellMutation>>,
+    /// Tool name used to label the next committed cell change.
+    label_hint: Option<String>,
 }
 
 impl History {
@@ -38,9 +70,15 @@ impl History {
             undo_stack: Vec::new(),
             redo_stack: Vec::new(),
             pending: None,
+            label_hint: None,
         }
     }
 
+    /// Set the tool name used to label subsequently committed cell changes.
+    pub fn set_label_hint(&mut self, hint: &str) {
+        self.label_hint = Some(hint.to_string());
+    }
+
     /// Start accumulating mutations for a drag stroke.
     pub fn begin_stroke(&mut self) {
         if self.pending.is_some() {
@@ -59,23 +97,31 @@ impl History {
         }
     }
 
-    /// Finish the current drag stroke and commit it as one action.
+    /// Finish the current drag stroke and commit it as one action, with
+    /// repeated edits to a cell coalesced (see `coalesce`).
     pub fn end_stroke(&mut self) {
         if let Some(mutations) = self.pending.take() {
+            let mutations = coalesce(mutations);
             if !mutations.is_empty() {
                 self.commit(Action::CellChange(mutations));
             }
         }
     }
 
-    /// Commit an action to the undo stack.
+    /// Commit an action to the undo stack, labelled from the current hint.
     pub fn commit(&mut self, action: Action) {
+        let label = action.label(self.label_hint.as_deref());
+        self.commit_labeled(label, action);
+    }
+
+    /// Commit an action to the undo stack with an explicit timeline label.
+    pub fn commit_labeled(&mut self, label: String, action: Action) {
         match &action {
             Action::CellChange(mutations) if mutations.is_empty() => return,
             _ => {}
         }
         self.redo_stack.clear();
-        self.undo_stack.push(action);
+        self.undo_stack.push(Entry { label, action });
         if self.undo_stack.len() > MAX_HISTORY {
             self.undo_stack.remove(0);
         }
@@ -83,8 +129,8 @@ impl History {
 
     /// Undo the last action, applying old cell values.
     pub fn undo(&mut self, canvas: &mut Canvas) -> bool {
-        if let Some(action) = self.undo_stack.pop() {
-            match &action {
+        if let Some(entry) = self.undo_stack.pop() {
+            match &entry.action {
                 Action::CellChange(mutations) => {
                     for m in mutations.iter().rev() {
                         canvas.set(m.x, m.y, m.old);
@@ -94,7 +140,7 @@ impl History {
                     canvas.replace(old_cells.clone(), *old_w, *old_h);
                 }
             }
-            self.redo_stack.push(action);
+            self.redo_stack.push(entry);
             true
         } else {
             false
@@ -103,8 +149,8 @@ impl History {
 
     /// Redo the last undone action, applying new cell values.
     pub fn redo(&mut self, canvas: &mut Canvas) -> bool {
-        if let Some(action) = self.redo_stack.pop() {
-            match &action {
+        if let Some(entry) = self.redo_stack.pop() {
+            match &entry.action {
                 Action::CellChange(mutations) => {
                     for m in mutations {
                         canvas.set(m.x, m.y, m.new);
@@ -114,7 +160,7 @@ impl History {
                     canvas.replace(new_cells.clone(), *new_w, *new_h);
                 }
             }
-            self.undo_stack.push(action);
+            self.undo_stack.push(entry);
             true
         } else {
             false
@@ -132,6 +178,53 @@ impl History {
     pub fn is_stroke_active(&self) -> bool {
         self.pending.is_some()
     }
+
+    /// Labels of every recorded action, oldest first: the undo stack followed
+    /// by the redo stack in redo order.
+    pub fn timeline(&self) -> Vec<String> {
+        self.undo_stack
+            .iter()
+            .chain(self.redo_stack.iter().rev())
+            .map(|e| e.label.clone())
+            .collect()
+    }
+
+    /// Number of actions currently applied (index into `timeline()` + 1).
+    pub fn position(&self) -> usize {
+        self.undo_stack.len()
+    }
+
+    /// Jump to a timeline position by repeated undo/redo.
+    /// Returns the number of steps taken.
+    pub fn jump_to(&mut self, canvas: &mut Canvas, target: usize) -> usize {
+        let mut steps = 0;
+        while self.position() > target && self.undo(canvas) {
+            steps += 1;
+        }
+        while self.position() < target && self.redo(canvas) {
+            steps += 1;
+        }
+        steps
+    }
+}
+
+/// Merge mutations of the same cell into one, keeping the first `old` and
+/// the last `new`, at the position of the cell's first edit. Cells that end
+/// up unchanged are dropped.
+pub fn coalesce(mutations: Vec<CellMutation>) -> Vec<CellMutation> {
+    let mut index: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut merged: Vec<CellMutation> = Vec::with_capacity(mutations.len());
+    for m in mutations {
+        match index.get(&(m.x, m.y)) {
+            Some(&i) => merged[i].new = m.new,
+            None => {
+                index.insert((m.x, m.y), merged.len());
+                merged.push(m);
+            }
+        }
+    }
+    merged.retain(|m| m.old != m.new);
+    merged
 }
 
 impl Default for History {
@@ -175,6 +268,42 @@ mod tests {
         assert_eq!(canvas.get(0, 0), Some(new));
     }
 
+    #[test]
+    fn test_stroke_coalesces_repeated_cell_edits() {
+        let mut canvas = Canvas::new();
+        let mut history = History::new();
+        let blue = Cell { ch: blocks::FULL, fg: Some(Rgb { r: 0, g: 0, b: 238 }), bg: None };
+        let before = canvas.get(1, 0).unwrap();
+
+        // Back and forth over (1,0) and (2,0), then (3,0) painted and erased
+        history.begin_stroke();
+        let edits = [(1, red_cell()), (2, red_cell()), (1, blue), (2, blue), (1, red_cell()), (3, blue), (3, before)];
+        for (x, new) in edits {
+            let old = canvas.get(x, 0).unwrap();
+            canvas.set(x, 0, new);
+            history.push_mutation(CellMutation { x, y: 0, old, new });
+        }
+        history.end_stroke();
+
+        assert_eq!(history.timeline(), vec!["edit x2".to_string()]);
+        history.undo(&mut canvas);
+        for x in 1..=3 {
+            assert_eq!(canvas.get(x, 0), Some(before), "undo restores pre-stroke cell {}", x);
+        }
+        history.redo(&mut canvas);
+        assert_eq!(canvas.get(1, 0), Some(red_cell()));
+        assert_eq!(canvas.get(2, 0), Some(blue));
+        assert_eq!(canvas.get(3, 0), Some(before));
+    }
+
+    #[test]
+    fn test_coalesce_keeps_first_touch_order() {
+        let c = |x: usize, old: Cell, new: Cell| CellMutation { x, y: 0, old, new };
+        let empty = Cell::default();
+        let out = coalesce(vec![c(5, empty, red_cell()), c(2, empty, red_cell()), c(5, red_cell(), red_cell())]);
+        assert_eq!(out.iter().map(|m| m.x).collect::<Vec<_>>(), vec![5, 2]);
+    }
+
     #[test]
     fn test_stroke_batching() {
         let mut canvas = Canvas::new();
@@ -396,4 +525,75 @@ mod tests {
         history.undo(&mut canvas);
         assert_eq!(canvas.get(3, 3), Some(Cell::default()));
     }
+
+    // --- Timeline tests ---
+
+    fn paint(canvas: &mut Canvas, history: &mut History, x: usize) {
+        let old = canvas.get(x, 0).unwrap();
+        let new = red_cell();
+        canvas.set(x, 0, new);
+        history.push_mutation(CellMutation { x, y: 0, old, new });
+    }
+
+    #[test]
+    fn test_timeline_labels() {
+        let mut canvas = Canvas::new_with_size(16, 16);
+        let mut history = History::new();
+        history.set_label_hint("pencil");
+        history.begin_stroke();
+        paint(&mut canvas, &mut history, 0);
+        paint(&mut canvas, &mut history, 1);
+        history.end_stroke();
+
+        let old_cells = canvas.cells();
+        canvas.resize(24, 16);
+        history.commit(Action::CanvasSnapshot {
+            old_cells, old_w: 16, old_h: 16,
+            new_cells: canvas.cells(), new_w: 24, new_h: 16,
+        });
+
+        assert_eq!(history.timeline(), vec!["pencil x2".to_string(), "resize 16x16\u{2192}24x16".to_string()]);
+        assert_eq!(history.position(), 2);
+
+        history.undo(&mut canvas);
+        // Redo entries stay on the timeline after the current position
+        assert_eq!(history.timeline().len(), 2);
+        assert_eq!(history.position(), 1);
+    }
+
+    #[test]
+    fn test_jump_matches_repeated_undo_redo() {
+        let mut canvas = Canvas::new_with_size(16, 16);
+        let mut history = History::new();
+        for x in 0..5 {
+            paint(&mut canvas, &mut history, x);
+        }
+
+        let mut reference = Canvas::new_with_size(16, 16);
+        let mut ref_history = History::new();
+        for x in 0..5 {
+            paint(&mut reference, &mut ref_history, x);
+        }
+
+        assert_eq!(history.jump_to(&mut canvas, 1), 4);
+        for _ in 0..4 {
+            ref_history.undo(&mut reference);
+        }
+        assert_eq!(canvas.cells(), reference.cells());
+        assert_eq!(history.position(), 1);
+        assert_eq!(history.timeline().len(), 5);
+
+        assert_eq!(history.jump_to(&mut canvas, 3), 2);
+        ref_history.redo(&mut reference);
+        ref_history.redo(&mut reference);
+        assert_eq!(canvas.cells(), reference.cells());
+
+        // Out-of-range targets clamp to the ends of the timeline
+        history.jump_to(&mut canvas, 99);
+        assert_eq!(history.position(), 5);
+        assert!(!history.can_redo());
+        history.jump_to(&mut canvas, 0);
+        assert!(!history.can_undo());
+        assert_eq!(canvas.get(0, 0), Some(Cell::default()));
+    }
 }
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::path::Path;
 
-use image::GenericImageView;
+use image::{AnimationDecoder, DynamicImage, GenericImageView};
 
 use crate::cell::{self, blocks, Cell, Rgb};
 
@@ -28,6 +28,17 @@ pub enum ImportCharSet {
     HalfBlocks,
 }
 
+/// Dithering applied while quantizing to a limited palette.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DitherMode {
+    #[default]
+    None,
+    /// Error diffusion: each pixel's quantization error spreads to unvisited neighbors.
+    FloydSteinberg,
+    /// 4x4 Bayer threshold offsets: a regular, stable pattern.
+    Ordered,
+}
+
 /// Import configuration.
 pub struct ImportOptions {
     pub fit_mode: FitMode,
@@ -43,6 +54,8 @@ pub struct ImportOptions {
     /// Posterize: reduce to N distinct colors via k-means clustering.
     /// None = off (keep all colors). Some(N) = reduce to N colors (2-64).
     pub posterize: Option<usize>,
+    /// Dither gradients when quantizing to 256 or 16 colors. Ignored for TrueColor.
+    pub dither: DitherMode,
 }
 
 impl Default for ImportOptions {
@@ -55,6 +68,7 @@ impl Default for ImportOptions {
             preserve_hue: true,
             normalize: true,
             posterize: None,
+            dither: DitherMode::None,
         }
     }
 }
@@ -215,6 +229,94 @@ fn posterize_pixels(pixels: &mut [Vec<Option<(u8, u8, u8)>>], n_colors: usize) {
     }
 }
 
+/// Dither the pixel grid in place, replacing each opaque pixel with its
+/// quantized palette color. Saturation boost is applied first, so the
+/// rasterizers can re-quantize the result without changing it.
+fn dither_pixels(
+    pixels: &mut [Vec<Option<(u8, u8, u8)>>],
+    mode: DitherMode,
+    color_mode: ImportColorMode,
+    color_boost: f32,
+    preserve_hue: bool,
+    cache: &mut HashMap<(u8, u8, u8), Rgb>,
+) {
+    for row in pixels.iter_mut() {
+        for (r, g, b) in row.iter_mut().flatten() {
+            (*r, *g, *b) = boost_saturation(*r, *g, *b, color_boost);
+        }
+    }
+
+    match mode {
+        DitherMode::None => {}
+        DitherMode::FloydSteinberg => {
+            let h = pixels.len();
+            let w = pixels.first().map_or(0, |r| r.len());
+            // Running error per pixel, carried forward as pixels are visited
+            let mut err = vec![vec![[0.0f32; 3]; w]; h];
+            for y in 0..h {
+                for x in 0..w {
+                    let Some((r, g, b)) = pixels[y][x] else {
+                        continue;
+                    };
+                    let want = [
+                        (r as f32 + err[y][x][0]).clamp(0.0, 255.0),
+                        (g as f32 + err[y][x][1]).clamp(0.0, 255.0),
+                        (b as f32 + err[y][x][2]).clamp(0.0, 255.0),
+                    ];
+                    let got = quantize(
+                        want[0] as u8,
+                        want[1] as u8,
+                        want[2] as u8,
+                        color_mode,
+                        1.0,
+                        preserve_hue,
+                        cache,
+                    );
+                    pixels[y][x] = Some((got.r, got.g, got.b));
+                    let e = [
+                        want[0] - got.r as f32,
+                        want[1] - got.g as f32,
+                        want[2] - got.b as f32,
+                    ];
+                    let mut spread = |nx: usize, ny: usize, weight: f32| {
+                        if ny < h && nx < w && pixels[ny][nx].is_some() {
+                            for (acc, v) in err[ny][nx].iter_mut().zip(e) {
+                                *acc += v * weight;
+                            }
+                        }
+                    };
+                    spread(x + 1, y, 7.0 / 16.0);
+                    if x > 0 {
+                        spread(x - 1, y + 1, 3.0 / 16.0);
+                    }
+                    spread(x, y + 1, 5.0 / 16.0);
+                    spread(x + 1, y + 1, 1.0 / 16.0);
+                }
+            }
+        }
+        DitherMode::Ordered => {
+            // Offset amplitude roughly matches the gap between palette levels
+            let amplitude = match color_mode {
+                ImportColorMode::Color16 => 96.0,
+                _ => 40.0,
+            };
+            for (y, row) in pixels.iter_mut().enumerate() {
+                for (x, px) in row.iter_mut().enumerate() {
+                    let Some((r, g, b)) = *px else {
+                        continue;
+                    };
+                    let t = crate::tools::BAYER_4X4[y % 4][x % 4] as f32;
+                    let offset = ((t + 0.5) / 16.0 - 0.5) * amplitude;
+                    let adj = |v: u8| (v as f32 + offset).clamp(0.0, 255.0) as u8;
+                    let got =
+                        quantize(adj(r), adj(g), adj(b), color_mode, 1.0, preserve_hue, cache);
+                    *px = Some((got.r, got.g, got.b));
+                }
+            }
+        }
+    }
+}
+
 /// Boost saturation of an RGB pixel so hues survive 256-palette quantization.
 /// Pushes each channel away from the mean (gray axis).
 fn boost_saturation(r: u8, g: u8, b: u8, factor: f32) -> (u8, u8, u8) {
@@ -391,6 +493,60 @@ pub fn import_image(
     // Decode image
     let img = image::open(path).map_err(|e| ImportError::DecodeFailed(e.to_string()))?;
 
+    image_to_cells(&img, target_width, target_height, options)
+}
+
+/// One decoded GIF frame, rasterized to cells.
+pub struct GifFrame {
+    pub cells: Vec<Vec<Cell>>,
+    /// How long the frame is shown, in milliseconds.
+    pub delay_ms: u32,
+}
+
+/// Import every frame of an animated GIF.
+///
+/// The decoder composites each frame onto the previous ones according to
+/// its disposal method, so every returned grid is a complete picture.
+/// Sizing and options behave exactly as in [`import_image`].
+pub fn import_gif_frames(
+    path: &Path,
+    target_width: usize,
+    target_height: usize,
+    options: &ImportOptions,
+) -> Result<Vec<GifFrame>, ImportError> {
+    if !path.exists() {
+        return Err(ImportError::FileNotFound);
+    }
+
+    let file = std::fs::File::open(path).map_err(|e| ImportError::DecodeFailed(e.to_string()))?;
+    let decoder = image::codecs::gif::GifDecoder::new(std::io::BufReader::new(file))
+        .map_err(|e| ImportError::InvalidFormat(e.to_string()))?;
+
+    let mut frames = Vec::new();
+    for frame in decoder.into_frames() {
+        let frame = frame.map_err(|e| ImportError::DecodeFailed(e.to_string()))?;
+        let (numer, denom) = frame.delay().numer_denom_ms();
+        let delay_ms = if denom == 0 { 0 } else { numer / denom };
+        let img = DynamicImage::ImageRgba8(frame.into_buffer());
+        frames.push(GifFrame {
+            cells: image_to_cells(&img, target_width, target_height, options)?,
+            delay_ms,
+        });
+    }
+
+    if frames.is_empty() {
+        return Err(ImportError::InvalidFormat("GIF has no frames".to_string()));
+    }
+    Ok(frames)
+}
+
+/// Rasterize a decoded image to a cell grid (shared by still and GIF import).
+fn image_to_cells(
+    img: &DynamicImage,
+    target_width: usize,
+    target_height: usize,
+    options: &ImportOptions,
+) -> Result<Vec<Vec<Cell>>, ImportError> {
     let (src_w, src_h) = img.dimensions();
     if src_w == 0 || src_h == 0 {
         return Err(ImportError::InvalidFormat("Image has zero dimensions".to_string()));
@@ -423,7 +579,7 @@ pub fn import_image(
 
     // Downscale image to the scaled dimensions
     let resized = image::imageops::resize(
-        &img,
+        img,
         scaled_w as u32,
         scaled_h as u32,
         image::imageops::FilterType::Lanczos3,
@@ -460,12 +616,27 @@ pub fn import_image(
     // Rasterize to cells
     let mut cache: HashMap<(u8, u8, u8), Rgb> = HashMap::new();
 
+    // Dither: pre-quantize the pixel grid so error carries across pixels.
+    // The boost is already applied, so the rasterizers must not repeat it.
+    let mut color_boost = options.color_boost;
+    if options.dither != DitherMode::None && options.color_mode != ImportColorMode::TrueColor {
+        dither_pixels(
+            &mut pixels,
+            options.dither,
+            options.color_mode,
+            color_boost,
+            options.preserve_hue,
+            &mut cache,
+        );
+        color_boost = 1.0;
+    }
+
     let cells = match options.char_set {
         ImportCharSet::FullBlocks => {
-            rasterize_full_blocks(&pixels, cell_w, cell_h, options.color_mode, options.color_boost, options.preserve_hue, &mut cache)
+            rasterize_full_blocks(&pixels, cell_w, cell_h, options.color_mode, color_boost, options.preserve_hue, &mut cache)
         }
         ImportCharSet::HalfBlocks => {
-            rasterize_half_blocks(&pixels, cell_w, cell_h, options.color_mode, options.color_boost, options.preserve_hue, &mut cache)
+            rasterize_half_blocks(&pixels, cell_w, cell_h, options.color_mode, color_boost, options.preserve_hue, &mut cache)
         }
     };
 
@@ -816,6 +987,47 @@ mod tests {
         assert!(bg.r > 100, "Expected red first frame, got r={}", bg.r);
     }
 
+    #[test]
+    fn test_gif_all_frames() {
+        let dir = std::env::temp_dir().join("kakukuma_test_import_frames");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("animated.gif");
+
+        write_test_gif(&path, 2, 2, &[(255, 0, 0), (0, 0, 255), (0, 255, 0)]);
+
+        let opts = ImportOptions {
+            color_mode: ImportColorMode::Color256,
+            char_set: ImportCharSet::FullBlocks,
+            normalize: false,
+            ..Default::default()
+        };
+        let frames = import_gif_frames(&path, 2, 2, &opts).unwrap();
+        assert_eq!(frames.len(), 3);
+
+        let bg = |i: usize| frames[i].cells[0][0].bg.unwrap();
+        assert!(bg(0).r > 100 && bg(0).b < 100, "frame 1 should be red");
+        assert!(bg(1).b > 100 && bg(1).r < 100, "frame 2 should be blue");
+        assert!(bg(2).g > 100 && bg(2).r < 100, "frame 3 should be green");
+        for frame in &frames {
+            assert_eq!(frame.cells.len(), 2);
+            assert_eq!(frame.cells[0].len(), 2);
+        }
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_gif_frames_rejects_non_gif() {
+        let dir = std::env::temp_dir().join("kakukuma_test_import_frames_png");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("still.png");
+        write_test_png(&path, 2, 2, &[(255, 0, 0, 255); 4]);
+
+        assert!(import_gif_frames(&path, 2, 2, &ImportOptions::default()).is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn test_invalid_file() {
         let path = Path::new("/nonexistent/path/image.png");
@@ -1040,4 +1252,55 @@ mod tests {
 
         let _ = std::fs::remove_dir_all(&dir);
     }
+
+    #[test]
+    fn test_dither_adds_palette_colors() {
+        // A dark gray gradient that rounds to black everywhere in 16-color mode;
+        // dithering should mix in lighter palette entries
+        let dir = std::env::temp_dir().join("kakukuma_test_dither");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("gradient.png");
+
+        let pixels: Vec<_> = (0..16 * 8).map(|i| {
+            let v = 30 + (i % 16) as u8;
+            (v, v, v, 255u8)
+        }).collect();
+        write_test_png(&path, 16, 8, &pixels);
+
+        let distinct = |cells: &[Vec<Cell>]| {
+            let mut colors: std::collections::HashSet<(u8, u8, u8)> = std::collections::HashSet::new();
+            for cell in cells.iter().flatten() {
+                for c in [cell.fg, cell.bg].into_iter().flatten() {
+                    colors.insert((c.r, c.g, c.b));
+                }
+            }
+            colors.len()
+        };
+
+        for char_set in [ImportCharSet::FullBlocks, ImportCharSet::HalfBlocks] {
+            let (w, h) = match char_set {
+                ImportCharSet::FullBlocks => (16, 8),
+                ImportCharSet::HalfBlocks => (16, 4),
+            };
+            let import = |dither| {
+                let opts = ImportOptions {
+                    color_mode: ImportColorMode::Color16,
+                    char_set,
+                    normalize: false,
+                    dither,
+                    ..Default::default()
+                };
+                import_image(&path, w, h, &opts).unwrap()
+            };
+            let plain = distinct(&import(DitherMode::None));
+            let fs = distinct(&import(DitherMode::FloydSteinberg));
+            let ordered = distinct(&import(DitherMode::Ordered));
+            assert!(fs > plain,
+                "Floyd-Steinberg should use more colors ({} vs {}) for {:?}", fs, plain, char_set);
+            assert!(ordered > plain,
+                "Ordered should use more colors ({} vs {}) for {:?}", ordered, plain, char_set);
+        }
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
@@ -3,6 +3,7 @@ use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, Mous
 use crate::app::{App, AppMode, MessageLevel};
 use crate::canvas::Canvas;
 use crate::history::{Action, History};
+use crate::keymap::KeyAction;
 use crate::palette::{PaletteItem, PaletteSection};
 use crate::tools::{ToolKind, ToolState};
 
@@ -16,6 +17,8 @@ pub struct CanvasArea {
     /// Viewport dimensions in canvas cells (set by renderer)
     pub viewport_w: usize,
     pub viewport_h: usize,
+    /// First screen column of the palette panel (0 when it isn't drawn)
+    pub palette_left: u16,
 }
 
 impl CanvasArea {
@@ -40,24 +43,36 @@ impl CanvasArea {
 }
 
 pub fn handle_event(app: &mut App, event: Event, canvas_area: &CanvasArea) {
+    // Debug screen dump works in every mode, and only with --debug-dump
+    if let Event::Key(KeyEvent { code: KeyCode::F(12), .. }) = event {
+        if app.debug_dump_path.is_some() {
+            app.debug_dump_pending = true;
+            return;
+        }
+    }
     match app.mode {
         AppMode::Help => {
-            // Any key dismisses help
-            if matches!(event, Event::Key(_)) {
-                app.mode = AppMode::Normal;
+            // Arrows scroll; any other key dismisses help
+            if let Event::Key(KeyEvent { code, .. }) = event {
+                match code {
+                    KeyCode::Up => app.help_scroll = app.help_scroll.saturating_sub(1),
+                    KeyCode::Down => app.help_scroll = app.help_scroll.saturating_add(1).min(app.help_max_scroll),
+                    KeyCode::PageUp => app.help_scroll = app.help_scroll.saturating_sub(10),
+                    KeyCode::PageDown => app.help_scroll = app.help_scroll.saturating_add(10).min(app.help_max_scroll),
+                    _ => app.mode = AppMode::Normal,
+                }
             }
             return;
         }
         AppMode::Quitting => {
             if let Event::Key(KeyEvent { code, .. }) = event {
-                match code {
-                    KeyCode::Char('y') | KeyCode::Char('Y') => {
-                        app.running = false;
-                    }
-                    _ => {
-                        app.mode = AppMode::Normal;
-                    }
-                }
+                handle_quit_prompt(app, code);
+            }
+            return;
+        }
+        AppMode::PaletteDeleteConfirm => {
+            if let Event::Key(KeyEvent { code, .. }) = event {
+                app.resolve_palette_delete(matches!(code, KeyCode::Char('y') | KeyCode::Char('Y')));
             }
             return;
         }
@@ -100,8 +115,8 @@ pub fn handle_event(app: &mut App, event: Event, canvas_area: &CanvasArea) {
             return;
         }
         AppMode::ColorSliders => {
-            if let Event::Key(KeyEvent { code, .. }) = event {
-                handle_color_sliders(app, code);
+            if let Event::Key(KeyEvent { code, modifiers, .. }) = event {
+                handle_color_sliders(app, code, modifiers);
             }
             return;
         }
@@ -183,6 +198,18 @@ pub fn handle_event(app: &mut App, event: Event, canvas_area: &CanvasArea) {
             }
             return;
         }
+        AppMode::HistoryTimeline => {
+            if let Event::Key(KeyEvent { code, .. }) = event {
+                handle_history_timeline(app, code);
+            }
+            return;
+        }
+        AppMode::ShiftCanvas => {
+            if let Event::Key(KeyEvent { code, .. }) = event {
+                handle_shift_canvas(app, code);
+            }
+            return;
+        }
         _ => {}
     }
 
@@ -259,184 +286,128 @@ fn try_flush_paste(app: &mut App) {
 }
 
 fn handle_key(app: &mut App, key: KeyEvent) {
-    // Ctrl combinations
-    if key.modifiers.contains(KeyModifiers::CONTROL) {
-        match key.code {
-            KeyCode::Char('z') => {
-                app.undo();
-                return;
-            }
-            KeyCode::Char('y') => {
-                app.redo();
-                return;
-            }
-            KeyCode::Char('s') => {
-                // Save
-                if !app.save_project() {
-                    // No path set — prompt for name
-                    app.text_input = app
-                        .project_name
-                        .clone()
-                        .unwrap_or_else(|| "untitled".to_string());
-                    app.mode = AppMode::SaveAs;
-                }
-                return;
-            }
-            KeyCode::Char('o') => {
-                // Open file dialog
-                app.open_file_dialog();
-                return;
-            }
-            KeyCode::Char('n') => {
-                // New canvas dialog
-                app.new_canvas_width = app.canvas.width;
-                app.new_canvas_height = app.canvas.height;
-                app.new_canvas_cursor = 0;
-                app.new_canvas_input = app.canvas.width.to_string();
-                app.mode = AppMode::NewCanvas;
-                return;
-            }
-            KeyCode::Char('r') => {
-                // Resize canvas dialog
-                app.new_canvas_width = app.canvas.width;
-                app.new_canvas_height = app.canvas.height;
-                app.new_canvas_cursor = 0;
-                app.new_canvas_input = app.canvas.width.to_string();
-                app.mode = AppMode::ResizeCanvas;
-                return;
-            }
-            KeyCode::Char('t') => {
-                app.cycle_theme();
-                return;
-            }
-            KeyCode::Char('e') => {
-                // Export dialog
-                app.export_format = 0;
-                app.export_dest = 0;
-                app.export_cursor = 0;
-                app.export_color_format = 0;
-                app.mode = AppMode::ExportDialog;
-                return;
-            }
-            KeyCode::Char('i') => {
-                // Ctrl+I is Tab in terminals — import moved to plain 'I' key
-                return;
-            }
-            KeyCode::Char('p') => {
-                // Command palette (unconditional — always works regardless of cursor state)
-                app.palette_query.clear();
-                app.palette_filtered = (0..crate::app::COMMANDS.len()).collect();
-                app.palette_selected_cmd = 0;
-                app.mode = AppMode::CommandPalette;
-                return;
-            }
-            KeyCode::Char('c') => {
-                if app.dirty {
-                    app.mode = AppMode::Quitting;
-                    app.set_status_with_level("Unsaved changes. Quit? (y/n)", MessageLevel::Warning);
-                } else {
-                    app.running = false;
-                }
-                return;
-            }
-            _ => return,
-        }
+    if let Some(action) = app.keymap.lookup(key.code, key.modifiers) {
+        run_key_action(app, action);
     }
+}
 
-    match key.code {
+fn open_command_palette(app: &mut App) {
+    app.palette_query.clear();
+    app.palette_filtered = (0..crate::app::COMMANDS.len()).collect();
+    app.palette_selected_cmd = 0;
+    app.mode = AppMode::CommandPalette;
+}
+
+fn request_quit(app: &mut App) {
+    if app.dirty {
+        app.mode = AppMode::Quitting;
+        app.set_status_with_level("Unsaved changes: [S]ave & quit, [D]iscard, [C]ancel", MessageLevel::Warning);
+    } else {
+        app.running = false;
+    }
+}
+
+/// Move the palette cursor by `delta` entries and pick up the color under it.
+fn move_palette_cursor(app: &mut App, delta: isize) {
+    let target = app.palette_cursor as isize + delta;
+    if target < 0 || target as usize >= app.palette_layout.len() {
+        return;
+    }
+    app.palette_cursor = target as usize;
+    if let Some(PaletteItem::Color(color)) = app.palette_layout.get(app.palette_cursor) {
+        app.color = *color;
+    }
+    app.ensure_palette_cursor_visible(15);
+}
+
+fn run_key_action(app: &mut App, action: KeyAction) {
+    match action {
         // Tool selection
-        KeyCode::Char('p') | KeyCode::Char('P') => {
+        KeyAction::Pencil => {
             app.active_tool = ToolKind::Pencil;
             app.cancel_tool();
         }
-        KeyCode::Char('e') | KeyCode::Char('E') => {
+        KeyAction::Eraser => {
             app.active_tool = ToolKind::Eraser;
             app.cancel_tool();
         }
-        KeyCode::Char('l') | KeyCode::Char('L') => {
+        KeyAction::Line => {
             app.active_tool = ToolKind::Line;
             app.cancel_tool();
         }
-        KeyCode::Char('r') | KeyCode::Char('R') => {
+        KeyAction::Rectangle => {
             app.active_tool = ToolKind::Rectangle;
             app.cancel_tool();
         }
-        KeyCode::Char('f') | KeyCode::Char('F') => {
+        KeyAction::Ellipse => {
+            app.active_tool = ToolKind::Ellipse;
+            app.cancel_tool();
+        }
+        KeyAction::Fill => {
             app.active_tool = ToolKind::Fill;
             app.cancel_tool();
         }
-        KeyCode::Char('k') | KeyCode::Char('K') => {
+        KeyAction::Eyedropper => {
             app.active_tool = ToolKind::Eyedropper;
             app.cancel_tool();
         }
-        KeyCode::Char('i') | KeyCode::Char('I') => {
+        KeyAction::Select => {
+            app.active_tool = ToolKind::Select;
+            app.cancel_tool();
+        }
+        KeyAction::ToggleClip => app.toggle_clip(),
+        KeyAction::GrowSelection => app.resize_selection(1),
+        KeyAction::ShrinkSelection => app.resize_selection(-1),
+        KeyAction::CopyOrQuit => {
+            if app.selection.is_some() {
+                app.copy_selection();
+            } else {
+                request_quit(app);
+            }
+        }
+        KeyAction::Paste => app.paste_clipboard(),
+        KeyAction::FlipHorizontal => app.flip_canvas(true),
+        KeyAction::FlipVertical => app.flip_canvas(false),
+        KeyAction::RotateCanvas => app.rotate_canvas(true),
+        KeyAction::InvertColors => app.invert_colors(),
+        KeyAction::ShiftMode => app.begin_shift(),
+        KeyAction::RadialSymmetry => app.cycle_radial_order(),
+        KeyAction::AxisLeft => app.nudge_symmetry_axis(-1, 0),
+        KeyAction::AxisRight => app.nudge_symmetry_axis(1, 0),
+        KeyAction::AxisUp => app.nudge_symmetry_axis(0, -1),
+        KeyAction::AxisDown => app.nudge_symmetry_axis(0, 1),
+        KeyAction::Import => {
             open_import_dialog(app);
-            return;
         }
 
         // Symmetry
-        KeyCode::Char('h') | KeyCode::Char('H') => {
+        KeyAction::SymmetryHorizontal => {
             app.symmetry = app.symmetry.toggle_horizontal();
             app.set_status(&format!("Symmetry: {}", app.symmetry.label()));
         }
-        KeyCode::Char('v') | KeyCode::Char('V') => {
+        KeyAction::SymmetryVertical => {
             app.symmetry = app.symmetry.toggle_vertical();
             app.set_status(&format!("Symmetry: {}", app.symmetry.label()));
         }
 
-        // Zoom cycle
-        KeyCode::Char('z') | KeyCode::Char('Z') => {
+        KeyAction::CycleZoom => {
             app.cycle_zoom();
         }
+        KeyAction::FollowCursor => app.toggle_follow_cursor(),
+        KeyAction::ToggleMinimap => app.toggle_minimap(),
 
         // Quick color pick: 1-9 → curated palette slots 0-8, 0 → slot 9
-        KeyCode::Char(c @ '1'..='9') => {
-            let n = (c as u8 - b'1') as usize;
+        KeyAction::QuickPick(n) => {
             app.quick_pick_color(n);
         }
-        KeyCode::Char('0') => {
-            app.quick_pick_color(9);
-        }
 
         // Palette navigation (uses palette_layout)
-        KeyCode::Up => {
-            if app.palette_cursor > 0 {
-                app.palette_cursor -= 1;
-                if let Some(PaletteItem::Color(color)) = app.palette_layout.get(app.palette_cursor) {
-                    app.color = *color;
-                }
-                app.ensure_palette_cursor_visible(15);
-            }
-        }
-        KeyCode::Down => {
-            if app.palette_cursor + 1 < app.palette_layout.len() {
-                app.palette_cursor += 1;
-                if let Some(PaletteItem::Color(color)) = app.palette_layout.get(app.palette_cursor) {
-                    app.color = *color;
-                }
-                app.ensure_palette_cursor_visible(15);
-            }
-        }
-        KeyCode::Left => {
-            if app.palette_cursor >= 6 {
-                app.palette_cursor -= 6;
-                if let Some(PaletteItem::Color(color)) = app.palette_layout.get(app.palette_cursor) {
-                    app.color = *color;
-                }
-                app.ensure_palette_cursor_visible(15);
-            }
-        }
-        KeyCode::Right => {
-            if app.palette_cursor + 6 < app.palette_layout.len() {
-                app.palette_cursor += 6;
-                if let Some(PaletteItem::Color(color)) = app.palette_layout.get(app.palette_cursor) {
-                    app.color = *color;
-                }
-                app.ensure_palette_cursor_visible(15);
-            }
-        }
+        KeyAction::PaletteUp => move_palette_cursor(app, -1),
+        KeyAction::PaletteDown => move_palette_cursor(app, 1),
+        KeyAction::PaletteLeft => move_palette_cursor(app, -6),
+        KeyAction::PaletteRight => move_palette_cursor(app, 6),
         // Enter on palette: toggle section header or select color
-        KeyCode::Enter => {
+        KeyAction::PaletteSelect => {
             if let Some(item) = app.palette_layout.get(app.palette_cursor).copied() {
                 match item {
                     PaletteItem::SectionHeader(section) => {
@@ -468,19 +439,10 @@ fn handle_key(app: &mut App, key: KeyEvent) {
         }
 
         // WASD canvas navigation
-        KeyCode::Char('w') | KeyCode::Char('W') => {
-            app.canvas_cursor.1 = app.canvas_cursor.1.saturating_sub(1);
-            app.canvas_cursor_active = true;
-            let (cx, cy) = app.canvas_cursor;
-            app.ensure_cursor_in_viewport(cx, cy, app.viewport_w, app.viewport_h);
-        }
-        KeyCode::Char('d') | KeyCode::Char('D') => {
-            app.canvas_cursor.0 = (app.canvas_cursor.0 + 1).min(app.canvas.width.saturating_sub(1));
-            app.canvas_cursor_active = true;
-            let (cx, cy) = app.canvas_cursor;
-            app.ensure_cursor_in_viewport(cx, cy, app.viewport_w, app.viewport_h);
-        }
-        KeyCode::Char(' ') => {
+        KeyAction::CursorUp => app.move_canvas_cursor(0, -1),
+        KeyAction::CursorRight => app.move_canvas_cursor(1, 0),
+        KeyAction::CursorStep => app.cycle_cursor_step(),
+        KeyAction::DrawOrPalette => {
             if app.canvas_cursor_active {
                 let (x, y) = app.canvas_cursor;
                 if matches!(app.active_tool, ToolKind::Pencil | ToolKind::Eraser) {
@@ -491,98 +453,126 @@ fn handle_key(app: &mut App, key: KeyEvent) {
                     app.end_stroke();
                 }
             } else {
-                // Open command palette
-                app.palette_query.clear();
-                app.palette_filtered = (0..crate::app::COMMANDS.len()).collect();
-                app.palette_selected_cmd = 0;
-                app.mode = AppMode::CommandPalette;
+                open_command_palette(app);
             }
         }
 
         // S key: canvas down if active, otherwise HSL sliders
-        KeyCode::Char('s') | KeyCode::Char('S') => {
+        KeyAction::DownOrSliders => {
             if app.canvas_cursor_active {
-                app.canvas_cursor.1 = (app.canvas_cursor.1 + 1).min(app.canvas.height.saturating_sub(1));
-                let (cx, cy) = app.canvas_cursor;
-                app.ensure_cursor_in_viewport(cx, cy, app.viewport_w, app.viewport_h);
+                app.move_canvas_cursor(0, 1);
             } else {
-                let (h, s, l) = crate::palette::rgb_to_hsl(app.color.r, app.color.g, app.color.b);
-                app.slider_h = h;
-                app.slider_s = s;
-                app.slider_l = l;
-                app.slider_active = 0;
-                app.mode = AppMode::ColorSliders;
+                app.open_color_sliders();
             }
         }
 
         // A key: canvas left if active, otherwise add to palette
-        KeyCode::Char('a') | KeyCode::Char('A') => {
+        KeyAction::LeftOrAddColor => {
             if app.canvas_cursor_active {
-                app.canvas_cursor.0 = app.canvas_cursor.0.saturating_sub(1);
-                let (cx, cy) = app.canvas_cursor;
-                app.ensure_cursor_in_viewport(cx, cy, app.viewport_w, app.viewport_h);
+                app.move_canvas_cursor(-1, 0);
             } else {
                 app.add_color_to_custom_palette();
             }
         }
 
-        // Custom palette dialog
-        KeyCode::Char('c') | KeyCode::Char('C') => {
+        KeyAction::PaletteManager => {
             app.open_palette_dialog();
         }
-
-        // Cycle block character type
-        KeyCode::Char('b') => {
+        KeyAction::CycleBlock => {
             app.cycle_block();
         }
-        KeyCode::Char('B') => {
+        KeyAction::BlockPicker => {
             app.open_block_picker();
         }
-
-        // Shade cycle (G key)
-        KeyCode::Char('g') | KeyCode::Char('G') => {
+        KeyAction::CycleShade => {
             app.cycle_shade();
         }
-
-        // Toggle filled/outline rectangle
-        KeyCode::Char('t') | KeyCode::Char('T') => {
+        KeyAction::ToggleFilledRect => {
             app.filled_rect = !app.filled_rect;
             app.set_status(if app.filled_rect { "Rect: Filled" } else { "Rect: Outline" });
         }
-
-        // Hex color input dialog
-        KeyCode::Char('x') | KeyCode::Char('X') => {
+        KeyAction::IsolateColor => {
+            app.toggle_isolate_color();
+        }
+        KeyAction::RotateView => {
+            app.cycle_view_rotation();
+        }
+        KeyAction::HistoryTimeline => {
+            app.open_history_timeline();
+        }
+        KeyAction::HexInput => {
             app.text_input = String::new();
             app.mode = AppMode::HexColorInput;
         }
+        KeyAction::SwapColors => {
+            app.swap_colors();
+        }
+        KeyAction::TogglePaintChannel => {
+            app.toggle_paint_channel();
+        }
 
         // Cancel multi-click tool / deactivate canvas cursor
-        KeyCode::Esc => {
-            if app.canvas_cursor_active {
+        KeyAction::Cancel => {
+            if app.view_rotation != 0 {
+                app.reset_view_rotation();
+            } else if app.canvas_cursor_active {
                 app.canvas_cursor_active = false;
                 app.set_status("Canvas cursor off");
+            } else if matches!(app.tool_state, ToolState::Idle) && app.selection.is_some() {
+                app.clear_selection();
+            } else if matches!(app.tool_state, ToolState::Idle) && app.clip.is_some() {
+                app.clear_clip();
             } else {
                 app.cancel_tool();
                 app.set_status("Cancelled");
             }
         }
 
-        // Help
-        KeyCode::Char('?') => {
+        KeyAction::Help => {
+            app.help_scroll = 0;
             app.mode = AppMode::Help;
         }
-
-        // Quit
-        KeyCode::Char('q') | KeyCode::Char('Q') => {
-            if app.dirty {
-                app.mode = AppMode::Quitting;
-                app.set_status_with_level("Unsaved changes. Quit? (y/n)", MessageLevel::Warning);
+        KeyAction::Quit => request_quit(app),
+
+        KeyAction::Undo => app.undo(),
+        KeyAction::Redo => app.redo(),
+        KeyAction::Save => {
+            if !app.save_project() {
+                // No path set — prompt for name
+                app.text_input = app
+                    .project_name
+                    .clone()
+                    .unwrap_or_else(|| "untitled".to_string());
+                app.mode = AppMode::SaveAs;
+            }
+        }
+        KeyAction::Open => {
+            app.open_file_dialog();
+        }
+        KeyAction::NewCanvas | KeyAction::Resize => {
+            app.new_canvas_width = app.canvas.width;
+            app.new_canvas_height = app.canvas.height;
+            app.new_canvas_cursor = 0;
+            app.new_canvas_input = app.canvas.width.to_string();
+            app.mode = if action == KeyAction::Resize {
+                AppMode::ResizeCanvas
             } else {
-                app.running = false;
-            }
-        }
-
-        _ => {}
+                AppMode::NewCanvas
+            };
+        }
+        KeyAction::CycleTheme => {
+            app.cycle_theme();
+        }
+        KeyAction::Export => {
+            app.export_format = 0;
+            app.export_dest = 0;
+            app.export_cursor = 0;
+            app.export_color_format = 0;
+            app.export_selection = false;
+            app.mode = AppMode::ExportDialog;
+        }
+        // Unconditional — always works regardless of cursor state
+        KeyAction::CommandPalette => open_command_palette(app),
     }
 }
 
@@ -665,6 +655,48 @@ fn handle_goto_input(app: &mut App, key: KeyEvent) {
     }
 }
 
+/// Unsaved-changes prompt: save & quit, quit without saving, or cancel.
+fn handle_quit_prompt(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Char('s') | KeyCode::Char('S') => app.save_and_quit(),
+        KeyCode::Char('d') | KeyCode::Char('D') => app.running = false,
+        _ => app.mode = AppMode::Normal,
+    }
+}
+
+fn handle_history_timeline(app: &mut App, code: KeyCode) {
+    let last = app.history.timeline().len();
+    match code {
+        KeyCode::Up => {
+            app.timeline_cursor = app.timeline_cursor.saturating_sub(1);
+        }
+        KeyCode::Down => {
+            app.timeline_cursor = (app.timeline_cursor + 1).min(last);
+        }
+        KeyCode::Home => app.timeline_cursor = 0,
+        KeyCode::End => app.timeline_cursor = last,
+        KeyCode::Enter => {
+            app.jump_to_history(app.timeline_cursor);
+            app.mode = AppMode::Normal;
+        }
+        KeyCode::Esc => app.mode = AppMode::Normal,
+        _ => {}
+    }
+}
+
+fn handle_shift_canvas(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Left | KeyCode::Char('[') => app.nudge_shift(-1, 0),
+        KeyCode::Right | KeyCode::Char(']') => app.nudge_shift(1, 0),
+        KeyCode::Up => app.nudge_shift(0, -1),
+        KeyCode::Down => app.nudge_shift(0, 1),
+        KeyCode::Char('w') | KeyCode::Char('W') => app.toggle_shift_wrap(),
+        KeyCode::Enter => app.apply_shift(),
+        KeyCode::Esc => app.cancel_shift(),
+        _ => {}
+    }
+}
+
 fn handle_file_dialog(app: &mut App, code: KeyCode) {
     match code {
         KeyCode::Up => {
@@ -680,7 +712,11 @@ fn handle_file_dialog(app: &mut App, code: KeyCode) {
         KeyCode::Enter => {
             if let Some(filename) = app.file_dialog_files.get(app.file_dialog_selected).cloned() {
                 app.mode = AppMode::Normal;
-                app.load_project(&filename);
+                if app.file_dialog_for_onion {
+                    app.load_onion_skin(&filename);
+                } else {
+                    app.load_project(&filename);
+                }
             }
         }
         KeyCode::Esc => {
@@ -691,8 +727,13 @@ fn handle_file_dialog(app: &mut App, code: KeyCode) {
 }
 
 fn handle_export_dialog(app: &mut App, code: KeyCode) {
-    // Row count: 0=format, 1=dest; if ANSI: 0=format, 1=color_format, 2=dest
-    let max_row = if app.export_format == 1 { 2 } else { 1 };
+    // Row count: 0=format, 1=dest; if ANSI: 0=format, 1=color_format, 2=dest;
+    // PNG always writes a file, so only the format row. HTML is like plain.
+    let max_row = match app.export_format {
+        1 => 2,
+        2 => 0,
+        _ => 1,
+    };
 
     match code {
         KeyCode::Up => {
@@ -707,11 +748,11 @@ fn handle_export_dialog(app: &mut App, code: KeyCode) {
         }
         KeyCode::Left | KeyCode::Right => {
             if app.export_cursor == 0 {
-                // Toggle format: PlainText <-> ANSI
-                app.export_format = 1 - app.export_format;
-                // Clamp cursor when switching from ANSI to plain text
-                if app.export_format == 0 && app.export_cursor > 1 {
-                    app.export_cursor = 1;
+                // Cycle format: PlainText -> ANSI -> PNG -> HTML
+                if code == KeyCode::Right {
+                    app.export_format = (app.export_format + 1) % 4;
+                } else {
+                    app.export_format = (app.export_format + 3) % 4;
                 }
             } else if app.export_format == 1 && app.export_cursor == 1 {
                 // Color format row (only when ANSI): cycle 0/1/2
@@ -725,6 +766,9 @@ fn handle_export_dialog(app: &mut App, code: KeyCode) {
                 app.export_dest = 1 - app.export_dest;
             }
         }
+        KeyCode::Char('s') | KeyCode::Char('S') => {
+            app.toggle_export_selection();
+        }
         KeyCode::Enter => {
             app.do_export();
         }
@@ -754,7 +798,13 @@ fn handle_text_input(app: &mut App, key: KeyEvent, purpose: TextInputPurpose) {
             match purpose {
                 TextInputPurpose::SaveAs => {
                     app.mode = AppMode::Normal;
-                    app.save_as(input.trim());
+                    let saved = app.save_as(input.trim());
+                    if app.quit_after_save {
+                        app.quit_after_save = false;
+                        if saved {
+                            app.running = false;
+                        }
+                    }
                 }
                 TextInputPurpose::ExportFile => {
                     app.export_to_file(input.trim());
@@ -772,6 +822,7 @@ fn handle_text_input(app: &mut App, key: KeyEvent, purpose: TextInputPurpose) {
         }
         KeyCode::Esc => {
             app.mode = AppMode::Normal;
+            app.quit_after_save = false;
         }
         KeyCode::Backspace => {
             app.text_input.pop();
@@ -785,8 +836,43 @@ fn handle_text_input(app: &mut App, key: KeyEvent, purpose: TextInputPurpose) {
     }
 }
 
-fn handle_color_sliders(app: &mut App, code: KeyCode) {
+/// Slider steps: Shift+←/→ fine, ←/→ default, PageUp/PageDown coarse.
+const SLIDER_FINE: i32 = 1;
+const SLIDER_STEP: i32 = 5;
+const SLIDER_COARSE: i32 = 25;
+
+/// Move the active slider by `delta`, clamped to its range
+/// (H 0..359, S/L 0..100, R/G/B 0..255).
+fn step_slider(app: &mut App, delta: i32) {
+    let (_, value, _) = app.slider(app.slider_active);
+    app.set_active_slider(value as i32 + delta);
+}
+
+/// Set the active slider from the typed digits (clamped). An empty buffer
+/// leaves the value alone.
+fn apply_slider_input(app: &mut App) {
+    if let Ok(value) = app.slider_input.parse::<i32>() {
+        app.set_active_slider(value);
+    }
+}
+
+fn handle_color_sliders(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
+    let step = if modifiers.contains(KeyModifiers::SHIFT) { SLIDER_FINE } else { SLIDER_STEP };
+    // Typed value is already applied; any other key commits it
+    if !matches!(code, KeyCode::Char('0'..='9') | KeyCode::Backspace) {
+        app.slider_input.clear();
+    }
     match code {
+        KeyCode::Char(c @ '0'..='9') => {
+            if app.slider_input.len() < 3 {
+                app.slider_input.push(c);
+            }
+            apply_slider_input(app);
+        }
+        KeyCode::Backspace => {
+            app.slider_input.pop();
+            apply_slider_input(app);
+        }
         KeyCode::Up => {
             if app.slider_active > 0 {
                 app.slider_active -= 1;
@@ -797,23 +883,13 @@ fn handle_color_sliders(app: &mut App, code: KeyCode) {
                 app.slider_active += 1;
             }
         }
-        KeyCode::Left => {
-            match app.slider_active {
-                0 => app.slider_h = app.slider_h.saturating_sub(5),
-                1 => app.slider_s = app.slider_s.saturating_sub(5),
-                _ => app.slider_l = app.slider_l.saturating_sub(5),
-            }
-        }
-        KeyCode::Right => {
-            match app.slider_active {
-                0 => app.slider_h = (app.slider_h + 5).min(359),
-                1 => app.slider_s = (app.slider_s + 5).min(100),
-                _ => app.slider_l = (app.slider_l + 5).min(100),
-            }
-        }
+        KeyCode::Left => step_slider(app, -step),
+        KeyCode::Right => step_slider(app, step),
+        KeyCode::PageDown => step_slider(app, -SLIDER_COARSE),
+        KeyCode::PageUp => step_slider(app, SLIDER_COARSE),
+        KeyCode::Tab => app.toggle_slider_mode(),
         KeyCode::Enter => {
-            let (r, g, b) = crate::palette::hsl_to_rgb(app.slider_h, app.slider_s, app.slider_l);
-            let color = crate::palette::nearest_color(r, g, b);
+            let color = app.slider_color();
             app.color = color;
             app.mode = AppMode::Normal;
             app.set_status(&format!("Color: {}", color.name()));
@@ -845,7 +921,7 @@ fn handle_palette_dialog(app: &mut App, code: KeyCode) {
             app.mode = AppMode::PaletteNameInput;
         }
         KeyCode::Char('d') | KeyCode::Char('D') => {
-            app.delete_selected_palette();
+            app.request_palette_delete();
         }
         KeyCode::Char('r') | KeyCode::Char('R') => {
             if !app.palette_dialog_files.is_empty() {
@@ -1079,11 +1155,12 @@ fn do_resize(app: &mut App, w: usize, h: usize) {
     let new_w = app.canvas.width;
     let new_h = app.canvas.height;
 
-    // Step 4: push to history
-    app.history.commit(Action::CanvasSnapshot {
+    // Step 4: push to history, labelled as a resize whatever tool ran last
+    let action = Action::CanvasSnapshot {
         old_cells, old_w, old_h,
         new_cells, new_w, new_h,
-    });
+    };
+    app.history.commit_labeled(action.label(Some("resize")), action);
 
     // Step 5: reset viewport
     app.viewport_x = 0;
@@ -1099,13 +1176,13 @@ fn handle_hex_input(app: &mut App, key: KeyEvent) {
         KeyCode::Enter => {
             match crate::cell::parse_hex_color(&app.text_input) {
                 Some(rgb) => {
-                    let matched = crate::palette::nearest_color(rgb.r, rgb.g, rgb.b);
-                    app.color = matched;
+                    // Keep the exact truecolor value; quantization happens at export time
+                    app.color = rgb;
                     app.mode = AppMode::Normal;
-                    app.set_status(&format!("Color: {} → {}", rgb.name(), matched.name()));
+                    app.set_status(&format!("Color: {}", rgb.name()));
                 }
                 None => {
-                    app.set_status_with_level("Invalid hex (use #RRGGBB)", MessageLevel::Error);
+                    app.set_status_with_level("Invalid color (use #RRGGBB, #RGB or a name)", MessageLevel::Error);
                 }
             }
         }
@@ -1116,7 +1193,9 @@ fn handle_hex_input(app: &mut App, key: KeyEvent) {
             app.text_input.pop();
         }
         KeyCode::Char(c) => {
-            if app.text_input.len() < 7 {
+            // Room for "#RRGGBB" and the longest CSS color name
+            let max_len = crate::cell::CSS_COLORS.iter().map(|(name, _)| name.len()).max().unwrap_or(0).max(7);
+            if app.text_input.len() < max_len {
                 app.text_input.push(c);
             }
         }
@@ -1179,6 +1258,13 @@ fn handle_block_picker(app: &mut App, key: KeyEvent) {
 }
 
 fn handle_mouse(app: &mut App, mouse: MouseEvent, canvas_area: &CanvasArea) {
+    // Screen positions don't map to canvas cells in rotated view
+    if app.view_rotation != 0 {
+        if matches!(mouse.kind, MouseEventKind::Down(_)) {
+            app.set_status_with_level("Rotated view is read-only (Esc to reset)", MessageLevel::Warning);
+        }
+        return;
+    }
     let zoom = app.zoom;
     let vp_x = app.viewport_x;
     let vp_y = app.viewport_y;
@@ -1191,6 +1277,7 @@ fn handle_mouse(app: &mut App, mouse: MouseEvent, canvas_area: &CanvasArea) {
                 // Start stroke for continuous tools
                 if matches!(app.active_tool, ToolKind::Pencil | ToolKind::Eraser) {
                     app.begin_stroke();
+                    app.drag_last = Some((x, y));
                 }
                 app.apply_tool(x, y);
             }
@@ -1199,11 +1286,19 @@ fn handle_mouse(app: &mut App, mouse: MouseEvent, canvas_area: &CanvasArea) {
             if let Some((x, y)) = canvas_area.screen_to_canvas(mouse.column, mouse.row, zoom, vp_x, vp_y) {
                 app.cursor = Some((x, y));
                 if matches!(app.active_tool, ToolKind::Pencil | ToolKind::Eraser) {
-                    app.apply_tool(x, y);
+                    // Fast drags skip cells between events; fill the gap
+                    for (px, py) in drag_points(app.drag_last, (x, y)) {
+                        app.apply_tool(px, py);
+                    }
+                    app.drag_last = Some((x, y));
                 }
+            } else {
+                // Left the canvas: don't join the exit and re-entry points
+                app.drag_last = None;
             }
         }
         MouseEventKind::Up(MouseButton::Left) => {
+            app.drag_last = None;
             if app.history.is_stroke_active() {
                 app.end_stroke();
             }
@@ -1230,10 +1325,33 @@ fn handle_mouse(app: &mut App, mouse: MouseEvent, canvas_area: &CanvasArea) {
                 app.cursor = None;
             }
         }
+        MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+            let up = matches!(mouse.kind, MouseEventKind::ScrollUp);
+            if let Some(cell) = canvas_area.screen_to_canvas(mouse.column, mouse.row, zoom, vp_x, vp_y) {
+                // Wheel up zooms in, keeping the hovered cell under the pointer
+                let offset = (mouse.column - canvas_area.left, mouse.row - canvas_area.top);
+                app.zoom_around(up, cell, offset);
+            } else if canvas_area.palette_left > 0 && mouse.column >= canvas_area.palette_left {
+                app.scroll_palette(up);
+            }
+        }
         _ => {}
     }
 }
 
+/// Cells to paint for a drag event at `to`: the line from the previous drag
+/// cell (exclusive, it's already painted) or just `to` when there's no gap.
+fn drag_points(last: Option<(usize, usize)>, to: (usize, usize)) -> Vec<(usize, usize)> {
+    match last {
+        Some(from) if from != to => {
+            let mut points = crate::tools::bresenham_line(from.0, from.1, to.0, to.1);
+            points.remove(0);
+            points
+        }
+        _ => vec![to],
+    }
+}
+
 /// Image file extensions accepted by the import browser.
 const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "bmp"];
 
@@ -1350,7 +1468,7 @@ fn handle_import_options(app: &mut App, code: KeyCode) {
             }
         }
         KeyCode::Down => {
-            if app.import_options_cursor < 5 {
+            if app.import_options_cursor < 6 {
                 app.import_options_cursor += 1;
             }
         }
@@ -1362,6 +1480,7 @@ fn handle_import_options(app: &mut App, code: KeyCode) {
                 3 => app.import_normalize = !app.import_normalize,
                 4 => app.import_preserve_hue = !app.import_preserve_hue,
                 5 => app.import_posterize = (app.import_posterize + 1) % POSTERIZE_PRESETS.len(),
+                6 => app.import_dither = (app.import_dither + 1) % 3,
                 _ => {}
             }
         }
@@ -1383,7 +1502,7 @@ fn handle_import_options(app: &mut App, code: KeyCode) {
 }
 
 fn do_import(app: &mut App) {
-    use crate::import::{self, FitMode, ImportCharSet, ImportColorMode, ImportOptions as ImportOpts};
+    use crate::import::{self, DitherMode, FitMode, ImportCharSet, ImportColorMode, ImportOptions as ImportOpts};
 
     let path = match &app.import_path {
         Some(p) => p.clone(),
@@ -1416,6 +1535,12 @@ fn do_import(app: &mut App) {
         .get(app.import_posterize)
         .and_then(|(_, v)| *v);
 
+    let dither = match app.import_dither {
+        1 => DitherMode::FloydSteinberg,
+        2 => DitherMode::Ordered,
+        _ => DitherMode::None,
+    };
+
     let opts = ImportOpts {
         fit_mode,
         color_mode,
@@ -1424,6 +1549,7 @@ fn do_import(app: &mut App) {
         preserve_hue: app.import_preserve_hue,
         normalize: app.import_normalize,
         posterize,
+        dither,
     };
 
     let target_w = app.canvas.width;
@@ -1447,7 +1573,7 @@ fn do_import(app: &mut App) {
             let new_w = app.canvas.width;
             let new_h = app.canvas.height;
 
-            app.history.commit(Action::CanvasSnapshot {
+            app.history.commit_labeled("import".to_string(), Action::CanvasSnapshot {
                 old_cells, old_w, old_h,
                 new_cells, new_w, new_h,
             });
@@ -1464,7 +1590,10 @@ fn do_import(app: &mut App) {
                 .map(|e| e.eq_ignore_ascii_case("gif"))
                 .unwrap_or(false);
             if is_gif {
-                app.set_status_with_level("Imported (GIF: first frame only)", MessageLevel::Success);
+                app.set_status_with_level(
+                    "Imported first GIF frame (kakukuma import --frames for all)",
+                    MessageLevel::Success,
+                );
             } else {
                 app.set_status_with_level("Image imported", MessageLevel::Success);
             }
@@ -1481,7 +1610,181 @@ mod tests {
     use super::*;
 
     fn area() -> CanvasArea {
-        CanvasArea { left: 10, top: 5, width: 64, height: 32, viewport_w: 64, viewport_h: 32 }
+        CanvasArea { left: 10, top: 5, width: 64, height: 32, viewport_w: 64, viewport_h: 32, palette_left: 100 }
+    }
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent { kind, column, row, modifiers: KeyModifiers::NONE }
+    }
+
+    #[test]
+    fn test_slider_steps_and_clamps() {
+        let mut app = App::new();
+        app.mode = AppMode::ColorSliders;
+        app.slider_active = 0;
+        app.slider_h = 100;
+        handle_color_sliders(&mut app, KeyCode::Right, KeyModifiers::NONE);
+        assert_eq!(app.slider_h, 105);
+        handle_color_sliders(&mut app, KeyCode::Left, KeyModifiers::SHIFT);
+        assert_eq!(app.slider_h, 104);
+        handle_color_sliders(&mut app, KeyCode::PageUp, KeyModifiers::NONE);
+        assert_eq!(app.slider_h, 129);
+
+        app.slider_h = 350;
+        handle_color_sliders(&mut app, KeyCode::PageUp, KeyModifiers::NONE);
+        assert_eq!(app.slider_h, 359);
+
+        app.slider_active = 2;
+        app.slider_l = 10;
+        handle_color_sliders(&mut app, KeyCode::PageDown, KeyModifiers::NONE);
+        assert_eq!(app.slider_l, 0);
+        app.slider_l = 99;
+        handle_color_sliders(&mut app, KeyCode::Right, KeyModifiers::SHIFT);
+        handle_color_sliders(&mut app, KeyCode::Right, KeyModifiers::SHIFT);
+        assert_eq!(app.slider_l, 100);
+    }
+
+    #[test]
+    fn test_slider_numeric_entry() {
+        let mut app = App::new();
+        app.mode = AppMode::ColorSliders;
+        app.slider_active = 0;
+        for c in ['2', '0', '0'] {
+            handle_color_sliders(&mut app, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        assert_eq!(app.slider_h, 200);
+        handle_color_sliders(&mut app, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!((app.slider_h, app.slider_input.as_str()), (20, "20"));
+
+        // Switching sliders commits and clears the buffer
+        handle_color_sliders(&mut app, KeyCode::Down, KeyModifiers::NONE);
+        assert!(app.slider_input.is_empty());
+        assert_eq!(app.slider_h, 20);
+
+        // Out of range clamps
+        for c in ['9', '9', '9'] {
+            handle_color_sliders(&mut app, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        assert_eq!(app.slider_s, 100);
+        handle_color_sliders(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.slider_input.is_empty());
+    }
+
+    #[test]
+    fn test_slider_rgb_mode_keeps_color() {
+        let mut app = App::new();
+        app.color = crate::cell::Rgb::new(200, 40, 10);
+        app.open_color_sliders();
+        let hsl_color = app.slider_color();
+
+        handle_color_sliders(&mut app, KeyCode::Tab, KeyModifiers::NONE);
+        assert!(app.slider_rgb);
+        assert_eq!(app.slider_color(), hsl_color);
+
+        // RGB sliders reach 255 and the exact value is applied
+        handle_color_sliders(&mut app, KeyCode::Char('3'), KeyModifiers::NONE);
+        handle_color_sliders(&mut app, KeyCode::Char('0'), KeyModifiers::NONE);
+        handle_color_sliders(&mut app, KeyCode::Char('0'), KeyModifiers::NONE);
+        assert_eq!(app.slider_r, 255);
+        handle_color_sliders(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.color, crate::cell::Rgb::new(255, hsl_color.g, hsl_color.b));
+
+        // Back to HSL from the new color
+        app.open_color_sliders();
+        handle_color_sliders(&mut app, KeyCode::Tab, KeyModifiers::NONE);
+        assert!(!app.slider_rgb);
+        let (h, s, l) = crate::palette::rgb_to_hsl(255, hsl_color.g, hsl_color.b);
+        assert_eq!((app.slider_h, app.slider_s, app.slider_l), (h, s, l));
+    }
+
+    #[test]
+    fn test_drag_points() {
+        assert_eq!(drag_points(None, (3, 3)), vec![(3, 3)]);
+        assert_eq!(drag_points(Some((3, 3)), (3, 3)), vec![(3, 3)]);
+        assert_eq!(drag_points(Some((2, 3)), (3, 3)), vec![(3, 3)]);
+        assert_eq!(drag_points(Some((0, 0)), (3, 0)), vec![(1, 0), (2, 0), (3, 0)]);
+    }
+
+    #[test]
+    fn test_fast_drag_fills_gap_in_one_stroke() {
+        let mut app = App::new();
+        app.active_tool = ToolKind::Pencil;
+        let a = area();
+        handle_mouse(&mut app, mouse(MouseEventKind::Down(MouseButton::Left), 10, 5), &a);
+        handle_mouse(&mut app, mouse(MouseEventKind::Drag(MouseButton::Left), 16, 5), &a);
+        handle_mouse(&mut app, mouse(MouseEventKind::Up(MouseButton::Left), 16, 5), &a);
+
+        for x in 0..=6 {
+            assert!(!app.canvas.get(x, 0).unwrap().is_empty(), "gap at x={}", x);
+        }
+        assert!(app.drag_last.is_none());
+        app.undo();
+        for x in 0..=6 {
+            assert!(app.canvas.get(x, 0).unwrap().is_empty(), "one undo clears the stroke");
+        }
+    }
+
+    #[test]
+    fn test_wheel_zoom_keeps_hovered_cell_under_pointer() {
+        let mut app = App::new();
+        app.canvas = Canvas::new_with_size(128, 64);
+        let a = area();
+        app.viewport_w = a.viewport_w;
+        app.viewport_h = a.viewport_h;
+        let (col, row) = (a.left + 20, a.top + 10);
+        assert_eq!(a.screen_to_canvas(col, row, app.zoom, app.viewport_x, app.viewport_y), Some((20, 10)));
+
+        for expected in [2, 4, 4] {
+            handle_mouse(&mut app, mouse(MouseEventKind::ScrollUp, col, row), &a);
+            assert_eq!(app.zoom, expected);
+            assert_eq!(
+                a.screen_to_canvas(col, row, app.zoom, app.viewport_x, app.viewport_y),
+                Some((20, 10)),
+                "hovered cell stays put at {}x",
+                app.zoom
+            );
+        }
+        assert_eq!((app.viewport_w, app.viewport_h), (16, 16));
+
+        handle_mouse(&mut app, mouse(MouseEventKind::ScrollDown, col, row), &a);
+        handle_mouse(&mut app, mouse(MouseEventKind::ScrollDown, col, row), &a);
+        handle_mouse(&mut app, mouse(MouseEventKind::ScrollDown, col, row), &a);
+        assert_eq!(app.zoom, 1, "zooming out stops at 1x");
+        assert_eq!((app.viewport_x, app.viewport_y), (0, 0));
+    }
+
+    #[test]
+    fn test_viewport_clamped_to_canvas() {
+        let mut app = App::new();
+        app.canvas = Canvas::new_with_size(128, 64);
+        app.viewport_w = 64;
+        app.viewport_h = 32;
+        app.viewport_x = 200;
+        app.viewport_y = 50;
+        app.clamp_viewport();
+        assert_eq!((app.viewport_x, app.viewport_y), (64, 32));
+
+        // Zooming out near the right edge pulls the view back inside
+        app.zoom = 2;
+        app.viewport_w = 32;
+        app.viewport_x = 96;
+        app.cycle_zoom();
+        assert_eq!(app.zoom, 4);
+        app.cycle_zoom();
+        assert_eq!(app.zoom, 1);
+        assert_eq!(app.viewport_x, 64);
+    }
+
+    #[test]
+    fn test_wheel_over_palette_scrolls_it() {
+        let mut app = App::new();
+        let a = area();
+        handle_mouse(&mut app, mouse(MouseEventKind::ScrollDown, a.palette_left + 3, 10), &a);
+        assert_eq!(app.palette_scroll, 1);
+        handle_mouse(&mut app, mouse(MouseEventKind::ScrollUp, a.palette_left + 3, 10), &a);
+        handle_mouse(&mut app, mouse(MouseEventKind::ScrollUp, a.palette_left + 3, 10), &a);
+        assert_eq!(app.palette_scroll, 0);
+        assert_eq!(app.zoom, 1, "palette scroll doesn't zoom");
     }
 
     #[test]
@@ -1521,6 +1824,136 @@ mod tests {
         assert_eq!(a.screen_to_canvas(14, 8, 1, 10, 5), Some((14, 8)));
     }
 
+    #[test]
+    fn test_hex_input_keeps_truecolor() {
+        let mut app = App::new();
+        app.mode = AppMode::HexColorInput;
+        app.text_input = "#123456".to_string();
+        handle_hex_input(&mut app, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(app.color, crate::cell::Rgb::new(0x12, 0x34, 0x56));
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    #[test]
+    fn test_hex_input_accepts_long_color_names() {
+        let mut app = App::new();
+        app.mode = AppMode::HexColorInput;
+        app.text_input.clear();
+        for c in "lightgoldenrodyellow".chars() {
+            handle_hex_input(&mut app, KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+        assert_eq!(app.text_input, "lightgoldenrodyellow");
+        handle_hex_input(&mut app, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(app.color, crate::cell::Rgb::new(250, 250, 210));
+    }
+
+    #[test]
+    fn test_history_timeline_navigation_and_jump() {
+        let mut app = App::new();
+        for x in 0..4 {
+            app.apply_tool(x, 0);
+        }
+        handle_key(&mut app, KeyEvent::new(KeyCode::Char('u'), KeyModifiers::NONE));
+        assert_eq!(app.mode, AppMode::HistoryTimeline);
+        assert_eq!(app.timeline_cursor, 4);
+
+        handle_history_timeline(&mut app, KeyCode::Down);
+        assert_eq!(app.timeline_cursor, 4, "cursor clamps at the newest step");
+        handle_history_timeline(&mut app, KeyCode::Up);
+        handle_history_timeline(&mut app, KeyCode::Up);
+        handle_history_timeline(&mut app, KeyCode::Enter);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert_eq!(app.history.position(), 2);
+        assert!(app.canvas.get(2, 0).unwrap().is_empty());
+
+        // Esc leaves history untouched
+        app.open_history_timeline();
+        handle_history_timeline(&mut app, KeyCode::Home);
+        handle_history_timeline(&mut app, KeyCode::Esc);
+        assert_eq!(app.history.position(), 2);
+    }
+
+    #[test]
+    fn test_quit_prompt_cancel_changes_nothing() {
+        let mut app = App::new();
+        app.apply_tool(0, 0);
+        app.mode = AppMode::Quitting;
+        handle_quit_prompt(&mut app, KeyCode::Char('y'));
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.running);
+        assert!(app.dirty);
+    }
+
+    #[test]
+    fn test_palette_delete_requires_confirmation() {
+        let path = std::env::temp_dir().join(format!("kaku_delete_{}.palette", std::process::id()));
+        let cp = crate::palette::CustomPalette { name: "doomed".to_string(), colors: vec![crate::cell::Rgb::WHITE, crate::cell::Rgb::BLACK] };
+        crate::palette::save_palette(&cp, &path).unwrap();
+        let filename = path.to_string_lossy().to_string();
+        let press = |app: &mut App, c: char| {
+            handle_event(app, Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)), &area());
+        };
+
+        let mut app = App::new();
+        app.mode = AppMode::PaletteDialog;
+        app.palette_dialog_files = vec![filename.clone()];
+        press(&mut app, 'd');
+        assert_eq!(app.mode, AppMode::PaletteDeleteConfirm);
+        assert_eq!(app.palette_delete_pending, Some((filename.clone(), Some(2))));
+
+        // Declining leaves the file and the dialog untouched
+        press(&mut app, 'n');
+        assert_eq!(app.mode, AppMode::PaletteDialog);
+        assert!(app.palette_delete_pending.is_none());
+        assert!(path.exists());
+        assert_eq!(app.palette_dialog_files, vec![filename]);
+
+        press(&mut app, 'd');
+        press(&mut app, 'y');
+        assert_eq!(app.mode, AppMode::PaletteDialog);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_quit_prompt_discard() {
+        let mut app = App::new();
+        app.apply_tool(0, 0);
+        app.mode = AppMode::Quitting;
+        handle_quit_prompt(&mut app, KeyCode::Char('d'));
+        assert!(!app.running);
+    }
+
+    #[test]
+    fn test_quit_prompt_save_persists_before_exit() {
+        let path = std::env::temp_dir().join(format!("kaku_quit_save_{}.kaku", std::process::id()));
+        let mut app = App::new();
+        app.project_path = Some(path.to_string_lossy().to_string());
+        app.apply_tool(0, 0);
+        app.mode = AppMode::Quitting;
+        handle_quit_prompt(&mut app, KeyCode::Char('s'));
+        assert!(!app.running);
+        assert!(!app.dirty);
+        let saved = crate::project::Project::load_from_file(&path).unwrap();
+        assert!(!saved.canvas.get(0, 0).unwrap().is_empty());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_quit_prompt_save_without_path_routes_to_save_as() {
+        let mut app = App::new();
+        app.apply_tool(0, 0);
+        app.mode = AppMode::Quitting;
+        handle_quit_prompt(&mut app, KeyCode::Char('s'));
+        assert_eq!(app.mode, AppMode::SaveAs);
+        assert!(app.running);
+
+        // Cancelling Save As keeps the editor open
+        handle_text_input(&mut app, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE), TextInputPurpose::SaveAs);
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(app.running);
+        assert!(!app.quit_after_save);
+    }
+
     // --- Cycle 16: NewCanvas free-text input tests ---
 
     #[test]
@@ -1672,9 +2105,12 @@ mod tests {
         let orig_w = app.canvas.width;
         let orig_h = app.canvas.height;
 
-        // Resize
+        // Resize right after drawing
+        app.history.set_label_hint("pencil");
         do_resize(&mut app, 64, 48);
         assert_eq!(app.canvas.width, 64);
+        let label = app.history.timeline().pop().unwrap();
+        assert!(label.starts_with("resize "), "{}", label);
 
         // Undo should restore original size and content
         app.undo();
@@ -1694,6 +2130,43 @@ mod tests {
         assert_eq!(app.viewport_y, 0);
     }
 
+    #[test]
+    fn test_remapped_key_dispatches() {
+        let mut app = App::new();
+        app.keymap.rebind(KeyAction::CycleZoom, KeyCode::Char('m'), false);
+        let zoom = app.zoom;
+        handle_key(&mut app, KeyEvent::new(KeyCode::Char('z'), KeyModifiers::NONE));
+        assert_eq!(app.zoom, zoom, "old key no longer bound");
+        handle_key(&mut app, KeyEvent::new(KeyCode::Char('M'), KeyModifiers::SHIFT));
+        assert_ne!(app.zoom, zoom);
+    }
+
+    #[test]
+    fn test_help_scrolls_and_closes() {
+        let mut app = App::new();
+        app.help_scroll = 7;
+        app.help_max_scroll = 5;
+        handle_key(&mut app, KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE));
+        assert_eq!(app.mode, AppMode::Help);
+        assert_eq!(app.help_scroll, 0, "opening help starts at the top");
+
+        let press = |app: &mut App, code| {
+            handle_event(app, Event::Key(KeyEvent::new(code, KeyModifiers::NONE)), &area());
+        };
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.help_scroll, 1);
+        assert_eq!(app.mode, AppMode::Help);
+        // Overshooting stops at the end, so one Up moves straight back
+        press(&mut app, KeyCode::PageDown);
+        assert_eq!(app.help_scroll, 5);
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.help_scroll, 4);
+        press(&mut app, KeyCode::Char('x'));
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
     // --- Import browse tests ---
 
     #[test]
@@ -1771,6 +2244,46 @@ mod tests {
 
     // --- Import options tests ---
 
+    #[test]
+    fn test_export_dialog_png_and_html_formats() {
+        let mut app = App::new();
+        app.mode = AppMode::ExportDialog;
+        app.export_format = 0;
+        app.export_cursor = 0;
+
+        handle_export_dialog(&mut app, KeyCode::Right);
+        assert_eq!(app.export_format, 1);
+        handle_export_dialog(&mut app, KeyCode::Right);
+        assert_eq!(app.export_format, 2);
+        handle_export_dialog(&mut app, KeyCode::Right);
+        assert_eq!(app.export_format, 3);
+        handle_export_dialog(&mut app, KeyCode::Right);
+        assert_eq!(app.export_format, 0);
+        handle_export_dialog(&mut app, KeyCode::Left);
+        assert_eq!(app.export_format, 3);
+        handle_export_dialog(&mut app, KeyCode::Left);
+        assert_eq!(app.export_format, 2);
+
+        // PNG has only the format row, and Enter asks for a .png filename
+        handle_export_dialog(&mut app, KeyCode::Down);
+        assert_eq!(app.export_cursor, 0);
+        handle_export_dialog(&mut app, KeyCode::Enter);
+        assert_eq!(app.mode, AppMode::ExportFile);
+        assert!(app.text_input.ends_with(".png"));
+
+        let path = std::env::temp_dir().join(format!("kakukuma_export_{}.png", std::process::id()));
+        app.canvas.set(0, 0, crate::cell::Cell {
+            ch: crate::cell::blocks::FULL,
+            fg: Some(crate::cell::Rgb::new(255, 0, 0)),
+            bg: None,
+        });
+        app.export_to_file(path.to_str().unwrap());
+        let img = image::open(&path).unwrap().to_rgba8();
+        assert_eq!(img.get_pixel(0, 0).0, [255, 0, 0, 255]);
+        assert_eq!(img.get_pixel(8, 0).0[3], 0, "empty cells should be transparent");
+        let _ = std::fs::remove_file(&path);
+    }
+
     #[test]
     fn test_import_options_navigation() {
         let mut app = App::new();
@@ -1792,12 +2305,25 @@ mod tests {
         handle_import_options(&mut app, KeyCode::Down);
         assert_eq!(app.import_options_cursor, 5);
 
-        // Can't go past 5
         handle_import_options(&mut app, KeyCode::Down);
-        assert_eq!(app.import_options_cursor, 5);
+        assert_eq!(app.import_options_cursor, 6);
+
+        // Can't go past 6
+        handle_import_options(&mut app, KeyCode::Down);
+        assert_eq!(app.import_options_cursor, 6);
 
         handle_import_options(&mut app, KeyCode::Up);
-        assert_eq!(app.import_options_cursor, 4);
+        assert_eq!(app.import_options_cursor, 5);
+
+        // Dither row cycles Off -> Floyd-Steinberg -> Ordered -> Off
+        app.import_options_cursor = 6;
+        assert_eq!(app.import_dither, 0);
+        handle_import_options(&mut app, KeyCode::Right);
+        assert_eq!(app.import_dither, 1);
+        handle_import_options(&mut app, KeyCode::Right);
+        assert_eq!(app.import_dither, 2);
+        handle_import_options(&mut app, KeyCode::Right);
+        assert_eq!(app.import_dither, 0);
 
         // Navigate to color row and toggle through 3 modes
         app.import_options_cursor = 1;
@@ -0,0 +1,404 @@
+//! Compact binary project format (`.kakb`).
+//!
+//! Same content as the JSON `.kaku` format, for large canvases where the
+//! per-cell JSON objects dominate file size. Layout (little-endian):
+//!
+//! ```text
+//! "KAKB" format:u8  version:u32  name created_at modified_at  color:[u8;3]
+//! symmetry:u8  reference:(u8 flag, string)  [settings]  [transforms]
+//! width:u16 height:u16  runs...
+//! ```
+//!
+//! Settings (format 2+) are a presence flag, then `tool:(u8 flag, string)
+//! block:(u8 flag, u32) filled_rect:u8` with 0 = unset, 1 = false, 2 = true.
+//! Transforms (format 3+) are `count:u8` then per transform a kind byte
+//! (0 = invert, 1 = hue shift + i16 degrees, 2 = posterize + u8 levels).
+//!
+//! Strings are `u32` length + UTF-8. Cells are stored row-major as runs of
+//! identical cells: `count:u16 ch:u32 flags:u8 [fg:[u8;3]] [bg:[u8;3]]`,
+//! where flags bit 0 / bit 1 mark fg / bg as present.
+
+use crate::canvas::{Canvas, MAX_DIMENSION, MIN_DIMENSION};
+use crate::cell::{Cell, Rgb};
+use crate::export::ExportTransform;
+use crate::project::{Project, ProjectHeader, ProjectSettings};
+use crate::symmetry::SymmetryMode;
+
+/// Leading bytes of every `.kakb` file.
+pub const MAGIC: &[u8; 4] = b"KAKB";
+const FORMAT_VERSION: u8 = 3;
+/// Oldest format still readable (1 = no settings, 2 = no transforms).
+const MIN_FORMAT_VERSION: u8 = 1;
+
+const HAS_FG: u8 = 1;
+const HAS_BG: u8 = 2;
+
+/// Whether `data` starts with the `.kakb` magic bytes.
+pub fn is_binary(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+pub fn encode(project: &Project) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(MAGIC);
+    out.push(FORMAT_VERSION);
+    out.extend_from_slice(&project.version.to_le_bytes());
+    put_str(&mut out, &project.name);
+    put_str(&mut out, &project.created_at);
+    put_str(&mut out, &project.modified_at);
+    put_rgb(&mut out, project.color);
+    out.push(project.symmetry.bits());
+    match &project.reference_image {
+        Some(path) => {
+            out.push(1);
+            put_str(&mut out, path);
+        }
+        None => out.push(0),
+    }
+    match &project.settings {
+        Some(settings) => {
+            out.push(1);
+            put_settings(&mut out, settings);
+        }
+        None => out.push(0),
+    }
+    out.push(project.transforms.len().min(u8::MAX as usize) as u8);
+    for t in project.transforms.iter().take(u8::MAX as usize) {
+        put_transform(&mut out, t);
+    }
+
+    let canvas = &project.canvas;
+    out.extend_from_slice(&(canvas.width as u16).to_le_bytes());
+    out.extend_from_slice(&(canvas.height as u16).to_le_bytes());
+    let mut run: Option<(Cell, u16)> = None;
+    for y in 0..canvas.height {
+        for x in 0..canvas.width {
+            let cell = canvas.get(x, y).unwrap_or_default();
+            run = match run {
+                Some((prev, n)) if prev == cell && n < u16::MAX => Some((prev, n + 1)),
+                Some((prev, n)) => {
+                    put_run(&mut out, prev, n);
+                    Some((cell, 1))
+                }
+                None => Some((cell, 1)),
+            };
+        }
+    }
+    if let Some((prev, n)) = run {
+        put_run(&mut out, prev, n);
+    }
+    out
+}
+
+/// Header fields of a `.kakb` file, without decoding its cell runs.
+pub fn decode_header(data: &[u8]) -> Result<ProjectHeader, String> {
+    read_header(&mut Reader { data, pos: 0 }).map(|(header, _, _)| header)
+}
+
+pub fn decode(data: &[u8]) -> Result<Project, String> {
+    let mut r = Reader { data, pos: 0 };
+    let (header, settings, transforms) = read_header(&mut r)?;
+    let (width, height) = (header.width, header.height);
+    let total = width * height;
+    let mut cells = Vec::with_capacity(total);
+    while cells.len() < total {
+        let count = u16::from_le_bytes(r.array()?) as usize;
+        let ch = char::from_u32(u32::from_le_bytes(r.array()?))
+            .ok_or("Invalid character in cell data")?;
+        let flags = r.u8()?;
+        let fg = if flags & HAS_FG != 0 { Some(r.rgb()?) } else { None };
+        let bg = if flags & HAS_BG != 0 { Some(r.rgb()?) } else { None };
+        if count == 0 || cells.len() + count > total {
+            return Err("Corrupt cell run".to_string());
+        }
+        cells.extend(std::iter::repeat_n(Cell { ch, fg, bg }, count));
+    }
+    if r.pos != data.len() {
+        return Err("Trailing data after canvas".to_string());
+    }
+
+    let mut canvas = Canvas::new_with_size(width, height);
+    let rows = cells.chunks(width.max(1)).map(|row| row.to_vec()).collect();
+    canvas.replace(rows, width, height);
+
+    Ok(Project {
+        version: header.version,
+        name: header.name,
+        created_at: header.created_at,
+        modified_at: header.modified_at,
+        color: header.color,
+        symmetry: header.symmetry,
+        canvas,
+        reference_image: header.reference_image,
+        settings,
+        transforms,
+        thumbnail: None,
+    })
+}
+
+/// Everything before the cell runs, leaving `r` positioned at the first run.
+fn read_header(r: &mut Reader) -> Result<(ProjectHeader, Option<ProjectSettings>, Vec<ExportTransform>), String> {
+    if r.take(4)? != MAGIC {
+        return Err("Not a .kakb file".to_string());
+    }
+    let format = r.u8()?;
+    if !(MIN_FORMAT_VERSION..=FORMAT_VERSION).contains(&format) {
+        return Err(format!("Unsupported .kakb format {}", format));
+    }
+    let version = u32::from_le_bytes(r.array()?);
+    let name = r.string()?;
+    let created_at = r.string()?;
+    let modified_at = r.string()?;
+    let color = r.rgb()?;
+    let symmetry = SymmetryMode::from_bits_truncate(r.u8()?);
+    let reference_image = match r.u8()? {
+        0 => None,
+        _ => Some(r.string()?),
+    };
+    let settings = match format {
+        1 => None,
+        _ => match r.u8()? {
+            0 => None,
+            _ => Some(r.settings()?),
+        },
+    };
+    let transforms = match format {
+        1 | 2 => Vec::new(),
+        _ => {
+            let count = r.u8()?;
+            (0..count).map(|_| r.transform()).collect::<Result<_, _>>()?
+        }
+    };
+
+    let width = u16::from_le_bytes(r.array()?) as usize;
+    let height = u16::from_le_bytes(r.array()?) as usize;
+    // Checked before allocating so a corrupt header can't request billions of cells
+    let valid = MIN_DIMENSION..=MAX_DIMENSION;
+    if !valid.contains(&width) || !valid.contains(&height) {
+        return Err(format!("Invalid canvas size {}x{}", width, height));
+    }
+
+    let header = ProjectHeader {
+        version,
+        name,
+        created_at,
+        modified_at,
+        color,
+        symmetry,
+        reference_image,
+        width,
+        height,
+    };
+    Ok((header, settings, transforms))
+}
+
+fn put_settings(out: &mut Vec<u8>, s: &ProjectSettings) {
+    match &s.tool {
+        Some(tool) => {
+            out.push(1);
+            put_str(out, tool);
+        }
+        None => out.push(0),
+    }
+    match s.block {
+        Some(ch) => {
+            out.push(1);
+            out.extend_from_slice(&(ch as u32).to_le_bytes());
+        }
+        None => out.push(0),
+    }
+    out.push(match s.filled_rect {
+        None => 0,
+        Some(false) => 1,
+        Some(true) => 2,
+    });
+}
+
+fn put_transform(out: &mut Vec<u8>, t: &ExportTransform) {
+    match *t {
+        ExportTransform::Invert => out.push(0),
+        ExportTransform::HueShift(deg) => {
+            out.push(1);
+            out.extend_from_slice(&deg.to_le_bytes());
+        }
+        ExportTransform::Posterize(levels) => out.extend_from_slice(&[2, levels]),
+    }
+}
+
+fn put_str(out: &mut Vec<u8>, s: &str) {
+    out.extend_from_slice(&(s.len() as u32).to_le_bytes());
+    out.extend_from_slice(s.as_bytes());
+}
+
+fn put_rgb(out: &mut Vec<u8>, c: Rgb) {
+    out.extend_from_slice(&[c.r, c.g, c.b]);
+}
+
+fn put_run(out: &mut Vec<u8>, cell: Cell, count: u16) {
+    out.extend_from_slice(&count.to_le_bytes());
+    out.extend_from_slice(&(cell.ch as u32).to_le_bytes());
+    let flags = (if cell.fg.is_some() { HAS_FG } else { 0 }) | (if cell.bg.is_some() { HAS_BG } else { 0 });
+    out.push(flags);
+    if let Some(fg) = cell.fg {
+        put_rgb(out, fg);
+    }
+    if let Some(bg) = cell.bg {
+        put_rgb(out, bg);
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        let end = self.pos.checked_add(n).filter(|&end| end <= self.data.len());
+        let end = end.ok_or("Unexpected end of .kakb data")?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        Ok(self.take(N)?.try_into().expect("take returns N bytes"))
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn rgb(&mut self) -> Result<Rgb, String> {
+        let [r, g, b] = self.array()?;
+        Ok(Rgb::new(r, g, b))
+    }
+
+    fn settings(&mut self) -> Result<ProjectSettings, String> {
+        let tool = match self.u8()? {
+            0 => None,
+            _ => Some(self.string()?),
+        };
+        let block = match self.u8()? {
+            0 => None,
+            _ => Some(
+                char::from_u32(u32::from_le_bytes(self.array()?)).ok_or("Invalid block character in settings")?,
+            ),
+        };
+        let filled_rect = match self.u8()? {
+            0 => None,
+            1 => Some(false),
+            _ => Some(true),
+        };
+        Ok(ProjectSettings { tool, block, filled_rect })
+    }
+
+    fn transform(&mut self) -> Result<ExportTransform, String> {
+        match self.u8()? {
+            0 => Ok(ExportTransform::Invert),
+            1 => Ok(ExportTransform::HueShift(i16::from_le_bytes(self.array()?))),
+            2 => Ok(ExportTransform::Posterize(self.u8()?)),
+            kind => Err(format!("Unknown transform kind {}", kind)),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        let len = u32::from_le_bytes(self.array()?) as usize;
+        String::from_utf8(self.take(len)?.to_vec()).map_err(|_| "Invalid UTF-8 string".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::blocks;
+
+    fn sample() -> Project {
+        let mut canvas = Canvas::new_with_size(24, 10);
+        canvas.set(3, 2, Cell { ch: blocks::UPPER_HALF, fg: Some(Rgb::new(1, 2, 3)), bg: None });
+        canvas.set(4, 2, Cell { ch: blocks::FULL, fg: Some(Rgb::new(255, 0, 0)), bg: Some(Rgb::new(0, 0, 9)) });
+        canvas.set(23, 9, Cell { ch: ' ', fg: None, bg: Some(Rgb::new(7, 7, 7)) });
+        let mut project = Project::new("sample", canvas, Rgb::new(10, 20, 30), SymmetryMode::QUAD);
+        project.reference_image = Some("ref.png".to_string());
+        project.settings = Some(ProjectSettings {
+            tool: Some("Fill".to_string()),
+            block: Some(blocks::SHADE_MEDIUM),
+            filled_rect: Some(true),
+        });
+        project.transforms = vec![ExportTransform::HueShift(90), ExportTransform::Invert, ExportTransform::Posterize(4)];
+        project
+    }
+
+    #[test]
+    fn round_trip_matches_json() {
+        let project = sample();
+        let bytes = encode(&project);
+        assert!(is_binary(&bytes));
+        let back = decode(&bytes).unwrap();
+        assert_eq!(
+            serde_json::to_string(&back).unwrap(),
+            serde_json::to_string(&project).unwrap()
+        );
+    }
+
+    #[test]
+    fn header_decodes_without_cell_runs() {
+        let bytes = encode(&sample());
+        // Truncated cell runs would fail a full decode
+        assert!(decode(&bytes[..bytes.len() - 20]).is_err());
+        let header = decode_header(&bytes[..bytes.len() - 20]).unwrap();
+        assert_eq!((header.width, header.height), (24, 10));
+        assert_eq!(header.name, "sample");
+        assert_eq!(header.reference_image.as_deref(), Some("ref.png"));
+    }
+
+    #[test]
+    fn much_smaller_than_json_for_large_canvas() {
+        let project = Project::new("big", Canvas::new_with_size(128, 128), Rgb::WHITE, SymmetryMode::empty());
+        let binary = encode(&project).len();
+        let json = serde_json::to_string_pretty(&project).unwrap().len();
+        assert!(binary * 100 < json, "binary {} vs json {}", binary, json);
+    }
+
+    #[test]
+    fn reads_format_1_without_settings() {
+        let mut project = sample();
+        project.settings = None;
+        project.transforms.clear();
+        let mut bytes = encode(&project);
+        // Format 1 had no settings flag or transform count before the size
+        bytes[4] = 1;
+        let strings = 4 * 3 + "sample".len() + project.created_at.len() + project.modified_at.len();
+        let flag_at = 4 + 1 + 4 + strings + 3 + 1 + 1 + 4 + "ref.png".len();
+        assert_eq!(bytes.remove(flag_at), 0);
+        assert_eq!(bytes.remove(flag_at), 0);
+        let back = decode(&bytes).unwrap();
+        assert!(back.settings.is_none());
+        assert_eq!(back.reference_image.as_deref(), Some("ref.png"));
+    }
+
+    #[test]
+    fn truncated_data_is_an_error() {
+        let bytes = encode(&sample());
+        for len in [0, 3, 10, bytes.len() - 1] {
+            assert!(decode(&bytes[..len]).is_err(), "len {}", len);
+        }
+        assert!(!is_binary(b"{\"version\": 5}"));
+    }
+
+    #[test]
+    fn oversized_header_is_rejected_before_reading_cells() {
+        let mut project = sample();
+        project.reference_image = None;
+        project.settings = None;
+        project.transforms.clear();
+        let mut bytes = encode(&project);
+        let strings = 4 * 3 + "sample".len() + project.created_at.len() + project.modified_at.len();
+        let size_at = 4 + 1 + 4 + strings + 3 + 1 + 1 + 1 + 1;
+        assert_eq!(&bytes[size_at..size_at + 4], &[24, 0, 10, 0]);
+        bytes[size_at..size_at + 4].copy_from_slice(&[0xFF; 4]);
+        // No cell data at all: the size check must fail first
+        let err = decode(&bytes[..size_at + 4]).unwrap_err();
+        assert_eq!(err, "Invalid canvas size 65535x65535");
+    }
+}
@@ -0,0 +1,363 @@
+//! Normal-mode key bindings. The input handler dispatches through this table
+//! and the help overlay is generated from it, so the two can't drift apart.
+
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// Something a Normal-mode key can do.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum KeyAction {
+    Pencil,
+    Eraser,
+    Line,
+    Rectangle,
+    Ellipse,
+    Fill,
+    Eyedropper,
+    Select,
+    ToggleClip,
+    GrowSelection,
+    ShrinkSelection,
+    CopyOrQuit,
+    Paste,
+    FlipHorizontal,
+    FlipVertical,
+    RotateCanvas,
+    InvertColors,
+    ShiftMode,
+    RadialSymmetry,
+    AxisLeft,
+    AxisRight,
+    AxisUp,
+    AxisDown,
+    ToggleFilledRect,
+    CycleBlock,
+    BlockPicker,
+    CycleShade,
+    /// Draw at the canvas cursor, or open the command palette when it's off
+    DrawOrPalette,
+    Cancel,
+    QuickPick(usize),
+    PaletteUp,
+    PaletteDown,
+    PaletteLeft,
+    PaletteRight,
+    PaletteSelect,
+    /// Canvas cursor down when active, otherwise HSL sliders
+    DownOrSliders,
+    /// Canvas cursor left when active, otherwise add color to palette
+    LeftOrAddColor,
+    HexInput,
+    SwapColors,
+    TogglePaintChannel,
+    PaletteManager,
+    CursorUp,
+    CursorRight,
+    CursorStep,
+    SymmetryHorizontal,
+    SymmetryVertical,
+    CycleZoom,
+    ToggleMinimap,
+    FollowCursor,
+    IsolateColor,
+    RotateView,
+    HistoryTimeline,
+    Import,
+    CycleTheme,
+    Resize,
+    Save,
+    Open,
+    NewCanvas,
+    Export,
+    CommandPalette,
+    Undo,
+    Redo,
+    Help,
+    Quit,
+}
+
+/// Help overlay grouping.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HelpSection {
+    Tools,
+    Drawing,
+    Colors,
+    Canvas,
+    File,
+}
+
+impl HelpSection {
+    pub const ALL: [HelpSection; 5] = [
+        HelpSection::Tools,
+        HelpSection::Drawing,
+        HelpSection::Colors,
+        HelpSection::Canvas,
+        HelpSection::File,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            HelpSection::Tools => "Tools",
+            HelpSection::Drawing => "Drawing",
+            HelpSection::Colors => "Colors",
+            HelpSection::Canvas => "Canvas",
+            HelpSection::File => "File",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Binding {
+    pub code: KeyCode,
+    pub ctrl: bool,
+    pub action: KeyAction,
+    pub section: HelpSection,
+    pub label: &'static str,
+}
+
+const fn key(c: char, action: KeyAction, section: HelpSection, label: &'static str) -> Binding {
+    Binding { code: KeyCode::Char(c), ctrl: false, action, section, label }
+}
+
+const fn ctrl(c: char, action: KeyAction, section: HelpSection, label: &'static str) -> Binding {
+    Binding { code: KeyCode::Char(c), ctrl: true, action, section, label }
+}
+
+const fn special(code: KeyCode, action: KeyAction, section: HelpSection, label: &'static str) -> Binding {
+    Binding { code, ctrl: false, action, section, label }
+}
+
+use HelpSection::*;
+use KeyAction as A;
+
+/// Default bindings, in help display order. Letters are stored lowercase and
+/// match either case unless an uppercase binding exists (b vs B).
+pub const DEFAULT_BINDINGS: &[Binding] = &[
+    key('p', A::Pencil, Tools, "Pencil"),
+    key('e', A::Eraser, Tools, "Eraser"),
+    key('l', A::Line, Tools, "Line"),
+    key('r', A::Rectangle, Tools, "Rectangle"),
+    key('O', A::Ellipse, Tools, "Ellipse"),
+    key('f', A::Fill, Tools, "Fill"),
+    key('k', A::Eyedropper, Tools, "Eyedropper"),
+    key('m', A::Select, Tools, "Select region"),
+    key('M', A::ToggleClip, Tools, "Clip drawing to selection"),
+    key('+', A::GrowSelection, Tools, "Grow selection"),
+    key('-', A::ShrinkSelection, Tools, "Shrink selection"),
+    ctrl('c', A::CopyOrQuit, Tools, "Copy selection (none: quit)"),
+    ctrl('v', A::Paste, Tools, "Paste at cursor"),
+    key('t', A::ToggleFilledRect, Tools, "Rect fill/outline"),
+    key('b', A::CycleBlock, Drawing, "Cycle block"),
+    key('B', A::BlockPicker, Drawing, "Block picker"),
+    key('g', A::CycleShade, Drawing, "Cycle shade"),
+    key(' ', A::DrawOrPalette, Drawing, "Draw at cursor / palette"),
+    special(KeyCode::Esc, A::Cancel, Drawing, "Cancel / cursor off"),
+    key('1', A::QuickPick(0), Colors, "Quick color"),
+    key('2', A::QuickPick(1), Colors, "Quick color"),
+    key('3', A::QuickPick(2), Colors, "Quick color"),
+    key('4', A::QuickPick(3), Colors, "Quick color"),
+    key('5', A::QuickPick(4), Colors, "Quick color"),
+    key('6', A::QuickPick(5), Colors, "Quick color"),
+    key('7', A::QuickPick(6), Colors, "Quick color"),
+    key('8', A::QuickPick(7), Colors, "Quick color"),
+    key('9', A::QuickPick(8), Colors, "Quick color"),
+    key('0', A::QuickPick(9), Colors, "Quick color"),
+    special(KeyCode::Up, A::PaletteUp, Colors, "Browse palette"),
+    special(KeyCode::Down, A::PaletteDown, Colors, "Browse palette"),
+    special(KeyCode::Left, A::PaletteLeft, Colors, "Browse palette"),
+    special(KeyCode::Right, A::PaletteRight, Colors, "Browse palette"),
+    special(KeyCode::Enter, A::PaletteSelect, Colors, "Select / toggle section"),
+    key('s', A::DownOrSliders, Colors, "HSL sliders (cursor: down)"),
+    key('x', A::HexInput, Colors, "Hex color input"),
+    key('X', A::SwapColors, Colors, "Swap fg/bg colors"),
+    key('n', A::TogglePaintChannel, Colors, "Paint fg/bg channel"),
+    key('c', A::PaletteManager, Colors, "Palette manager"),
+    key('a', A::LeftOrAddColor, Colors, "Add to palette (cursor: left)"),
+    key('w', A::CursorUp, Canvas, "Cursor up"),
+    key('d', A::CursorRight, Canvas, "Cursor right"),
+    key('y', A::CursorStep, Canvas, "Cursor step 1/5/10"),
+    key('h', A::SymmetryHorizontal, Canvas, "Horizontal symmetry"),
+    key('v', A::SymmetryVertical, Canvas, "Vertical symmetry"),
+    key('<', A::AxisLeft, Canvas, "Move mirror axes"),
+    key('>', A::AxisRight, Canvas, "Move mirror axes"),
+    key('{', A::AxisUp, Canvas, "Move mirror axes"),
+    key('}', A::AxisDown, Canvas, "Move mirror axes"),
+    key('*', A::RadialSymmetry, Canvas, "Radial symmetry 3/4/6/8/off"),
+    key('|', A::FlipHorizontal, Canvas, "Flip canvas left-right"),
+    key('_', A::FlipVertical, Canvas, "Flip canvas top-bottom"),
+    key('z', A::CycleZoom, Canvas, "Cycle zoom"),
+    key('Z', A::ToggleMinimap, Canvas, "Minimap"),
+    ctrl('f', A::FollowCursor, Canvas, "Follow cursor (center view)"),
+    key('o', A::IsolateColor, Canvas, "Isolate color"),
+    key('j', A::RotateView, Canvas, "Rotate view 90\u{00B0}"),
+    key('[', A::ShiftMode, Canvas, "Shift drawing (arrows, Enter/Esc)"),
+    key(')', A::RotateCanvas, Canvas, "Rotate canvas 90\u{00B0} clockwise"),
+    key('~', A::InvertColors, Canvas, "Invert colors"),
+    key('u', A::HistoryTimeline, Canvas, "Undo history"),
+    key('i', A::Import, Canvas, "Import image"),
+    ctrl('t', A::CycleTheme, Canvas, "Cycle theme"),
+    ctrl('r', A::Resize, Canvas, "Resize canvas"),
+    ctrl('s', A::Save, File, "Save"),
+    ctrl('o', A::Open, File, "Open"),
+    ctrl('n', A::NewCanvas, File, "New canvas"),
+    ctrl('e', A::Export, File, "Export"),
+    ctrl('p', A::CommandPalette, File, "Command palette"),
+    ctrl('z', A::Undo, File, "Undo"),
+    ctrl('y', A::Redo, File, "Redo"),
+    key('?', A::Help, File, "Help"),
+    key('q', A::Quit, File, "Quit"),
+];
+
+pub struct Keymap {
+    bindings: Vec<Binding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Keymap { bindings: DEFAULT_BINDINGS.to_vec() }
+    }
+}
+
+impl Keymap {
+    pub fn bindings(&self) -> &[Binding] {
+        &self.bindings
+    }
+
+    /// Resolve a key press to an action. An exact match wins; otherwise an
+    /// uppercase letter falls back to its lowercase binding.
+    pub fn lookup(&self, code: KeyCode, modifiers: KeyModifiers) -> Option<KeyAction> {
+        let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+        let find = |code: KeyCode| {
+            self.bindings.iter().find(|b| b.code == code && b.ctrl == ctrl).map(|b| b.action)
+        };
+        find(code).or_else(|| match code {
+            KeyCode::Char(c) if c.is_ascii_uppercase() => find(KeyCode::Char(c.to_ascii_lowercase())),
+            _ => None,
+        })
+    }
+
+    /// Move every binding of `action` to a single new key, dropping whatever
+    /// that key was bound to before.
+    #[cfg(test)]
+    pub fn rebind(&mut self, action: KeyAction, code: KeyCode, ctrl: bool) {
+        let Some(pos) = self.bindings.iter().position(|b| b.action == action) else {
+            return;
+        };
+        let mut binding = self.bindings[pos];
+        binding.code = code;
+        binding.ctrl = ctrl;
+        self.bindings.retain(|b| b.action != action && !(b.code == code && b.ctrl == ctrl));
+        let pos = pos.min(self.bindings.len());
+        self.bindings.insert(pos, binding);
+    }
+
+    /// Help rows for a section: (keys, label). Consecutive bindings with the
+    /// same label share a row ("1-0", "↑/↓/←/→").
+    pub fn help_rows(&self, section: HelpSection) -> Vec<(String, &'static str)> {
+        let mut rows: Vec<(Vec<String>, &'static str)> = Vec::new();
+        for b in self.bindings.iter().filter(|b| b.section == section) {
+            let name = key_name(b.code, b.ctrl);
+            match rows.last_mut() {
+                Some((keys, label)) if *label == b.label => keys.push(name),
+                _ => rows.push((vec![name], b.label)),
+            }
+        }
+        rows.into_iter()
+            .map(|(keys, label)| {
+                let keys = if keys.len() > 4 {
+                    format!("{}-{}", keys[0], keys[keys.len() - 1])
+                } else {
+                    keys.join("/")
+                };
+                (keys, label)
+            })
+            .collect()
+    }
+}
+
+/// Display name for a key, e.g. "P", "Shift+B", "Ctrl+S", "Space".
+pub fn key_name(code: KeyCode, ctrl: bool) -> String {
+    let base = match code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) if c.is_ascii_uppercase() => format!("Shift+{}", c),
+        KeyCode::Char(c) => c.to_ascii_uppercase().to_string(),
+        KeyCode::Esc => "Esc".to_string(),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Up => "\u{2191}".to_string(),
+        KeyCode::Down => "\u{2193}".to_string(),
+        KeyCode::Left => "\u{2190}".to_string(),
+        KeyCode::Right => "\u{2192}".to_string(),
+        other => format!("{:?}", other),
+    };
+    if ctrl { format!("Ctrl+{}", base) } else { base }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_is_case_insensitive_for_letters() {
+        let km = Keymap::default();
+        assert_eq!(km.lookup(KeyCode::Char('p'), KeyModifiers::NONE), Some(KeyAction::Pencil));
+        assert_eq!(km.lookup(KeyCode::Char('P'), KeyModifiers::SHIFT), Some(KeyAction::Pencil));
+        // Explicit uppercase binding wins
+        assert_eq!(km.lookup(KeyCode::Char('B'), KeyModifiers::SHIFT), Some(KeyAction::BlockPicker));
+        assert_eq!(km.lookup(KeyCode::Char('b'), KeyModifiers::NONE), Some(KeyAction::CycleBlock));
+    }
+
+    #[test]
+    fn test_uppercase_never_reaches_canvas_wide_edits() {
+        // Caps lock must not turn a toggle into a destructive canvas operation
+        let km = Keymap::default();
+        for c in ['h', 'v', 'j', 'n', 'r'] {
+            assert_eq!(
+                km.lookup(KeyCode::Char(c.to_ascii_uppercase()), KeyModifiers::SHIFT),
+                km.lookup(KeyCode::Char(c), KeyModifiers::NONE),
+                "{}",
+                c
+            );
+        }
+    }
+
+    #[test]
+    fn test_lookup_ctrl_is_separate() {
+        let km = Keymap::default();
+        assert_eq!(km.lookup(KeyCode::Char('s'), KeyModifiers::CONTROL), Some(KeyAction::Save));
+        assert_eq!(km.lookup(KeyCode::Char('s'), KeyModifiers::NONE), Some(KeyAction::DownOrSliders));
+        assert_eq!(km.lookup(KeyCode::Char('i'), KeyModifiers::CONTROL), None);
+    }
+
+    #[test]
+    fn test_no_duplicate_keys() {
+        let km = Keymap::default();
+        for (i, a) in km.bindings().iter().enumerate() {
+            for b in &km.bindings()[i + 1..] {
+                assert!(
+                    !(a.code == b.code && a.ctrl == b.ctrl),
+                    "{} bound twice",
+                    key_name(a.code, a.ctrl)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_rebind_moves_key() {
+        let mut km = Keymap::default();
+        km.rebind(KeyAction::CycleZoom, KeyCode::Char('m'), false);
+        assert_eq!(km.lookup(KeyCode::Char('m'), KeyModifiers::NONE), Some(KeyAction::CycleZoom));
+        assert_eq!(km.lookup(KeyCode::Char('z'), KeyModifiers::NONE), None);
+        let canvas = km.help_rows(HelpSection::Canvas);
+        assert!(canvas.contains(&("M".to_string(), "Cycle zoom")));
+    }
+
+    #[test]
+    fn test_help_rows_merge_shared_labels() {
+        let km = Keymap::default();
+        let colors = km.help_rows(HelpSection::Colors);
+        assert_eq!(colors[0], ("1-0".to_string(), "Quick color"));
+        assert_eq!(colors[1].0, "\u{2191}/\u{2193}/\u{2190}/\u{2192}");
+        let file = km.help_rows(HelpSection::File);
+        assert!(file.contains(&("Q".to_string(), "Quit")));
+    }
+}
@@ -3,6 +3,7 @@ pub mod cell;
 pub mod export;
 pub mod history;
 pub mod import;
+pub mod kakb;
 pub mod oplog;
 pub mod palette;
 pub mod project;
@@ -4,6 +4,7 @@ pub use kakukuma::cell;
 pub use kakukuma::export;
 pub use kakukuma::history;
 pub use kakukuma::import;
+pub use kakukuma::kakb;
 pub use kakukuma::oplog;
 pub use kakukuma::palette;
 pub use kakukuma::project;
@@ -14,9 +15,11 @@ pub use kakukuma::tools;
 mod app;
 mod cli;
 mod input;
+mod keymap;
 mod ui;
 
 use std::io;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use crossterm::event::{self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture};
@@ -27,13 +30,33 @@ use crossterm::terminal::{
 use ratatui::Terminal;
 use ratatui::backend::CrosstermBackend;
 
-use app::App;
+use app::{App, MessageLevel, FAVORITE_TOOLS_ENV};
 use clap::Parser;
 use input::CanvasArea;
 
 fn main() -> io::Result<()> {
     let args = cli::Cli::parse();
 
+    // Every oplog call (draw, undo, redo, history, ...) reads these
+    if let Some(ref dir) = args.log_dir {
+        std::env::set_var(oplog::LOG_DIR_ENV, dir);
+    }
+    if let Some(n) = args.max_entries {
+        std::env::set_var(oplog::MAX_ENTRIES_ENV, n.to_string());
+    }
+    if let Some(secs) = args.coalesce_secs {
+        std::env::set_var(oplog::COALESCE_ENV, secs.to_string());
+    }
+    cli::set_output_style(if args.quiet {
+        cli::OutputStyle::Quiet
+    } else if args.json {
+        cli::OutputStyle::Compact
+    } else if args.pretty {
+        cli::OutputStyle::Pretty
+    } else {
+        cli::OutputStyle::Default
+    });
+
     match args.command {
         Some(cmd) => {
             // CLI path — no terminal initialization
@@ -41,12 +64,23 @@ fn main() -> io::Result<()> {
         }
         None => {
             // TUI path — existing behavior
-            run_tui(args.file)
+            let favorite_tools = args.favorite_tools.or_else(|| {
+                std::env::var(FAVORITE_TOOLS_ENV)
+                    .ok()
+                    .map(|list| list.split(',').map(str::to_string).collect())
+            });
+            run_tui(args.file, args.color_format, args.recent_colors, favorite_tools, args.debug_dump)
         }
     }
 }
 
-fn run_tui(file: Option<String>) -> io::Result<()> {
+fn run_tui(
+    file: Option<String>,
+    color_format: Option<cli::CliColorFormat>,
+    recent_colors: Option<u8>,
+    favorite_tools: Option<Vec<String>>,
+    debug_dump: Option<PathBuf>,
+) -> io::Result<()> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -62,7 +96,7 @@ fn run_tui(file: Option<String>) -> io::Result<()> {
         original_hook(panic_info);
     }));
 
-    let result = run(&mut terminal, file);
+    let result = run(&mut terminal, file, color_format, recent_colors, favorite_tools, debug_dump);
 
     // Restore terminal
     disable_raw_mode()?;
@@ -77,8 +111,32 @@ fn run_tui(file: Option<String>) -> io::Result<()> {
     result
 }
 
-fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, file: Option<String>) -> io::Result<()> {
+fn run(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    file: Option<String>,
+    color_format: Option<cli::CliColorFormat>,
+    recent_colors: Option<u8>,
+    favorite_tools: Option<Vec<String>>,
+    debug_dump: Option<PathBuf>,
+) -> io::Result<()> {
     let mut app = App::new();
+    app.debug_dump_path = debug_dump;
+    // An explicit --color-format always wins over detection
+    if let Some(ref f) = color_format {
+        app.color_format = export::resolve_color_format(cli::to_color_format(f));
+    }
+    if let Some(n) = recent_colors {
+        app.set_recent_capacity(n as usize);
+    }
+    if let Some(ref names) = favorite_tools {
+        let unknown = app.set_favorite_tools(names);
+        if !unknown.is_empty() {
+            app.set_status_with_level(
+                &format!("Ignoring unknown favorite tools: {}", unknown.join(", ")),
+                MessageLevel::Warning,
+            );
+        }
+    }
     let mut canvas_area = CanvasArea {
         left: 0,
         top: 0,
@@ -86,6 +144,7 @@ fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, file: Option<Strin
         height: 0,
         viewport_w: 0,
         viewport_h: 0,
+        palette_left: 0,
     };
 
     // Load file from command-line argument if provided
@@ -100,13 +159,32 @@ fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, file: Option<Strin
 
     while app.running {
         // Render
-        terminal.draw(|f| {
+        let frame = terminal.draw(|f| {
             canvas_area = ui::render(f, &app);
         })?;
 
+        // Dump exactly the frame now on screen, before anything else changes it
+        if app.debug_dump_pending {
+            app.debug_dump_pending = false;
+            if let Some(path) = app.debug_dump_path.clone() {
+                match std::fs::write(&path, ui::dump_buffer(frame.buffer)) {
+                    Ok(()) => app.set_status_with_level(
+                        &format!("Screen dumped to {}", path.display()),
+                        MessageLevel::Success,
+                    ),
+                    Err(e) => app.set_status_with_level(
+                        &format!("Screen dump failed: {}", e),
+                        MessageLevel::Error,
+                    ),
+                }
+            }
+        }
+
         // Store viewport dimensions for input handling
         app.viewport_w = canvas_area.viewport_w;
         app.viewport_h = canvas_area.viewport_h;
+        app.clamp_viewport();
+        app.help_max_scroll = ui::help_max_scroll(&app, frame.area.height);
 
         // Poll for events with timeout for status message ticking
         if event::poll(Duration::from_millis(100))? {
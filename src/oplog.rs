@@ -8,6 +8,21 @@ use crate::history::CellMutation;
 
 const MAX_LOG_ENTRIES: usize = 256;
 
+/// Range accepted for a configured entry limit.
+pub const MIN_MAX_ENTRIES: usize = 8;
+pub const MAX_MAX_ENTRIES: usize = 65536;
+
+/// Environment variable overriding how many entries a log keeps (default 256).
+pub const MAX_ENTRIES_ENV: &str = "KAKUKUMA_MAX_LOG_ENTRIES";
+
+/// Environment variable enabling coalescing: consecutive entries of the same
+/// command at most this many seconds apart are merged into one (off when unset).
+pub const COALESCE_ENV: &str = "KAKUKUMA_OPLOG_COALESCE_SECS";
+
+/// Environment variable naming a directory to hold all operation logs
+/// instead of writing `art.kaku.log` sidecars next to each project.
+pub const LOG_DIR_ENV: &str = "KAKUKUMA_LOG_DIR";
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct LogHeader {
     pub pointer: usize,
@@ -19,6 +34,27 @@ pub struct LogEntry {
     pub timestamp: String,
     pub command: String,
     pub mutations: Vec<LogMutation>,
+    /// Set when the operation changed the canvas size. Its mutations then
+    /// hold the cells a shrink cropped away, so undo can put them back.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resize: Option<LogResize>,
+}
+
+/// Canvas size before and after an operation.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LogResize {
+    pub old_width: usize,
+    pub old_height: usize,
+    pub new_width: usize,
+    pub new_height: usize,
+}
+
+impl LogResize {
+    /// Size covering both the old and new canvas. Replays grow to this
+    /// first so mutations on either side of the change land in bounds.
+    pub fn union(&self) -> (usize, usize) {
+        (self.old_width.max(self.new_width), self.old_height.max(self.new_height))
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -71,18 +107,139 @@ impl LogCell {
     }
 }
 
-/// Derive log path from .kaku path: "art.kaku" -> "art.kaku.log"
+/// Project path standing for stdin/stdout, which has no log.
+pub const STDIO: &str = "-";
+
+/// Derive log path from .kaku path: "art.kaku" -> "art.kaku.log", or a
+/// file in `$KAKUKUMA_LOG_DIR` when that is set (see [`log_path_in`]).
+/// A streamed project ([`STDIO`]) gets an empty path, which writes ignore.
 pub fn log_path(kaku_path: &Path) -> PathBuf {
-    let mut p = kaku_path.as_os_str().to_os_string();
-    p.push(".log");
-    PathBuf::from(p)
+    if kaku_path == Path::new(STDIO) {
+        return PathBuf::new();
+    }
+    let dir = std::env::var_os(LOG_DIR_ENV).filter(|d| !d.is_empty());
+    log_path_in(kaku_path, dir.as_deref().map(Path::new))
+}
+
+/// Log path for `kaku_path` inside `log_dir` (sidecar when None). The name
+/// keeps the project's file name for readability and adds a hash of its
+/// absolute path, so same-named projects in different folders don't collide.
+pub fn log_path_in(kaku_path: &Path, log_dir: Option<&Path>) -> PathBuf {
+    let Some(dir) = log_dir else {
+        let mut p = kaku_path.as_os_str().to_os_string();
+        p.push(".log");
+        return PathBuf::from(p);
+    };
+    let name = kaku_path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let hash = fnv1a(absolute_path(kaku_path).to_string_lossy().as_bytes());
+    dir.join(format!("{}.{:016x}.log", name, hash))
+}
+
+/// Absolute form of `path` without requiring the file itself to exist,
+/// so a project resolves to the same log before and after it's created.
+fn absolute_path(path: &Path) -> PathBuf {
+    let parent = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    let parent = parent.canonicalize().unwrap_or_else(|_| {
+        std::env::current_dir().map(|cwd| cwd.join(&parent)).unwrap_or(parent)
+    });
+    match path.file_name() {
+        Some(name) => parent.join(name),
+        None => parent,
+    }
+}
+
+/// 64-bit FNV-1a: stable across builds, unlike `DefaultHasher`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Entry limit for `append`: `$KAKUKUMA_MAX_LOG_ENTRIES` clamped to
+/// 8–65536, or 256 when unset or not a number.
+pub fn max_entries() -> usize {
+    std::env::var(MAX_ENTRIES_ENV)
+        .ok()
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .map_or(MAX_LOG_ENTRIES, |n| n.clamp(MIN_MAX_ENTRIES, MAX_MAX_ENTRIES))
+}
+
+/// Coalescing window for `append`: `$KAKUKUMA_OPLOG_COALESCE_SECS`, or None
+/// (every operation its own entry) when unset, zero, or not a number.
+pub fn coalesce_window() -> Option<u64> {
+    std::env::var(COALESCE_ENV)
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .filter(|&secs| secs > 0)
+}
+
+/// Seconds since the Unix epoch for a `YYYY-MM-DDTHH:MM:SSZ` timestamp.
+fn timestamp_secs(ts: &str) -> Option<u64> {
+    let fields: Vec<u64> = ts
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse().ok())
+        .collect::<Option<_>>()?;
+    let [year, month, day, hours, minutes, seconds] = fields[..] else {
+        return None;
+    };
+    // Inverse of `days_to_date` (days from civil, March-based years)
+    let y = if month <= 2 { year.checked_sub(1)? } else { year };
+    let (era, yoe) = (y / 400, y % 400);
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day.checked_sub(1)?;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = (era * 146097 + doe).checked_sub(719468)?;
+    Some(days * 86400 + hours * 3600 + minutes * 60 + seconds)
+}
+
+/// Whether `next` continues `last` closely enough to share one entry.
+fn continues(last: &LogEntry, next: &LogEntry, window: u64) -> bool {
+    if last.command != next.command || last.resize.is_some() || next.resize.is_some() {
+        return false;
+    }
+    match (timestamp_secs(&last.timestamp), timestamp_secs(&next.timestamp)) {
+        (Some(a), Some(b)) => b >= a && b - a <= window,
+        _ => false,
+    }
+}
+
+/// Fold `next` into `last`: per cell, the first `old` and the last `new`.
+/// `last` takes `next`'s timestamp, so a steady run keeps coalescing.
+fn merge_entries(last: &mut LogEntry, next: LogEntry) {
+    let to_cell_mutation = |m: &LogMutation| CellMutation {
+        x: m.x,
+        y: m.y,
+        old: m.old.to_cell(),
+        new: m.new.to_cell(),
+    };
+    let combined = last.mutations.iter().chain(&next.mutations).map(to_cell_mutation).collect();
+    last.mutations = crate::history::coalesce(combined)
+        .iter()
+        .map(LogMutation::from_cell_mutation)
+        .collect();
+    last.timestamp = next.timestamp;
+}
+
+/// Create the log's directory if it's missing (a fresh `$KAKUKUMA_LOG_DIR`).
+fn ensure_parent(path: &Path) -> io::Result<()> {
+    match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => std::fs::create_dir_all(dir),
+        _ => Ok(()),
+    }
 }
 
 /// Initialize an empty log file with a header line.
 pub fn init_log(path: &Path) -> io::Result<()> {
+    if path.as_os_str().is_empty() {
+        return Ok(());
+    }
     let header = LogHeader { pointer: 0, total: 0 };
     let line = serde_json::to_string(&header)
         .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    ensure_parent(path)?;
     std::fs::write(path, format!("{}\n", line))
 }
 
@@ -122,6 +279,7 @@ fn read_raw(path: &Path) -> io::Result<(LogHeader, Vec<LogEntry>)> {
 
 /// Write header and entries back to the log file.
 fn write_raw(path: &Path, header: &LogHeader, entries: &[LogEntry]) -> io::Result<()> {
+    ensure_parent(path)?;
     let mut file = std::fs::File::create(path)?;
     let header_json = serde_json::to_string(header)
         .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
@@ -135,19 +293,45 @@ fn write_raw(path: &Path, header: &LogHeader, entries: &[LogEntry]) -> io::Resul
 }
 
 /// Append an entry to the operation log.
-/// Truncates redo entries (entries after undo pointer) and prunes to MAX_LOG_ENTRIES.
+/// Truncates redo entries (entries after undo pointer) and prunes to [`max_entries`].
+/// Coalesces with the previous entry when [`coalesce_window`] is set.
 pub fn append(path: &Path, entry: LogEntry) -> io::Result<()> {
+    append_coalescing(path, entry, max_entries(), coalesce_window())
+}
+
+/// [`append`] with an explicit entry limit. A limit below the log's current
+/// size drops the oldest entries, so undo reaches back `limit` operations.
+pub fn append_with_limit(path: &Path, entry: LogEntry, limit: usize) -> io::Result<()> {
+    append_coalescing(path, entry, limit, None)
+}
+
+/// [`append_with_limit`] that merges `entry` into the last one when both run
+/// the same command at most `window` seconds apart and nothing was undone in
+/// between. A merge that nets out to no change removes the entry.
+pub fn append_coalescing(path: &Path, entry: LogEntry, limit: usize, window: Option<u64>) -> io::Result<()> {
+    if path.as_os_str().is_empty() {
+        return Ok(());
+    }
+    let limit = limit.max(1);
     let (header, mut entries) = read_raw(path)?;
+    let nothing_undone = header.pointer == entries.len();
 
     // Truncate undone entries (everything after pointer)
     entries.truncate(header.pointer);
 
-    // Append new entry
-    entries.push(entry);
+    match (window, entries.last_mut()) {
+        (Some(window), Some(last)) if nothing_undone && continues(last, &entry, window) => {
+            merge_entries(last, entry);
+            if last.mutations.is_empty() {
+                entries.pop();
+            }
+        }
+        _ => entries.push(entry),
+    }
 
     // Prune to max
-    if entries.len() > MAX_LOG_ENTRIES {
-        let excess = entries.len() - MAX_LOG_ENTRIES;
+    if entries.len() > limit {
+        let excess = entries.len() - limit;
         entries.drain(0..excess);
     }
 
@@ -165,6 +349,22 @@ pub fn active_entries(path: &Path) -> io::Result<Vec<LogEntry>> {
     Ok(entries.into_iter().take(header.pointer).collect())
 }
 
+/// Read only the header line. None if the log doesn't exist or is empty.
+/// Cheap regardless of how many entries the log holds.
+pub fn read_header(path: &Path) -> io::Result<Option<LogHeader>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let file = std::fs::File::open(path)?;
+    let mut line = String::new();
+    if io::BufReader::new(file).read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    serde_json::from_str(&line)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Log header corrupt: {}", e)))
+}
+
 /// Read all entries (active + undone) with header.
 pub fn read_log(path: &Path) -> io::Result<(LogHeader, Vec<LogEntry>)> {
     read_raw(path)
@@ -224,6 +424,7 @@ pub fn make_entry(command: &str, mutations: &[CellMutation]) -> LogEntry {
         timestamp: crate::project::now_iso8601(),
         command: command.to_string(),
         mutations: mutations.iter().map(LogMutation::from_cell_mutation).collect(),
+        resize: None,
     }
 }
 
@@ -259,6 +460,55 @@ mod tests {
         make_entry(cmd, &[make_mutation(x, y)])
     }
 
+    #[test]
+    fn test_entries_without_resize_still_parse() {
+        let line = r#"{"timestamp":"2024-01-01T00:00:00Z","command":"pencil","mutations":[]}"#;
+        let entry: LogEntry = serde_json::from_str(line).unwrap();
+        assert!(entry.resize.is_none());
+        assert!(!serde_json::to_string(&entry).unwrap().contains("resize"));
+    }
+
+    #[test]
+    fn test_timestamp_secs_inverts_date() {
+        assert_eq!(timestamp_secs("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(timestamp_secs("2024-03-01T12:30:05Z"), Some(1709296205));
+        assert_eq!(timestamp_secs("2000-02-29T00:00:00Z"), Some(951782400));
+        assert_eq!(timestamp_secs("garbage"), None);
+        let now = crate::project::now_iso8601();
+        assert_eq!(timestamp_secs(&now).map(|_| ()), Some(()));
+    }
+
+    #[test]
+    fn test_coalescing_merges_same_command_runs() {
+        let path = test_log_path();
+        init_log(&path).unwrap();
+        let red = Cell { ch: blocks::FULL, fg: Some(Rgb::new(255, 0, 0)), bg: None };
+        let blue = Cell { fg: Some(Rgb::new(0, 0, 255)), ..red };
+        let at = |ts: &str, cmd: &str, muts: &[CellMutation]| LogEntry { timestamp: ts.to_string(), ..make_entry(cmd, muts) };
+        let paint = |x, old, new| CellMutation { x, y: 0, old, new };
+
+        append_coalescing(&path, at("2024-01-01T00:00:00Z", "pencil", &[paint(0, Cell::default(), red)]), 256, Some(2)).unwrap();
+        append_coalescing(&path, at("2024-01-01T00:00:01Z", "pencil", &[paint(0, red, blue), paint(1, Cell::default(), red)]), 256, Some(2)).unwrap();
+        // Different command, then too late: separate entries
+        append_coalescing(&path, at("2024-01-01T00:00:02Z", "fill", &[paint(2, Cell::default(), red)]), 256, Some(2)).unwrap();
+        append_coalescing(&path, at("2024-01-01T00:00:09Z", "fill", &[paint(3, Cell::default(), red)]), 256, Some(2)).unwrap();
+
+        let (header, entries) = read_log(&path).unwrap();
+        assert_eq!((header.pointer, entries.len()), (3, 3));
+        let merged = &entries[0];
+        assert_eq!(merged.mutations.len(), 2);
+        assert_eq!(merged.mutations[0].old.to_cell(), Cell::default());
+        assert_eq!(merged.mutations[0].new.to_cell(), blue);
+        assert_eq!(merged.timestamp, "2024-01-01T00:00:01Z");
+
+        // Undoing the merged entry reverses every cell it touched
+        let undone = pop_for_undo(&path, 3).unwrap();
+        let first = undone.iter().find(|e| e.command == "pencil").unwrap();
+        assert!(first.mutations.iter().all(|m| m.old.to_cell() == Cell::default()));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
     #[test]
     fn test_log_path_derivation() {
         let p = log_path(Path::new("art.kaku"));
@@ -268,6 +518,29 @@ mod tests {
         assert_eq!(p, PathBuf::from("/tmp/my art.kaku.log"));
     }
 
+    #[test]
+    fn test_log_path_in_dir() {
+        let dir = Path::new("/var/logs");
+        assert_eq!(log_path_in(Path::new("art.kaku"), None), PathBuf::from("art.kaku.log"));
+
+        let a = log_path_in(Path::new("/srv/a/art.kaku"), Some(dir));
+        let b = log_path_in(Path::new("/srv/b/art.kaku"), Some(dir));
+        assert_eq!(a.parent(), Some(dir));
+        assert!(a.file_name().unwrap().to_string_lossy().starts_with("art.kaku."));
+        assert_ne!(a, b, "same name in different folders must not collide");
+        assert_eq!(a, log_path_in(Path::new("/srv/a/art.kaku"), Some(dir)));
+    }
+
+    #[test]
+    fn test_log_dir_created_on_write() {
+        let dir = std::env::temp_dir().join(format!("kaku_oplog_dir_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = log_path_in(Path::new("art.kaku"), Some(&dir));
+        append(&path, make_entry_helper("a", 1, 1)).unwrap();
+        assert_eq!(read_header(&path).unwrap().unwrap().pointer, 1);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn test_init_and_read_empty() {
         let path = test_log_path();
@@ -281,6 +554,21 @@ mod tests {
         let _ = std::fs::remove_file(&path);
     }
 
+    #[test]
+    fn test_read_header_only() {
+        let path = test_log_path();
+        assert!(read_header(&path).unwrap().is_none());
+
+        init_log(&path).unwrap();
+        append(&path, make_entry_helper("a", 1, 1)).unwrap();
+        append(&path, make_entry_helper("b", 2, 2)).unwrap();
+        pop_for_undo(&path, 1).unwrap();
+        let header = read_header(&path).unwrap().unwrap();
+        assert_eq!((header.pointer, header.total), (1, 2));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
     #[test]
     fn test_append_and_read() {
         let path = test_log_path();
@@ -389,6 +677,29 @@ mod tests {
         let _ = std::fs::remove_file(&path);
     }
 
+    #[test]
+    fn test_lower_limit_prunes_existing_log() {
+        let path = test_log_path();
+        init_log(&path).unwrap();
+        for i in 0..40 {
+            append_with_limit(&path, make_entry_helper(&format!("cmd {}", i), i % 48, 0), 100).unwrap();
+        }
+
+        append_with_limit(&path, make_entry_helper("cmd 40", 0, 1), 10).unwrap();
+        let (header, entries) = read_log(&path).unwrap();
+        assert_eq!((header.pointer, header.total), (10, 10));
+        assert_eq!(entries[0].command, "cmd 31");
+        assert_eq!(entries[9].command, "cmd 40");
+
+        // Undo still covers the whole retained window, then stops
+        let undone = pop_for_undo(&path, 20).unwrap();
+        assert_eq!(undone.len(), 10);
+        assert_eq!(undone[0].command, "cmd 31");
+        assert!(pop_for_undo(&path, 1).is_err());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
     #[test]
     fn test_undo_on_empty_log() {
         let path = test_log_path();
@@ -90,6 +90,130 @@ pub fn save_palette(palette: &CustomPalette, path: &Path) -> Result<(), String>
     std::fs::write(path, json).map_err(|e| format!("Write error: {}", e))
 }
 
+/// Plain color list: one `#RRGGBB` per line.
+pub fn to_hex_list(colors: &[Rgb]) -> String {
+    colors.iter().map(|c| format!("{}\n", c.name())).collect()
+}
+
+/// Parse a plain color list (the inverse of `to_hex_list`). Blank lines are
+/// skipped; each other line may be any form `parse_hex_color` accepts.
+pub fn parse_hex_list(text: &str) -> Result<Vec<Rgb>, String> {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| (i, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| {
+            crate::cell::parse_hex_color(line)
+                .ok_or_else(|| format!("Line {}: invalid color '{}'", i + 1, line))
+        })
+        .collect()
+}
+
+/// GIMP palette (`.gpl`): a `GIMP Palette` header, then one `R G B<TAB>name`
+/// line per color, named by its hex code.
+pub fn to_gpl(name: &str, colors: &[Rgb]) -> String {
+    let mut out = format!("GIMP Palette\nName: {}\nColumns: 0\n#\n", name);
+    for c in colors {
+        out.push_str(&format!("{:3} {:3} {:3}\t{}\n", c.r, c.g, c.b, c.name()));
+    }
+    out
+}
+
+/// Parse a GIMP palette (the inverse of `to_gpl`). Header fields, comments
+/// and blank lines are skipped; color names are ignored.
+#[cfg(test)]
+pub fn parse_gpl(text: &str) -> Result<Vec<Rgb>, String> {
+    let mut lines = text.lines().enumerate();
+    match lines.next() {
+        Some((_, header)) if header.trim() == "GIMP Palette" => {}
+        _ => return Err("Missing 'GIMP Palette' header".to_string()),
+    }
+    lines
+        .map(|(i, line)| (i, line.trim()))
+        .filter(|(_, line)| {
+            !(line.is_empty() || line.starts_with('#') || line.starts_with("Name:") || line.starts_with("Columns:"))
+        })
+        .map(|(i, line)| {
+            let channel = |s: Option<&str>| s.and_then(|s| s.parse::<u8>().ok());
+            let mut parts = line.split_whitespace();
+            match (channel(parts.next()), channel(parts.next()), channel(parts.next())) {
+                (Some(r), Some(g), Some(b)) => Ok(Rgb::new(r, g, b)),
+                _ => Err(format!("Line {}: expected 'R G B [name]', got '{}'", i + 1, line)),
+            }
+        })
+        .collect()
+}
+
+/// Adobe Swatch Exchange (`.ase`), version 1.0: the `ASEF` signature, then
+/// one color-entry block per color. Each block holds a UTF-16BE name (the
+/// hex code, NUL-terminated), the `RGB ` model and three big-endian f32
+/// channels in 0.0-1.0, all big-endian.
+pub fn to_ase(colors: &[Rgb]) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(b"ASEF");
+    out.extend_from_slice(&1u16.to_be_bytes());
+    out.extend_from_slice(&0u16.to_be_bytes());
+    out.extend_from_slice(&(colors.len() as u32).to_be_bytes());
+    for c in colors {
+        let name: Vec<u16> = c.name().encode_utf16().chain(std::iter::once(0)).collect();
+        let mut block = Vec::new();
+        block.extend_from_slice(&(name.len() as u16).to_be_bytes());
+        for unit in &name {
+            block.extend_from_slice(&unit.to_be_bytes());
+        }
+        block.extend_from_slice(b"RGB ");
+        for v in [c.r, c.g, c.b] {
+            block.extend_from_slice(&(v as f32 / 255.0).to_be_bytes());
+        }
+        block.extend_from_slice(&2u16.to_be_bytes()); // Color type: normal
+        out.extend_from_slice(&0x0001u16.to_be_bytes()); // Block type: color entry
+        out.extend_from_slice(&(block.len() as u32).to_be_bytes());
+        out.extend_from_slice(&block);
+    }
+    out
+}
+
+/// Colors split by which of two palettes contains them.
+#[derive(Debug, Default, PartialEq)]
+pub struct PaletteDiff {
+    pub only_a: Vec<Rgb>,
+    pub only_b: Vec<Rgb>,
+    pub both: Vec<Rgb>,
+}
+
+/// Compare two color lists by exact RGB. Each set keeps first-seen order and
+/// lists a color once even if a palette repeats it.
+pub fn diff_palettes(a: &[Rgb], b: &[Rgb]) -> PaletteDiff {
+    use std::collections::HashSet;
+    let key = |c: &Rgb| (c.r, c.g, c.b);
+    let in_a: HashSet<(u8, u8, u8)> = a.iter().map(key).collect();
+    let in_b: HashSet<(u8, u8, u8)> = b.iter().map(key).collect();
+    let mut seen = HashSet::new();
+    let mut diff = PaletteDiff::default();
+    for &c in a.iter().chain(b) {
+        if !seen.insert(key(&c)) {
+            continue;
+        }
+        match (in_a.contains(&key(&c)), in_b.contains(&key(&c))) {
+            (true, true) => diff.both.push(c),
+            (true, false) => diff.only_a.push(c),
+            _ => diff.only_b.push(c),
+        }
+    }
+    diff
+}
+
+/// Union several color lists, keeping the first occurrence of each color.
+pub fn merge_palettes(lists: &[Vec<Rgb>]) -> Vec<Rgb> {
+    let mut seen = std::collections::HashSet::new();
+    lists
+        .iter()
+        .flatten()
+        .filter(|c| seen.insert((c.r, c.g, c.b)))
+        .copied()
+        .collect()
+}
+
 pub struct HueGroup {
     #[allow(dead_code)] // Used in tests; may be displayed in expanded sections later
     pub name: &'static str,
@@ -249,6 +373,19 @@ pub fn nearest_color(r: u8, g: u8, b: u8) -> Rgb {
     color256_to_rgb(idx)
 }
 
+/// The color in `colors` closest to `color`, with its Euclidean RGB distance.
+/// None when `colors` is empty.
+pub fn nearest_in(colors: &[Rgb], color: Rgb) -> Option<(Rgb, f64)> {
+    let distance = |c: &Rgb| {
+        let d = |a: u8, b: u8| (a as f64 - b as f64).powi(2);
+        (d(c.r, color.r) + d(c.g, color.g) + d(c.b, color.b)).sqrt()
+    };
+    colors
+        .iter()
+        .map(|c| (*c, distance(c)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -263,6 +400,71 @@ mod tests {
         assert_eq!(DEFAULT_PALETTE.len(), 24);
     }
 
+    #[test]
+    fn test_hex_list_round_trips() {
+        let text = to_hex_list(&DEFAULT_PALETTE);
+        assert_eq!(text.lines().count(), DEFAULT_PALETTE.len());
+        assert_eq!(parse_hex_list(&text).unwrap(), DEFAULT_PALETTE.to_vec());
+
+        assert_eq!(parse_hex_list("\n#f00\n  #00FF00 \n").unwrap(), vec![Rgb::new(255, 0, 0), Rgb::new(0, 255, 0)]);
+        assert!(parse_hex_list("#FF0000\nnot a color\n").unwrap_err().contains("Line 2"));
+    }
+
+    #[test]
+    fn test_gpl_round_trips() {
+        let text = to_gpl("mine", &DEFAULT_PALETTE);
+        assert!(text.starts_with("GIMP Palette\nName: mine\n"));
+        assert_eq!(parse_gpl(&text).unwrap(), DEFAULT_PALETTE.to_vec());
+
+        let gimp = "GIMP Palette\nName: x\n# comment\n\n255   0   0\tRed\n  0 128 255 Azure\n";
+        assert_eq!(parse_gpl(gimp).unwrap(), vec![Rgb::new(255, 0, 0), Rgb::new(0, 128, 255)]);
+        assert!(parse_gpl("#FF0000\n").is_err());
+        assert!(parse_gpl("GIMP Palette\n300 0 0\n").unwrap_err().contains("Line 2"));
+    }
+
+    #[test]
+    fn test_ase_layout() {
+        let ase = to_ase(&[Rgb::new(255, 0, 51)]);
+        assert_eq!(&ase[0..12], b"ASEF\x00\x01\x00\x00\x00\x00\x00\x01");
+        // Block type and length: 2 + 8 * 2 name + 4 model + 12 channels + 2 type
+        assert_eq!(&ase[12..14], &[0x00, 0x01]);
+        assert_eq!(u32::from_be_bytes(ase[14..18].try_into().unwrap()), 36);
+        assert_eq!(ase.len(), 18 + 36);
+        // Name "#FF0033" as UTF-16BE with its NUL, counted in code units
+        assert_eq!(&ase[18..20], &[0x00, 0x08]);
+        assert_eq!(&ase[20..24], &[0x00, b'#', 0x00, b'F']);
+        assert_eq!(&ase[34..36], &[0x00, 0x00]);
+        assert_eq!(&ase[36..40], b"RGB ");
+        let channel = |i: usize| f32::from_be_bytes(ase[40 + i * 4..44 + i * 4].try_into().unwrap());
+        assert_eq!((channel(0), channel(1), channel(2)), (1.0, 0.0, 0.2));
+        assert_eq!(&ase[52..54], &[0x00, 0x02]);
+    }
+
+    #[test]
+    fn test_diff_palettes_splits_sets() {
+        let red = Rgb::new(255, 0, 0);
+        let green = Rgb::new(0, 255, 0);
+        let blue = Rgb::new(0, 0, 255);
+        let diff = diff_palettes(&[red, green, red], &[blue, green]);
+        assert_eq!(diff.only_a, vec![red]);
+        assert_eq!(diff.only_b, vec![blue]);
+        assert_eq!(diff.both, vec![green]);
+
+        let same = diff_palettes(&DEFAULT_PALETTE, &DEFAULT_PALETTE);
+        assert!(same.only_a.is_empty() && same.only_b.is_empty());
+        assert_eq!(same.both.len(), DEFAULT_PALETTE.len());
+    }
+
+    #[test]
+    fn test_merge_palettes_first_occurrence_wins() {
+        let red = Rgb::new(255, 0, 0);
+        let green = Rgb::new(0, 255, 0);
+        let blue = Rgb::new(0, 0, 255);
+        let merged = merge_palettes(&[vec![green, red, green], vec![blue, red]]);
+        assert_eq!(merged, vec![green, red, blue]);
+        assert!(merge_palettes(&[]).is_empty());
+    }
+
     #[test]
     fn test_all_216_covered() {
         let groups = build_hue_groups();
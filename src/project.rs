@@ -2,8 +2,49 @@ use serde::{Deserialize, Serialize};
 
 use crate::canvas::Canvas;
 use crate::cell::Rgb;
+use crate::export::ExportTransform;
 use crate::symmetry::SymmetryMode;
 
+/// On-disk encoding of a project. JSON stays the default for inspectability.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ProjectFormat {
+    /// Pretty-printed JSON (`.kaku`)
+    Json,
+    /// Compact binary (`.kakb`), see [`crate::kakb`]
+    Binary,
+}
+
+impl ProjectFormat {
+    /// Pick the format for a path by extension: `.kakb` is binary, anything
+    /// else is JSON.
+    pub fn for_path(path: &std::path::Path) -> Self {
+        if path.extension().and_then(|e| e.to_str()) == Some("kakb") {
+            ProjectFormat::Binary
+        } else {
+            ProjectFormat::Json
+        }
+    }
+}
+
+/// Editor state saved with a project so reopening it restores the working
+/// setup. Every field is optional; the CLI carries the block through
+/// unchanged.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ProjectSettings {
+    /// Active tool name, as shown in the toolbar ("Pencil", "Rect", ...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub tool: Option<String>,
+    /// Active block character
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub block: Option<char>,
+    /// Rectangle tool draws filled rather than outlined
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub filled_rect: Option<bool>,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Project {
     pub version: u32,
@@ -16,6 +57,193 @@ pub struct Project {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub reference_image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub settings: Option<ProjectSettings>,
+    /// Color adjustments applied, in order, to exported and previewed copies
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default)]
+    pub transforms: Vec<ExportTransform>,
+    /// Preview regenerated on every JSON save; see [`Thumbnail`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub thumbnail: Option<Thumbnail>,
+}
+
+/// Summary fields of a project, read without building its canvas.
+pub struct ProjectHeader {
+    pub version: u32,
+    pub name: String,
+    pub created_at: String,
+    pub modified_at: String,
+    pub color: Rgb,
+    pub symmetry: SymmetryMode,
+    pub reference_image: Option<String>,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl ProjectHeader {
+    /// Parse the header of project data in either format. JSON cell data is
+    /// skipped over rather than deserialized; binary files stop before the
+    /// cell runs.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, String> {
+        #[derive(Deserialize)]
+        struct Header {
+            version: u32,
+            name: String,
+            created_at: String,
+            modified_at: String,
+            color: Rgb,
+            symmetry: SymmetryMode,
+            #[serde(default)]
+            reference_image: Option<String>,
+            canvas: CanvasSize,
+        }
+        #[derive(Deserialize)]
+        struct CanvasSize {
+            #[serde(default = "default_width")]
+            width: usize,
+            #[serde(default = "default_height")]
+            height: usize,
+        }
+        fn default_width() -> usize {
+            crate::canvas::DEFAULT_WIDTH
+        }
+        fn default_height() -> usize {
+            crate::canvas::DEFAULT_HEIGHT
+        }
+
+        let header = if crate::kakb::is_binary(data) {
+            crate::kakb::decode_header(data).map_err(|e| format!("Parse error: {}", e))?
+        } else {
+            let h: Header = serde_json::from_slice(data).map_err(|e| format!("Parse error: {}", e))?;
+            ProjectHeader {
+                version: h.version,
+                name: h.name,
+                created_at: h.created_at,
+                modified_at: h.modified_at,
+                color: h.color,
+                symmetry: h.symmetry,
+                reference_image: h.reference_image,
+                width: h.canvas.width,
+                height: h.canvas.height,
+            }
+        };
+        check_version(header.version)?;
+        Ok(header)
+    }
+}
+
+/// Reject files written by a newer kakukuma.
+fn check_version(version: u32) -> Result<(), String> {
+    // Accept v1 (legacy 16-color), v2 (256-color), v3 (dynamic canvas), v4 (generic char), v5 (RGB), v6 (reference)
+    if version > 6 {
+        return Err(format!("File version {} is newer than supported (v6)", version));
+    }
+    Ok(())
+}
+
+/// Longest thumbnail edge in pixels, so previews stay small on disk.
+pub const THUMBNAIL_MAX: usize = 16;
+
+/// Downsampled color grid of the canvas, cached in `.kaku` files so file
+/// pickers can show previews without rebuilding every canvas.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Thumbnail {
+    pub width: usize,
+    pub height: usize,
+    /// Canvas size and save time it was made from; a mismatch means stale.
+    pub canvas_width: usize,
+    pub canvas_height: usize,
+    pub modified_at: String,
+    /// Row-major `RRGGBB` per pixel, `------` where the area is empty.
+    pub pixels: String,
+}
+
+impl Thumbnail {
+    /// Average the visible color of each block of cells. Cells without a
+    /// color don't count; a block with none is left empty.
+    pub fn from_canvas(canvas: &Canvas, modified_at: &str) -> Self {
+        let scale = canvas.width.max(canvas.height).div_ceil(THUMBNAIL_MAX).max(1);
+        let width = canvas.width.div_ceil(scale);
+        let height = canvas.height.div_ceil(scale);
+        let mut pixels = String::with_capacity(width * height * 6);
+        for ty in 0..height {
+            for tx in 0..width {
+                let (mut sum, mut n) = ([0u32; 3], 0u32);
+                for y in ty * scale..((ty + 1) * scale).min(canvas.height) {
+                    for x in tx * scale..((tx + 1) * scale).min(canvas.width) {
+                        let cell = canvas.get(x, y).unwrap_or_default();
+                        let visible = if cell.ch == ' ' { cell.bg } else { cell.fg.or(cell.bg) };
+                        if let Some(c) = visible {
+                            sum[0] += c.r as u32;
+                            sum[1] += c.g as u32;
+                            sum[2] += c.b as u32;
+                            n += 1;
+                        }
+                    }
+                }
+                if n == 0 {
+                    pixels.push_str("------");
+                } else {
+                    let [r, g, b] = sum.map(|s| (s / n) as u8);
+                    pixels.push_str(&format!("{:02X}{:02X}{:02X}", r, g, b));
+                }
+            }
+        }
+        Thumbnail {
+            width,
+            height,
+            canvas_width: canvas.width,
+            canvas_height: canvas.height,
+            modified_at: modified_at.to_string(),
+            pixels,
+        }
+    }
+
+    /// Color at (x, y), or None if the pixel is empty or out of range.
+    pub fn pixel(&self, x: usize, y: usize) -> Option<Rgb> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let i = (y * self.width + x) * 6;
+        let hex = self.pixels.get(i..i + 6)?;
+        crate::cell::parse_hex_color(&format!("#{}", hex))
+    }
+
+    /// Whether this thumbnail was made by the save that produced `project`.
+    pub fn is_fresh_for(&self, project: &Project) -> bool {
+        self.is_fresh(&project.modified_at, project.canvas.width, project.canvas.height)
+    }
+
+    /// Whether this thumbnail matches a save stamped `modified_at` with a
+    /// `width`x`height` canvas, and its pixel data is complete.
+    fn is_fresh(&self, modified_at: &str, width: usize, height: usize) -> bool {
+        self.modified_at == modified_at
+            && (self.canvas_width, self.canvas_height) == (width, height)
+            && self.pixels.len() == self.width * self.height * 6
+    }
+}
+
+/// Read the cached thumbnail of a `.kaku` file. The whole file is still read
+/// and parsed, but the cell data is skipped instead of building a `Canvas`.
+/// Returns None for binary files, files without one, or stale ones.
+pub fn read_thumbnail(path: &std::path::Path) -> Option<Thumbnail> {
+    #[derive(Deserialize)]
+    struct Header {
+        modified_at: String,
+        canvas: CanvasSize,
+        thumbnail: Option<Thumbnail>,
+    }
+    #[derive(Deserialize)]
+    struct CanvasSize {
+        width: usize,
+        height: usize,
+    }
+    let text = std::fs::read_to_string(path).ok()?;
+    let header: Header = serde_json::from_str(&text).ok()?;
+    header.thumbnail.filter(|t| t.is_fresh(&header.modified_at, header.canvas.width, header.canvas.height))
 }
 
 impl Project {
@@ -30,10 +258,26 @@ impl Project {
             symmetry: sym,
             canvas,
             reference_image: None,
+            settings: None,
+            transforms: Vec::new(),
+            thumbnail: None,
         }
     }
 
+    /// Save in the format implied by the path's extension.
     pub fn save_to_file(&mut self, path: &std::path::Path) -> Result<(), String> {
+        self.save_to_file_as(path, ProjectFormat::for_path(path))
+    }
+
+    /// Save in an explicit format, e.g. to a temp file that will be renamed.
+    pub fn save_to_file_as(&mut self, path: &std::path::Path, format: ProjectFormat) -> Result<(), String> {
+        let data = self.to_bytes(format)?;
+        std::fs::write(path, data)
+            .map_err(|e| format!("Write error: {}", e))
+    }
+
+    /// Serialize for saving, stamping the modification time and thumbnail.
+    pub fn to_bytes(&mut self, format: ProjectFormat) -> Result<Vec<u8>, String> {
         self.modified_at = now_iso8601();
         // Set version to 6 when reference_image is present, otherwise keep 5
         if self.reference_image.is_some() {
@@ -41,35 +285,53 @@ impl Project {
         } else if self.version < 6 {
             // Keep existing version (don't downgrade a v6 file that had reference removed)
         }
-        let json = serde_json::to_string_pretty(self)
-            .map_err(|e| format!("Serialize error: {}", e))?;
-        std::fs::write(path, json)
-            .map_err(|e| format!("Write error: {}", e))
+        // Binary files decode fast enough to render directly
+        self.thumbnail = match format {
+            ProjectFormat::Json => Some(Thumbnail::from_canvas(&self.canvas, &self.modified_at)),
+            ProjectFormat::Binary => None,
+        };
+        Ok(match format {
+            ProjectFormat::Json => serde_json::to_string_pretty(self)
+                .map_err(|e| format!("Serialize error: {}", e))?
+                .into_bytes(),
+            ProjectFormat::Binary => crate::kakb::encode(self),
+        })
     }
 
+    /// Load either format; binary files are recognized by their magic bytes,
+    /// whatever the extension.
     pub fn load_from_file(path: &std::path::Path) -> Result<Self, String> {
-        let data = std::fs::read_to_string(path)
+        let data = std::fs::read(path)
             .map_err(|e| format!("Read error: {}", e))?;
-        let project: Project = serde_json::from_str(&data)
-            .map_err(|e| format!("Parse error: {}", e))?;
-        // Accept v1 (legacy 16-color), v2 (256-color), v3 (dynamic canvas), v4 (generic char), v5 (RGB), v6 (reference)
-        if project.version > 6 {
-            return Err(format!(
-                "File version {} is newer than supported (v6)",
-                project.version
-            ));
+        Self::from_bytes(data)
+    }
+
+    /// Parse project data in either format.
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self, String> {
+        let mut project: Project = if crate::kakb::is_binary(&data) {
+            crate::kakb::decode(&data).map_err(|e| format!("Parse error: {}", e))?
+        } else {
+            let text = String::from_utf8(data)
+                .map_err(|e| format!("Read error: {}", e))?;
+            serde_json::from_str(&text)
+                .map_err(|e| format!("Parse error: {}", e))?
+        };
+        check_version(project.version)?;
+        // Edited by something that didn't refresh it: don't trust it
+        if project.thumbnail.as_ref().is_some_and(|t| !t.is_fresh_for(&project)) {
+            project.thumbnail = None;
         }
         Ok(project)
     }
 }
 
-/// List .kaku files in the given directory, sorted by name.
+/// List project files (.kaku and .kakb) in the given directory, sorted by name.
 pub fn list_kaku_files(dir: &std::path::Path) -> Vec<String> {
     let mut files = Vec::new();
     if let Ok(entries) = std::fs::read_dir(dir) {
         for entry in entries.flatten() {
             let path = entry.path();
-            if path.extension().and_then(|e| e.to_str()) == Some("kaku") {
+            if matches!(path.extension().and_then(|e| e.to_str()), Some("kaku" | "kakb")) {
                 if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
                     files.push(name.to_string());
                 }
@@ -86,7 +348,7 @@ pub fn find_autosave(dir: &std::path::Path) -> Option<String> {
         for entry in entries.flatten() {
             let path = entry.path();
             if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                if name.ends_with(".kaku.autosave") {
+                if name.ends_with(".kaku.autosave") || name.ends_with(".kakb.autosave") {
                     return Some(name.to_string());
                 }
             }
@@ -152,7 +414,7 @@ mod tests {
             "test-project",
             canvas,
             color256_to_rgb(2),
-            SymmetryMode::Horizontal,
+            SymmetryMode::HORIZONTAL,
         );
 
         let dir = std::env::temp_dir();
@@ -162,7 +424,7 @@ mod tests {
         let loaded = Project::load_from_file(&path).unwrap();
         assert_eq!(loaded.name, "test-project");
         assert_eq!(loaded.color, color256_to_rgb(2));
-        assert_eq!(loaded.symmetry, SymmetryMode::Horizontal);
+        assert_eq!(loaded.symmetry, SymmetryMode::HORIZONTAL);
         assert_eq!(loaded.version, 5);
         assert_eq!(
             loaded.canvas.get(5, 10),
@@ -190,7 +452,7 @@ mod tests {
             "color-test",
             canvas,
             Rgb::new(100, 200, 50),
-            SymmetryMode::Off,
+            SymmetryMode::empty(),
         );
 
         let dir = std::env::temp_dir();
@@ -216,7 +478,7 @@ mod tests {
         // Build a valid v1-style project with string color name,
         // then patch the JSON to use the legacy "Green" format.
         let canvas = Canvas::new();
-        let mut project = Project::new("legacy-art", canvas, color256_to_rgb(2), crate::symmetry::SymmetryMode::Off);
+        let mut project = Project::new("legacy-art", canvas, color256_to_rgb(2), crate::symmetry::SymmetryMode::empty());
         project.version = 1;
 
         let dir = std::env::temp_dir();
@@ -294,7 +556,7 @@ mod tests {
             });
         }
 
-        let mut project = Project::new("shade-test", canvas, Rgb::WHITE, SymmetryMode::Off);
+        let mut project = Project::new("shade-test", canvas, Rgb::WHITE, SymmetryMode::empty());
         let dir = std::env::temp_dir();
         let path = dir.join("kaku_test_roundtrip_shades.kaku");
         project.save_to_file(&path).unwrap();
@@ -324,7 +586,7 @@ mod tests {
             });
         }
 
-        let mut project = Project::new("fill-test", canvas, Rgb::WHITE, SymmetryMode::Off);
+        let mut project = Project::new("fill-test", canvas, Rgb::WHITE, SymmetryMode::empty());
         let dir = std::env::temp_dir();
         let path = dir.join("kaku_test_roundtrip_fills.kaku");
         project.save_to_file(&path).unwrap();
@@ -349,7 +611,7 @@ mod tests {
             });
         }
 
-        let mut project = Project::new("all-blocks", canvas, Rgb::WHITE, SymmetryMode::Off);
+        let mut project = Project::new("all-blocks", canvas, Rgb::WHITE, SymmetryMode::empty());
         let dir = std::env::temp_dir();
         let path = dir.join("kaku_test_roundtrip_all_blocks.kaku");
         project.save_to_file(&path).unwrap();
@@ -385,7 +647,7 @@ mod tests {
             });
         }
 
-        let mut project = Project::new("color-exact", canvas, Rgb::new(42, 43, 44), SymmetryMode::Off);
+        let mut project = Project::new("color-exact", canvas, Rgb::new(42, 43, 44), SymmetryMode::empty());
         let dir = std::env::temp_dir();
         let path = dir.join("kaku_test_roundtrip_colors_exact.kaku");
         project.save_to_file(&path).unwrap();
@@ -406,7 +668,7 @@ mod tests {
     #[test]
     fn test_v6_roundtrip_with_reference() {
         let canvas = Canvas::new();
-        let mut project = Project::new("ref-test", canvas, Rgb::WHITE, SymmetryMode::Off);
+        let mut project = Project::new("ref-test", canvas, Rgb::WHITE, SymmetryMode::empty());
         project.reference_image = Some("photo.png".to_string());
 
         let dir = std::env::temp_dir();
@@ -423,7 +685,7 @@ mod tests {
     #[test]
     fn test_v5_loads_without_reference() {
         let canvas = Canvas::new();
-        let mut project = Project::new("v5-test", canvas, Rgb::WHITE, SymmetryMode::Off);
+        let mut project = Project::new("v5-test", canvas, Rgb::WHITE, SymmetryMode::empty());
 
         let dir = std::env::temp_dir();
         let path = dir.join("kaku_test_v5_no_ref.kaku");
@@ -439,7 +701,7 @@ mod tests {
     #[test]
     fn test_v5_without_reference_stays_v5() {
         let canvas = Canvas::new();
-        let mut project = Project::new("stay-v5", canvas, Rgb::WHITE, SymmetryMode::Off);
+        let mut project = Project::new("stay-v5", canvas, Rgb::WHITE, SymmetryMode::empty());
         assert_eq!(project.version, 5);
 
         let dir = std::env::temp_dir();
@@ -454,4 +716,51 @@ mod tests {
 
         let _ = std::fs::remove_file(&path);
     }
+
+    #[test]
+    fn test_thumbnail_downsamples_and_caps_size() {
+        let mut canvas = Canvas::new_with_size(64, 32);
+        let red = Rgb::new(200, 0, 0);
+        for y in 0..4 {
+            for x in 0..4 {
+                canvas.set(x, y, Cell { ch: blocks::FULL, fg: Some(red), bg: None });
+            }
+        }
+        // Half of the next 4x4 block is blue: only colored cells are averaged
+        canvas.set(4, 0, Cell { ch: ' ', fg: None, bg: Some(Rgb::new(0, 0, 100)) });
+
+        let thumb = Thumbnail::from_canvas(&canvas, "t");
+        assert_eq!((thumb.width, thumb.height), (16, 8));
+        assert_eq!(thumb.pixels.len(), 16 * 8 * 6);
+        assert_eq!(thumb.pixel(0, 0), Some(red));
+        assert_eq!(thumb.pixel(1, 0), Some(Rgb::new(0, 0, 100)));
+        assert_eq!(thumb.pixel(2, 0), None);
+        assert_eq!(thumb.pixel(16, 0), None);
+
+        // Small canvases keep one pixel per cell
+        let small = Thumbnail::from_canvas(&Canvas::new_with_size(8, 8), "t");
+        assert_eq!((small.width, small.height), (8, 8));
+    }
+
+    #[test]
+    fn test_thumbnail_refreshed_on_save_and_dropped_when_stale() {
+        let path = std::env::temp_dir().join(format!("kaku_test_thumb_{}.kaku", std::process::id()));
+        let mut canvas = Canvas::new_with_size(16, 16);
+        canvas.set(0, 0, Cell { ch: blocks::FULL, fg: Some(Rgb::new(1, 2, 3)), bg: None });
+        let mut project = Project::new("thumb", canvas, Rgb::WHITE, SymmetryMode::empty());
+        project.save_to_file(&path).unwrap();
+
+        let thumb = read_thumbnail(&path).expect("fresh thumbnail");
+        assert_eq!(thumb.pixel(0, 0), Some(Rgb::new(1, 2, 3)));
+        assert!(Project::load_from_file(&path).unwrap().thumbnail.is_some());
+
+        // An edit that didn't go through save leaves the thumbnail stale
+        let mut json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        json["modified_at"] = serde_json::json!("2000-01-01T00:00:00Z");
+        std::fs::write(&path, json.to_string()).unwrap();
+        assert!(read_thumbnail(&path).is_none());
+        assert!(Project::load_from_file(&path).unwrap().thumbnail.is_none());
+
+        let _ = std::fs::remove_file(&path);
+    }
 }
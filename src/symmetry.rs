@@ -1,94 +1,249 @@
-use serde::{Deserialize, Serialize};
+use bitflags::bitflags;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::history::CellMutation;
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
-pub enum SymmetryMode {
-    Off,
-    Horizontal,
-    Vertical,
-    Quad,
+bitflags! {
+    /// Set of independent mirror axes. The empty set means symmetry is off.
+    #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+    pub struct SymmetryMode: u8 {
+        /// Mirror left/right (across the vertical center line)
+        const HORIZONTAL = 1 << 0;
+        /// Mirror top/bottom (across the horizontal center line)
+        const VERTICAL = 1 << 1;
+        /// Mirror across the top-left to bottom-right diagonal
+        const DIAGONAL = 1 << 2;
+        /// Mirror across the top-right to bottom-left diagonal
+        const ANTI_DIAGONAL = 1 << 3;
+        /// Horizontal + vertical (4-fold)
+        const QUAD = Self::HORIZONTAL.bits() | Self::VERTICAL.bits();
+        /// Every axis (8-fold kaleidoscope)
+        const KALEIDOSCOPE = Self::QUAD.bits() | Self::DIAGONAL.bits() | Self::ANTI_DIAGONAL.bits();
+    }
 }
 
+/// Axis names used in project files, in bit order.
+const AXIS_NAMES: [(SymmetryMode, &str, &str); 4] = [
+    (SymmetryMode::HORIZONTAL, "Horizontal", "H"),
+    (SymmetryMode::VERTICAL, "Vertical", "V"),
+    (SymmetryMode::DIAGONAL, "Diagonal", "D"),
+    (SymmetryMode::ANTI_DIAGONAL, "AntiDiagonal", "A"),
+];
+
 impl SymmetryMode {
     pub fn toggle_horizontal(self) -> SymmetryMode {
-        match self {
-            SymmetryMode::Off => SymmetryMode::Horizontal,
-            SymmetryMode::Horizontal => SymmetryMode::Off,
-            SymmetryMode::Vertical => SymmetryMode::Quad,
-            SymmetryMode::Quad => SymmetryMode::Vertical,
-        }
+        self ^ SymmetryMode::HORIZONTAL
     }
 
     pub fn toggle_vertical(self) -> SymmetryMode {
-        match self {
-            SymmetryMode::Off => SymmetryMode::Vertical,
-            SymmetryMode::Vertical => SymmetryMode::Off,
-            SymmetryMode::Horizontal => SymmetryMode::Quad,
-            SymmetryMode::Quad => SymmetryMode::Horizontal,
-        }
+        self ^ SymmetryMode::VERTICAL
+    }
+
+    pub fn toggle_diagonal(self) -> SymmetryMode {
+        self ^ SymmetryMode::DIAGONAL
+    }
+
+    pub fn toggle_anti_diagonal(self) -> SymmetryMode {
+        self ^ SymmetryMode::ANTI_DIAGONAL
     }
 
     pub fn has_horizontal(self) -> bool {
-        matches!(self, SymmetryMode::Horizontal | SymmetryMode::Quad)
+        self.contains(SymmetryMode::HORIZONTAL)
     }
 
     pub fn has_vertical(self) -> bool {
-        matches!(self, SymmetryMode::Vertical | SymmetryMode::Quad)
+        self.contains(SymmetryMode::VERTICAL)
+    }
+
+    pub fn has_diagonal(self) -> bool {
+        self.contains(SymmetryMode::DIAGONAL)
+    }
+
+    pub fn has_anti_diagonal(self) -> bool {
+        self.contains(SymmetryMode::ANTI_DIAGONAL)
+    }
+
+    pub fn label(self) -> String {
+        if self.is_empty() {
+            return "Off".to_string();
+        }
+        if self == SymmetryMode::HORIZONTAL {
+            return "Horiz".to_string();
+        }
+        if self == SymmetryMode::VERTICAL {
+            return "Vert".to_string();
+        }
+        if self == SymmetryMode::QUAD {
+            return "Quad".to_string();
+        }
+        if self == SymmetryMode::KALEIDOSCOPE {
+            return "Kaleido".to_string();
+        }
+        AXIS_NAMES
+            .iter()
+            .filter(|(axis, _, _)| self.contains(*axis))
+            .map(|(_, _, short)| *short)
+            .collect::<Vec<_>>()
+            .join("+")
+    }
+
+    /// Reflect (x, y) across one axis. None if the image falls off the canvas
+    /// (diagonals on a non-square canvas, or an off-center mirror axis).
+    fn reflect(
+        axis: SymmetryMode,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        axes: (usize, usize),
+    ) -> Option<(usize, usize)> {
+        let (rx, ry) = if axis == SymmetryMode::HORIZONTAL {
+            (axes.0.checked_sub(x)?, y)
+        } else if axis == SymmetryMode::VERTICAL {
+            (x, axes.1.checked_sub(y)?)
+        } else if axis == SymmetryMode::DIAGONAL {
+            (y, x)
+        } else {
+            ((width - 1).checked_sub(y)?, (height - 1).checked_sub(x)?)
+        };
+        if rx < width && ry < height { Some((rx, ry)) } else { None }
     }
+}
+
+/// Project files store the mode as a name. The original four modes keep
+/// their names ("Off", "Horizontal", "Vertical", "Quad"); other sets join
+/// axis names with '+', e.g. "Horizontal+Diagonal".
+impl Serialize for SymmetryMode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let name = if self.is_empty() {
+            "Off".to_string()
+        } else if *self == SymmetryMode::QUAD {
+            "Quad".to_string()
+        } else {
+            AXIS_NAMES
+                .iter()
+                .filter(|(axis, _, _)| self.contains(*axis))
+                .map(|(_, name, _)| *name)
+                .collect::<Vec<_>>()
+                .join("+")
+        };
+        serializer.serialize_str(&name)
+    }
+}
 
-    pub fn label(self) -> &'static str {
-        match self {
-            SymmetryMode::Off => "Off",
-            SymmetryMode::Horizontal => "Horiz",
-            SymmetryMode::Vertical => "Vert",
-            SymmetryMode::Quad => "Quad",
+impl<'de> Deserialize<'de> for SymmetryMode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        let mut mode = SymmetryMode::empty();
+        for part in s.split('+') {
+            mode |= match part {
+                "Off" => SymmetryMode::empty(),
+                "Quad" => SymmetryMode::QUAD,
+                other => AXIS_NAMES
+                    .iter()
+                    .find(|(_, name, _)| *name == other)
+                    .map(|(axis, _, _)| *axis)
+                    .ok_or_else(|| serde::de::Error::custom(format!("unknown symmetry axis '{}'", other)))?,
+            };
         }
+        Ok(mode)
     }
 }
 
+/// Mirror axes through the geometric center of a canvas.
+///
+/// Axes are in half-cell units: column `x` mirrors to `axes.0 - x` and row
+/// `y` to `axes.1 - y`. An even value puts the axis through the middle of cell
+/// `value / 2`; an odd value puts it on the line between two cells.
+pub fn center_axes(width: usize, height: usize) -> (usize, usize) {
+    (width.saturating_sub(1), height.saturating_sub(1))
+}
+
 /// Given a list of mutations, produce mirrored copies based on symmetry mode.
-/// Returns the original mutations plus any mirrored ones.
+/// Returns the original mutations plus any mirrored ones. Enabled axes are
+/// applied transitively, so each point expands to its full orbit (up to 8
+/// cells with every axis on); duplicates on an axis are emitted once.
 pub fn apply_symmetry(mutations: Vec<CellMutation>, mode: SymmetryMode, width: usize, height: usize) -> Vec<CellMutation> {
-    if mode == SymmetryMode::Off {
+    apply_symmetry_around(mutations, mode, width, height, center_axes(width, height))
+}
+
+/// Like [`apply_symmetry`], but the horizontal and vertical mirrors use the
+/// given axes (see [`center_axes`] for the units). Diagonals always run
+/// corner to corner.
+pub fn apply_symmetry_around(
+    mutations: Vec<CellMutation>,
+    mode: SymmetryMode,
+    width: usize,
+    height: usize,
+    axes: (usize, usize),
+) -> Vec<CellMutation> {
+    if mode.is_empty() {
         return mutations;
     }
 
     let mut result = Vec::with_capacity(mutations.len() * 4);
 
     for m in &mutations {
-        result.push(m.clone());
-
-        if mode.has_horizontal() {
-            let mx = width - 1 - m.x;
-            if mx != m.x {
-                let mut mirrored = m.clone();
-                mirrored.x = mx;
-                result.push(mirrored);
+        let mut orbit = vec![(m.x, m.y)];
+        let mut i = 0;
+        while i < orbit.len() {
+            let (x, y) = orbit[i];
+            for (axis, _, _) in AXIS_NAMES.iter().filter(|(axis, _, _)| mode.contains(*axis)) {
+                if let Some(p) = SymmetryMode::reflect(*axis, x, y, width, height, axes) {
+                    if !orbit.contains(&p) {
+                        orbit.push(p);
+                    }
+                }
             }
+            i += 1;
         }
 
-        if mode.has_vertical() {
-            let my = height - 1 - m.y;
-            if my != m.y {
-                let mut mirrored = m.clone();
-                mirrored.y = my;
-                result.push(mirrored);
-            }
+        for (x, y) in orbit {
+            let mut mirrored = m.clone();
+            mirrored.x = x;
+            mirrored.y = y;
+            result.push(mirrored);
         }
+    }
+
+    result
+}
+
+/// Orders offered for radial symmetry, in cycle order.
+pub const RADIAL_ORDERS: [usize; 4] = [3, 4, 6, 8];
 
-        if mode == SymmetryMode::Quad {
-            let mx = width - 1 - m.x;
-            let my = height - 1 - m.y;
-            if mx != m.x && my != m.y {
-                let mut mirrored = m.clone();
-                mirrored.x = mx;
-                mirrored.y = my;
-                result.push(mirrored);
+/// Add `order - 1` copies of each mutation rotated about the mirror center
+/// (`axes` in half-cell units, see [`center_axes`]) in steps of 360/order
+/// degrees, rounded to the nearest cell. Copies falling off the canvas are
+/// dropped, and a cell reached more than once keeps only its first mutation.
+pub fn apply_radial(
+    mutations: Vec<CellMutation>,
+    order: usize,
+    width: usize,
+    height: usize,
+    axes: (usize, usize),
+) -> Vec<CellMutation> {
+    if order < 2 {
+        return mutations;
+    }
+    let (cx, cy) = (axes.0 as f64 / 2.0, axes.1 as f64 / 2.0);
+    let mut seen = std::collections::HashSet::new();
+    let mut result = Vec::with_capacity(mutations.len() * order);
+    for step in 0..order {
+        let (sin, cos) = (std::f64::consts::TAU * step as f64 / order as f64).sin_cos();
+        for m in &mutations {
+            let (dx, dy) = (m.x as f64 - cx, m.y as f64 - cy);
+            let x = (cx + dx * cos - dy * sin).round();
+            let y = (cy + dx * sin + dy * cos).round();
+            if x < 0.0 || y < 0.0 || x >= width as f64 || y >= height as f64 {
+                continue;
+            }
+            let (x, y) = (x as usize, y as usize);
+            if seen.insert((x, y)) {
+                result.push(CellMutation { x, y, ..m.clone() });
             }
         }
     }
-
     result
 }
 
@@ -113,14 +268,14 @@ mod tests {
     #[test]
     fn test_off_no_mirror() {
         let mutations = vec![make_mutation(5, 10)];
-        let result = apply_symmetry(mutations, SymmetryMode::Off, 32, 32);
+        let result = apply_symmetry(mutations, SymmetryMode::empty(), 32, 32);
         assert_eq!(result.len(), 1);
     }
 
     #[test]
     fn test_horizontal_mirror() {
         let mutations = vec![make_mutation(5, 10)];
-        let result = apply_symmetry(mutations, SymmetryMode::Horizontal, 32, 32);
+        let result = apply_symmetry(mutations, SymmetryMode::HORIZONTAL, 32, 32);
         assert_eq!(result.len(), 2);
         assert_eq!(result[0].x, 5);
         assert_eq!(result[1].x, 26); // 31 - 5
@@ -129,7 +284,7 @@ mod tests {
     #[test]
     fn test_vertical_mirror() {
         let mutations = vec![make_mutation(5, 10)];
-        let result = apply_symmetry(mutations, SymmetryMode::Vertical, 32, 32);
+        let result = apply_symmetry(mutations, SymmetryMode::VERTICAL, 32, 32);
         assert_eq!(result.len(), 2);
         assert_eq!(result[0].y, 10);
         assert_eq!(result[1].y, 21); // 31 - 10
@@ -138,7 +293,7 @@ mod tests {
     #[test]
     fn test_quad_mirror() {
         let mutations = vec![make_mutation(5, 10)];
-        let result = apply_symmetry(mutations, SymmetryMode::Quad, 32, 32);
+        let result = apply_symmetry(mutations, SymmetryMode::QUAD, 32, 32);
         assert_eq!(result.len(), 4);
         assert_eq!((result[0].x, result[0].y), (5, 10));
         assert_eq!((result[1].x, result[1].y), (26, 10));
@@ -151,11 +306,49 @@ mod tests {
         // Point on the horizontal center axis (x=15, x mirrored = 16, not same)
         // Point exactly on center for odd: with 32 width, there's no exact center cell
         let mutations = vec![make_mutation(15, 10)];
-        let result = apply_symmetry(mutations, SymmetryMode::Horizontal, 32, 32);
+        let result = apply_symmetry(mutations, SymmetryMode::HORIZONTAL, 32, 32);
         assert_eq!(result.len(), 2);
         assert_eq!(result[1].x, 16); // 31 - 15
     }
 
+    #[test]
+    fn test_custom_axis_through_cell() {
+        // Axis through the middle of column 10: 7 mirrors to 13, 10 stays put
+        let result = apply_symmetry_around(
+            vec![make_mutation(7, 4), make_mutation(10, 4)],
+            SymmetryMode::HORIZONTAL,
+            32,
+            32,
+            (20, 31),
+        );
+        let xs: Vec<_> = result.iter().map(|m| m.x).collect();
+        assert_eq!(xs, vec![7, 13, 10]);
+    }
+
+    #[test]
+    fn test_custom_axis_drops_off_canvas_images() {
+        // Row axis through row 4: row 20 has no image on the canvas, row 1 mirrors to 7
+        let result = apply_symmetry_around(
+            vec![make_mutation(3, 20), make_mutation(3, 1)],
+            SymmetryMode::VERTICAL,
+            32,
+            32,
+            (31, 8),
+        );
+        let ys: Vec<_> = result.iter().map(|m| m.y).collect();
+        assert_eq!(ys, vec![20, 1, 7]);
+    }
+
+    #[test]
+    fn test_center_axes_match_default() {
+        assert_eq!(center_axes(32, 24), (31, 23));
+        let m = vec![make_mutation(5, 10)];
+        let a = apply_symmetry(m.clone(), SymmetryMode::QUAD, 32, 24);
+        let b = apply_symmetry_around(m, SymmetryMode::QUAD, 32, 24, center_axes(32, 24));
+        let pos = |v: &[CellMutation]| v.iter().map(|m| (m.x, m.y)).collect::<Vec<_>>();
+        assert_eq!(pos(&a), pos(&b));
+    }
+
     // --- Cycle 15 QA: Shade character symmetry tests ---
 
     fn make_shade_mutation(x: usize, y: usize) -> CellMutation {
@@ -174,7 +367,7 @@ mod tests {
     #[test]
     fn test_symmetry_shade_horizontal() {
         let mutations = vec![make_shade_mutation(5, 10)];
-        let result = apply_symmetry(mutations, SymmetryMode::Horizontal, 32, 32);
+        let result = apply_symmetry(mutations, SymmetryMode::HORIZONTAL, 32, 32);
         assert_eq!(result.len(), 2);
         assert_eq!(result[0].new.ch, blocks::SHADE_MEDIUM);
         assert_eq!(result[1].new.ch, blocks::SHADE_MEDIUM);
@@ -185,7 +378,7 @@ mod tests {
     #[test]
     fn test_symmetry_shade_quad() {
         let mutations = vec![make_shade_mutation(3, 7)];
-        let result = apply_symmetry(mutations, SymmetryMode::Quad, 32, 32);
+        let result = apply_symmetry(mutations, SymmetryMode::QUAD, 32, 32);
         assert_eq!(result.len(), 4);
         for m in &result {
             assert_eq!(m.new.ch, blocks::SHADE_MEDIUM);
@@ -196,4 +389,88 @@ mod tests {
         assert_eq!((result[2].x, result[2].y), (3, 24));
         assert_eq!((result[3].x, result[3].y), (28, 24));
     }
+
+    // --- Combined axes ---
+
+    #[test]
+    fn test_toggles_map_onto_legacy_modes() {
+        let off = SymmetryMode::empty();
+        assert_eq!(off.toggle_horizontal(), SymmetryMode::HORIZONTAL);
+        assert_eq!(off.toggle_vertical(), SymmetryMode::VERTICAL);
+        assert_eq!(SymmetryMode::HORIZONTAL.toggle_vertical(), SymmetryMode::QUAD);
+        assert_eq!(SymmetryMode::QUAD.toggle_horizontal(), SymmetryMode::VERTICAL);
+        assert_eq!(SymmetryMode::QUAD.label(), "Quad");
+        assert_eq!(off.label(), "Off");
+    }
+
+    #[test]
+    fn test_diagonal_mirror() {
+        let result = apply_symmetry(vec![make_mutation(2, 5)], SymmetryMode::DIAGONAL, 16, 16);
+        assert_eq!(result.len(), 2);
+        assert_eq!((result[1].x, result[1].y), (5, 2));
+
+        let result = apply_symmetry(vec![make_mutation(2, 5)], SymmetryMode::ANTI_DIAGONAL, 16, 16);
+        assert_eq!(result.len(), 2);
+        assert_eq!((result[1].x, result[1].y), (10, 13));
+    }
+
+    #[test]
+    fn test_kaleidoscope_eight_fold() {
+        let result = apply_symmetry(vec![make_mutation(2, 5)], SymmetryMode::KALEIDOSCOPE, 16, 16);
+        assert_eq!(result.len(), 8);
+        let mut points: Vec<(usize, usize)> = result.iter().map(|m| (m.x, m.y)).collect();
+        points.sort();
+        points.dedup();
+        assert_eq!(points.len(), 8);
+        // H + diagonal alone already generates the full orbit transitively
+        let hd = SymmetryMode::HORIZONTAL | SymmetryMode::DIAGONAL;
+        assert_eq!(apply_symmetry(vec![make_mutation(2, 5)], hd, 16, 16).len(), 8);
+    }
+
+    #[test]
+    fn test_radial_order_four_off_center_dab() {
+        let result = apply_radial(vec![make_mutation(20, 10)], 4, 32, 32, center_axes(32, 32));
+        let points: Vec<(usize, usize)> = result.iter().map(|m| (m.x, m.y)).collect();
+        // Quarter turns about (15.5, 15.5)
+        assert_eq!(points, vec![(20, 10), (21, 20), (11, 21), (10, 11)]);
+    }
+
+    #[test]
+    fn test_radial_dedupes_and_clips() {
+        // The center cell maps onto itself at every step
+        let center = apply_radial(vec![make_mutation(8, 8)], 6, 17, 17, center_axes(17, 17));
+        assert_eq!(center.len(), 1);
+        // On a wide canvas, a point far from the center rotates off the top and bottom
+        let wide = apply_radial(vec![make_mutation(1, 4)], 4, 40, 10, center_axes(40, 10));
+        assert!(wide.len() < 4);
+        assert!(wide.iter().all(|m| m.x < 40 && m.y < 10));
+    }
+
+    #[test]
+    fn test_diagonal_point_on_axis_not_duplicated() {
+        let result = apply_symmetry(vec![make_mutation(4, 4)], SymmetryMode::DIAGONAL, 16, 16);
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test]
+    fn test_diagonal_off_canvas_dropped() {
+        // On a wide canvas, (20, 1) reflects to (1, 20), which is out of bounds
+        let result = apply_symmetry(vec![make_mutation(20, 1)], SymmetryMode::DIAGONAL, 32, 16);
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test]
+    fn test_serde_keeps_legacy_names() {
+        for (mode, name) in [
+            (SymmetryMode::empty(), "\"Off\""),
+            (SymmetryMode::HORIZONTAL, "\"Horizontal\""),
+            (SymmetryMode::VERTICAL, "\"Vertical\""),
+            (SymmetryMode::QUAD, "\"Quad\""),
+            (SymmetryMode::HORIZONTAL | SymmetryMode::DIAGONAL, "\"Horizontal+Diagonal\""),
+        ] {
+            assert_eq!(serde_json::to_string(&mode).unwrap(), name);
+            assert_eq!(serde_json::from_str::<SymmetryMode>(name).unwrap(), mode);
+        }
+        assert!(serde_json::from_str::<SymmetryMode>("\"Sideways\"").is_err());
+    }
 }
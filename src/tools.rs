@@ -8,8 +8,10 @@ pub enum ToolKind {
     Eraser,
     Line,
     Rectangle,
+    Ellipse,
     Fill,
     Eyedropper,
+    Select,
 }
 
 impl ToolKind {
@@ -19,8 +21,10 @@ impl ToolKind {
             ToolKind::Eraser => "Eraser",
             ToolKind::Line => "Line",
             ToolKind::Rectangle => "Rect",
+            ToolKind::Ellipse => "Oval",
             ToolKind::Fill => "Fill",
             ToolKind::Eyedropper => "Pick",
+            ToolKind::Select => "Select",
         }
     }
 
@@ -30,8 +34,10 @@ impl ToolKind {
             ToolKind::Eraser => "\u{25FB}",    // ◻
             ToolKind::Line => "\u{2571}",      // ╱
             ToolKind::Rectangle => "\u{25AD}", // ▭
+            ToolKind::Ellipse => "\u{25CB}",   // ○
             ToolKind::Fill => "\u{25C9}",      // ◉
             ToolKind::Eyedropper => "\u{25C8}", // ◈
+            ToolKind::Select => "\u{2B1A}",     // ⬚
         }
     }
 
@@ -41,19 +47,50 @@ impl ToolKind {
             ToolKind::Eraser => "E",
             ToolKind::Line => "L",
             ToolKind::Rectangle => "R",
+            ToolKind::Ellipse => "O",
             ToolKind::Fill => "F",
             ToolKind::Eyedropper => "I",
+            ToolKind::Select => "M",
         }
     }
 
-    pub const ALL: [ToolKind; 6] = [
+    pub const ALL: [ToolKind; 8] = [
         ToolKind::Pencil,
         ToolKind::Eraser,
         ToolKind::Line,
         ToolKind::Rectangle,
+        ToolKind::Ellipse,
         ToolKind::Fill,
         ToolKind::Eyedropper,
+        ToolKind::Select,
     ];
+
+    /// Parse a tool by its toolbar name or full name, case-insensitively
+    /// ("rect" or "rectangle", "oval" or "ellipse", "pick" or "eyedropper").
+    pub fn from_name(name: &str) -> Option<ToolKind> {
+        let name = name.trim().to_ascii_lowercase();
+        let full = |tool: ToolKind| match tool {
+            ToolKind::Rectangle => "rectangle",
+            ToolKind::Ellipse => "ellipse",
+            ToolKind::Eyedropper => "eyedropper",
+            _ => "",
+        };
+        Self::ALL
+            .into_iter()
+            .find(|&tool| tool.name().to_ascii_lowercase() == name || full(tool) == name)
+    }
+
+    /// Toolbar order: `favorites` first (in the given order, duplicates
+    /// dropped), then the remaining tools in their default order.
+    pub fn ordered(favorites: &[ToolKind]) -> Vec<ToolKind> {
+        let mut order: Vec<ToolKind> = Vec::with_capacity(Self::ALL.len());
+        for &tool in favorites.iter().chain(Self::ALL.iter()) {
+            if !order.contains(&tool) {
+                order.push(tool);
+            }
+        }
+        order
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -61,6 +98,8 @@ pub enum ToolState {
     Idle,
     LineStart { x: usize, y: usize },
     RectStart { x: usize, y: usize },
+    EllipseStart { x: usize, y: usize },
+    SelectStart { x: usize, y: usize },
 }
 
 /// Place a single cell (pencil).
@@ -189,7 +228,251 @@ pub fn rectangle(
     mutations
 }
 
-/// Iterative flood fill from (start_x, start_y).
+/// Whether (x, y) is drawn by the ellipse inscribed in the box
+/// (x0,y0)-(x1,y1): cells whose centers fall inside it, or for an outline
+/// only those with an edge neighbor outside. Equal corners give one cell; a
+/// box one cell wide or tall gives a straight line.
+pub fn on_ellipse(x0: usize, y0: usize, x1: usize, y1: usize, filled: bool, x: usize, y: usize) -> bool {
+    let (min_x, max_x) = (x0.min(x1), x0.max(x1));
+    let (min_y, max_y) = (y0.min(y1), y0.max(y1));
+    let cx = (min_x + max_x) as f64 / 2.0;
+    let cy = (min_y + max_y) as f64 / 2.0;
+    // Radii reach the outer edge of the corner cells
+    let rx = (max_x - min_x) as f64 / 2.0 + 0.5;
+    let ry = (max_y - min_y) as f64 / 2.0 + 0.5;
+    let inside = |x: usize, y: usize| {
+        let dx = (x as f64 - cx) / rx;
+        let dy = (y as f64 - cy) / ry;
+        (min_x..=max_x).contains(&x) && (min_y..=max_y).contains(&y) && dx * dx + dy * dy <= 1.0
+    };
+
+    inside(x, y)
+        && (filled
+            || x == min_x
+            || y == min_y
+            || !inside(x - 1, y)
+            || !inside(x + 1, y)
+            || !inside(x, y - 1)
+            || !inside(x, y + 1))
+}
+
+/// Draw an ellipse inscribed in the box (x0,y0)-(x1,y1), outlined or filled.
+#[allow(clippy::too_many_arguments)]
+pub fn ellipse(
+    canvas: &Canvas,
+    x0: usize,
+    y0: usize,
+    x1: usize,
+    y1: usize,
+    ch: char,
+    fg: Option<Rgb>,
+    bg: Option<Rgb>,
+    filled: bool,
+) -> Vec<CellMutation> {
+    let new = Cell { ch, fg, bg };
+    let mut mutations = Vec::new();
+    for y in y0.min(y1)..=y0.max(y1) {
+        for x in x0.min(x1)..=x0.max(x1) {
+            if on_ellipse(x0, y0, x1, y1, filled, x, y) {
+                if let Some(old) = canvas.get(x, y) {
+                    if old != new {
+                        mutations.push(CellMutation { x, y, old, new });
+                    }
+                }
+            }
+        }
+    }
+    mutations
+}
+
+/// Place a cell every `spacing` cells in both axes within the region
+/// (x0,y0)-(x1,y1). The pattern starts at the region's top-left corner and is
+/// clipped to the canvas. Spacing 1 fills the region solidly; 0 draws nothing.
+#[allow(clippy::too_many_arguments)]
+pub fn stipple(
+    canvas: &Canvas,
+    x0: usize,
+    y0: usize,
+    x1: usize,
+    y1: usize,
+    spacing: usize,
+    ch: char,
+    fg: Option<Rgb>,
+    bg: Option<Rgb>,
+) -> Vec<CellMutation> {
+    if spacing == 0 {
+        return Vec::new();
+    }
+    let min_x = x0.min(x1);
+    let max_x = x0.max(x1).min(canvas.width.saturating_sub(1));
+    let min_y = y0.min(y1);
+    let max_y = y0.max(y1).min(canvas.height.saturating_sub(1));
+    let new = Cell { ch, fg, bg };
+    let mut mutations = Vec::new();
+
+    for y in (min_y..=max_y).step_by(spacing) {
+        for x in (min_x..=max_x).step_by(spacing) {
+            if let Some(old) = canvas.get(x, y) {
+                if old != new {
+                    mutations.push(CellMutation { x, y, old, new });
+                }
+            }
+        }
+    }
+    mutations
+}
+
+/// 4x4 Bayer threshold matrix (values 0-15) for ordered dithering.
+pub(crate) const BAYER_4X4: [[u8; 4]; 4] = [
+    [0, 8, 2, 10],
+    [12, 4, 14, 6],
+    [3, 11, 1, 9],
+    [15, 7, 13, 5],
+];
+
+/// Fill the region (x0,y0)-(x1,y1) with full blocks mixing colors `a` and
+/// `b` by a 4x4 ordered (Bayer) dither, `ratio` percent of cells taking `b`
+/// (0 = solid `a`, 100 = solid `b`). The pattern is anchored to canvas
+/// coordinates, so neighboring fills line up. Clipped to the canvas.
+#[allow(clippy::too_many_arguments)]
+pub fn dither2(
+    canvas: &Canvas,
+    x0: usize,
+    y0: usize,
+    x1: usize,
+    y1: usize,
+    a: Rgb,
+    b: Rgb,
+    ratio: u8,
+) -> Vec<CellMutation> {
+    let ratio = ratio.min(100) as u32;
+    let max_x = x0.max(x1).min(canvas.width.saturating_sub(1));
+    let max_y = y0.max(y1).min(canvas.height.saturating_sub(1));
+    let mut mutations = Vec::new();
+
+    for y in y0.min(y1)..=max_y {
+        for x in x0.min(x1)..=max_x {
+            let threshold = BAYER_4X4[y % 4][x % 4] as u32;
+            let color = if threshold * 100 < ratio * 16 { b } else { a };
+            let new = Cell { ch: crate::cell::blocks::FULL, fg: Some(color), bg: None };
+            if let Some(old) = canvas.get(x, y) {
+                if old != new {
+                    mutations.push(CellMutation { x, y, old, new });
+                }
+            }
+        }
+    }
+    mutations
+}
+
+/// Box-drawing line style for `frame`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FrameStyle {
+    Single,
+    Double,
+    Rounded,
+}
+
+impl FrameStyle {
+    pub const ALL: [FrameStyle; 3] = [FrameStyle::Single, FrameStyle::Double, FrameStyle::Rounded];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            FrameStyle::Single => "single",
+            FrameStyle::Double => "double",
+            FrameStyle::Rounded => "rounded",
+        }
+    }
+
+    /// Parse a style by name, case-insensitively.
+    pub fn from_name(name: &str) -> Option<FrameStyle> {
+        let name = name.trim().to_ascii_lowercase();
+        Self::ALL.into_iter().find(|style| style.name() == name)
+    }
+
+    /// Glyphs as [top-left, top-right, bottom-left, bottom-right, horizontal, vertical].
+    pub fn glyphs(self) -> [char; 6] {
+        match self {
+            FrameStyle::Single => ['\u{250C}', '\u{2510}', '\u{2514}', '\u{2518}', '\u{2500}', '\u{2502}'], // ┌┐└┘─│
+            FrameStyle::Double => ['\u{2554}', '\u{2557}', '\u{255A}', '\u{255D}', '\u{2550}', '\u{2551}'], // ╔╗╚╝═║
+            FrameStyle::Rounded => ['\u{256D}', '\u{256E}', '\u{2570}', '\u{256F}', '\u{2500}', '\u{2502}'], // ╭╮╰╯─│
+        }
+    }
+}
+
+/// Draw a box-drawing border along the canvas edge in `fg`. Only the outer
+/// ring of cells is touched, and each keeps its existing background.
+/// Canvases narrower or shorter than 2 cells get no frame.
+pub fn frame(canvas: &Canvas, style: FrameStyle, fg: Option<Rgb>) -> Vec<CellMutation> {
+    let (w, h) = (canvas.width, canvas.height);
+    if w < 2 || h < 2 {
+        return Vec::new();
+    }
+    let [tl, tr, bl, br, horiz, vert] = style.glyphs();
+    let mut mutations = Vec::new();
+
+    for y in 0..h {
+        for x in 0..w {
+            let ch = match (x, y) {
+                (0, 0) => tl,
+                (x, 0) if x == w - 1 => tr,
+                (0, y) if y == h - 1 => bl,
+                (x, y) if x == w - 1 && y == h - 1 => br,
+                (_, 0) => horiz,
+                (_, y) if y == h - 1 => horiz,
+                (0, _) => vert,
+                (x, _) if x == w - 1 => vert,
+                _ => continue,
+            };
+            if let Some(old) = canvas.get(x, y) {
+                let new = Cell { ch, fg, bg: old.bg };
+                if old != new {
+                    mutations.push(CellMutation { x, y, old, new });
+                }
+            }
+        }
+    }
+    mutations
+}
+
+/// Which part of a cell a flood fill matches on and repaints.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum FillTarget {
+    /// Whole cells: connectivity and replacement use character, fg and bg.
+    #[default]
+    Full,
+    /// Connected cells with the same fg get the new fg; ch and bg are kept.
+    FgOnly,
+    /// Connected cells with the same bg get the new bg; ch and fg are kept.
+    BgOnly,
+}
+
+impl FillTarget {
+    /// Whether `cell` belongs to the region started at `start`.
+    fn matches(self, cell: Cell, start: Cell) -> bool {
+        match self {
+            FillTarget::Full => cell == start,
+            FillTarget::FgOnly => cell.fg == start.fg,
+            FillTarget::BgOnly => cell.bg == start.bg,
+        }
+    }
+
+    /// `cell` repainted with the targeted channel(s) of `paint`.
+    fn apply(self, cell: Cell, paint: Cell) -> Cell {
+        match self {
+            FillTarget::Full => paint,
+            FillTarget::FgOnly => Cell { fg: paint.fg, ..cell },
+            FillTarget::BgOnly => Cell { bg: paint.bg, ..cell },
+        }
+    }
+}
+
+/// Flood fill the connected region around (start_x, start_y). `target`
+/// picks the channel used for both connectivity and repainting; `ch` is
+/// only used by `FillTarget::Full`. Cells connect through their edges, or
+/// also through corners when `diagonal` is set; diagonal fills leak through
+/// one-cell-wide diagonal gaps in an outline.
+#[allow(clippy::too_many_arguments)]
 pub fn flood_fill(
     canvas: &Canvas,
     start_x: usize,
@@ -197,14 +480,16 @@ pub fn flood_fill(
     ch: char,
     fg: Option<Rgb>,
     bg: Option<Rgb>,
+    target: FillTarget,
+    diagonal: bool,
 ) -> Vec<CellMutation> {
-    let target = match canvas.get(start_x, start_y) {
+    let start = match canvas.get(start_x, start_y) {
         Some(cell) => cell,
         None => return vec![],
     };
 
-    let new = Cell { ch, fg, bg };
-    if target == new {
+    let paint = Cell { ch, fg, bg };
+    if target.apply(start, paint) == start {
         return vec![]; // No-op: already the target color
     }
 
@@ -218,21 +503,16 @@ pub fn flood_fill(
         if x >= w || y >= h || visited[y * w + x] {
             continue;
         }
-        if let Some(cell) = canvas.get(x, y) {
-            if cell != target {
-                continue;
-            }
-        } else {
-            continue;
-        }
+        let cell = match canvas.get(x, y) {
+            Some(cell) if target.matches(cell, start) => cell,
+            _ => continue,
+        };
 
         visited[y * w + x] = true;
-        mutations.push(CellMutation {
-            x,
-            y,
-            old: target,
-            new,
-        });
+        let new = target.apply(cell, paint);
+        if new != cell {
+            mutations.push(CellMutation { x, y, old: cell, new });
+        }
 
         if x > 0 {
             stack.push((x - 1, y));
@@ -246,11 +526,156 @@ pub fn flood_fill(
         if y + 1 < h {
             stack.push((x, y + 1));
         }
+        if diagonal {
+            // Out-of-range neighbors wrap to usize::MAX and are skipped above
+            for (dx, dy) in [(-1isize, -1isize), (1, -1), (-1, 1), (1, 1)] {
+                stack.push((x.wrapping_add_signed(dx), y.wrapping_add_signed(dy)));
+            }
+        }
+    }
+
+    mutations
+}
+
+/// Colors a cell actually shows: the glyph color (unless blank) and the background.
+fn visible_colors(cell: Cell) -> impl Iterator<Item = Rgb> {
+    let fg = if cell.is_empty() { None } else { cell.fg };
+    fg.into_iter().chain(cell.bg)
+}
+
+/// Every channel of `a` is within `tolerance` of `b`.
+fn within(a: Rgb, b: Rgb, tolerance: u8) -> bool {
+    a.r.abs_diff(b.r) <= tolerance && a.g.abs_diff(b.g) <= tolerance && a.b.abs_diff(b.b) <= tolerance
+}
+
+/// Recolor: swap every fg and bg within `tolerance` (per channel) of `from`
+/// to `to`, across the whole canvas. One mutation per changed cell.
+pub fn replace_color(canvas: &Canvas, from: Rgb, to: Rgb, tolerance: u8) -> Vec<CellMutation> {
+    let swap = |c: Option<Rgb>| c.map(|c| if within(c, from, tolerance) { to } else { c });
+    let mut mutations = Vec::new();
+    for y in 0..canvas.height {
+        for x in 0..canvas.width {
+            let Some(old) = canvas.get(x, y) else { continue };
+            let new = Cell { fg: swap(old.fg), bg: swap(old.bg), ..old };
+            if new != old {
+                mutations.push(CellMutation { x, y, old, new });
+            }
+        }
+    }
+    mutations
+}
+
+/// Magic-wand background removal: flood from every border cell through cells
+/// whose visible colors are all within `tolerance` (per channel) of the
+/// top-left corner's color, clearing them. Same-colored regions that don't
+/// touch the border are left alone.
+pub fn remove_background(canvas: &Canvas, tolerance: u8) -> Vec<CellMutation> {
+    let Some(target) = canvas.get(0, 0).and_then(|c| visible_colors(c).next()) else {
+        return vec![]; // Corner is already transparent
+    };
+    let close = |c: Rgb| within(c, target, tolerance);
+    let is_background = |cell: Cell| {
+        let mut colors = visible_colors(cell).peekable();
+        colors.peek().is_some() && colors.all(close)
+    };
+
+    let w = canvas.width;
+    let h = canvas.height;
+    let mut visited = vec![false; w * h];
+    let mut stack: Vec<(usize, usize)> = (0..w)
+        .flat_map(|x| [(x, 0), (x, h - 1)])
+        .chain((0..h).flat_map(|y| [(0, y), (w - 1, y)]))
+        .collect();
+    let mut mutations = Vec::new();
+
+    while let Some((x, y)) = stack.pop() {
+        if x >= w || y >= h || visited[y * w + x] {
+            continue;
+        }
+        visited[y * w + x] = true;
+        let Some(cell) = canvas.get(x, y) else { continue };
+        if !is_background(cell) {
+            continue;
+        }
+        mutations.push(CellMutation { x, y, old: cell, new: Cell::default() });
+        if x > 0 {
+            stack.push((x - 1, y));
+        }
+        if y > 0 {
+            stack.push((x, y - 1));
+        }
+        stack.push((x + 1, y));
+        stack.push((x, y + 1));
     }
 
     mutations
 }
 
+/// How `merge` combines an overlay cell with the base cell beneath it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum BlendMode {
+    /// The overlay cell replaces the base cell.
+    #[default]
+    Over,
+    /// Colors multiply channel-wise (darkens).
+    Multiply,
+    /// Inverse of multiplying the inverses (lightens).
+    Screen,
+}
+
+impl BlendMode {
+    fn blend(self, base: Rgb, top: Rgb) -> Rgb {
+        let channel = |b: u8, t: u8| -> u8 {
+            let (b, t) = (b as u32, t as u32);
+            match self {
+                BlendMode::Over => t as u8,
+                BlendMode::Multiply => (b * t / 255) as u8,
+                BlendMode::Screen => (255 - (255 - b) * (255 - t) / 255) as u8,
+            }
+        };
+        Rgb::new(channel(base.r, top.r), channel(base.g, top.g), channel(base.b, top.b))
+    }
+}
+
+/// Stamp `overlay`'s non-empty cells onto `base` with their top-left at
+/// (at_x, at_y). Empty overlay cells leave the base alone; cells falling
+/// outside the base are dropped. In the color modes the overlay glyph is
+/// kept and each of its colors blends with the base's color in the same
+/// slot, or the base's visible color when that slot is empty; the base bg
+/// shows through where the overlay has none.
+pub fn merge(base: &Canvas, overlay: &Canvas, at_x: usize, at_y: usize, mode: BlendMode) -> Vec<CellMutation> {
+    let mut mutations = Vec::new();
+    for oy in 0..overlay.height {
+        for ox in 0..overlay.width {
+            let (x, y) = (at_x.saturating_add(ox), at_y.saturating_add(oy));
+            let (Some(top), Some(old)) = (overlay.get(ox, oy), base.get(x, y)) else {
+                continue;
+            };
+            if top.is_empty() {
+                continue;
+            }
+            let new = match mode {
+                BlendMode::Over => top,
+                _ => {
+                    let visible = if old.ch == ' ' { old.bg } else { old.fg.or(old.bg) };
+                    let mix = |slot: Option<Rgb>, base_slot: Option<Rgb>| {
+                        slot.map(|t| match base_slot.or(visible) {
+                            Some(b) => mode.blend(b, t),
+                            None => t,
+                        })
+                    };
+                    // A transparent overlay bg shows the base bg through
+                    Cell { ch: top.ch, fg: mix(top.fg, old.fg), bg: mix(top.bg, old.bg).or(old.bg) }
+                }
+            };
+            if new != old {
+                mutations.push(CellMutation { x, y, old, new });
+            }
+        }
+    }
+    mutations
+}
+
 /// Pick color from a canvas cell.
 pub fn eyedropper(canvas: &Canvas, x: usize, y: usize) -> Option<(Option<Rgb>, Option<Rgb>, char)> {
     canvas.get(x, y).map(|cell| (cell.fg, cell.bg, cell.ch))
@@ -267,10 +692,90 @@ mod tests {
     use super::*;
     use crate::cell::blocks;
 
+    fn block(c: Rgb) -> Cell {
+        Cell { ch: blocks::FULL, fg: Some(c), bg: None }
+    }
+
+    fn apply(canvas: &mut Canvas, mutations: &[CellMutation]) {
+        for m in mutations {
+            canvas.set(m.x, m.y, m.new);
+        }
+    }
+
+    #[test]
+    fn test_merge_over_keeps_base_under_empty_cells() {
+        let red = Rgb::new(255, 0, 0);
+        let blue = Rgb::new(0, 0, 255);
+        let mut base = Canvas::new_with_size(16, 16);
+        base.set(2, 2, block(red));
+        base.set(3, 2, block(red));
+
+        // 8x8 overlay, only (1,0) drawn; placed at (2,2) it covers both base cells
+        let mut overlay = Canvas::new_with_size(8, 8);
+        overlay.set(1, 0, block(blue));
+
+        let mutations = merge(&base, &overlay, 2, 2, BlendMode::Over);
+        assert_eq!(mutations.len(), 1, "only the non-empty overlay cell lands");
+        apply(&mut base, &mutations);
+        assert_eq!(base.get(2, 2), Some(block(red)), "empty overlay cell must not erase");
+        assert_eq!(base.get(3, 2), Some(block(blue)), "drawn overlay cell replaces");
+    }
+
+    #[test]
+    fn test_merge_clips_to_base() {
+        let base = Canvas::new_with_size(8, 8);
+        let mut overlay = Canvas::new_with_size(8, 8);
+        overlay.set(0, 0, block(Rgb::new(1, 2, 3)));
+        overlay.set(7, 7, block(Rgb::new(1, 2, 3)));
+        let mutations = merge(&base, &overlay, 4, 4, BlendMode::Over);
+        assert_eq!(mutations.len(), 1);
+        assert_eq!((mutations[0].x, mutations[0].y), (4, 4));
+    }
+
+    #[test]
+    fn test_merge_multiply_and_screen() {
+        let mut base = Canvas::new_with_size(8, 8);
+        base.set(0, 0, block(Rgb::new(200, 100, 0)));
+        // Base is a bg-only space; the overlay's fg blends with that visible bg
+        base.set(1, 0, Cell { ch: ' ', fg: None, bg: Some(Rgb::new(0, 0, 0)) });
+        let mut overlay = Canvas::new_with_size(8, 8);
+        overlay.set(0, 0, block(Rgb::new(128, 255, 255)));
+        overlay.set(1, 0, block(Rgb::new(10, 20, 30)));
+
+        let m = merge(&base, &overlay, 0, 0, BlendMode::Multiply);
+        assert_eq!(m[0].new.fg, Some(Rgb::new(100, 100, 0)));
+        assert_eq!(m[1].new.fg, Some(Rgb::new(0, 0, 0)));
+
+        let s = merge(&base, &overlay, 0, 0, BlendMode::Screen);
+        assert_eq!(s[0].new.fg, Some(Rgb::new(228, 255, 255)));
+        assert_eq!(s[1].new.fg, Some(Rgb::new(10, 20, 30)));
+        // The base bg stays under a glyph without its own bg
+        assert_eq!(s[1].new.bg, Some(Rgb::new(0, 0, 0)));
+    }
+
     const RED: Option<Rgb> = Some(Rgb { r: 205, g: 0, b: 0 });
     const BLUE: Option<Rgb> = Some(Rgb { r: 0, g: 0, b: 238 });
     const GREEN: Option<Rgb> = Some(Rgb { r: 0, g: 205, b: 0 });
 
+    #[test]
+    fn test_tool_from_name() {
+        assert_eq!(ToolKind::from_name("fill"), Some(ToolKind::Fill));
+        assert_eq!(ToolKind::from_name(" Rect "), Some(ToolKind::Rectangle));
+        assert_eq!(ToolKind::from_name("rectangle"), Some(ToolKind::Rectangle));
+        assert_eq!(ToolKind::from_name("Ellipse"), Some(ToolKind::Ellipse));
+        assert_eq!(ToolKind::from_name("Eyedropper"), Some(ToolKind::Eyedropper));
+        assert_eq!(ToolKind::from_name("lasso"), None);
+        assert_eq!(ToolKind::from_name(""), None);
+    }
+
+    #[test]
+    fn test_tool_order_puts_favorites_first() {
+        let order = ToolKind::ordered(&[ToolKind::Fill, ToolKind::Pencil, ToolKind::Fill]);
+        assert_eq!(order.len(), ToolKind::ALL.len());
+        assert_eq!(&order[..3], &[ToolKind::Fill, ToolKind::Pencil, ToolKind::Eraser]);
+        assert_eq!(ToolKind::ordered(&[]), ToolKind::ALL.to_vec());
+    }
+
     fn empty_cell() -> Cell {
         Cell::default()
     }
@@ -376,6 +881,119 @@ mod tests {
         assert_eq!(mutations.len(), 8);
     }
 
+    #[test]
+    fn test_ellipse_outline_and_filled() {
+        let canvas = Canvas::new();
+        let outline = ellipse(&canvas, 0, 0, 4, 4, blocks::FULL, RED, None, false);
+        let filled = ellipse(&canvas, 4, 4, 0, 0, blocks::FULL, RED, None, true);
+        // 5x5 circle: the four corners are cut off, the 3x3 middle is interior
+        assert_eq!(filled.len(), 21);
+        assert_eq!(outline.len(), 12);
+        let at = |m: &[CellMutation], p: (usize, usize)| m.iter().any(|m| (m.x, m.y) == p);
+        for p in [(1, 0), (2, 0), (0, 2), (4, 2), (2, 4), (3, 4)] {
+            assert!(at(&outline, p), "{:?} on the outline", p);
+        }
+        assert!(!at(&outline, (0, 0)) && !at(&filled, (0, 0)));
+        assert!(!at(&outline, (1, 1)) && at(&filled, (1, 1)));
+    }
+
+    #[test]
+    fn test_ellipse_degenerate_boxes() {
+        let canvas = Canvas::new();
+        let single = ellipse(&canvas, 5, 5, 5, 5, blocks::FULL, RED, None, false);
+        assert_eq!(single.len(), 1);
+        assert_eq!((single[0].x, single[0].y), (5, 5));
+        assert_eq!(ellipse(&canvas, 0, 3, 9, 3, blocks::FULL, RED, None, false).len(), 10);
+        assert_eq!(ellipse(&canvas, 2, 0, 2, 7, blocks::FULL, RED, None, false).len(), 8);
+    }
+
+    #[test]
+    fn test_stipple_spacing_one_fills_solidly() {
+        let canvas = Canvas::new();
+        let mutations = stipple(&canvas, 2, 2, 5, 4, 1, blocks::FULL, RED, None);
+        assert_eq!(mutations.len(), 4 * 3);
+    }
+
+    #[test]
+    fn test_stipple_origin_is_region_top_left() {
+        let canvas = Canvas::new();
+        // Corners given bottom-right first still anchor at the top-left
+        let mutations = stipple(&canvas, 9, 9, 3, 3, 3, blocks::FULL, RED, None);
+        let points: Vec<(usize, usize)> = mutations.iter().map(|m| (m.x, m.y)).collect();
+        assert_eq!(points, vec![
+            (3, 3), (6, 3), (9, 3),
+            (3, 6), (6, 6), (9, 6),
+            (3, 9), (6, 9), (9, 9),
+        ]);
+    }
+
+    #[test]
+    fn test_stipple_clips_to_canvas() {
+        let canvas = Canvas::new_with_size(16, 16);
+        let mutations = stipple(&canvas, 10, 10, 200, 200, 4, blocks::FULL, RED, None);
+        // x and y in {10, 14}
+        assert_eq!(mutations.len(), 4);
+        assert!(mutations.iter().all(|m| m.x < 16 && m.y < 16));
+    }
+
+    #[test]
+    fn test_dither2_ratio_extremes_are_solid() {
+        let canvas = Canvas::new_with_size(8, 8);
+        let (a, b) = (RED.unwrap(), BLUE.unwrap());
+        let solid_a = dither2(&canvas, 0, 0, 7, 7, a, b, 0);
+        assert_eq!(solid_a.len(), 64);
+        assert!(solid_a.iter().all(|m| m.new.fg == Some(a)));
+        let solid_b = dither2(&canvas, 0, 0, 7, 7, a, b, 100);
+        assert!(solid_b.iter().all(|m| m.new.fg == Some(b)));
+    }
+
+    #[test]
+    fn test_dither2_half_is_checkerboard_and_clipped() {
+        let canvas = Canvas::new_with_size(8, 8);
+        let (a, b) = (RED.unwrap(), BLUE.unwrap());
+        let half = dither2(&canvas, 0, 0, 3, 3, a, b, 50);
+        assert_eq!(half.iter().filter(|m| m.new.fg == Some(b)).count(), 8);
+        // Bayer 50% is a checkerboard
+        assert!(half.iter().all(|m| (m.new.fg == Some(b)) == ((m.x + m.y) % 2 == 0)));
+        // Same input, same pattern
+        let again = dither2(&canvas, 0, 0, 3, 3, a, b, 50);
+        assert!(half.iter().zip(&again).all(|(m, n)| m.new == n.new));
+
+        let clipped = dither2(&canvas, 6, 6, 200, 200, a, b, 25);
+        assert_eq!(clipped.len(), 4);
+        assert!(clipped.iter().all(|m| m.x >= 6 && m.y >= 6));
+    }
+
+    #[test]
+    fn test_frame_style_names() {
+        assert_eq!(FrameStyle::from_name("Double"), Some(FrameStyle::Double));
+        assert_eq!(FrameStyle::from_name(" rounded "), Some(FrameStyle::Rounded));
+        assert_eq!(FrameStyle::from_name("dotted"), None);
+    }
+
+    #[test]
+    fn test_frame_touches_only_border_cells() {
+        let mut canvas = Canvas::new_with_size(10, 8);
+        let inner = Cell { ch: blocks::FULL, fg: BLUE, bg: None };
+        canvas.set(4, 4, inner);
+        canvas.set(0, 3, Cell { ch: blocks::FULL, fg: BLUE, bg: GREEN });
+
+        let mutations = frame(&canvas, FrameStyle::Rounded, RED);
+        assert_eq!(mutations.len(), 2 * 10 + 2 * 6);
+        assert!(mutations.iter().all(|m| m.x == 0 || m.x == 9 || m.y == 0 || m.y == 7));
+
+        let at = |x, y| mutations.iter().find(|m| m.x == x && m.y == y).unwrap().new;
+        assert_eq!(at(0, 0).ch, '\u{256D}');
+        assert_eq!(at(9, 0).ch, '\u{256E}');
+        assert_eq!(at(0, 7).ch, '\u{2570}');
+        assert_eq!(at(9, 7).ch, '\u{256F}');
+        assert_eq!(at(5, 0).ch, '\u{2500}');
+        assert_eq!(at(9, 5).ch, '\u{2502}');
+        assert_eq!(at(5, 7).fg, RED);
+        // Existing background is kept under the border glyph
+        assert_eq!(at(0, 3).bg, GREEN);
+    }
+
     #[test]
     fn test_flood_fill_boundary() {
         let mut canvas = Canvas::new();
@@ -390,12 +1008,67 @@ mod tests {
         }
         canvas.set(0, 1, wall);
         canvas.set(2, 1, wall);
-        let mutations = flood_fill(&canvas, 1, 1, blocks::FULL, BLUE, None);
+        let mutations = flood_fill(&canvas, 1, 1, blocks::FULL, BLUE, None, FillTarget::Full, false);
         assert_eq!(mutations.len(), 1);
         assert_eq!(mutations[0].x, 1);
         assert_eq!(mutations[0].y, 1);
     }
 
+    #[test]
+    fn test_remove_background_keeps_enclosed_regions() {
+        let mut canvas = Canvas::new_with_size(8, 8);
+        let bg = Cell { ch: blocks::FULL, fg: BLUE, bg: None };
+        let near_bg = Cell { ch: blocks::FULL, fg: Some(Rgb { r: 3, g: 0, b: 236 }), bg: None };
+        let wall = Cell { ch: blocks::FULL, fg: RED, bg: None };
+        for y in 0..8 {
+            for x in 0..8 {
+                canvas.set(x, y, bg);
+            }
+        }
+        canvas.set(7, 0, near_bg);
+        // 4x4 red ring at (2..=5) enclosing a 2x2 pocket of background blue
+        for i in 2..=5 {
+            canvas.set(i, 2, wall);
+            canvas.set(i, 5, wall);
+            canvas.set(2, i, wall);
+            canvas.set(5, i, wall);
+        }
+
+        let exact = remove_background(&canvas, 0);
+        assert_eq!(exact.len(), 64 - 16 - 1, "near-match corner survives at tolerance 0");
+
+        let mutations = remove_background(&canvas, 4);
+        assert_eq!(mutations.len(), 64 - 16);
+        assert!(mutations.iter().all(|m| m.new.is_empty()));
+        assert!(!mutations.iter().any(|m| (3..=4).contains(&m.x) && (3..=4).contains(&m.y)));
+    }
+
+    #[test]
+    fn test_remove_background_transparent_corner_is_noop() {
+        let mut canvas = Canvas::new_with_size(8, 8);
+        canvas.set(3, 3, Cell { ch: blocks::FULL, fg: RED, bg: None });
+        assert!(remove_background(&canvas, 255).is_empty());
+    }
+
+    #[test]
+    fn test_replace_color_swaps_fg_and_bg_within_tolerance() {
+        let red = Rgb::new(205, 0, 0);
+        let near = Rgb::new(200, 4, 0);
+        let blue = Rgb::new(0, 0, 238);
+        let mut canvas = Canvas::new_with_size(4, 4);
+        canvas.set(0, 0, Cell { ch: blocks::FULL, fg: Some(red), bg: None });
+        canvas.set(1, 0, Cell { ch: blocks::UPPER_HALF, fg: Some(Rgb::new(0, 255, 0)), bg: Some(near) });
+        canvas.set(2, 0, Cell { ch: blocks::FULL, fg: Some(Rgb::new(0, 255, 0)), bg: None });
+
+        assert_eq!(replace_color(&canvas, red, blue, 0).len(), 1);
+        let mutations = replace_color(&canvas, red, blue, 5);
+        assert_eq!(mutations.len(), 2);
+        apply(&mut canvas, &mutations);
+        assert_eq!(canvas.get(0, 0).unwrap().fg, Some(blue));
+        assert_eq!(canvas.get(1, 0).unwrap().bg, Some(blue));
+        assert_eq!(canvas.get(1, 0).unwrap().fg, Some(Rgb::new(0, 255, 0)));
+    }
+
     #[test]
     fn test_flood_fill_noop() {
         let canvas = Canvas::new();
@@ -406,10 +1079,28 @@ mod tests {
             ' ',
             Some(Rgb::WHITE),
             None,
+            FillTarget::Full,
+            false,
         );
         assert_eq!(mutations.len(), 0);
     }
 
+    #[test]
+    fn test_flood_fill_diagonal_spreads_through_checkerboard() {
+        let mut canvas = Canvas::new_with_size(6, 6);
+        for y in 0..6 {
+            for x in 0..6 {
+                if (x + y) % 2 == 1 {
+                    canvas.set(x, y, Cell { ch: blocks::FULL, fg: RED, bg: None });
+                }
+            }
+        }
+        let edges = flood_fill(&canvas, 0, 0, blocks::FULL, BLUE, None, FillTarget::Full, false);
+        assert_eq!(edges.len(), 1, "4-connected stops at the red cells");
+        let corners = flood_fill(&canvas, 0, 0, blocks::FULL, BLUE, None, FillTarget::Full, true);
+        assert_eq!(corners.len(), 18, "8-connected reaches every empty square");
+    }
+
     #[test]
     fn test_flood_fill_entire_canvas() {
         let canvas = Canvas::new();
@@ -420,10 +1111,85 @@ mod tests {
             blocks::FULL,
             RED,
             None,
+            FillTarget::Full,
+            false,
         );
         assert_eq!(mutations.len(), canvas.width * canvas.height);
     }
 
+    #[test]
+    fn test_flood_fill_covers_canvas_larger_than_32() {
+        let canvas = Canvas::new_with_size(64, 48);
+        let mutations = flood_fill(&canvas, 63, 47, blocks::FULL, RED, None, FillTarget::Full, false);
+        assert_eq!(mutations.len(), 64 * 48);
+        assert!(mutations.iter().any(|m| m.x == 63 && m.y == 0));
+        assert!(mutations.iter().any(|m| m.x == 0 && m.y == 47));
+    }
+
+    #[test]
+    fn test_flood_fill_bounded_region_on_large_canvas() {
+        let mut canvas = Canvas::new_with_size(100, 100);
+        let wall = Cell { ch: blocks::FULL, fg: RED, bg: None };
+        // Ring from (40,40) to (90,90) enclosing a 49x49 pocket past column 32
+        for i in 40..=90 {
+            canvas.set(i, 40, wall);
+            canvas.set(i, 90, wall);
+            canvas.set(40, i, wall);
+            canvas.set(90, i, wall);
+        }
+        let inside = flood_fill(&canvas, 70, 70, blocks::FULL, BLUE, None, FillTarget::Full, false);
+        assert_eq!(inside.len(), 49 * 49);
+        assert!(inside.iter().all(|m| (41..=89).contains(&m.x) && (41..=89).contains(&m.y)));
+
+        let outside = flood_fill(&canvas, 99, 99, blocks::FULL, BLUE, None, FillTarget::Full, false);
+        assert_eq!(outside.len(), 100 * 100 - 51 * 51);
+    }
+
+    #[test]
+    fn test_flood_fill_bg_only_keeps_glyphs() {
+        let mut canvas = Canvas::new_with_size(8, 8);
+        // Mixed glyphs/fg on a shared bg, walled off by a different bg at x=4
+        canvas.set(0, 0, Cell { ch: blocks::UPPER_HALF, fg: RED, bg: BLUE });
+        canvas.set(1, 0, Cell { ch: blocks::LOWER_HALF, fg: GREEN, bg: BLUE });
+        canvas.set(2, 0, Cell { ch: ' ', fg: None, bg: BLUE });
+        for y in 0..8 {
+            canvas.set(4, y, Cell { ch: ' ', fg: None, bg: RED });
+        }
+        let mut blue_region = 0;
+        for y in 0..8 {
+            for x in 0..4 {
+                if y > 0 || x == 3 {
+                    canvas.set(x, y, Cell { ch: ' ', fg: None, bg: BLUE });
+                }
+                blue_region += 1;
+            }
+        }
+
+        let mutations = flood_fill(&canvas, 0, 0, blocks::FULL, None, GREEN, FillTarget::BgOnly, false);
+        assert_eq!(mutations.len(), blue_region);
+        let first = mutations.iter().find(|m| (m.x, m.y) == (0, 0)).unwrap();
+        assert_eq!(first.new, Cell { ch: blocks::UPPER_HALF, fg: RED, bg: GREEN });
+        assert!(mutations.iter().all(|m| m.x < 4 && m.new.ch == m.old.ch && m.new.fg == m.old.fg));
+    }
+
+    #[test]
+    fn test_flood_fill_fg_only_connects_by_fg() {
+        let mut canvas = Canvas::new_with_size(8, 8);
+        // Same fg, different glyphs and bg: one region for FgOnly, not for Full
+        canvas.set(0, 0, Cell { ch: blocks::FULL, fg: RED, bg: None });
+        canvas.set(1, 0, Cell { ch: blocks::UPPER_HALF, fg: RED, bg: BLUE });
+        canvas.set(2, 0, Cell { ch: blocks::FULL, fg: BLUE, bg: None });
+
+        let full = flood_fill(&canvas, 0, 0, blocks::FULL, GREEN, None, FillTarget::Full, false);
+        assert_eq!(full.len(), 1);
+        let fg = flood_fill(&canvas, 0, 0, blocks::FULL, GREEN, None, FillTarget::FgOnly, false);
+        assert_eq!(fg.len(), 2);
+        let second = fg.iter().find(|m| m.x == 1).unwrap();
+        assert_eq!(second.new, Cell { ch: blocks::UPPER_HALF, fg: GREEN, bg: BLUE });
+        // Already the requested fg: nothing to do
+        assert!(flood_fill(&canvas, 0, 0, ' ', RED, None, FillTarget::FgOnly, false).is_empty());
+    }
+
     #[test]
     fn test_rectangle_outline() {
         let canvas = Canvas::new();
@@ -596,7 +1362,7 @@ mod tests {
     fn test_fill_shade_char() {
         let canvas = Canvas::new();
         // Fill entire empty region with shade char
-        let mutations = flood_fill(&canvas, 0, 0, blocks::SHADE_MEDIUM, RED, None);
+        let mutations = flood_fill(&canvas, 0, 0, blocks::SHADE_MEDIUM, RED, None, FillTarget::Full, false);
         assert!(!mutations.is_empty(), "Fill should produce mutations");
         // All mutations should use shade char
         for m in &mutations {
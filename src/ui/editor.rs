@@ -4,11 +4,13 @@ use ratatui::layout::Rect;
 use ratatui::style::{Color, Style};
 use ratatui::widgets::{Block, Borders, BorderType, Widget};
 
-use crate::app::{App, ReferenceLayer, dim_color};
-use crate::cell::{blocks, is_half_block, Cell, ResolvedHalfBlock, resolve_half_block};
+use crate::app::{App, ReferenceLayer, Selection, dim_color};
+use crate::canvas::Canvas;
+use crate::cell::{blocks, char_width, is_half_block, Cell, ResolvedHalfBlock, Rgb, resolve_half_block};
+use crate::export::{to_display_color, ColorFormat};
 use crate::input::CanvasArea;
 use crate::theme::Theme;
-use crate::tools::{self, ToolState};
+use crate::tools::{self, ToolKind, ToolState};
 
 /// Direction of a symmetry axis at a given cell position.
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -47,6 +49,16 @@ fn render_axis_cell(
     }
 }
 
+/// Whether column/row `i` is drawn as part of a mirror axis given in half-cell
+/// units: the cell the axis passes through, or both cells flanking it.
+fn on_axis(i: usize, axis: usize) -> bool {
+    if axis % 2 == 0 {
+        i * 2 == axis
+    } else {
+        i == axis / 2 || i == axis / 2 + 1
+    }
+}
+
 /// Return the visual background color for an empty/transparent cell position.
 fn grid_bg(x: usize, y: usize, show_grid: bool, theme: &Theme) -> Color {
     if show_grid {
@@ -64,46 +76,123 @@ fn grid_bg(x: usize, y: usize, show_grid: bool, theme: &Theme) -> Color {
 /// the reference layer first, then falling back to grid.
 fn grid_or_reference_bg(
     x: usize, y: usize, show_grid: bool, theme: &Theme,
-    reference: Option<&ReferenceLayer>,
+    reference: Option<&ReferenceLayer>, format: ColorFormat,
 ) -> Color {
     if let Some(ref_layer) = reference {
         if ref_layer.visible {
             if let Some(Some(ref_color)) = ref_layer.colors.get(y).and_then(|row| row.get(x)) {
                 let dimmed = dim_color(ref_color, ref_layer.brightness);
-                return dimmed.to_ratatui();
+                return to_display_color(dimmed, format);
             }
         }
     }
     grid_bg(x, y, show_grid, theme)
 }
 
+/// Isolate-color view: cells that use `color` as fg or bg pass through
+/// untouched, every other cell has its colors dimmed to 25%.
+fn isolate_cell(cell: Cell, color: Rgb) -> Cell {
+    if cell.fg == Some(color) || cell.bg == Some(color) {
+        return cell;
+    }
+    Cell {
+        ch: cell.ch,
+        fg: cell.fg.map(|c| dim_color(&c, 0)),
+        bg: cell.bg.map(|c| dim_color(&c, 0)),
+    }
+}
+
+/// Onion-skin view: the traced project's cell at 50% brightness, so it
+/// reads as a guide rather than as part of the artwork.
+fn ghost_cell(cell: Cell) -> Cell {
+    Cell {
+        ch: cell.ch,
+        fg: cell.fg.map(|c| dim_color(&c, 1)),
+        bg: cell.bg.map(|c| dim_color(&c, 1)),
+    }
+}
+
+/// Shown in place of a glyph that can't fill its cell's columns exactly.
+const UNFIT_GLYPH: char = '?';
+
+/// Text filling `columns` terminal columns for one cell. Wide glyphs are
+/// drawn once per two columns; glyphs that don't fit (wide at zoom 1,
+/// zero-width) become `UNFIT_GLYPH`, so cells never shift the grid.
+fn cell_text(ch: char, columns: usize) -> String {
+    match char_width(ch) {
+        1 => std::iter::repeat_n(ch, columns).collect(),
+        2 if columns.is_multiple_of(2) => std::iter::repeat_n(ch, columns / 2).collect(),
+        _ => std::iter::repeat_n(UNFIT_GLYPH, columns).collect(),
+    }
+}
+
 /// Thin wrapper around `cell::resolve_half_block` that maps transparent halves
 /// to grid background colors for terminal display.
 fn resolve_half_block_for_display(
     cell: Cell, x: usize, y: usize, show_grid: bool, theme: &Theme,
-    reference: Option<&ReferenceLayer>,
+    reference: Option<&ReferenceLayer>, format: ColorFormat,
 ) -> (char, Color, Color) {
     let resolved = resolve_half_block(&cell).unwrap_or(ResolvedHalfBlock {
         ch: cell.ch, fg: cell.fg, bg: cell.bg,
     });
 
     if resolved.ch == ' ' {
-        return (' ', Color::Reset, grid_or_reference_bg(x, y, show_grid, theme, reference));
+        return (' ', Color::Reset, grid_or_reference_bg(x, y, show_grid, theme, reference, format));
     }
 
-    let fg = resolved.fg.map_or(Color::Reset, |rgb| rgb.to_ratatui());
+    let fg = resolved.fg.map_or(Color::Reset, |rgb| to_display_color(rgb, format));
     let bg = resolved.bg.map_or(
-        grid_or_reference_bg(x, y, show_grid, theme, reference),
-        |rgb| rgb.to_ratatui(),
+        grid_or_reference_bg(x, y, show_grid, theme, reference, format),
+        |rgb| to_display_color(rgb, format),
     );
     (resolved.ch, fg, bg)
 }
 
+/// Rotate a half-block glyph clockwise by `quarter_turns` quarter turns.
+/// Other glyphs are returned unchanged.
+fn rotate_glyph(ch: char, quarter_turns: usize) -> char {
+    const CYCLE: [char; 4] = [blocks::UPPER_HALF, blocks::RIGHT_HALF, blocks::LOWER_HALF, blocks::LEFT_HALF];
+    match CYCLE.iter().position(|&c| c == ch) {
+        Some(i) => CYCLE[(i + quarter_turns) % 4],
+        None => ch,
+    }
+}
+
+/// Build a clockwise-rotated copy of the canvas for the rotated view.
+/// The app's canvas is never touched.
+fn rotated_view(canvas: &Canvas, degrees: u16) -> Canvas {
+    let turns = (degrees / 90 % 4) as usize;
+    let (w, h) = (canvas.width, canvas.height);
+    let (nw, nh) = if turns % 2 == 1 { (h, w) } else { (w, h) };
+    let mut out = Canvas::new_with_size(nw, nh);
+    for y in 0..h {
+        for x in 0..w {
+            let Some(mut cell) = canvas.get(x, y) else { continue };
+            cell.ch = rotate_glyph(cell.ch, turns);
+            let (nx, ny) = match turns {
+                1 => (h - 1 - y, x),
+                2 => (w - 1 - x, h - 1 - y),
+                3 => (y, w - 1 - x),
+                _ => (x, y),
+            };
+            out.set(nx, ny, cell);
+        }
+    }
+    out
+}
+
 /// Render the canvas editor and return the screen area for mouse mapping.
 pub fn render(f: &mut Frame, app: &App, area: Rect) -> CanvasArea {
     let theme = app.theme();
     let zoom = app.zoom as u16;
 
+    // Rotated view draws a rotated copy from its top-left; drawing is disabled
+    let rotated = (app.view_rotation != 0).then(|| rotated_view(&app.canvas, app.view_rotation));
+    let (canvas, vp_x0, vp_y0) = match &rotated {
+        Some(c) => (c, 0, 0),
+        None => (&app.canvas, app.viewport_x, app.viewport_y),
+    };
+
     // Viewport: how many canvas cells fit in the available area
     let inner_w = area.width.saturating_sub(2); // border
     let inner_h = area.height.saturating_sub(2);
@@ -114,8 +203,8 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) -> CanvasArea {
     };
 
     // Visible canvas dimensions (clamped to actual canvas size)
-    let vis_w = vp_w.min(app.canvas.width.saturating_sub(app.viewport_x));
-    let vis_h = vp_h.min(app.canvas.height.saturating_sub(app.viewport_y));
+    let vis_w = vp_w.min(canvas.width.saturating_sub(vp_x0));
+    let vis_h = vp_h.min(canvas.height.saturating_sub(vp_y0));
 
     let canvas_w = vis_w as u16 * zoom;
     let canvas_h = match zoom {
@@ -149,14 +238,14 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) -> CanvasArea {
     // Scroll indicators on border edges
     let buf = f.buffer_mut();
     let ind_style = Style::default().fg(theme.dim);
-    if app.viewport_x > 0 {
+    if vp_x0 > 0 {
         // Left arrow on left border
         let mid_y = bordered_rect.y + bordered_rect.height / 2;
         if mid_y < bordered_rect.y + bordered_rect.height {
             buf.set_string(bordered_rect.x, mid_y, "\u{25C0}", ind_style);
         }
     }
-    if app.viewport_x + vis_w < app.canvas.width {
+    if vp_x0 + vis_w < canvas.width {
         // Right arrow on right border
         let mid_y = bordered_rect.y + bordered_rect.height / 2;
         let right_x = bordered_rect.x + bordered_rect.width.saturating_sub(1);
@@ -164,14 +253,14 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) -> CanvasArea {
             buf.set_string(right_x, mid_y, "\u{25B6}", ind_style);
         }
     }
-    if app.viewport_y > 0 {
+    if vp_y0 > 0 {
         // Up arrow on top border
         let mid_x = bordered_rect.x + bordered_rect.width / 2;
         if mid_x < bordered_rect.x + bordered_rect.width {
             buf.set_string(mid_x, bordered_rect.y, "\u{25B2}", ind_style);
         }
     }
-    if app.viewport_y + vis_h < app.canvas.height {
+    if vp_y0 + vis_h < canvas.height {
         // Down arrow on bottom border
         let mid_x = bordered_rect.x + bordered_rect.width / 2;
         let bot_y = bordered_rect.y + bordered_rect.height.saturating_sub(1);
@@ -181,7 +270,7 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) -> CanvasArea {
     }
 
     // Render canvas inside the border
-    let widget = CanvasWidget { app };
+    let widget = CanvasWidget { app, canvas, origin: (vp_x0, vp_y0), rotated: rotated.is_some() };
     f.render_widget(widget, inner_rect);
 
     CanvasArea {
@@ -191,14 +280,31 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) -> CanvasArea {
         height: inner_rect.height,
         viewport_w: vp_w,
         viewport_h: vp_h,
+        palette_left: 0,
     }
 }
 
 struct CanvasWidget<'a> {
     app: &'a App,
+    /// Canvas to draw: the app's canvas, or a rotated copy in rotated view
+    canvas: &'a Canvas,
+    origin: (usize, usize),
+    /// Rotated view: coordinates don't match the app's canvas, so cursor,
+    /// tool preview, symmetry axes, reference layer and onion skin are skipped
+    rotated: bool,
 }
 
 impl<'a> CanvasWidget<'a> {
+    /// Outline of the current selection, or of the one being dragged out.
+    fn is_on_selection_outline(&self, x: usize, y: usize) -> bool {
+        if let (ToolState::SelectStart { x: x0, y: y0 }, Some(cursor)) =
+            (&self.app.tool_state, self.app.effective_cursor())
+        {
+            return Selection::from_corners((*x0, *y0), cursor).on_border(x, y);
+        }
+        self.app.selection.is_some_and(|sel| sel.on_border(x, y))
+    }
+
     fn is_in_tool_preview(&self, x: usize, y: usize) -> bool {
         let cursor = match self.app.effective_cursor() {
             Some(c) => c,
@@ -217,6 +323,11 @@ impl<'a> CanvasWidget<'a> {
                 let is_border = x == min_x || x == max_x || y == min_y || y == max_y;
                 x >= min_x && x <= max_x && y >= min_y && y <= max_y && is_border
             }
+            ToolState::EllipseStart { x: x0, y: y0 } => {
+                tools::on_ellipse(*x0, *y0, cursor.0, cursor.1, false, x, y)
+            }
+            // Drawn as an outline by `is_on_selection_outline`, not as paint
+            ToolState::SelectStart { .. } => false,
             ToolState::Idle => false,
         }
     }
@@ -227,9 +338,10 @@ impl<'a> Widget for CanvasWidget<'a> {
         let zoom = self.app.zoom;
         let show_grid = zoom > 1;
         let theme = self.app.theme();
-        let vp_x = self.app.viewport_x;
-        let vp_y = self.app.viewport_y;
-        let reference = self.app.reference_layer.as_ref();
+        let (vp_x, vp_y) = self.origin;
+        let reference = if self.rotated { None } else { self.app.reference_layer.as_ref() };
+        let onion = if self.rotated { None } else { self.app.onion_skin.as_ref() };
+        let format = self.app.color_format;
 
         // Viewport dimensions in canvas cells
         let vp_w = (area.width / zoom as u16) as usize;
@@ -238,8 +350,11 @@ impl<'a> Widget for CanvasWidget<'a> {
             _ => area.height as usize,
         };
 
-        let vis_w = vp_w.min(self.app.canvas.width.saturating_sub(vp_x));
-        let vis_h = vp_h.min(self.app.canvas.height.saturating_sub(vp_y));
+        let vis_w = vp_w.min(self.canvas.width.saturating_sub(vp_x));
+        let vis_h = vp_h.min(self.canvas.height.saturating_sub(vp_y));
+
+        // Where a dab at the cursor would land (empty in rotated view)
+        let footprint = self.app.brush_footprint();
 
         for vy in 0..vis_h {
             for vx in 0..vis_w {
@@ -256,48 +371,73 @@ impl<'a> Widget for CanvasWidget<'a> {
                     continue;
                 }
 
-                let cell = match self.app.canvas.get(x, y) {
+                let cell = match self.canvas.get(x, y) {
                     Some(c) => c,
                     None => continue,
                 };
 
-                let is_cursor = self.app.effective_cursor() == Some((x, y));
+                let is_cursor = !self.rotated && self.app.effective_cursor() == Some((x, y));
 
                 // Tool preview overlay (line/rect in progress)
-                let render_cell = if self.is_in_tool_preview(x, y) && !is_cursor {
+                let render_cell = if !self.rotated && self.is_in_tool_preview(x, y) && !is_cursor {
                     tools::compose_cell(
                         cell,
                         self.app.active_block,
                         Some(self.app.color),
                         None,
                     )
+                } else if !is_cursor && footprint.contains(&(x, y)) {
+                    // Brush footprint ghost: what the dab would paint or erase
+                    match self.app.active_tool {
+                        ToolKind::Eraser => Cell::default(),
+                        _ => tools::compose_cell(cell, self.app.active_block, Some(self.app.color), None),
+                    }
                 } else {
                     cell
                 };
 
+                // Isolate-color overlay (display only, canvas is untouched)
+                let render_cell = if self.app.isolate_color && !render_cell.is_empty() {
+                    isolate_cell(render_cell, self.app.color)
+                } else {
+                    render_cell
+                };
+
+                // Art outside the clip is dimmed: drawing can't touch it
+                let render_cell = match self.app.clip {
+                    Some(clip) if !self.rotated && !clip.contains(x, y) && !render_cell.is_empty() => ghost_cell(render_cell),
+                    _ => render_cell,
+                };
+
+                // Onion skin shows through empty cells only (display only)
+                let canvas_empty = render_cell.is_empty();
+                let render_cell = match onion.and_then(|o| o.cell_at(x, y)) {
+                    Some(ghost) if canvas_empty => ghost_cell(ghost),
+                    _ => render_cell,
+                };
+
                 // Resolve to (char, fg, bg) using unified path
                 let (mut ch_out, mut fg, mut bg) = if render_cell.ch == blocks::FULL {
-                    let c = render_cell.fg.map_or(Color::Reset, |rgb| rgb.to_ratatui());
+                    let c = render_cell.fg.map_or(Color::Reset, |rgb| to_display_color(rgb, format));
                     ('\u{2588}', c, c)
                 } else if render_cell.is_empty() {
-                    (' ', Color::Reset, grid_or_reference_bg(x, y, show_grid, theme, reference))
+                    (' ', Color::Reset, grid_or_reference_bg(x, y, show_grid, theme, reference, format))
                 } else if is_half_block(render_cell.ch) {
-                    resolve_half_block_for_display(render_cell, x, y, show_grid, theme, reference)
+                    resolve_half_block_for_display(render_cell, x, y, show_grid, theme, reference, format)
                 } else {
                     // Fractional fills, shades, and other single-color blocks
-                    let fg_color = render_cell.fg.map_or(Color::Reset, |rgb| rgb.to_ratatui());
-                    (render_cell.ch, fg_color, grid_or_reference_bg(x, y, show_grid, theme, reference))
+                    let fg_color = render_cell.fg.map_or(Color::Reset, |rgb| to_display_color(rgb, format));
+                    (render_cell.ch, fg_color, grid_or_reference_bg(x, y, show_grid, theme, reference, format))
                 };
 
-                // Symmetry axis visualization
-                let canvas_w = self.app.canvas.width;
-                let canvas_h = self.app.canvas.height;
-                let mid_x = canvas_w / 2;
-                let mid_y = canvas_h / 2;
-                let on_v_line = self.app.symmetry.has_horizontal()
-                    && (x == mid_x.saturating_sub(1) || x == mid_x);
-                let on_h_line = self.app.symmetry.has_vertical()
-                    && (y == mid_y.saturating_sub(1) || y == mid_y);
+                // Symmetry axis visualization, at the configured mirror axes
+                let (axis_x, axis_y) = self.app.symmetry_axes();
+                let on_v_line = !self.rotated
+                    && self.app.symmetry.has_horizontal()
+                    && on_axis(x, axis_x);
+                let on_h_line = !self.rotated
+                    && self.app.symmetry.has_vertical()
+                    && on_axis(y, axis_y);
                 if (on_v_line || on_h_line) && !is_cursor {
                     let direction = match (on_v_line, on_h_line) {
                         (true, true) => AxisDirection::Intersection,
@@ -306,13 +446,23 @@ impl<'a> Widget for CanvasWidget<'a> {
                         _ => unreachable!(),
                     };
                     let result = render_axis_cell(
-                        ch_out, fg, bg, direction, theme, render_cell.is_empty(),
+                        ch_out, fg, bg, direction, theme, canvas_empty,
                     );
                     ch_out = result.0;
                     fg = result.1;
                     bg = result.2;
                 }
 
+                // Selection outline: dotted on empty cells, highlighted bg on art
+                if !self.rotated && !is_cursor && self.is_on_selection_outline(x, y) {
+                    if canvas_empty {
+                        ch_out = '\u{00B7}'; // ·
+                        fg = theme.highlight;
+                    } else {
+                        bg = theme.highlight;
+                    }
+                }
+
                 // Cursor inversion
                 if is_cursor {
                     std::mem::swap(&mut fg, &mut bg);
@@ -322,15 +472,11 @@ impl<'a> Widget for CanvasWidget<'a> {
 
                 // Paint across zoom width
                 match zoom {
-                    1 => {
-                        buf.set_string(screen_x, screen_y, ch_out.to_string(), style);
-                    }
-                    2 => {
-                        let s: String = std::iter::repeat_n(ch_out, 2).collect();
-                        buf.set_string(screen_x, screen_y, &s, style);
+                    1 | 2 => {
+                        buf.set_string(screen_x, screen_y, cell_text(ch_out, zoom as usize), style);
                     }
                     4 => {
-                        let s: String = std::iter::repeat_n(ch_out, 4).collect();
+                        let s = cell_text(ch_out, 4);
                         buf.set_string(screen_x, screen_y, &s, style);
                         // Second row: same content
                         if screen_y + 1 < area.y + area.height {
@@ -343,7 +489,7 @@ impl<'a> Widget for CanvasWidget<'a> {
         }
 
         // Startup hint overlay on blank canvas
-        if self.app.show_startup_hint && self.app.canvas.is_empty() {
+        if self.app.show_startup_hint && !self.rotated && self.app.canvas.is_empty() {
             let line1 = "Click anywhere to start drawing";
             let line2 = "? Help   Ctrl+P Commands";
             let dim = Style::default().fg(theme.dim);
@@ -361,9 +507,46 @@ impl<'a> Widget for CanvasWidget<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cell::Rgb;
     use crate::theme::WARM;
 
+    // --- rotated view tests ---
+
+    #[test]
+    fn rotated_view_quarter_turn_swaps_dims_and_glyphs() {
+        let mut canvas = Canvas::new_with_size(10, 8);
+        canvas.set(0, 0, make_cell(blocks::UPPER_HALF, Some(Rgb::new(255, 0, 0)), None));
+        let view = rotated_view(&canvas, 90);
+        assert_eq!((view.width, view.height), (8, 10));
+        // Top-left moves to top-right; the upper half becomes a right half
+        let moved = view.get(7, 0).unwrap();
+        assert_eq!(moved.ch, blocks::RIGHT_HALF);
+        assert_eq!(moved.fg, Some(Rgb::new(255, 0, 0)));
+        // The source canvas is untouched
+        assert_eq!(canvas.get(0, 0).unwrap().ch, blocks::UPPER_HALF);
+    }
+
+    #[test]
+    fn rotated_view_full_turn_is_identity() {
+        let mut canvas = Canvas::new_with_size(12, 9);
+        canvas.set(3, 5, make_cell(blocks::LEFT_HALF, Some(Rgb::new(1, 2, 3)), None));
+        let mut view = canvas.clone();
+        for _ in 0..4 {
+            view = rotated_view(&view, 90);
+        }
+        assert_eq!(view.cells(), canvas.cells());
+        assert_eq!(rotated_view(&canvas, 180).get(8, 3).unwrap().ch, blocks::RIGHT_HALF);
+    }
+
+    #[test]
+    fn on_axis_between_and_through_cells() {
+        // 32 wide, default axis 31: the two middle columns
+        assert!(on_axis(15, 31) && on_axis(16, 31));
+        assert!(!on_axis(14, 31) && !on_axis(17, 31));
+        // Axis through column 10
+        assert!(on_axis(10, 20));
+        assert!(!on_axis(9, 20) && !on_axis(11, 20));
+    }
+
     // --- grid_bg tests ---
 
     #[test]
@@ -395,7 +578,7 @@ mod tests {
 
     #[test]
     fn upper_half_one_transparent_bottom() {
-        let (ch, fg, bg) = resolve_half_block_for_display(make_cell(blocks::UPPER_HALF, Some(RED), None), 0, 0, true, &WARM, None);
+        let (ch, fg, bg) = resolve_half_block_for_display(make_cell(blocks::UPPER_HALF, Some(RED), None), 0, 0, true, &WARM, None, ColorFormat::Color256);
         assert_eq!(ch, '▀');
         assert_eq!(fg, Color::Indexed(1));
         assert_eq!(bg, WARM.grid_even);
@@ -403,7 +586,7 @@ mod tests {
 
     #[test]
     fn upper_half_both_opaque() {
-        let (ch, fg, bg) = resolve_half_block_for_display(make_cell(blocks::UPPER_HALF, Some(RED), Some(BLUE)), 0, 0, true, &WARM, None);
+        let (ch, fg, bg) = resolve_half_block_for_display(make_cell(blocks::UPPER_HALF, Some(RED), Some(BLUE)), 0, 0, true, &WARM, None, ColorFormat::Color256);
         assert_eq!(ch, '▀');
         assert_eq!(fg, Color::Indexed(1));
         assert_eq!(bg, Color::Indexed(4));
@@ -411,7 +594,7 @@ mod tests {
 
     #[test]
     fn upper_half_one_transparent_top_flips() {
-        let (ch, fg, bg) = resolve_half_block_for_display(make_cell(blocks::UPPER_HALF, None, Some(BLUE)), 0, 0, true, &WARM, None);
+        let (ch, fg, bg) = resolve_half_block_for_display(make_cell(blocks::UPPER_HALF, None, Some(BLUE)), 0, 0, true, &WARM, None, ColorFormat::Color256);
         assert_eq!(ch, '▄');
         assert_eq!(fg, Color::Indexed(4));
         assert_eq!(bg, WARM.grid_even);
@@ -419,14 +602,14 @@ mod tests {
 
     #[test]
     fn upper_half_both_transparent() {
-        let (ch, _fg, bg) = resolve_half_block_for_display(make_cell(blocks::UPPER_HALF, None, None), 0, 0, true, &WARM, None);
+        let (ch, _fg, bg) = resolve_half_block_for_display(make_cell(blocks::UPPER_HALF, None, None), 0, 0, true, &WARM, None, ColorFormat::Color256);
         assert_eq!(ch, ' ');
         assert_eq!(bg, WARM.grid_even);
     }
 
     #[test]
     fn left_half_one_transparent_right() {
-        let (ch, fg, bg) = resolve_half_block_for_display(make_cell(blocks::LEFT_HALF, Some(RED), None), 1, 0, true, &WARM, None);
+        let (ch, fg, bg) = resolve_half_block_for_display(make_cell(blocks::LEFT_HALF, Some(RED), None), 1, 0, true, &WARM, None, ColorFormat::Color256);
         assert_eq!(ch, '▌');
         assert_eq!(fg, Color::Indexed(1));
         assert_eq!(bg, WARM.grid_odd);
@@ -434,7 +617,7 @@ mod tests {
 
     #[test]
     fn left_half_flips_when_left_transparent() {
-        let (ch, fg, bg) = resolve_half_block_for_display(make_cell(blocks::LEFT_HALF, None, Some(RED)), 0, 0, true, &WARM, None);
+        let (ch, fg, bg) = resolve_half_block_for_display(make_cell(blocks::LEFT_HALF, None, Some(RED)), 0, 0, true, &WARM, None, ColorFormat::Color256);
         assert_eq!(ch, '▐');
         assert_eq!(fg, Color::Indexed(1));
         assert_eq!(bg, WARM.grid_even);
@@ -442,7 +625,7 @@ mod tests {
 
     #[test]
     fn lower_half_defensive() {
-        let (ch, fg, bg) = resolve_half_block_for_display(make_cell(blocks::LOWER_HALF, Some(BLUE), None), 0, 0, true, &WARM, None);
+        let (ch, fg, bg) = resolve_half_block_for_display(make_cell(blocks::LOWER_HALF, Some(BLUE), None), 0, 0, true, &WARM, None, ColorFormat::Color256);
         assert_eq!(ch, '▄');
         assert_eq!(fg, Color::Indexed(4));
         assert_eq!(bg, WARM.grid_even);
@@ -450,7 +633,7 @@ mod tests {
 
     #[test]
     fn right_half_defensive() {
-        let (ch, fg, bg) = resolve_half_block_for_display(make_cell(blocks::RIGHT_HALF, Some(RED), None), 0, 0, true, &WARM, None);
+        let (ch, fg, bg) = resolve_half_block_for_display(make_cell(blocks::RIGHT_HALF, Some(RED), None), 0, 0, true, &WARM, None, ColorFormat::Color256);
         assert_eq!(ch, '▐');
         assert_eq!(fg, Color::Indexed(1));
         assert_eq!(bg, WARM.grid_even);
@@ -458,7 +641,7 @@ mod tests {
 
     #[test]
     fn resolve_grid_off_uses_reset() {
-        let (ch, fg, bg) = resolve_half_block_for_display(make_cell(blocks::UPPER_HALF, Some(RED), None), 0, 0, false, &WARM, None);
+        let (ch, fg, bg) = resolve_half_block_for_display(make_cell(blocks::UPPER_HALF, Some(RED), None), 0, 0, false, &WARM, None, ColorFormat::Color256);
         assert_eq!(ch, '▀');
         assert_eq!(fg, Color::Indexed(1));
         assert_eq!(bg, Color::Reset);
@@ -466,7 +649,7 @@ mod tests {
 
     #[test]
     fn left_half_both_opaque() {
-        let (ch, fg, bg) = resolve_half_block_for_display(make_cell(blocks::LEFT_HALF, Some(RED), Some(BLUE)), 0, 0, true, &WARM, None);
+        let (ch, fg, bg) = resolve_half_block_for_display(make_cell(blocks::LEFT_HALF, Some(RED), Some(BLUE)), 0, 0, true, &WARM, None, ColorFormat::Color256);
         assert_eq!(ch, '▌');
         assert_eq!(fg, Color::Indexed(1));
         assert_eq!(bg, Color::Indexed(4));
@@ -510,12 +693,63 @@ mod tests {
         assert_eq!(bg, WARM.panel_bg);
     }
 
+    // --- isolate_cell tests ---
+
+    #[test]
+    fn isolate_keeps_fg_match() {
+        let cell = make_cell(blocks::FULL, Some(RED), None);
+        assert_eq!(isolate_cell(cell, RED), cell);
+    }
+
+    #[test]
+    fn isolate_keeps_bg_match() {
+        let cell = make_cell(blocks::UPPER_HALF, Some(BLUE), Some(RED));
+        assert_eq!(isolate_cell(cell, RED), cell);
+    }
+
+    #[test]
+    fn isolate_dims_non_matching() {
+        let cell = make_cell(blocks::UPPER_HALF, Some(BLUE), Some(BLUE));
+        let out = isolate_cell(cell, RED);
+        assert_eq!(out.ch, blocks::UPPER_HALF);
+        assert_eq!(out.fg, Some(dim_color(&BLUE, 0)));
+        assert_eq!(out.bg, Some(dim_color(&BLUE, 0)));
+    }
+
+    #[test]
+    fn isolate_preserves_transparency() {
+        let cell = make_cell(blocks::LEFT_HALF, Some(BLUE), None);
+        let out = isolate_cell(cell, RED);
+        assert_eq!(out.bg, None);
+    }
+
+    #[test]
+    fn cell_text_keeps_columns_for_wide_glyphs() {
+        let wide = '\u{5B57}'; // 字
+        assert_eq!(cell_text(blocks::FULL, 2), "\u{2588}\u{2588}");
+        assert_eq!(cell_text('a', 1), "a");
+        assert_eq!(cell_text(wide, 2), wide.to_string());
+        assert_eq!(cell_text(wide, 4), format!("{}{}", wide, wide));
+        // Can't fit a single column, or draws nothing at all
+        assert_eq!(cell_text(wide, 1), "?");
+        assert_eq!(cell_text('\u{0301}', 2), "??");
+    }
+
+    #[test]
+    fn ghost_cell_dims_and_keeps_glyph() {
+        let cell = make_cell(blocks::LOWER_HALF, Some(RED), None);
+        let out = ghost_cell(cell);
+        assert_eq!(out.ch, blocks::LOWER_HALF);
+        assert_eq!(out.fg, Some(dim_color(&RED, 1)));
+        assert_eq!(out.bg, None);
+    }
+
     // --- Cycle 018: Reference layer rendering tests ---
 
     #[test]
     fn grid_or_reference_bg_without_reference() {
         // Without reference layer, should fall back to grid_bg
-        let bg = grid_or_reference_bg(0, 0, true, &WARM, None);
+        let bg = grid_or_reference_bg(0, 0, true, &WARM, None, ColorFormat::Color256);
         assert_eq!(bg, WARM.grid_even);
     }
 
@@ -528,7 +762,7 @@ mod tests {
             brightness: 0,
             visible: true,
         };
-        let bg = grid_or_reference_bg(0, 0, true, &WARM, Some(&ref_layer));
+        let bg = grid_or_reference_bg(0, 0, true, &WARM, Some(&ref_layer), ColorFormat::Color256);
         // Should be dimmed reference color (25% at brightness 0)
         let expected = dim_color(&ref_color, 0).to_ratatui();
         assert_eq!(bg, expected);
@@ -543,7 +777,7 @@ mod tests {
             visible: false,
         };
         // Hidden reference should fall back to grid
-        let bg = grid_or_reference_bg(0, 0, true, &WARM, Some(&ref_layer));
+        let bg = grid_or_reference_bg(0, 0, true, &WARM, Some(&ref_layer), ColorFormat::Color256);
         assert_eq!(bg, WARM.grid_even);
     }
 
@@ -556,7 +790,7 @@ mod tests {
             visible: true,
         };
         // Transparent pixel should fall back to grid
-        let bg = grid_or_reference_bg(0, 0, true, &WARM, Some(&ref_layer));
+        let bg = grid_or_reference_bg(0, 0, true, &WARM, Some(&ref_layer), ColorFormat::Color256);
         assert_eq!(bg, WARM.grid_even);
     }
 
@@ -571,7 +805,7 @@ mod tests {
             visible: true,
         };
         let (ch, fg, bg) = resolve_half_block_for_display(
-            make_cell(blocks::UPPER_HALF, Some(RED), None), 0, 0, true, &WARM, Some(&ref_layer),
+            make_cell(blocks::UPPER_HALF, Some(RED), None), 0, 0, true, &WARM, Some(&ref_layer), ColorFormat::Color256,
         );
         assert_eq!(ch, '▀');
         assert_eq!(fg, Color::Indexed(1));
@@ -4,15 +4,54 @@ pub mod palette;
 pub mod statusbar;
 
 use ratatui::Frame;
+use ratatui::buffer::Buffer;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Margin, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph};
 
 use crate::app::{App, AppMode};
+use crate::canvas::Canvas;
+use crate::cell::{blocks, Cell, Rgb};
+use crate::export::to_display_color;
 use crate::input::CanvasArea;
 use crate::theme::Theme;
 
+/// Debug screen dump: the rendered text grid, then one line per cell with
+/// its position, symbol, fg/bg and any modifiers.
+pub fn dump_buffer(buf: &Buffer) -> String {
+    use unicode_width::UnicodeWidthStr;
+
+    let area = buf.area;
+    let mut out = format!("screen {}x{}\n", area.width, area.height);
+    for y in area.top()..area.bottom() {
+        // A wide glyph covers the next cell too; don't print that cell again
+        let mut covered = 0;
+        for x in area.left()..area.right() {
+            if covered > 0 {
+                covered -= 1;
+                continue;
+            }
+            let symbol = buf[(x, y)].symbol();
+            covered = symbol.width().saturating_sub(1);
+            out.push_str(symbol);
+        }
+        out.push('\n');
+    }
+    out.push('\n');
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = &buf[(x, y)];
+            out.push_str(&format!("{},{} {:?} fg={:?} bg={:?}", x, y, cell.symbol(), cell.fg, cell.bg));
+            if !cell.modifier.is_empty() {
+                out.push_str(&format!(" mod={:?}", cell.modifier));
+            }
+            out.push('\n');
+        }
+    }
+    out
+}
+
 /// Render the full UI and return the canvas area for mouse mapping.
 pub fn render(f: &mut Frame, app: &App) -> CanvasArea {
     let size = f.area();
@@ -55,6 +94,7 @@ pub fn render(f: &mut Frame, app: &App) -> CanvasArea {
             height: 0,
             viewport_w: 0,
             viewport_h: 0,
+            palette_left: 0,
         };
     }
 
@@ -121,7 +161,14 @@ pub fn render(f: &mut Frame, app: &App) -> CanvasArea {
     ], theme);
 
     // Canvas — unified zoom-aware renderer
-    let canvas_screen_area = editor::render(f, app, canvas_area);
+    let mut canvas_screen_area = editor::render(f, app, canvas_area);
+    canvas_screen_area.palette_left = palette_area.x;
+    if app.show_minimap {
+        render_minimap(f, app, canvas_area);
+    }
+    if let Some(order) = app.radial_order {
+        render_radial_overlay(f, app, order, canvas_area);
+    }
 
     // Palette (3 boxes)
     let colors_lines = palette::color_lines(app);
@@ -152,6 +199,10 @@ pub fn render(f: &mut Frame, app: &App) -> CanvasArea {
         AppMode::Recovery => render_recovery_prompt(f, app, size),
         AppMode::ColorSliders => render_color_sliders(f, app, size),
         AppMode::PaletteDialog => render_palette_dialog(f, app, size),
+        AppMode::PaletteDeleteConfirm => {
+            render_palette_dialog(f, app, size);
+            render_palette_delete_prompt(f, app, size);
+        }
         AppMode::PaletteNameInput => render_text_input(f, app, size, "New Palette", "Enter palette name:"),
         AppMode::PaletteRename => render_text_input(f, app, size, "Rename Palette", "Enter new name:"),
         AppMode::PaletteExport => render_text_input(f, app, size, "Export Palette", "Enter destination path:"),
@@ -164,12 +215,142 @@ pub fn render(f: &mut Frame, app: &App) -> CanvasArea {
         AppMode::ImportOptions => render_import_options(f, app, size),
         AppMode::CommandPalette => render_command_palette(f, app, size),
         AppMode::GotoInput => render_goto_input(f, app, size),
+        AppMode::HistoryTimeline => render_history_timeline(f, app, size),
+        AppMode::ShiftCanvas => render_shift_canvas(f, app, size),
         _ => {}
     }
 
     canvas_screen_area
 }
 
+/// Largest minimap, in terminal cells inside its border.
+const MINIMAP_MAX_W: usize = 24;
+const MINIMAP_MAX_H: usize = 8;
+
+/// Color a cell shows in its top or bottom half, for downscaling. Vertical
+/// half blocks split; everything else is one color.
+fn half_pixel(cell: Cell, top: bool) -> Option<Rgb> {
+    if cell.is_empty() {
+        return None;
+    }
+    match crate::cell::resolve_half_block(&cell) {
+        Some(r) if r.ch == blocks::UPPER_HALF => if top { r.fg } else { r.bg },
+        Some(r) if r.ch == blocks::LOWER_HALF => if top { r.bg } else { r.fg },
+        Some(r) => r.fg.or(r.bg),
+        None if cell.ch == ' ' => cell.bg,
+        None => cell.fg.or(cell.bg),
+    }
+}
+
+/// Downscale the whole canvas (nearest neighbor, shape kept) to half-block
+/// pixels fitting `max_w` x `max_h` terminal cells: two pixel rows per cell
+/// row, like the half-block import. `None` is an empty pixel.
+fn minimap_pixels(canvas: &Canvas, max_w: usize, max_h: usize) -> Vec<Vec<Option<Rgb>>> {
+    let (w, h) = (canvas.width, canvas.height * 2);
+    let scale = (max_w as f32 / w as f32).min((max_h * 2) as f32 / h as f32).min(1.0);
+    let mw = ((w as f32 * scale).ceil() as usize).clamp(1, max_w);
+    let mh = ((h as f32 * scale).ceil() as usize).clamp(1, max_h * 2);
+    (0..mh)
+        .map(|py| {
+            let sy = py * h / mh;
+            (0..mw)
+                .map(|px| canvas.get(px * w / mw, sy / 2).and_then(|c| half_pixel(c, sy % 2 == 0)))
+                .collect()
+        })
+        .collect()
+}
+
+/// Minimap overlay in the canvas area's bottom-right corner: the whole
+/// canvas downscaled, with everything outside the current viewport dimmed.
+fn render_minimap(f: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
+    let canvas = &app.canvas;
+    let pixels = minimap_pixels(canvas, MINIMAP_MAX_W, MINIMAP_MAX_H);
+    let (mw, mh) = (pixels[0].len(), pixels.len());
+    let rows = mh.div_ceil(2);
+    let map_area = Rect::new(
+        area.right().saturating_sub(mw as u16 + 2),
+        area.bottom().saturating_sub(rows as u16 + 2),
+        mw as u16 + 2,
+        rows as u16 + 2,
+    )
+    .intersection(area);
+
+    // Viewport in canvas cells; a minimap cell is inside when the canvas
+    // cell its top pixel samples is
+    let (vx, vy) = (app.viewport_x, app.viewport_y);
+    let (vw, vh) = (app.viewport_w.max(1), app.viewport_h.max(1));
+    let in_view = |col: usize, row: usize| {
+        let cx = col * canvas.width / mw;
+        let cy = row * 2 * canvas.height * 2 / mh / 2;
+        (vx..vx + vw).contains(&cx) && (vy..vy + vh).contains(&cy)
+    };
+
+    let lines: Vec<Line> = (0..rows)
+        .map(|row| {
+            let spans: Vec<Span> = (0..mw)
+                .map(|col| {
+                    let visible = in_view(col, row);
+                    let shade = |c: Option<Rgb>| match c {
+                        Some(rgb) if visible => to_display_color(rgb, app.color_format),
+                        Some(rgb) => to_display_color(crate::app::dim_color(&rgb, 1), app.color_format),
+                        None if visible => theme.dim,
+                        None => Color::Reset,
+                    };
+                    let top = shade(pixels[row * 2][col]);
+                    let bottom = shade(pixels.get(row * 2 + 1).and_then(|r| r[col]));
+                    Span::styled("\u{2580}", Style::default().fg(top).bg(bottom))
+                })
+                .collect();
+            Line::from(spans)
+        })
+        .collect();
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.separator))
+        .title(" Map ");
+    f.render_widget(Clear, map_area);
+    f.render_widget(Paragraph::new(lines).block(block), map_area);
+}
+
+/// Entries of the radial symmetry overlay, in cycle order, each paired
+/// with whether it is the active order.
+fn radial_overlay_items(current: usize) -> Vec<(String, bool)> {
+    crate::symmetry::RADIAL_ORDERS
+        .iter()
+        .map(|&n| (n.to_string(), n == current))
+        .chain(std::iter::once(("off".to_string(), false)))
+        .collect()
+}
+
+/// Small box in the canvas area's top-right corner listing the radial
+/// orders with the active one highlighted; `*` steps to the next.
+fn render_radial_overlay(f: &mut Frame, app: &App, order: usize, area: Rect) {
+    let theme = app.theme();
+    let mut spans = vec![Span::raw(" ")];
+    for (label, active) in radial_overlay_items(order) {
+        let style = if active {
+            Style::default().fg(Color::Black).bg(theme.accent).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.dim)
+        };
+        spans.push(Span::styled(label, style));
+        spans.push(Span::raw(" "));
+    }
+    let width = spans.iter().map(|s| s.width()).sum::<usize>() as u16 + 2;
+    let overlay_area = Rect::new(area.right().saturating_sub(width), area.y, width, 3).intersection(area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.separator))
+        .title(" Radial (*) ");
+    f.render_widget(Clear, overlay_area);
+    f.render_widget(Paragraph::new(Line::from(spans)).block(block), overlay_area);
+}
+
 struct BoxContent<'a> {
     title: &'a str,
     lines: &'a [ratatui::text::Line<'static>],
@@ -324,13 +505,23 @@ fn render_header(f: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         .unwrap_or("untitled");
     let dirty_marker = if app.dirty { "*" } else { "" };
     let tool_name = app.active_tool.name();
-    let sym = app.symmetry.label();
+    let sym = match app.radial_order {
+        Some(n) if app.symmetry.is_empty() => format!("Radial {}", n),
+        Some(n) => format!("{} + Radial {}", app.symmetry.label(), n),
+        None => app.symmetry.label(),
+    };
+    let channel = if app.paint_bg { "BG" } else { "FG" };
+    let view = if app.view_rotation != 0 {
+        format!("  View: \u{21BB}{}\u{00B0}", app.view_rotation)
+    } else {
+        String::new()
+    };
 
     let header_text = format!(
         " \u{0295}\u{2022}\u{1d25}\u{2022}\u{0294} kakukuma \u{2014} {}{} {:>width$}",
         name,
         dirty_marker,
-        format!("Tool: {}  Sym: {}", tool_name, sym),
+        format!("Tool: {}  Paint: {}  Sym: {}{}", tool_name, channel, sym, view),
         width = (area.width as usize).saturating_sub(name.len() + dirty_marker.len() + 22)
     );
 
@@ -339,123 +530,78 @@ fn render_header(f: &mut Frame, app: &App, area: Rect, theme: &Theme) {
     f.render_widget(header, area);
 }
 
+/// Rows of help text visible at once on a screen `screen_height` tall.
+fn help_body_height(lines: u16, screen_height: u16) -> u16 {
+    lines.min(24).min(screen_height.saturating_sub(2))
+}
+
+/// Furthest the help overlay can scroll on a screen `screen_height` tall.
+pub fn help_max_scroll(app: &App, screen_height: u16) -> u16 {
+    use crate::keymap::HelpSection;
+    // Per section a blank line, title and underline; then the mouse hint
+    let lines = HelpSection::ALL.iter().map(|s| 3 + app.keymap.help_rows(*s).len()).sum::<usize>() + 2;
+    let lines = lines.min(u16::MAX as usize) as u16;
+    lines - help_body_height(lines, screen_height)
+}
+
 fn render_help(f: &mut Frame, app: &App, area: Rect) {
+    use crate::keymap::HelpSection;
     use ratatui::text::Span;
     let theme = app.theme();
 
     let sep = Style::default().fg(theme.separator).bg(theme.panel_bg);
     let hdr = Style::default().fg(theme.accent).bg(theme.panel_bg);
     let txt = Style::default().fg(Color::White).bg(theme.panel_bg);
+    let key = Style::default().fg(theme.highlight).bg(theme.panel_bg);
     let dim = Style::default().fg(theme.dim).bg(theme.panel_bg);
 
-    let lines: Vec<ratatui::text::Line> = vec![
-        ratatui::text::Line::from(""),
-        ratatui::text::Line::from(vec![
-            Span::styled("  Tools", hdr),
-            Span::styled("                Canvas", hdr),
-        ]),
-        ratatui::text::Line::from(vec![
-            Span::styled("  \u{2500}\u{2500}\u{2500}\u{2500}\u{2500}", sep),
-            Span::styled("                \u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}", sep),
-        ]),
-        ratatui::text::Line::from(vec![
-            Span::styled("  P  Pencil", txt),
-            Span::styled("         WASD  Move cursor", txt),
-        ]),
-        ratatui::text::Line::from(vec![
-            Span::styled("  E  Eraser", txt),
-            Span::styled("         Space Draw at cursor", txt),
-        ]),
-        ratatui::text::Line::from(vec![
-            Span::styled("  L  Line", txt),
-            Span::styled("           Mouse Click/drag", txt),
-        ]),
-        ratatui::text::Line::from(vec![
-            Span::styled("  R  Rectangle", txt),
-            Span::styled("      Z    Cycle zoom (1x/2x/4x)", txt),
-        ]),
-        ratatui::text::Line::from(vec![
-            Span::styled("  F  Fill", txt),
-            Span::styled("           B    Cycle block", txt),
-        ]),
-        ratatui::text::Line::from(vec![
-            Span::styled("  I  Eyedropper", txt),
-            Span::styled("     \u{21E7}B   Block picker", txt),
-        ]),
-        ratatui::text::Line::from(vec![
-            Span::styled("                    ", txt),
-            Span::styled("G    Cycle shade (\u{2591}\u{2592}\u{2593})", txt),
-        ]),
-        ratatui::text::Line::from(vec![
-            Span::styled("                    ", txt),
-            Span::styled("T    Rect fill/outline", txt),
-        ]),
-        ratatui::text::Line::from(""),
-        ratatui::text::Line::from(vec![
-            Span::styled("  Colors", hdr),
-            Span::styled("            Symmetry", hdr),
-        ]),
-        ratatui::text::Line::from(vec![
-            Span::styled("  \u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}", sep),
-            Span::styled("            \u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}", sep),
-        ]),
-        ratatui::text::Line::from(vec![
-            Span::styled("  1-0  Quick pick", txt),
-            Span::styled("   H  Horizontal mirror", txt),
-        ]),
-        ratatui::text::Line::from(vec![
-            Span::styled("  S    HSL sliders", txt),
-            Span::styled("  V  Vertical mirror", txt),
-        ]),
-        ratatui::text::Line::from(Span::styled("  X    Hex color input", txt)),
-        ratatui::text::Line::from(vec![
-            Span::styled("  A    Add color", txt),
-            Span::styled("    File", hdr),
-        ]),
-        ratatui::text::Line::from(vec![
-            Span::styled("  C    Palettes", txt),
-            Span::styled("     \u{2500}\u{2500}\u{2500}\u{2500}", sep),
-        ]),
-        ratatui::text::Line::from(vec![
-            Span::styled("                    ", txt),
-            Span::styled("^S Save  ^O Open", txt),
-        ]),
-        ratatui::text::Line::from(vec![
-            Span::styled("  Palette", hdr),
-            Span::styled("           ^N New   ^E Export", txt),
-        ]),
-        ratatui::text::Line::from(vec![
-            Span::styled("  \u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}\u{2500}", sep),
-            Span::styled("           ^Z Undo  ^Y Redo", txt),
-        ]),
-        ratatui::text::Line::from(vec![
-            Span::styled("  \u{2191}\u{2193}\u{2190}\u{2192} Browse", txt),
-            Span::styled("        ^T Theme", txt),
-        ]),
-        ratatui::text::Line::from(vec![
-            Span::styled("  Enter  Select/Toggle", txt),
-            Span::styled("  Q Quit  ? Help", txt),
-        ]),
-        ratatui::text::Line::from(""),
-        ratatui::text::Line::from(Span::styled(
-            "         Press any key to close",
-            dim,
-        )),
-    ];
+    // Built from the live keymap so remapped keys show up here
+    let mut lines: Vec<ratatui::text::Line> = Vec::new();
+    for section in HelpSection::ALL {
+        lines.push(ratatui::text::Line::from(""));
+        let title = section.title();
+        lines.push(ratatui::text::Line::from(Span::styled(format!("  {}", title), hdr)));
+        lines.push(ratatui::text::Line::from(Span::styled(
+            format!("  {}", "\u{2500}".repeat(title.chars().count())),
+            sep,
+        )));
+        for (keys, label) in app.keymap.help_rows(section) {
+            lines.push(ratatui::text::Line::from(vec![
+                Span::styled(format!("  {:<12}", keys), key),
+                Span::styled(label, txt),
+            ]));
+        }
+    }
+    lines.push(ratatui::text::Line::from(""));
+    lines.push(ratatui::text::Line::from(vec![
+        Span::styled("  Mouse       ", key),
+        Span::styled("Click/drag to draw, right-click picks", txt),
+    ]));
 
-    let width = 48;
-    let height = lines.len() as u16 + 2;
+    let width = 48u16.min(area.width);
+    // Fixed box; content scrolls inside it
+    let body_height = help_body_height(lines.len() as u16, area.height);
+    let height = body_height + 2;
+    let max_scroll = (lines.len() as u16).saturating_sub(body_height);
+    let scroll = app.help_scroll.min(max_scroll);
     let x = (area.width.saturating_sub(width)) / 2;
     let y = (area.height.saturating_sub(height)) / 2;
     let help_area = Rect::new(x, y, width, height);
 
+    let footer = if max_scroll > 0 {
+        " \u{2191}\u{2193} Scroll \u{00B7} any other key to close "
+    } else {
+        " Press any key to close "
+    };
     let help = Paragraph::new(lines)
         .style(Style::default().fg(Color::White).bg(theme.panel_bg))
+        .scroll((scroll, 0))
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
                 .title(" Help ")
+                .title_bottom(Line::from(Span::styled(footer, dim)))
                 .style(Style::default().fg(Color::White).bg(theme.panel_bg)),
         );
     f.render_widget(Clear, help_area);
@@ -469,7 +615,10 @@ fn render_quit_prompt(f: &mut Frame, area: Rect) {
     let y = (area.height.saturating_sub(height)) / 2;
     let prompt_area = Rect::new(x, y, width, height);
 
-    let prompt = Paragraph::new(" Unsaved changes. Quit? (y/n)")
+    let prompt = Paragraph::new(vec![
+        Line::from(" Unsaved changes."),
+        Line::from(" [S]ave & quit  [D]iscard  [C]ancel"),
+    ])
         .style(Style::default().fg(Color::White).bg(Color::Red))
         .block(
             Block::default()
@@ -482,6 +631,36 @@ fn render_quit_prompt(f: &mut Frame, area: Rect) {
     f.render_widget(prompt, prompt_area);
 }
 
+fn render_palette_delete_prompt(f: &mut Frame, app: &App, area: Rect) {
+    let Some((ref filename, count)) = app.palette_delete_pending else {
+        return;
+    };
+    let width = 44;
+    let height = 5;
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+    let prompt_area = Rect::new(x, y, width, height);
+
+    let summary = match count {
+        Some(n) => format!(" Delete {} ({} colors)?", filename, n),
+        None => format!(" Delete {}?", filename),
+    };
+    let prompt = Paragraph::new(vec![
+        Line::from(summary),
+        Line::from(" [Y]es, delete  [N]o"),
+    ])
+        .style(Style::default().fg(Color::White).bg(Color::Red))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title(" Delete Palette ")
+                .style(Style::default().fg(Color::White).bg(Color::Red)),
+        );
+    f.render_widget(Clear, prompt_area);
+    f.render_widget(prompt, prompt_area);
+}
+
 fn render_file_dialog(f: &mut Frame, app: &App, area: Rect) {
     let theme = app.theme();
     let file_count = app.file_dialog_files.len();
@@ -491,6 +670,12 @@ fn render_file_dialog(f: &mut Frame, app: &App, area: Rect) {
     let y = (area.height.saturating_sub(height)) / 2;
     let dialog_area = Rect::new(x, y, width, height);
 
+    let (title, action) = if app.file_dialog_for_onion {
+        (" Onion Skin ", "Load")
+    } else {
+        (" Open File ", "Open")
+    };
+
     let mut lines: Vec<ratatui::text::Line> = Vec::new();
     let visible_start = if app.file_dialog_selected > (height as usize).saturating_sub(5) {
         app.file_dialog_selected - (height as usize).saturating_sub(5)
@@ -517,7 +702,7 @@ fn render_file_dialog(f: &mut Frame, app: &App, area: Rect) {
 
     lines.push(ratatui::text::Line::from(""));
     lines.push(ratatui::text::Line::from(ratatui::text::Span::styled(
-        " \u{2191}\u{2193} Navigate  Enter Open  Esc Cancel",
+        format!(" \u{2191}\u{2193} Navigate  Enter {}  Esc Cancel", action),
         Style::default().fg(theme.dim).bg(theme.panel_bg),
     )));
 
@@ -527,7 +712,7 @@ fn render_file_dialog(f: &mut Frame, app: &App, area: Rect) {
             Block::default()
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .title(" Open File ")
+                .title(title)
                 .style(Style::default().fg(Color::White).bg(theme.panel_bg)),
         );
     f.render_widget(Clear, dialog_area);
@@ -538,12 +723,12 @@ fn render_export_dialog(f: &mut Frame, app: &App, area: Rect) {
     let theme = app.theme();
     let is_colored = app.export_format == 1;
     let width = 42;
-    let height = if is_colored { 17 } else { 12 };
+    let height = if is_colored { 19 } else { 14 };
     let x = (area.width.saturating_sub(width)) / 2;
     let y = (area.height.saturating_sub(height)) / 2;
     let dialog_area = Rect::new(x, y, width, height);
 
-    let format_opts = ["Plain", "Colored"];
+    let format_opts = ["Plain", "Colored", "PNG", "HTML"];
     let color_fmt_opts = ["24-bit RGB", "256 color", "16 color"];
     let dest_opts = ["Clipboard", "File"];
 
@@ -569,17 +754,18 @@ fn render_export_dialog(f: &mut Frame, app: &App, area: Rect) {
             Style::default().fg(Color::White).bg(theme.panel_bg)
         };
         fmt_spans.push(ratatui::text::Span::styled(format!(" {} ", opt), style));
-        if i == 0 {
+        if i < format_opts.len() - 1 {
             fmt_spans.push(ratatui::text::Span::raw(" "));
         }
     }
     lines.push(ratatui::text::Line::from(fmt_spans));
 
     // Format description
-    let fmt_desc = if is_colored {
-        "  Blocks with ANSI color codes"
-    } else {
-        "  Block characters only, no color"
+    let fmt_desc = match app.export_format {
+        1 => "  Blocks with ANSI color codes",
+        2 => "  Image, 8x16 pixels per cell",
+        3 => "  Styled <pre> block for web pages",
+        _ => "  Block characters only, no color",
     };
     lines.push(ratatui::text::Line::from(ratatui::text::Span::styled(fmt_desc, dim_style)));
     lines.push(ratatui::text::Line::from(""));
@@ -621,29 +807,54 @@ fn render_export_dialog(f: &mut Frame, app: &App, area: Rect) {
 
     // Destination row (cursor == 1 for Plain, cursor == 2 for Colored)
     let dest_cursor = if is_colored { 2 } else { 1 };
-    let ext = if is_colored { ".ans" } else { ".txt" };
+    let ext = match app.export_format {
+        1 => ".ans",
+        2 => ".png",
+        3 => ".html",
+        _ => ".txt",
+    };
     lines.push(ratatui::text::Line::from(ratatui::text::Span::styled(
         format!(" Destination ({}):", ext),
         Style::default().fg(theme.accent).bg(theme.panel_bg),
     )));
-    let mut dest_spans = Vec::new();
-    dest_spans.push(ratatui::text::Span::raw("  "));
-    for (i, opt) in dest_opts.iter().enumerate() {
-        let selected = i == app.export_dest;
-        let focused = app.export_cursor == dest_cursor;
-        let style = if selected && focused {
-            Style::default().fg(Color::Indexed(16)).bg(theme.highlight)
-        } else if selected {
-            Style::default().fg(Color::Indexed(16)).bg(Color::Gray)
-        } else {
-            Style::default().fg(Color::White).bg(theme.panel_bg)
-        };
-        dest_spans.push(ratatui::text::Span::styled(format!(" {} ", opt), style));
-        if i == 0 {
-            dest_spans.push(ratatui::text::Span::raw(" "));
+    if app.export_format == 2 {
+        // PNG is binary, so it is always written to a file
+        lines.push(ratatui::text::Line::from(ratatui::text::Span::styled("   File ", dim_style)));
+    } else {
+        let mut dest_spans = Vec::new();
+        dest_spans.push(ratatui::text::Span::raw("  "));
+        for (i, opt) in dest_opts.iter().enumerate() {
+            let selected = i == app.export_dest;
+            let focused = app.export_cursor == dest_cursor;
+            let style = if selected && focused {
+                Style::default().fg(Color::Indexed(16)).bg(theme.highlight)
+            } else if selected {
+                Style::default().fg(Color::Indexed(16)).bg(Color::Gray)
+            } else {
+                Style::default().fg(Color::White).bg(theme.panel_bg)
+            };
+            dest_spans.push(ratatui::text::Span::styled(format!(" {} ", opt), style));
+            if i == 0 {
+                dest_spans.push(ratatui::text::Span::raw(" "));
+            }
         }
+        lines.push(ratatui::text::Line::from(dest_spans));
     }
-    lines.push(ratatui::text::Line::from(dest_spans));
+    lines.push(ratatui::text::Line::from(""));
+
+    // Selection-only toggle (S); greyed out without a selection
+    let region = match app.selection {
+        Some(sel) if app.export_selection => ratatui::text::Span::styled(
+            format!(" [S] Selection only: {}x{}", sel.width, sel.height),
+            Style::default().fg(theme.accent).bg(theme.panel_bg),
+        ),
+        Some(_) => ratatui::text::Span::styled(
+            " [S] Selection only: Off",
+            Style::default().fg(Color::White).bg(theme.panel_bg),
+        ),
+        None => ratatui::text::Span::styled(" [S] Selection only: (no selection)", dim_style),
+    };
+    lines.push(ratatui::text::Line::from(region));
     lines.push(ratatui::text::Line::from(""));
 
     lines.push(ratatui::text::Line::from(ratatui::text::Span::styled(
@@ -725,17 +936,13 @@ fn render_recovery_prompt(f: &mut Frame, app: &App, area: Rect) {
 fn render_color_sliders(f: &mut Frame, app: &App, area: Rect) {
     let theme = app.theme();
     let width = 44;
-    let height = 15;
+    let height = 16;
     let x = (area.width.saturating_sub(width)) / 2;
     let y = (area.height.saturating_sub(height)) / 2;
     let dialog_area = Rect::new(x, y, width, height);
 
     let bar_width = 20;
-    let sliders: [(&str, u16, u16); 3] = [
-        ("H", app.slider_h, 359),
-        ("S", app.slider_s as u16, 100),
-        ("L", app.slider_l as u16, 100),
-    ];
+    let sliders: [(&str, u16, u16); 3] = [app.slider(0), app.slider(1), app.slider(2)];
 
     let mut lines: Vec<ratatui::text::Line> = Vec::new();
 
@@ -764,19 +971,26 @@ fn render_color_sliders(f: &mut Frame, app: &App, area: Rect) {
         lines.push(ratatui::text::Line::from(vec![
             ratatui::text::Span::styled(format!(" {} ", label), label_style),
             ratatui::text::Span::styled(bar, bar_style),
-            ratatui::text::Span::styled(
-                format!(" {:>3}", value),
-                Style::default().fg(Color::White).bg(theme.panel_bg),
-            ),
+            if is_active && !app.slider_input.is_empty() {
+                // Typed entry: show the digits with a caret
+                ratatui::text::Span::styled(
+                    format!(" {:>3}\u{2588}", app.slider_input),
+                    Style::default().fg(theme.accent).bg(theme.panel_bg),
+                )
+            } else {
+                ratatui::text::Span::styled(
+                    format!(" {:>3}", value),
+                    Style::default().fg(Color::White).bg(theme.panel_bg),
+                )
+            },
         ]));
     }
 
     lines.push(ratatui::text::Line::from(""));
 
     // Live preview
-    let (r, g, b) = crate::palette::hsl_to_rgb(app.slider_h, app.slider_s, app.slider_l);
-    let preview_color = crate::palette::nearest_color(r, g, b);
-    let preview_rcolor = preview_color.to_ratatui();
+    let preview_color = app.slider_color();
+    let preview_rcolor = crate::export::to_display_color(preview_color, app.color_format);
     let idx_256 = crate::cell::nearest_256(&preview_color);
 
     lines.push(ratatui::text::Line::from(vec![
@@ -806,6 +1020,10 @@ fn render_color_sliders(f: &mut Frame, app: &App, area: Rect) {
         " \u{2191}\u{2193} Slider  \u{2190}\u{2192} Adjust  Enter Apply  Esc Cancel",
         Style::default().fg(theme.dim).bg(theme.panel_bg),
     )));
+    lines.push(ratatui::text::Line::from(ratatui::text::Span::styled(
+        " Shift \u{00B1}1  PgUp/Dn \u{00B1}25  0-9 Type  Tab RGB",
+        Style::default().fg(theme.dim).bg(theme.panel_bg),
+    )));
 
     let dialog = Paragraph::new(lines)
         .style(Style::default().fg(Color::White).bg(theme.panel_bg))
@@ -813,7 +1031,7 @@ fn render_color_sliders(f: &mut Frame, app: &App, area: Rect) {
             Block::default()
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .title(" Color Sliders ")
+                .title(if app.slider_rgb { " Color Sliders (RGB) " } else { " Color Sliders (HSL) " })
                 .style(Style::default().fg(Color::White).bg(theme.panel_bg)),
         );
     f.render_widget(Clear, dialog_area);
@@ -900,7 +1118,7 @@ fn render_palette_dialog(f: &mut Frame, app: &App, area: Rect) {
 fn render_hex_input(f: &mut Frame, app: &App, area: Rect) {
     let theme = app.theme();
     let width = 40u16;
-    let height = 9u16;
+    let height = 10u16;
     let x = (area.width.saturating_sub(width)) / 2;
     let y = (area.height.saturating_sub(height)) / 2;
     let dialog_area = Rect::new(x, y, width, height);
@@ -908,7 +1126,7 @@ fn render_hex_input(f: &mut Frame, app: &App, area: Rect) {
     let mut lines: Vec<ratatui::text::Line> = Vec::new();
 
     lines.push(ratatui::text::Line::from(ratatui::text::Span::styled(
-        " Enter hex color (#RRGGBB):",
+        " Enter color (#RRGGBB, #RGB or name):",
         Style::default().fg(theme.accent).bg(theme.panel_bg),
     )));
     lines.push(ratatui::text::Line::from(""));
@@ -920,9 +1138,8 @@ fn render_hex_input(f: &mut Frame, app: &App, area: Rect) {
 
     // Live preview when input is a valid hex color
     let parsed = crate::cell::parse_hex_color(&app.text_input);
-    if let Some(rgb) = parsed {
-        let preview_color = crate::palette::nearest_color(rgb.r, rgb.g, rgb.b);
-        let preview_rcolor = preview_color.to_ratatui();
+    if let Some(preview_color) = parsed {
+        let preview_rcolor = crate::export::to_display_color(preview_color, app.color_format);
         lines.push(ratatui::text::Line::from(vec![
             ratatui::text::Span::styled(
                 " Preview: ",
@@ -937,6 +1154,30 @@ fn render_hex_input(f: &mut Frame, app: &App, area: Rect) {
                 Style::default().fg(theme.dim).bg(theme.panel_bg),
             ),
         ]));
+        // Closest curated palette color, updated as you type
+        if let Some((nearest, distance)) = app.nearest_palette_color(preview_color) {
+            let closeness = if distance == 0.0 {
+                "  exact".to_string()
+            } else {
+                format!("  \u{0394} {:.1}", distance)
+            };
+            lines.push(ratatui::text::Line::from(vec![
+                ratatui::text::Span::styled(
+                    " Nearest: ",
+                    Style::default().fg(theme.dim).bg(theme.panel_bg),
+                ),
+                ratatui::text::Span::styled(
+                    "\u{2588}\u{2588}\u{2588}\u{2588}",
+                    Style::default()
+                        .fg(crate::export::to_display_color(nearest, app.color_format))
+                        .bg(theme.panel_bg),
+                ),
+                ratatui::text::Span::styled(
+                    format!("  {}{}", nearest.name(), closeness),
+                    Style::default().fg(theme.dim).bg(theme.panel_bg),
+                ),
+            ]));
+        }
     } else {
         lines.push(ratatui::text::Line::from(ratatui::text::Span::styled(
             " Preview: ----",
@@ -1218,7 +1459,7 @@ fn render_import_browse(f: &mut Frame, app: &App, area: Rect) {
 
 fn render_import_options(f: &mut Frame, app: &App, area: Rect) {
     let theme = app.theme();
-    let height = 17u16;
+    let height = 18u16;
     let width = 48;
     let x = (area.width.saturating_sub(width)) / 2;
     let y = (area.height.saturating_sub(height)) / 2;
@@ -1302,6 +1543,17 @@ fn render_import_options(f: &mut Frame, app: &App, area: Rect) {
         row_style(5),
     )));
 
+    // Row 6: Dither
+    let dither_label = match app.import_dither {
+        1 => "Floyd-Steinberg",
+        2 => "Ordered",
+        _ => "Off",
+    };
+    lines.push(Line::from(Span::styled(
+        format!("  Dither:    < {} >", dither_label),
+        row_style(6),
+    )));
+
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
         " \u{2190}\u{2192} Change  N/H Toggle  Enter Import  Esc Back",
@@ -1431,3 +1683,150 @@ fn render_goto_input(f: &mut Frame, app: &App, area: Rect) {
     )));
     f.render_widget(input, inner);
 }
+
+/// Shift mode panel, kept at the top so the shifted drawing stays visible.
+fn render_shift_canvas(f: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
+    let dialog_w = 46u16.min(area.width.saturating_sub(4));
+    let dialog_h = 4u16;
+    let x = area.x + (area.width.saturating_sub(dialog_w)) / 2;
+    let dialog_area = Rect::new(x, area.y + 1, dialog_w, dialog_h.min(area.height));
+    f.render_widget(Clear, dialog_area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(" Shift drawing ")
+        .border_style(Style::default().fg(theme.accent))
+        .style(Style::default().bg(theme.panel_bg));
+    let inner = block.inner(dialog_area);
+    f.render_widget(block, dialog_area);
+    let (dx, dy) = app.shift_offset;
+    let lines = vec![
+        Line::from(Span::styled(
+            format!("dx {:+}  dy {:+}  wrap {}", dx, dy, if app.shift_wrap { "on" } else { "off" }),
+            Style::default().fg(Color::White).bg(theme.panel_bg),
+        )),
+        Line::from(Span::styled(
+            "\u{2190}\u{2191}\u{2193}\u{2192} Move  W Wrap  Enter Apply  Esc Cancel",
+            Style::default().fg(theme.dim).bg(theme.panel_bg),
+        )),
+    ];
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+fn render_history_timeline(f: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
+    let timeline = app.history.timeline();
+    let position = app.history.position();
+    let width = 40u16.min(area.width.saturating_sub(4));
+    let max_visible = 12usize;
+    let height = (max_visible as u16 + 4).min(area.height.saturating_sub(2));
+    let x = area.x + (area.width.saturating_sub(width)) / 2;
+    let y = area.y + (area.height.saturating_sub(height)) / 2;
+    let dialog_area = Rect::new(x, y, width, height);
+
+    // Row 0 is the state before any recorded action
+    let total = timeline.len() + 1;
+    let scroll = if app.timeline_cursor >= max_visible {
+        app.timeline_cursor - max_visible + 1
+    } else {
+        0
+    };
+
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    for row in scroll..total.min(scroll + max_visible) {
+        let label = if row == 0 {
+            "(start)".to_string()
+        } else {
+            timeline[row - 1].clone()
+        };
+        let marker = if row == position { "\u{25B6}" } else { " " };
+        let style = if row == app.timeline_cursor {
+            Style::default().fg(Color::Black).bg(theme.highlight)
+        } else if row > position {
+            // Undone actions still reachable via redo
+            Style::default().fg(theme.dim).bg(theme.panel_bg)
+        } else {
+            Style::default().fg(Color::White).bg(theme.panel_bg)
+        };
+        lines.push(Line::from(Span::styled(
+            format!("{} {:>3} {}", marker, row, label),
+            style,
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "\u{2191}\u{2193} Move  Enter Jump  Esc Cancel",
+        Style::default().fg(theme.dim).bg(theme.panel_bg),
+    )));
+
+    f.render_widget(Clear, dialog_area);
+    let dialog = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title(" Undo History ")
+            .border_style(Style::default().fg(theme.accent))
+            .style(Style::default().bg(theme.panel_bg)),
+    );
+    f.render_widget(dialog, dialog_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_radial_overlay_marks_active_order() {
+        let items = radial_overlay_items(6);
+        let labels: Vec<&str> = items.iter().map(|(l, _)| l.as_str()).collect();
+        assert_eq!(labels, ["3", "4", "6", "8", "off"]);
+        let active: Vec<&str> = items.iter().filter(|(_, a)| *a).map(|(l, _)| l.as_str()).collect();
+        assert_eq!(active, ["6"]);
+    }
+
+    #[test]
+    fn test_help_max_scroll_grows_as_screen_shrinks() {
+        let app = App::new();
+        let tall = help_max_scroll(&app, 200);
+        assert!(tall > 0, "help has more than 24 lines");
+        // 24 visible rows on a tall screen, 8 on a 10-row one
+        assert_eq!(help_max_scroll(&app, 10), tall + 16);
+    }
+
+    #[test]
+    fn test_minimap_downscales_and_keeps_shape() {
+        let red = Rgb::new(255, 0, 0);
+        let mut canvas = Canvas::new_with_size(96, 32);
+        canvas.set(0, 0, Cell { ch: blocks::FULL, fg: Some(red), bg: None });
+
+        let pixels = minimap_pixels(&canvas, 24, 8);
+        // 96x64 pixels scaled by 1/4 to fit 24 columns; 16 pixel rows = 8 cell rows
+        assert_eq!((pixels[0].len(), pixels.len()), (24, 16));
+        assert_eq!(pixels[0][0], Some(red));
+        assert_eq!(pixels[0][1], None);
+
+        // Small canvases are never scaled up; half blocks split into two pixels
+        let mut small = Canvas::new_with_size(8, 8);
+        small.set(2, 1, Cell { ch: blocks::LOWER_HALF, fg: Some(red), bg: None });
+        let pixels = minimap_pixels(&small, 24, 8);
+        assert_eq!((pixels[0].len(), pixels.len()), (8, 16));
+        assert_eq!((pixels[2][2], pixels[3][2]), (None, Some(red)));
+    }
+
+    #[test]
+    fn test_dump_buffer_lists_grid_and_cell_colors() {
+        let mut buf = Buffer::with_lines(["ab", "c\u{2588}"]);
+        buf[(1, 1)].set_fg(Color::Indexed(196)).set_bg(Color::Indexed(196));
+        buf[(0, 0)].set_style(Style::default().add_modifier(Modifier::REVERSED));
+
+        let dump = dump_buffer(&buf);
+        let mut lines = dump.lines();
+        assert_eq!(lines.next(), Some("screen 2x2"));
+        assert_eq!(lines.next(), Some("ab"));
+        assert_eq!(lines.next(), Some("c\u{2588}"));
+        assert!(dump.contains("0,0 \"a\" fg=Reset bg=Reset mod=REVERSED\n"));
+        assert!(dump.contains("1,1 \"\u{2588}\" fg=Indexed(196) bg=Indexed(196)\n"));
+        assert_eq!(dump.lines().filter(|l| l.contains(" fg=")).count(), 4);
+    }
+}
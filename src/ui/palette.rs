@@ -173,7 +173,7 @@ pub fn info_lines(app: &App) -> Vec<Line<'static>> {
     let theme = app.theme();
     let dim = Style::default().fg(theme.dim);
     let color_style = Style::default()
-        .bg(app.color.to_ratatui());
+        .bg(crate::export::to_display_color(app.color, app.color_format));
 
     // Line 1: color swatch + name (mixed styles, centered)
     let swatch = "    ";
@@ -186,10 +186,29 @@ pub fn info_lines(app: &App) -> Vec<Line<'static>> {
         Span::styled(name, dim),
     ]);
 
+    // Line 2: background swatch, or "none" when drawing transparent
+    let line2 = match app.bg_color {
+        Some(bg) => {
+            let name = format!(" bg {}", bg.name());
+            let pad = PALETTE_INNER_WIDTH.saturating_sub(4 + name.len()) / 2;
+            Line::from(vec![
+                Span::raw(" ".repeat(pad.max(1))),
+                Span::styled(
+                    swatch.to_string(),
+                    Style::default().bg(crate::export::to_display_color(bg, app.color_format)),
+                ),
+                Span::styled(name, dim),
+            ])
+        }
+        None => center_line("bg none", dim),
+    };
+
     vec![
         line1,
+        line2,
         center_line("\u{2191}\u{2193} Browse", dim),
         center_line("[S]liders [X] Hex", dim),
+        center_line("\u{21E7}X Swap fg/bg", dim),
         center_line("[C]ustom", dim),
         center_line("[A]dd color", dim),
     ]
@@ -207,6 +226,17 @@ mod tests {
         }).collect::<Vec<_>>().join("\n")
     }
 
+    #[test]
+    fn test_info_lines_show_fg_and_bg() {
+        let mut app = App::new();
+        app.color = Rgb::new(255, 0, 0);
+        assert!(lines_text(&info_lines(&app)).contains("bg none"));
+        app.swap_colors();
+        let text = lines_text(&info_lines(&app));
+        assert!(text.contains("#FF0000"), "got: {}", text);
+        assert!(text.contains("bg #FF0000"), "got: {}", text);
+    }
+
     #[test]
     fn test_recent_section_renders() {
         let mut app = App::new();
@@ -5,6 +5,17 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 
 use crate::app::{App, MessageLevel};
+use crate::theme::Theme;
+
+/// Status message color for a severity; info uses the theme highlight.
+fn message_color(theme: &Theme, level: MessageLevel) -> Color {
+    match level {
+        MessageLevel::Info => theme.highlight,
+        MessageLevel::Success => theme.msg_success,
+        MessageLevel::Warning => theme.msg_warning,
+        MessageLevel::Error => theme.msg_error,
+    }
+}
 
 pub fn render(f: &mut Frame, app: &App, area: Rect) {
     let theme = app.theme();
@@ -12,12 +23,7 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
 
     // Status message takes priority
     if let Some(ref msg) = app.status_message {
-        let fg = match msg.level {
-            MessageLevel::Info => theme.highlight,
-            MessageLevel::Success => theme.msg_success,
-            MessageLevel::Warning => theme.msg_warning,
-            MessageLevel::Error => theme.msg_error,
-        };
+        let fg = message_color(theme, msg.level);
         spans.push(Span::styled(
             format!(" {} ", msg.text),
             Style::default().fg(fg).bg(theme.panel_bg),
@@ -67,6 +73,13 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
             space_label,
             Style::default().fg(theme.dim).bg(theme.panel_bg),
         ));
+        // WASD step size, only when it isn't the default single cell
+        if app.cursor_step > 1 {
+            spans.push(Span::styled(
+                format!("[Y]Step {} ", app.cursor_step),
+                Style::default().fg(theme.dim).bg(theme.panel_bg),
+            ));
+        }
 
         spans.push(Span::styled("\u{2502} ", sep_style));
 
@@ -82,7 +95,7 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
         // Active color swatch
         right_spans.push(Span::styled(
             "  ",
-            Style::default().bg(app.color.to_ratatui()),
+            Style::default().bg(crate::export::to_display_color(app.color, app.color_format)),
         ));
         right_spans.push(Span::styled(" ", Style::default().bg(theme.panel_bg)));
 
@@ -125,12 +138,7 @@ pub fn build_spans(app: &App) -> Vec<Span<'static>> {
     let mut spans = Vec::new();
 
     if let Some(ref msg) = app.status_message {
-        let fg = match msg.level {
-            MessageLevel::Info => theme.highlight,
-            MessageLevel::Success => theme.msg_success,
-            MessageLevel::Warning => theme.msg_warning,
-            MessageLevel::Error => theme.msg_error,
-        };
+        let fg = message_color(theme, msg.level);
         spans.push(Span::styled(
             format!(" {} ", msg.text),
             Style::default().fg(fg).bg(theme.panel_bg),
@@ -171,6 +179,13 @@ pub fn build_spans(app: &App) -> Vec<Span<'static>> {
             space_label,
             Style::default().fg(theme.dim).bg(theme.panel_bg),
         ));
+        // WASD step size, only when it isn't the default single cell
+        if app.cursor_step > 1 {
+            spans.push(Span::styled(
+                format!("[Y]Step {} ", app.cursor_step),
+                Style::default().fg(theme.dim).bg(theme.panel_bg),
+            ));
+        }
         spans.push(Span::styled("\u{2502} ", sep_style));
         spans.push(Span::styled(
             format!("{}\u{00d7}{}", app.canvas.width, app.canvas.height),
@@ -311,4 +326,13 @@ mod tests {
         let text = spans_text(&build_spans(&app));
         assert!(text.contains("\u{2389}Draw"), "Status bar should show ⎵Draw when cursor active, got: {}", text);
     }
+
+    #[test]
+    fn test_status_bar_shows_cursor_step_when_not_one() {
+        let mut app = App::new();
+        assert!(!spans_text(&build_spans(&app)).contains("Step"));
+        app.cycle_cursor_step();
+        let text = spans_text(&build_spans(&app));
+        assert!(text.contains("[Y]Step 5"), "Status bar should show the cursor step, got: {}", text);
+    }
 }
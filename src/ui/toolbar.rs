@@ -4,12 +4,12 @@ use ratatui::text::{Line, Span};
 use crate::app::App;
 use crate::tools::ToolKind;
 
-/// Tool list: 6 tool entries.
+/// Tool list, in the user's order (favorites first).
 pub fn tool_lines(app: &App) -> Vec<Line<'static>> {
     let theme = app.theme();
     let mut lines: Vec<Line> = Vec::new();
 
-    for tool in ToolKind::ALL {
+    for &tool in &app.tool_order {
         let is_active = app.active_tool == tool;
         let prefix = if is_active { "\u{25B8}" } else { " " }; // ▸ or space
         let style = if is_active {
@@ -29,24 +29,23 @@ pub fn tool_lines(app: &App) -> Vec<Line<'static>> {
     lines
 }
 
-/// Symmetry toggle row: [H] [V].
+/// Symmetry toggle row: [H] [V] [\] [/], one indicator per axis.
 pub fn symmetry_lines(app: &App) -> Vec<Line<'static>> {
     let theme = app.theme();
     let sym = app.symmetry;
-    let h_style = if sym.has_horizontal() {
-        Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default().fg(theme.dim)
-    };
-    let v_style = if sym.has_vertical() {
-        Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default().fg(theme.dim)
+    let axis_style = |on: bool| {
+        if on {
+            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.dim)
+        }
     };
 
     vec![Line::from(vec![
-        Span::styled(" [H] ", h_style),
-        Span::styled("[V]", v_style),
+        Span::styled(" [H] ", axis_style(sym.has_horizontal())),
+        Span::styled("[V] ", axis_style(sym.has_vertical())),
+        Span::styled("[\\] ", axis_style(sym.has_diagonal())),
+        Span::styled("[/]", axis_style(sym.has_anti_diagonal())),
     ])]
 }
 
@@ -114,7 +113,7 @@ pub fn color_swatch_lines(app: &App) -> Vec<Line<'static>> {
         Span::styled(" ", Style::default()),
         Span::styled(
             "    ",
-            Style::default().bg(app.color.to_ratatui()),
+            Style::default().bg(crate::export::to_display_color(app.color, app.color_format)),
         ),
         Span::styled(
             format!(" {}", app.color.name()),
@@ -136,6 +135,20 @@ mod tests {
         }).collect::<Vec<_>>().join("\n")
     }
 
+    #[test]
+    fn test_tool_lines_follow_favorite_order() {
+        let mut app = App::new();
+        let unknown = app.set_favorite_tools(&["fill".to_string(), "lasso".to_string()]);
+        assert_eq!(unknown, vec!["lasso".to_string()]);
+        app.active_tool = ToolKind::Fill;
+        let lines = tool_lines(&app);
+        assert_eq!(lines.len(), ToolKind::ALL.len());
+        let first = lines_text(&lines[..1]);
+        assert!(first.contains("\u{25B8}F") && first.contains("Fill"), "got: {}", first);
+        let second = lines_text(&lines[1..2]);
+        assert!(second.contains(" P ") && second.contains("Pencil"), "got: {}", second);
+    }
+
     #[test]
     fn test_block_lines_shows_shades() {
         let app = App::new();
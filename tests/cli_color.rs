@@ -0,0 +1,35 @@
+mod helpers;
+
+use helpers::*;
+
+#[test]
+fn color_reports_all_forms() {
+    let out = run_ok(kakukuma().args(["color", "#FF0000"]));
+    let json = stdout_json(&out);
+    assert_eq!(json["hex"], "#FF0000");
+    assert_eq!(json["rgb"]["r"], 255);
+    assert_eq!(json["hsl"]["h"], 0);
+    assert_eq!(json["hsl"]["s"], 100);
+    assert_eq!(json["hsl"]["l"], 50);
+    assert_eq!(json["css_name"], "red");
+    assert_eq!(json["xterm256"]["index"], 9);
+    assert_eq!(json["ansi16"]["index"], 9);
+    assert_eq!(json["ansi16"]["name"], "BrightRed");
+}
+
+#[test]
+fn color_accepts_short_hex_and_names() {
+    let short = stdout_json(&run_ok(kakukuma().args(["color", "#f00"])));
+    let named = stdout_json(&run_ok(kakukuma().args(["color", "Red"])));
+    assert_eq!(short["hex"], named["hex"]);
+
+    let plain = stdout_json(&run_ok(kakukuma().args(["color", "#123456"])));
+    assert!(plain["css_name"].is_null());
+}
+
+#[test]
+fn color_invalid_input_fails() {
+    let out = kakukuma().args(["color", "nope"]).output().unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("#RGB"));
+}
@@ -0,0 +1,39 @@
+mod helpers;
+
+use helpers::*;
+
+#[test]
+fn crop_shrinks_to_content_and_undoes() {
+    let f = temp_file("crop");
+    let file = f.to_str().unwrap();
+    run_ok(kakukuma().args(["new", file, "--size", "40x30"]));
+    run_ok(kakukuma().args(["draw", "rect", file, "10,5", "21,16", "--color", "#FF0000"]));
+
+    let json = stdout_json(&run_ok(kakukuma().args(["crop", file])));
+    assert_eq!(json["new"], "12x12");
+    assert_eq!(json["offset"]["x"], 10);
+    let cell = stdout_json(&run_ok(kakukuma().args(["inspect", file, "0,0"])));
+    assert_eq!(cell["fg"], "#FF0000");
+
+    // Already cropped
+    let out = kakukuma().args(["crop", file]).output().unwrap();
+    assert_eq!(out.status.code(), Some(1));
+
+    run_ok(kakukuma().args(["undo", file]));
+    let cell = stdout_json(&run_ok(kakukuma().args(["inspect", file, "21,16"])));
+    assert_eq!(cell["fg"], "#FF0000");
+    let cell = stdout_json(&run_ok(kakukuma().args(["inspect", file, "0,0"])));
+    assert_eq!(cell["empty"], true);
+
+    cleanup(&f);
+}
+
+#[test]
+fn crop_empty_canvas_is_an_error() {
+    let f = temp_file("crop_empty");
+    let file = f.to_str().unwrap();
+    run_ok(kakukuma().args(["new", file]));
+    let out = kakukuma().args(["crop", file]).output().unwrap();
+    assert_eq!(out.status.code(), Some(1));
+    cleanup(&f);
+}
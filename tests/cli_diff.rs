@@ -73,3 +73,37 @@ fn diff_before_empty_log_fails() {
     assert!(!out.status.success());
     cleanup(&f);
 }
+
+#[test]
+fn diff_at_step_spans_several_operations() {
+    let f = temp_file("diff_at");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--width", "16", "--height", "16"]));
+    for (coord, color) in [("1,1", "#FF0000"), ("2,2", "#00FF00"), ("1,1", "#0000FF")] {
+        run_ok(kakukuma().args(["draw", "pencil", f.to_str().unwrap(), coord, "--color", color]));
+    }
+
+    let from_start = stdout_json(&run_ok(kakukuma().args(["diff", f.to_str().unwrap(), "--at", "0"])));
+    assert_eq!(from_start["operations"], 3);
+    assert_eq!(from_start["added"], 2);
+    assert_eq!(from_start["modified"], 0);
+    assert_eq!(from_start["unchanged"], 16 * 16 - 2);
+
+    let after_first = stdout_json(&run_ok(kakukuma().args(["diff", f.to_str().unwrap(), "--at", "1"])));
+    assert_eq!(after_first["added"], 1);
+    assert_eq!(after_first["modified"], 1);
+    let red_to_blue = after_first["changes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["x"] == 1)
+        .unwrap();
+    assert_eq!(red_to_blue["before"]["fg"], "#FF0000");
+    assert_eq!(red_to_blue["after"]["fg"], "#0000FF");
+
+    // Past the pointer clamps to the current state
+    let clamped = stdout_json(&run_ok(kakukuma().args(["diff", f.to_str().unwrap(), "--at", "99"])));
+    assert_eq!(clamped["at"], 3);
+    assert_eq!(clamped["changes"].as_array().unwrap().len(), 0);
+
+    cleanup(&f);
+}
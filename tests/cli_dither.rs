@@ -0,0 +1,28 @@
+mod helpers;
+
+use helpers::*;
+
+#[test]
+fn dither2_fills_region_as_one_undo_step() {
+    let f = temp_file("dither2");
+    let file = f.to_str().unwrap();
+    run_ok(kakukuma().args(["new", file, "--size", "16x16"]));
+
+    let out = run_ok(kakukuma().args(["dither2", file, "2,2,5,5", "#000000", "#FFFFFF", "--ratio", "25"]));
+    let json = stdout_json(&out);
+    assert_eq!(json["cells_modified"], 16);
+
+    let stats = stdout_json(&run_ok(kakukuma().args(["stats", file])));
+    assert_eq!(stats["fill"]["filled"], 16);
+    let outside = stdout_json(&run_ok(kakukuma().args(["inspect", file, "6,6"])));
+    assert_eq!(outside["empty"], true);
+
+    run_ok(kakukuma().args(["undo", file]));
+    let stats = stdout_json(&run_ok(kakukuma().args(["stats", file])));
+    assert_eq!(stats["fill"]["filled"], 0);
+
+    let bad = kakukuma().args(["dither2", file, "0,0,3,3", "red", "blue", "--ratio", "101"]).output().unwrap();
+    assert!(!bad.status.success());
+
+    cleanup(&f);
+}
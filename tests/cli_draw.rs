@@ -106,6 +106,30 @@ fn draw_rect_filled() {
     cleanup(&f);
 }
 
+#[test]
+fn draw_ellipse_outline_and_filled() {
+    let f = create_canvas("draw_ellipse");
+    let out = run_ok(kakukuma().args([
+        "draw", "ellipse", f.to_str().unwrap(), "0,0", "4,4", "--color", "#00FF00",
+    ]));
+    let json = stdout_json(&out);
+    assert_eq!(json["tool"], "ellipse");
+    assert_eq!(json["cells_modified"], 12);
+    let corner = stdout_json(&run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), "0,0"])));
+    assert_eq!(corner["empty"], true);
+    let center = stdout_json(&run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), "2,2"])));
+    assert_eq!(center["empty"], true);
+
+    let out = run_ok(kakukuma().args([
+        "draw", "ellipse", f.to_str().unwrap(), "0,0", "4,4", "--color", "#00FF00", "--filled",
+    ]));
+    assert_eq!(stdout_json(&out)["cells_modified"], 9, "only the interior was left");
+    let center = stdout_json(&run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), "2,2"])));
+    assert_eq!(center["fg"], "#00FF00");
+
+    cleanup(&f);
+}
+
 #[test]
 fn draw_fill() {
     let f = create_canvas("draw_fill");
@@ -121,6 +145,48 @@ fn draw_fill() {
     cleanup(&f);
 }
 
+#[test]
+fn draw_fill_bg_target_keeps_glyphs() {
+    let f = create_canvas("draw_fill_bg");
+    run_ok(kakukuma().args([
+        "draw", "pencil", f.to_str().unwrap(), "5,5", "--color", "#FF0000", "--char", "upper-half",
+    ]));
+    let out = run_ok(kakukuma().args([
+        "draw", "fill", f.to_str().unwrap(), "0,0", "--bg", "#0000FF", "--target", "bg",
+    ]));
+    // Every cell shares the empty bg, including the painted one
+    assert_eq!(stdout_json(&out)["cells_modified"], 256);
+
+    let cell = stdout_json(&run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), "5,5"])));
+    assert_eq!(cell["fg"], "#FF0000");
+    assert_eq!(cell["bg"], "#0000FF");
+    assert_eq!(cell["char"], "\u{2580}");
+
+    cleanup(&f);
+}
+
+#[test]
+fn draw_fill_diagonal_leaks_through_corner_gaps() {
+    let f = create_canvas("draw_fill_diag");
+    // A diagonal wall cutting off the top-left corner
+    run_ok(kakukuma().args([
+        "draw", "line", f.to_str().unwrap(), "0,2", "2,0", "--color", "#FF0000",
+    ]));
+
+    let out = run_ok(kakukuma().args([
+        "draw", "fill", f.to_str().unwrap(), "0,0", "--color", "#FFFF00",
+    ]));
+    assert_eq!(stdout_json(&out)["cells_modified"], 3);
+    run_ok(kakukuma().args(["undo", f.to_str().unwrap()]));
+
+    let out = run_ok(kakukuma().args([
+        "draw", "fill", f.to_str().unwrap(), "0,0", "--color", "#FFFF00", "--diagonal",
+    ]));
+    assert_eq!(stdout_json(&out)["cells_modified"], 256 - 3);
+
+    cleanup(&f);
+}
+
 #[test]
 fn draw_eyedropper() {
     let f = create_canvas("draw_eye");
@@ -146,8 +212,21 @@ fn draw_invalid_color_fails() {
         .unwrap();
     assert!(!out.status.success());
     let stderr = String::from_utf8_lossy(&out.stderr);
-    assert!(stderr.contains("Invalid hex color"));
+    assert!(stderr.contains("Invalid color"));
     assert!(stderr.contains("#RRGGBB"));
+    assert!(stderr.contains("#RGB"));
+    cleanup(&f);
+}
+
+#[test]
+fn draw_short_hex_and_css_name() {
+    let f = create_canvas("draw_named_color");
+    run_ok(kakukuma().args([
+        "draw", "pencil", f.to_str().unwrap(), "1,1", "--color", "#F80", "--bg", "cornflowerblue",
+    ]));
+    let json = stdout_json(&run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), "1,1"])));
+    assert_eq!(json["fg"], "#FF8800");
+    assert_eq!(json["bg"], "#6495ED");
     cleanup(&f);
 }
 
@@ -163,3 +242,107 @@ fn draw_invalid_coords_fails() {
     assert!(stderr.contains("exceeds"));
     cleanup(&f);
 }
+
+#[test]
+fn draw_pencil_named_char() {
+    let f = create_canvas("draw_named_char");
+    run_ok(kakukuma().args([
+        "draw", "pencil", f.to_str().unwrap(), "2,2", "--color", "#FF0000", "--char", "shade-dark",
+    ]));
+    let out = run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), "2,2"]));
+    let cell = stdout_json(&out);
+    assert_eq!(cell["char"], "\u{2593}");
+    cleanup(&f);
+}
+
+#[test]
+fn draw_pencil_unknown_char_lists_tokens() {
+    let f = create_canvas("draw_bad_char");
+    let out = kakukuma()
+        .args(["draw", "pencil", f.to_str().unwrap(), "2,2", "--char", "sparkle"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("upper-half"));
+    assert!(stderr.contains("shade-dark"));
+    cleanup(&f);
+}
+
+#[test]
+fn draw_rejects_wide_glyph() {
+    let f = create_canvas("draw_wide_char");
+    let out = kakukuma()
+        .args(["draw", "pencil", f.to_str().unwrap(), "2,2", "--char", "\u{5B57}"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("2 columns wide"), "stderr: {}", stderr);
+    let cell = stdout_json(&run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), "2,2"])));
+    assert_eq!(cell["empty"], true);
+    cleanup(&f);
+}
+
+#[test]
+fn draw_verbose_lists_mutations() {
+    let f = create_canvas("draw_verbose");
+    // Off by default
+    let quiet = run_ok(kakukuma().args([
+        "draw", "pencil", f.to_str().unwrap(), "1,1", "--color", "#FF0000",
+    ]));
+    assert!(stdout_json(&quiet).get("mutations").is_none());
+
+    let out = run_ok(kakukuma().args([
+        "draw", "fill", f.to_str().unwrap(), "0,0", "--color", "#00FF00", "--verbose",
+    ]));
+    let json = stdout_json(&out);
+    let mutations = json["mutations"].as_array().unwrap();
+    // Large sets are emitted in full
+    assert_eq!(mutations.len() as u64, json["cells_modified"].as_u64().unwrap());
+    assert_eq!(mutations.len(), 255);
+
+    // Each entry matches what inspect reports afterward
+    for m in mutations.iter().take(5) {
+        let coord = format!("{},{}", m["x"], m["y"]);
+        let cell = stdout_json(&run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), &coord])));
+        assert_eq!(&cell, m);
+    }
+
+    cleanup(&f);
+}
+
+#[test]
+fn draw_stipple_grid() {
+    let f = create_canvas("draw_stipple");
+    let out = run_ok(kakukuma().args([
+        "draw", "stipple", f.to_str().unwrap(), "1,1,20,20", "--spacing", "5", "--color", "#FF00FF",
+    ]));
+    let json = stdout_json(&out);
+    assert_eq!(json["tool"], "stipple");
+    // Clipped to the 16x16 canvas: x and y in {1, 6, 11}
+    assert_eq!(json["cells_modified"], 9);
+
+    let dot = stdout_json(&run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), "6,11"])));
+    assert_eq!(dot["fg"], "#FF00FF");
+    let gap = stdout_json(&run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), "7,11"])));
+    assert_eq!(gap["empty"], true);
+
+    // Logged, so it can be undone
+    run_ok(kakukuma().args(["undo", f.to_str().unwrap()]));
+    let undone = stdout_json(&run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), "6,11"])));
+    assert_eq!(undone["empty"], true);
+
+    cleanup(&f);
+}
+
+#[test]
+fn draw_stipple_zero_spacing_fails() {
+    let f = create_canvas("draw_stipple_zero");
+    let out = kakukuma()
+        .args(["draw", "stipple", f.to_str().unwrap(), "0,0,3,3", "--spacing", "0"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    cleanup(&f);
+}
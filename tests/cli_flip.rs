@@ -0,0 +1,37 @@
+mod helpers;
+
+use helpers::*;
+
+#[test]
+fn flip_vertical_mirrors_half_blocks_and_undoes() {
+    let f = temp_file("flip");
+    let file = f.to_str().unwrap();
+    run_ok(kakukuma().args(["new", file, "--size", "8x8"]));
+    run_ok(kakukuma().args([
+        "draw", "pencil", file, "2,0", "--fg", "#FF0000", "--bg", "#0000FF", "--char", "upper-half",
+    ]));
+
+    let json = stdout_json(&run_ok(kakukuma().args(["transform", "flip", file, "--vertical"])));
+    assert_eq!(json["cells_modified"], 2);
+
+    let cell = stdout_json(&run_ok(kakukuma().args(["inspect", file, "2,7"])));
+    assert_eq!(cell["char"], "\u{2584}");
+    assert_eq!(cell["fg"], "#FF0000");
+    assert_eq!(cell["bg"], "#0000FF");
+
+    run_ok(kakukuma().args(["undo", file]));
+    let cell = stdout_json(&run_ok(kakukuma().args(["inspect", file, "2,0"])));
+    assert_eq!(cell["char"], "\u{2580}");
+
+    cleanup(&f);
+}
+
+#[test]
+fn flip_requires_a_direction() {
+    let f = temp_file("flip_none");
+    let file = f.to_str().unwrap();
+    run_ok(kakukuma().args(["new", file]));
+    let out = kakukuma().args(["transform", "flip", file]).output().unwrap();
+    assert_eq!(out.status.code(), Some(1));
+    cleanup(&f);
+}
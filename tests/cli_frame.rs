@@ -0,0 +1,42 @@
+mod helpers;
+
+use helpers::*;
+
+#[test]
+fn frame_draws_border_only_as_one_undo_step() {
+    let f = temp_file("frame");
+    let file = f.to_str().unwrap();
+    run_ok(kakukuma().args(["new", file, "--width", "12", "--height", "8"]));
+    run_ok(kakukuma().args(["draw", "pencil", file, "5,4", "--color", "#00FF00"]));
+
+    let out = run_ok(kakukuma().args(["frame", file, "red", "--style", "double"]));
+    let json = stdout_json(&out);
+    assert_eq!(json["style"], "double");
+    assert_eq!(json["color"], "#FF0000");
+    assert_eq!(json["cells_modified"], 2 * 12 + 2 * 6);
+
+    let corner = stdout_json(&run_ok(kakukuma().args(["inspect", file, "0,0"])));
+    assert_eq!(corner["char"], "\u{2554}");
+    assert_eq!(corner["fg"], "#FF0000");
+    let inner = stdout_json(&run_ok(kakukuma().args(["inspect", file, "5,4"])));
+    assert_eq!(inner["fg"], "#00FF00");
+
+    run_ok(kakukuma().args(["undo", file]));
+    let restored = stdout_json(&run_ok(kakukuma().args(["inspect", file, "0,0"])));
+    assert_eq!(restored["empty"], true);
+
+    cleanup(&f);
+}
+
+#[test]
+fn frame_rejects_unknown_style() {
+    let f = temp_file("frame_style");
+    let file = f.to_str().unwrap();
+    run_ok(kakukuma().args(["new", file]));
+
+    let out = kakukuma().args(["frame", file, "#FFFFFF", "--style", "dotted"]).output().unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("single, double, rounded"));
+
+    cleanup(&f);
+}
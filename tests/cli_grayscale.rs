@@ -0,0 +1,33 @@
+mod helpers;
+
+use helpers::*;
+
+#[test]
+fn grayscale_red_becomes_gray() {
+    let f = temp_file("grayscale");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--width", "8", "--height", "8"]));
+    run_ok(kakukuma().args(["draw", "pencil", f.to_str().unwrap(), "2,2", "--color", "#FF0000"]));
+
+    let json = stdout_json(&run_ok(kakukuma().args(["transform", "grayscale", f.to_str().unwrap()])));
+    assert_eq!(json["cells_modified"], 1);
+
+    let cell = stdout_json(&run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), "2,2"])));
+    let hex = cell["fg"].as_str().unwrap();
+    assert_eq!(&hex[1..3], &hex[3..5], "R=G: {}", hex);
+    assert_eq!(&hex[3..5], &hex[5..7], "G=B: {}", hex);
+
+    run_ok(kakukuma().args(["undo", f.to_str().unwrap()]));
+    let cell = stdout_json(&run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), "2,2"])));
+    assert_eq!(cell["fg"], "#FF0000");
+
+    cleanup(&f);
+}
+
+#[test]
+fn grayscale_rejects_amount_over_100() {
+    let f = temp_file("grayscale_amount");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--width", "8", "--height", "8"]));
+    let out = kakukuma().args(["transform", "grayscale", f.to_str().unwrap(), "--amount", "150"]).output().unwrap();
+    assert!(!out.status.success());
+    cleanup(&f);
+}
@@ -0,0 +1,90 @@
+mod helpers;
+
+use helpers::*;
+
+#[test]
+fn info_reports_header_and_log() {
+    let f = temp_file("info_basic");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--width", "20", "--height", "12"]));
+    run_ok(kakukuma().args([
+        "draw", "pencil", f.to_str().unwrap(), "3,3", "--color", "#FF0000",
+    ]));
+
+    let out = run_ok(kakukuma().args(["info", f.to_str().unwrap()]));
+    let json = stdout_json(&out);
+
+    assert_eq!(json["width"], 20);
+    assert_eq!(json["height"], 12);
+    assert_eq!(json["format"], "json");
+    assert_eq!(json["size_bytes"], std::fs::metadata(&f).unwrap().len());
+    assert_eq!(json["log"]["exists"], true);
+    assert_eq!(json["log"]["pointer"], 1);
+    assert_eq!(json["log"]["total"], 1);
+    assert_eq!(json["autosave"]["exists"], false);
+
+    cleanup(&f);
+}
+
+#[test]
+fn info_handles_missing_sidecars() {
+    let f = temp_file("info_no_log");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap()]));
+    let _ = std::fs::remove_file(f.with_extension("kaku.log"));
+
+    let out = run_ok(kakukuma().args(["info", f.to_str().unwrap()]));
+    let json = stdout_json(&out);
+
+    assert_eq!(json["log"]["exists"], false);
+    assert!(json["log"]["pointer"].is_null());
+    assert!(json["log"]["total"].is_null());
+    assert_eq!(json["autosave"]["exists"], false);
+
+    cleanup(&f);
+}
+
+#[test]
+fn info_skips_cell_data() {
+    let f = temp_file("info_header_only");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--size", "16x8"]));
+    // Cells info would choke on if it decoded them
+    let mut json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&f).unwrap()).unwrap();
+    json["canvas"]["cells"] = serde_json::json!("not cells");
+    std::fs::write(&f, json.to_string()).unwrap();
+
+    let out = stdout_json(&run_ok(kakukuma().args(["info", f.to_str().unwrap()])));
+    assert_eq!(out["width"], 16);
+    assert_eq!(out["height"], 8);
+
+    cleanup(&f);
+}
+
+#[test]
+fn info_missing_file_fails() {
+    let out = kakukuma().args(["info", "/nonexistent/info.kaku"]).output().unwrap();
+    assert!(!out.status.success());
+}
+
+#[test]
+fn output_flags_control_success_json() {
+    let f = temp_file("info_output");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap()]));
+
+    let default = run_ok(kakukuma().args(["info", f.to_str().unwrap()]));
+    assert_eq!(String::from_utf8_lossy(&default.stdout).trim().lines().count(), 1);
+
+    let pretty = run_ok(kakukuma().args(["--pretty", "info", f.to_str().unwrap()]));
+    assert!(String::from_utf8_lossy(&pretty.stdout).trim().lines().count() > 1);
+    assert_eq!(stdout_json(&pretty)["width"], stdout_json(&default)["width"]);
+
+    let quiet = run_ok(kakukuma().args([
+        "draw", "pencil", f.to_str().unwrap(), "1,1", "--color", "#FF0000", "--quiet",
+    ]));
+    assert!(quiet.stdout.is_empty());
+
+    let failed = kakukuma().args(["-q", "info", "/nonexistent/quiet.kaku"]).output().unwrap();
+    assert!(!failed.status.success());
+    assert!(failed.stdout.is_empty());
+    assert!(!failed.stderr.is_empty());
+
+    cleanup(&f);
+}
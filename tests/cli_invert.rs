@@ -0,0 +1,24 @@
+mod helpers;
+
+use helpers::*;
+
+#[test]
+fn invert_white_to_black_and_undo() {
+    let f = temp_file("invert");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--width", "8", "--height", "8"]));
+    run_ok(kakukuma().args(["draw", "pencil", f.to_str().unwrap(), "2,2", "--color", "#FFFFFF"]));
+
+    let json = stdout_json(&run_ok(kakukuma().args(["transform", "invert", f.to_str().unwrap()])));
+    assert_eq!(json["cells_modified"], 1);
+
+    let cell = stdout_json(&run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), "2,2"])));
+    assert_eq!(cell["fg"], "#000000");
+    let empty = stdout_json(&run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), "0,0"])));
+    assert_eq!(empty["empty"], true);
+
+    run_ok(kakukuma().args(["undo", f.to_str().unwrap()]));
+    let cell = stdout_json(&run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), "2,2"])));
+    assert_eq!(cell["fg"], "#FFFFFF");
+
+    cleanup(&f);
+}
@@ -0,0 +1,52 @@
+mod helpers;
+
+use helpers::*;
+
+#[test]
+fn mask_json_matches_stats_filled() {
+    let f = temp_file("mask");
+    let file = f.to_str().unwrap();
+    run_ok(kakukuma().args(["new", file, "--size", "12x8"]));
+    run_ok(kakukuma().args(["draw", "rect", file, "1,1", "4,3", "--color", "#FF0000"]));
+    run_ok(kakukuma().args(["draw", "pencil", file, "10,6", "--fg", "#00FF00", "--bg", "#0000FF", "--char", "upper-half"]));
+
+    let json = stdout_json(&run_ok(kakukuma().args(["mask", file])));
+    let stats = stdout_json(&run_ok(kakukuma().args(["stats", file])));
+    assert_eq!(json["filled"], stats["fill"]["filled"]);
+
+    let rows = json["mask"].as_array().unwrap();
+    assert_eq!(rows.len(), 8);
+    assert_eq!(rows[0].as_array().unwrap().len(), 12);
+    let set: usize = rows.iter()
+        .map(|r| r.as_array().unwrap().iter().filter(|b| b.as_bool().unwrap()).count())
+        .sum();
+    assert_eq!(json["filled"], set);
+    assert_eq!(rows[1][1], true);
+    assert_eq!(rows[2][2], false);
+    assert_eq!(rows[6][10], true);
+
+    cleanup(&f);
+}
+
+#[test]
+fn mask_png_is_one_pixel_per_cell() {
+    let f = temp_file("mask_png");
+    let file = f.to_str().unwrap();
+    let png = f.with_extension("png");
+    run_ok(kakukuma().args(["new", file, "--size", "12x8"]));
+    run_ok(kakukuma().args(["draw", "pencil", file, "3,2", "--color", "#FF0000"]));
+
+    let json = stdout_json(&run_ok(kakukuma().args(["mask", file, "--output", png.to_str().unwrap()])));
+    assert_eq!(json["format"], "png");
+    assert_eq!(json["filled"], 1);
+    let img = image::open(&png).unwrap().to_luma8();
+    assert_eq!(img.dimensions(), (12, 8));
+    assert_eq!(img.get_pixel(3, 2).0, [255]);
+    assert_eq!(img.get_pixel(4, 2).0, [0]);
+
+    let out = kakukuma().args(["mask", file, "--output", "mask.txt"]).output().unwrap();
+    assert!(!out.status.success());
+
+    let _ = std::fs::remove_file(&png);
+    cleanup(&f);
+}
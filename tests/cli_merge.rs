@@ -0,0 +1,106 @@
+mod helpers;
+
+use helpers::*;
+
+fn inspect(f: &std::path::Path, coord: &str) -> serde_json::Value {
+    stdout_json(&run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), coord])))
+}
+
+fn pencil(f: &std::path::Path, coord: &str, color: &str) {
+    run_ok(kakukuma().args(["draw", "pencil", f.to_str().unwrap(), coord, "--color", color]));
+}
+
+#[test]
+fn merge_over_keeps_base_under_empty_overlay_cells() {
+    let base = temp_file("merge_base");
+    let top = temp_file("merge_top");
+    let out = temp_file("merge_out");
+    for f in [&base, &top] {
+        run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--width", "8", "--height", "8"]));
+    }
+    pencil(&base, "2,2", "#FF0000");
+    pencil(&base, "4,4", "#FF0000");
+    pencil(&top, "0,0", "#00FF00");
+
+    let json = stdout_json(&run_ok(kakukuma().args([
+        "merge", base.to_str().unwrap(), top.to_str().unwrap(),
+        "--output", out.to_str().unwrap(), "--at", "4,4",
+    ])));
+    assert_eq!(json["mode"], "over");
+    assert_eq!(json["cells_modified"], 1);
+
+    // Drawn overlay cell replaces the base, empty ones leave it visible
+    assert_eq!(inspect(&out, "4,4")["fg"], "#00FF00");
+    assert_eq!(inspect(&out, "2,2")["fg"], "#FF0000");
+    // The base file itself is untouched
+    assert_eq!(inspect(&base, "4,4")["fg"], "#FF0000");
+
+    cleanup(&base);
+    cleanup(&top);
+    cleanup(&out);
+}
+
+#[test]
+fn merge_in_place_is_one_undo_step() {
+    let base = temp_file("merge_inplace");
+    let top = temp_file("merge_inplace_top");
+    for f in [&base, &top] {
+        run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--width", "8", "--height", "8"]));
+    }
+    pencil(&top, "1,1", "#0000FF");
+    pencil(&top, "6,6", "#0000FF");
+
+    run_ok(kakukuma().args([
+        "merge", base.to_str().unwrap(), top.to_str().unwrap(), "--output", base.to_str().unwrap(),
+    ]));
+    assert_eq!(inspect(&base, "1,1")["fg"], "#0000FF");
+
+    run_ok(kakukuma().args(["undo", base.to_str().unwrap()]));
+    assert_eq!(inspect(&base, "1,1")["empty"], true);
+    assert_eq!(inspect(&base, "6,6")["empty"], true);
+
+    cleanup(&base);
+    cleanup(&top);
+}
+
+#[test]
+fn merge_refuses_existing_output_without_force() {
+    let base = temp_file("merge_exist");
+    let out = temp_file("merge_exist_out");
+    for f in [&base, &out] {
+        run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--width", "8", "--height", "8"]));
+    }
+    let status = kakukuma()
+        .args(["merge", base.to_str().unwrap(), base.to_str().unwrap(), "--output", out.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert_eq!(status.status.code(), Some(1));
+
+    cleanup(&base);
+    cleanup(&out);
+}
+
+#[test]
+fn merge_force_reports_removed_sidecars() {
+    let base = temp_file("merge_force_sidecars");
+    let out = temp_file("merge_force_sidecars_out");
+    for f in [&base, &out] {
+        run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--width", "8", "--height", "8"]));
+    }
+    let bak = format!("{}.bak", out.display());
+    std::fs::write(&bak, "stale").unwrap();
+
+    let json = stdout_json(&run_ok(kakukuma().args([
+        "merge",
+        base.to_str().unwrap(),
+        base.to_str().unwrap(),
+        "--output",
+        out.to_str().unwrap(),
+        "--force",
+    ])));
+    assert_eq!(json["removed_sidecars"], serde_json::json!([bak]));
+    assert!(!std::path::Path::new(&bak).exists());
+
+    cleanup(&base);
+    cleanup(&out);
+}
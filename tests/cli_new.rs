@@ -53,6 +53,34 @@ fn new_force_overwrites() {
     cleanup(&f);
 }
 
+#[test]
+fn new_force_removes_stale_sidecars() {
+    let f = temp_file("new_force_sidecars");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap()]));
+    let autosave = format!("{}.autosave", f.display());
+    let bak = format!("{}.bak", f.display());
+    let unrelated = format!("{}.notes", f.display());
+    for p in [&autosave, &bak, &unrelated] {
+        std::fs::write(p, "stale").unwrap();
+    }
+
+    let out = run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--force"]));
+    let json = stdout_json(&out);
+    let removed: Vec<&str> = json["removed_sidecars"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.as_str().unwrap())
+        .collect();
+    assert_eq!(removed, vec![autosave.as_str(), bak.as_str()]);
+    assert!(!std::path::Path::new(&autosave).exists());
+    assert!(!std::path::Path::new(&bak).exists());
+    assert!(std::path::Path::new(&unrelated).exists());
+
+    let _ = std::fs::remove_file(&unrelated);
+    cleanup(&f);
+}
+
 #[test]
 fn new_creates_log_file() {
     let f = temp_file("new_log");
@@ -61,3 +89,30 @@ fn new_creates_log_file() {
     assert!(log.exists());
     cleanup(&f);
 }
+
+#[test]
+fn new_refuses_orphaned_sidecars_without_force() {
+    let f = temp_file("new_orphans");
+    let log = f.with_extension("kaku.log");
+    let autosave = format!("{}.autosave", f.display());
+    std::fs::write(&log, "{\"pointer\":3,\"total\":3}\n").unwrap();
+    std::fs::write(&autosave, "stale").unwrap();
+
+    let out = kakukuma().args(["new", f.to_str().unwrap()]).output().unwrap();
+    assert!(!out.status.success());
+    assert!(!f.exists());
+    let err: serde_json::Value = serde_json::from_slice(&out.stderr).unwrap();
+    assert!(err["error"].as_str().unwrap().contains("--force"));
+    let orphaned = err["orphaned_sidecars"].as_array().unwrap();
+    assert_eq!(orphaned.len(), 2);
+
+    let out = run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--force"]));
+    let json = stdout_json(&out);
+    assert_eq!(json["orphaned_sidecars"].as_array().unwrap().len(), 2);
+    assert!(!std::path::Path::new(&autosave).exists());
+    // The stale log is replaced by a fresh, empty one
+    let header = std::fs::read_to_string(&log).unwrap();
+    assert!(header.contains("\"total\":0"), "log: {}", header);
+
+    cleanup(&f);
+}
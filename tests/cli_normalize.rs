@@ -0,0 +1,42 @@
+mod helpers;
+
+use helpers::*;
+
+#[test]
+fn normalize_is_idempotent() {
+    let f = temp_file("normalize_idem");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--width", "16", "--height", "16"]));
+    run_ok(kakukuma().args([
+        "draw", "pencil", f.to_str().unwrap(), "1,1", "--color", "#FF0000", "--char", "lower-half",
+    ]));
+    run_ok(kakukuma().args([
+        "draw", "pencil", f.to_str().unwrap(), "2,2", "--fg", "#00FF00", "--bg", "#00FF00", "--char", "shade-light",
+    ]));
+
+    let out = run_ok(kakukuma().args(["normalize", f.to_str().unwrap()]));
+    let json = stdout_json(&out);
+    assert_eq!(json["cells_modified"], 2);
+
+    let out = run_ok(kakukuma().args(["normalize", f.to_str().unwrap()]));
+    let json = stdout_json(&out);
+    assert_eq!(json["cells_modified"], 0);
+
+    cleanup(&f);
+}
+
+#[test]
+fn normalize_is_undoable() {
+    let f = temp_file("normalize_undo");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--width", "16", "--height", "16"]));
+    run_ok(kakukuma().args([
+        "draw", "pencil", f.to_str().unwrap(), "3,3", "--color", "#FF0000", "--char", "lower-half",
+    ]));
+    run_ok(kakukuma().args(["normalize", f.to_str().unwrap()]));
+    run_ok(kakukuma().args(["undo", f.to_str().unwrap()]));
+
+    let out = run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), "3,3"]));
+    let cell = stdout_json(&out);
+    assert_eq!(cell["char"], "\u{2584}");
+
+    cleanup(&f);
+}
@@ -0,0 +1,165 @@
+mod helpers;
+
+use helpers::*;
+
+fn palette_dir(prefix: &str) -> std::path::PathBuf {
+    let dir = temp_file(prefix).with_extension("d");
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn palette_diff_reports_three_sets() {
+    let dir = palette_dir("palette_diff");
+    for (name, color) in [("a", "#FF0000"), ("a", "#000000"), ("b", "#000000"), ("b", "#0000FF")] {
+        run_ok(kakukuma().current_dir(&dir).args(["palette", "add", name, color]));
+    }
+
+    let out = run_ok(kakukuma().current_dir(&dir).args(["palette", "diff", "a", "b"]));
+    let json = stdout_json(&out);
+    assert_eq!(json["only_a"], serde_json::json!(["#FF0000"]));
+    assert_eq!(json["only_b"], serde_json::json!(["#0000FF"]));
+    assert_eq!(json["both"], serde_json::json!(["#000000"]));
+
+    // `default` resolves to the built-in palette
+    let out = run_ok(kakukuma().current_dir(&dir).args(["palette", "diff", "default", "default"]));
+    let json = stdout_json(&out);
+    assert_eq!(json["both"].as_array().unwrap().len(), 24);
+    assert!(json["only_a"].as_array().unwrap().is_empty());
+
+    let missing = kakukuma().current_dir(&dir).args(["palette", "diff", "a", "nope"]).output().unwrap();
+    assert!(!missing.status.success());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn palette_merge_dedupes_in_order() {
+    let dir = palette_dir("palette_merge");
+    for (name, color) in [("a", "#FF0000"), ("a", "#000000"), ("b", "#000000"), ("b", "#0000FF")] {
+        run_ok(kakukuma().current_dir(&dir).args(["palette", "add", name, color]));
+    }
+
+    let out = run_ok(kakukuma().current_dir(&dir).args(["palette", "merge", "both", "b", "a"]));
+    let json = stdout_json(&out);
+    assert_eq!(json["total_colors"], 3);
+    assert_eq!(json["duplicates_removed"], 1);
+
+    let shown = stdout_json(&run_ok(kakukuma().current_dir(&dir).args(["palette", "show", "both"])));
+    let hexes: Vec<_> = shown["colors"].as_array().unwrap().iter().map(|c| c["hex"].clone()).collect();
+    assert_eq!(hexes, vec!["#000000", "#0000FF", "#FF0000"]);
+
+    let missing = kakukuma().current_dir(&dir).args(["palette", "merge", "out", "a", "nope"]).output().unwrap();
+    assert!(!missing.status.success());
+    assert!(String::from_utf8_lossy(&missing.stderr).contains("'nope' not found"));
+    assert!(!dir.join("out.palette").exists());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn palette_create_append_merges_new_colors() {
+    let dir = palette_dir("palette_append");
+    let f = temp_file("palette_append");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--width", "8", "--height", "8"]));
+    for (coord, color) in [("0,0", "#FF0000"), ("1,0", "#00FF00")] {
+        run_ok(kakukuma().args(["draw", "pencil", f.to_str().unwrap(), coord, "--color", color]));
+    }
+    run_ok(kakukuma().current_dir(&dir).args(["palette", "add", "art", "#FF0000"]));
+    run_ok(kakukuma().current_dir(&dir).args(["palette", "add", "art", "#0000FF"]));
+
+    let out = run_ok(kakukuma().current_dir(&dir).args(["palette", "create", "art", f.to_str().unwrap(), "--append"]));
+    let json = stdout_json(&out);
+    // Blank cells count too: their white fg
+    assert_eq!(json["colors_extracted"], 3);
+    assert_eq!(json["pre_existing"], 2);
+    assert_eq!(json["colors_added"], 2);
+    assert_eq!(json["total_colors"], 4);
+    let shown = stdout_json(&run_ok(kakukuma().current_dir(&dir).args(["palette", "show", "art"])));
+    let hexes: Vec<_> = shown["colors"].as_array().unwrap().iter().map(|c| c["hex"].clone()).collect();
+    assert_eq!(hexes, vec!["#FF0000", "#0000FF", "#00FF00", "#FFFFFF"]);
+
+    // Without --append the palette is replaced
+    let out = run_ok(kakukuma().current_dir(&dir).args(["palette", "create", "art", f.to_str().unwrap()]));
+    assert!(stdout_json(&out).get("total_colors").is_none());
+    let shown = stdout_json(&run_ok(kakukuma().current_dir(&dir).args(["palette", "show", "art"])));
+    assert_eq!(shown["colors"].as_array().unwrap().len(), 3);
+
+    cleanup(&f);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn palette_swatches_writes_wrapped_png() {
+    let dir = palette_dir("palette_swatches");
+    let out = run_ok(kakukuma().current_dir(&dir).args([
+        "palette", "swatches", "default", "strip.png", "--columns", "10",
+    ]));
+    let json = stdout_json(&out);
+    assert_eq!(json["colors"], 24);
+    // 24 colors at 10 per row wrap onto 3 rows
+    assert_eq!(json["width"], 10 * 64);
+    assert_eq!(json["height"], 3 * 40);
+    assert!(dir.join("strip.png").exists());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn palette_export_formats() {
+    let dir = palette_dir("palette_export");
+    run_ok(kakukuma().current_dir(&dir).args(["palette", "add", "mine", "#FF8000"]));
+    run_ok(kakukuma().current_dir(&dir).args(["palette", "add", "mine", "#102030"]));
+
+    let out = run_ok(kakukuma().current_dir(&dir).args([
+        "palette", "export", "mine", "mine.hex", "--format", "hex",
+    ]));
+    assert_eq!(stdout_json(&out)["colors"], 2);
+    let hex = std::fs::read_to_string(dir.join("mine.hex")).unwrap();
+    assert_eq!(hex, "#FF8000\n#102030\n");
+
+    run_ok(kakukuma().current_dir(&dir).args([
+        "palette", "export", "default", "default.json", "--format", "json",
+    ]));
+    let text = std::fs::read_to_string(dir.join("default.json")).unwrap();
+    let list: Vec<String> = serde_json::from_str(&text).unwrap();
+    assert_eq!(list.len(), 24);
+
+    run_ok(kakukuma().current_dir(&dir).args([
+        "palette", "export", "mine", "--output", "mine.gpl", "--format", "gpl",
+    ]));
+    let gpl = std::fs::read_to_string(dir.join("mine.gpl")).unwrap();
+    let rows: Vec<Vec<u8>> = gpl
+        .lines()
+        .skip_while(|l| *l != "#")
+        .skip(1)
+        .map(|l| l.split_whitespace().take(3).map(|v| v.parse().unwrap()).collect())
+        .collect();
+    assert!(gpl.starts_with("GIMP Palette\nName: mine\n"));
+    assert_eq!(rows, vec![vec![255, 128, 0], vec![16, 32, 48]]);
+
+    run_ok(kakukuma().current_dir(&dir).args([
+        "palette", "export", "mine", "--output", "mine.ase", "--format", "ase",
+    ]));
+    let ase = std::fs::read(dir.join("mine.ase")).unwrap();
+    assert_eq!(&ase[0..4], b"ASEF");
+    assert_eq!(u32::from_be_bytes(ase[8..12].try_into().unwrap()), 2);
+
+    // Default format still copies the .palette file
+    run_ok(kakukuma().current_dir(&dir).args(["palette", "export", "mine", "copy.palette"]));
+    assert_eq!(
+        std::fs::read_to_string(dir.join("copy.palette")).unwrap(),
+        std::fs::read_to_string(dir.join("mine.palette")).unwrap()
+    );
+
+    let bad = kakukuma()
+        .current_dir(&dir)
+        .args(["palette", "export", "mine", "x", "--format", "tmtheme"])
+        .output()
+        .unwrap();
+    assert!(!bad.status.success());
+    let no_output = kakukuma().current_dir(&dir).args(["palette", "export", "mine"]).output().unwrap();
+    assert_eq!(no_output.status.code(), Some(1));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
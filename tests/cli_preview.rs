@@ -45,6 +45,34 @@ fn preview_region_filtering() {
     cleanup(&f);
 }
 
+#[test]
+fn preview_json_non_empty_only_is_flat() {
+    let f = create_canvas_with_art("preview_json_flat");
+    let out = run_ok(kakukuma().args([
+        "preview", f.to_str().unwrap(), "--format", "json", "--non-empty-only",
+    ]));
+    let json = stdout_json(&out);
+    let cells = json["cells"].as_array().unwrap();
+    assert_eq!(cells.len(), 1);
+    assert_eq!(cells[0]["x"], 5);
+    assert_eq!(cells[0]["y"], 5);
+    assert_eq!(cells[0]["fg"], "#FF0000");
+    assert_eq!(json["non_empty_count"], 1);
+    cleanup(&f);
+}
+
+#[test]
+fn preview_json_compact_single_line() {
+    let f = create_canvas_with_art("preview_json_compact");
+    let out = run_ok(kakukuma().args([
+        "preview", f.to_str().unwrap(), "--format", "json", "--compact",
+    ]));
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert_eq!(stdout.trim_end().lines().count(), 1);
+    assert_eq!(stdout_json(&out)["non_empty_count"], 1);
+    cleanup(&f);
+}
+
 #[test]
 fn preview_plain_non_empty() {
     let f = create_canvas_with_art("preview_plain");
@@ -55,3 +83,196 @@ fn preview_plain_non_empty() {
     assert!(!stdout.contains("\x1b["));
     cleanup(&f);
 }
+
+#[test]
+fn preview_deterministic_ignores_environment() {
+    let f = create_canvas_with_art("preview_deterministic");
+    let run = |colorterm: Option<&str>, term: &str| {
+        let mut cmd = kakukuma();
+        cmd.args(["preview", f.to_str().unwrap(), "--deterministic"])
+            .env("TERM", term)
+            .env("NO_COLOR", "1");
+        match colorterm {
+            Some(v) => cmd.env("COLORTERM", v),
+            None => cmd.env_remove("COLORTERM"),
+        };
+        run_ok(&mut cmd).stdout
+    };
+
+    let truecolor = run(Some("truecolor"), "xterm-256color");
+    let basic = run(None, "linux");
+    assert_eq!(truecolor, basic);
+    // Auto pins to plain 256-color
+    let pinned = run_ok(kakukuma().args(["preview", f.to_str().unwrap(), "--color-format", "256"]));
+    assert_eq!(truecolor, pinned.stdout);
+    assert!(String::from_utf8_lossy(&truecolor).contains("\x1b[38;5;"));
+
+    cleanup(&f);
+}
+
+#[test]
+fn preview_single_cell_and_column_regions_are_exact() {
+    let f = create_canvas_with_art("preview_region_exact");
+    run_ok(kakukuma().args([
+        "draw", "line", f.to_str().unwrap(), "7,2", "7,9", "--color", "#00FF00",
+    ]));
+
+    let out = run_ok(kakukuma().args([
+        "preview", f.to_str().unwrap(), "--format", "plain", "--region", "5,5,5,5",
+    ]));
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "█");
+
+    let out = run_ok(kakukuma().args([
+        "preview", f.to_str().unwrap(), "--format", "ansi", "--region", "5,5,5,5",
+    ]));
+    let ansi = String::from_utf8_lossy(&out.stdout);
+    assert_eq!(ansi.matches('█').count(), 1);
+    assert!(!ansi.contains(' '));
+
+    // Reversed corners, one column wide
+    let out = run_ok(kakukuma().args([
+        "preview", f.to_str().unwrap(), "--format", "plain", "--region", "7,9,7,2",
+    ]));
+    let plain = String::from_utf8_lossy(&out.stdout);
+    assert_eq!(plain.lines().count(), 8);
+    assert!(plain.lines().all(|line| line.chars().count() == 1));
+
+    let out = run_ok(kakukuma().args([
+        "preview", f.to_str().unwrap(), "--format", "json", "--region", "7,2,7,9",
+    ]));
+    let json = stdout_json(&out);
+    let rows = json["cells"].as_array().unwrap();
+    assert_eq!(rows.len(), 8);
+    assert!(rows.iter().all(|row| row.as_array().unwrap().len() == 1));
+
+    cleanup(&f);
+}
+
+#[test]
+fn preview_applies_stored_transforms_without_changing_canvas() {
+    let f = create_canvas_with_art("preview_transform");
+    let out = run_ok(kakukuma().args([
+        "transform", "export", f.to_str().unwrap(), "--add", "invert", "--add", "posterize:2",
+    ]));
+    assert_eq!(stdout_json(&out)["transforms"], serde_json::json!(["invert", "posterize:2"]));
+
+    let out = run_ok(kakukuma().args([
+        "preview", f.to_str().unwrap(), "--format", "json", "--non-empty-only",
+    ]));
+    assert_eq!(stdout_json(&out)["cells"][0]["fg"], "#00FFFF");
+    let out = run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), "5,5"]));
+    assert_eq!(stdout_json(&out)["fg"], "#FF0000");
+
+    let out = run_ok(kakukuma().args(["transform", "export", f.to_str().unwrap(), "--clear"]));
+    assert_eq!(stdout_json(&out)["transforms"], serde_json::json!([]));
+    let out = run_ok(kakukuma().args([
+        "preview", f.to_str().unwrap(), "--format", "json", "--non-empty-only",
+    ]));
+    assert_eq!(stdout_json(&out)["cells"][0]["fg"], "#FF0000");
+
+    let bad = kakukuma().args(["transform", "export", f.to_str().unwrap(), "--add", "blur:2"]).output().unwrap();
+    assert!(!bad.status.success());
+
+    cleanup(&f);
+}
+
+#[test]
+fn preview_and_export_svg() {
+    let f = create_canvas_with_art("preview_svg");
+    let out = run_ok(kakukuma().args(["preview", f.to_str().unwrap(), "--format", "svg"]));
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.starts_with("<svg"));
+    assert_eq!(stdout.matches("<rect").count(), 1);
+    assert!(stdout.contains("fill=\"#FF0000\""));
+
+    // Export picks SVG from the extension
+    let svg_path = f.with_extension("svg");
+    let out = run_ok(kakukuma().args(["export", f.to_str().unwrap(), svg_path.to_str().unwrap()]));
+    assert_eq!(stdout_json(&out)["format"], "svg");
+    let written = std::fs::read_to_string(&svg_path).unwrap();
+    assert_eq!(written, stdout);
+
+    let _ = std::fs::remove_file(&svg_path);
+    cleanup(&f);
+}
+
+#[test]
+fn export_html_by_extension() {
+    let f = create_canvas_with_art("export_html");
+    let html_path = f.with_extension("html");
+    let out = run_ok(kakukuma().args(["export", f.to_str().unwrap(), html_path.to_str().unwrap()]));
+    assert_eq!(stdout_json(&out)["format"], "html");
+    let written = std::fs::read_to_string(&html_path).unwrap();
+    assert!(written.starts_with("<pre"));
+    assert_eq!(written.matches("<span style=\"color:#FF0000\">").count(), 1);
+
+    let _ = std::fs::remove_file(&html_path);
+    cleanup(&f);
+}
+
+#[test]
+fn preview_graphics_sixel_and_kitty() {
+    let f = create_canvas_with_art("preview_graphics");
+    let out = run_ok(kakukuma().args(["preview", f.to_str().unwrap(), "--graphics", "sixel"]));
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    // One cropped 8x16 cell, painted red
+    assert!(stdout.starts_with("\x1bP0;1;0q\"1;1;8;16"));
+    assert!(stdout.contains("#0;2;100;0;0"));
+    assert!(stdout.trim_end().ends_with("\x1b\\"));
+
+    let out = run_ok(kakukuma().args([
+        "preview", f.to_str().unwrap(), "--graphics", "kitty", "--cell-size", "4x8",
+    ]));
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.starts_with("\x1b_Ga=T,f=100,m=0;"));
+    cleanup(&f);
+}
+
+#[test]
+fn preview_braille() {
+    let f = create_canvas_with_art("preview_braille");
+    let out = run_ok(kakukuma().args(["preview", f.to_str().unwrap(), "--format", "braille"]));
+    // A single drawn cell crops to one glyph with only dot 1 raised
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "\u{2801}");
+    cleanup(&f);
+}
+
+#[test]
+fn export_plain_with_ascii_ramp() {
+    let f = temp_file("export_ramp");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--width", "8", "--height", "8"]));
+    run_ok(kakukuma().args(["draw", "pencil", f.to_str().unwrap(), "0,0", "--color", "#FFFFFF"]));
+    run_ok(kakukuma().args(["draw", "pencil", f.to_str().unwrap(), "1,0", "--color", "#000000"]));
+    let txt = f.with_extension("txt");
+
+    let out = run_ok(kakukuma().args([
+        "export", f.to_str().unwrap(), txt.to_str().unwrap(), "--ramp=.o@",
+    ]));
+    assert_eq!(stdout_json(&out)["ramp"], ".o@");
+    assert_eq!(std::fs::read_to_string(&txt).unwrap(), "@.");
+
+    run_ok(kakukuma().args([
+        "export", f.to_str().unwrap(), txt.to_str().unwrap(), "--ramp=.o@", "--invert",
+    ]));
+    assert_eq!(std::fs::read_to_string(&txt).unwrap(), ".@");
+
+    // Bare --ramp uses the default ramp; black maps to its space, trimmed at line end
+    run_ok(kakukuma().args(["export", f.to_str().unwrap(), txt.to_str().unwrap(), "--ramp"]));
+    assert_eq!(std::fs::read_to_string(&txt).unwrap(), "@");
+
+    let _ = std::fs::remove_file(&txt);
+    cleanup(&f);
+}
+
+#[test]
+fn export_ramp_rejects_non_plain_formats() {
+    let f = create_canvas_with_art("export_ramp_ansi");
+    let ans = f.with_extension("ans");
+    let out = kakukuma()
+        .args(["export", f.to_str().unwrap(), ans.to_str().unwrap(), "--ramp"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("--ramp only applies"));
+    cleanup(&f);
+}
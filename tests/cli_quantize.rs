@@ -0,0 +1,54 @@
+mod helpers;
+
+use helpers::*;
+
+fn fg_at(file: &str, coord: &str) -> String {
+    let json = stdout_json(&run_ok(kakukuma().args(["inspect", file, coord])));
+    json["fg"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn quantize_bakes_colors_and_is_undoable() {
+    let f = temp_file("quantize");
+    let file = f.to_str().unwrap();
+    run_ok(kakukuma().args(["new", file]));
+    run_ok(kakukuma().args(["draw", "pencil", file, "1,1", "--color", "#123456"]));
+    run_ok(kakukuma().args(["draw", "pencil", file, "2,1", "--color", "#FF0000"]));
+
+    let json = stdout_json(&run_ok(kakukuma().args(["quantize", file, "--depth", "truecolor"])));
+    assert_eq!(json["cells_modified"], 0);
+    assert_eq!(fg_at(file, "1,1"), "#123456");
+
+    let json = stdout_json(&run_ok(kakukuma().args(["quantize", file, "--depth", "256"])));
+    assert_eq!(json["depth"], "256");
+    // #FF0000 is already xterm-256 color 196
+    assert_eq!(json["cells_modified"], 1);
+    let snapped = fg_at(file, "1,1");
+    assert_ne!(snapped, "#123456");
+
+    // Already-quantized colors stay put
+    let json = stdout_json(&run_ok(kakukuma().args(["quantize", file, "--depth", "256"])));
+    assert_eq!(json["cells_modified"], 0);
+
+    run_ok(kakukuma().args(["undo", file]));
+    assert_eq!(fg_at(file, "1,1"), "#123456");
+
+    cleanup(&f);
+}
+
+#[test]
+fn quantize_to_16_colors() {
+    let f = temp_file("quantize_16");
+    let file = f.to_str().unwrap();
+    run_ok(kakukuma().args(["new", file]));
+    run_ok(kakukuma().args(["draw", "pencil", file, "0,0", "--color", "#F01010"]));
+
+    let json = stdout_json(&run_ok(kakukuma().args(["quantize", file, "--depth", "16"])));
+    assert_eq!(json["cells_modified"], 1);
+    let fg = fg_at(file, "0,0");
+    let color = stdout_json(&run_ok(kakukuma().args(["color", &fg])));
+    // Snapped colors are their own nearest ANSI-16 entry
+    assert_eq!(color["ansi16"]["hex"], fg);
+
+    cleanup(&f);
+}
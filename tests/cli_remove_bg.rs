@@ -0,0 +1,31 @@
+mod helpers;
+
+use helpers::*;
+
+#[test]
+fn remove_bg_clears_border_connected_background_only() {
+    let f = temp_file("remove_bg");
+    let file = f.to_str().unwrap();
+    run_ok(kakukuma().args(["new", file, "--width", "16", "--height", "16"]));
+    run_ok(kakukuma().args(["draw", "fill", file, "0,0", "--color", "#0000FF"]));
+    // Red outline enclosing a 4x4 pocket of the same blue
+    run_ok(kakukuma().args(["draw", "rect", file, "4,4", "9,9", "--color", "#FF0000"]));
+
+    let out = run_ok(kakukuma().args(["remove-bg", file]));
+    let json = stdout_json(&out);
+    assert_eq!(json["cells_cleared"], 256 - 36);
+
+    let outside = stdout_json(&run_ok(kakukuma().args(["inspect", file, "0,0"])));
+    assert_eq!(outside["empty"], true);
+    let pocket = stdout_json(&run_ok(kakukuma().args(["inspect", file, "6,6"])));
+    assert_eq!(pocket["fg"], "#0000FF");
+    let outline = stdout_json(&run_ok(kakukuma().args(["inspect", file, "4,4"])));
+    assert_eq!(outline["fg"], "#FF0000");
+
+    // One log entry, so a single undo restores the background
+    run_ok(kakukuma().args(["undo", file]));
+    let restored = stdout_json(&run_ok(kakukuma().args(["inspect", file, "0,0"])));
+    assert_eq!(restored["fg"], "#0000FF");
+
+    cleanup(&f);
+}
@@ -0,0 +1,52 @@
+mod helpers;
+
+use helpers::*;
+
+fn inspect(f: &std::path::Path, coord: &str) -> serde_json::Value {
+    stdout_json(&run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), coord])))
+}
+
+#[test]
+fn replace_red_with_blue() {
+    let f = temp_file("replace");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--width", "8", "--height", "8"]));
+    for coord in ["1,1", "2,2", "3,3"] {
+        run_ok(kakukuma().args(["draw", "pencil", f.to_str().unwrap(), coord, "--color", "#FF0000"]));
+    }
+    run_ok(kakukuma().args(["draw", "pencil", f.to_str().unwrap(), "5,5", "--color", "#00FF00"]));
+
+    let json = stdout_json(&run_ok(kakukuma().args([
+        "replace", f.to_str().unwrap(), "--from", "#FF0000", "--to", "#0000FF",
+    ])));
+    assert_eq!(json["cells_modified"], 3);
+
+    for coord in ["1,1", "2,2", "3,3"] {
+        assert_eq!(inspect(&f, coord)["fg"], "#0000FF");
+    }
+    assert_eq!(inspect(&f, "5,5")["fg"], "#00FF00");
+
+    run_ok(kakukuma().args(["undo", f.to_str().unwrap()]));
+    assert_eq!(inspect(&f, "2,2")["fg"], "#FF0000");
+
+    cleanup(&f);
+}
+
+#[test]
+fn replace_tolerance_catches_near_colors() {
+    let f = temp_file("replace_tol");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--width", "8", "--height", "8"]));
+    run_ok(kakukuma().args(["draw", "pencil", f.to_str().unwrap(), "1,1", "--color", "#FA0500"]));
+
+    let exact = stdout_json(&run_ok(kakukuma().args([
+        "replace", f.to_str().unwrap(), "--from", "#FF0000", "--to", "#0000FF",
+    ])));
+    assert_eq!(exact["cells_modified"], 0);
+
+    let loose = stdout_json(&run_ok(kakukuma().args([
+        "replace", f.to_str().unwrap(), "--from", "#FF0000", "--to", "#0000FF", "--tolerance", "8",
+    ])));
+    assert_eq!(loose["cells_modified"], 1);
+    assert_eq!(inspect(&f, "1,1")["fg"], "#0000FF");
+
+    cleanup(&f);
+}
@@ -0,0 +1,45 @@
+mod helpers;
+
+use helpers::*;
+
+#[test]
+fn resize_shrink_crops_and_undo_restores() {
+    let f = temp_file("resize");
+    let file = f.to_str().unwrap();
+    run_ok(kakukuma().args(["new", file, "--size", "16x16"]));
+    run_ok(kakukuma().args(["draw", "pencil", file, "14,14", "--fg", "#FF0000"]));
+
+    let json = stdout_json(&run_ok(kakukuma().args(["resize", file, "--size", "10x12"])));
+    assert_eq!(json["resized"], true);
+    assert_eq!(json["old"], "16x16");
+    assert_eq!(json["new"], "10x12");
+    assert_eq!(json["cropped"], true);
+
+    run_ok(kakukuma().args(["undo", file]));
+    let info = stdout_json(&run_ok(kakukuma().args(["stats", file])));
+    assert_eq!(info["fill"]["filled"], 1);
+    let cell = stdout_json(&run_ok(kakukuma().args(["inspect", file, "14,14"])));
+    assert_eq!(cell["fg"], "#FF0000");
+
+    run_ok(kakukuma().args(["redo", file]));
+    let json = stdout_json(&run_ok(kakukuma().args(["resize", file, "--width", "20"])));
+    assert_eq!(json["old"], "10x12");
+    assert_eq!(json["new"], "20x12");
+    assert_eq!(json["cropped"], false);
+
+    cleanup(&f);
+}
+
+#[test]
+fn resize_to_same_size_is_an_error() {
+    let f = temp_file("resize_same");
+    let file = f.to_str().unwrap();
+    run_ok(kakukuma().args(["new", file, "--size", "16x16"]));
+    let out = kakukuma().args(["resize", file, "--size", "16x16"]).output().unwrap();
+    assert_eq!(out.status.code(), Some(1));
+    // Clamps to 8x8 first, so asking for less than the minimum is the same size
+    run_ok(kakukuma().args(["resize", file, "--size", "8x8"]));
+    let out = kakukuma().args(["resize", file, "--size", "2x2"]).output().unwrap();
+    assert_eq!(out.status.code(), Some(1));
+    cleanup(&f);
+}
@@ -0,0 +1,66 @@
+mod helpers;
+
+use helpers::*;
+
+#[test]
+fn rotate_swaps_size_and_round_trips() {
+    let f = temp_file("rotate");
+    let file = f.to_str().unwrap();
+    run_ok(kakukuma().args(["new", file, "--size", "16x8"]));
+    run_ok(kakukuma().args(["draw", "pencil", file, "15,0", "--fg", "#FF0000"]));
+
+    let json = stdout_json(&run_ok(kakukuma().args(["transform", "rotate", file, "--cw"])));
+    assert_eq!(json["new_width"], 8);
+    assert_eq!(json["new_height"], 16);
+    let cell = stdout_json(&run_ok(kakukuma().args(["inspect", file, "7,15"])));
+    assert_eq!(cell["fg"], "#FF0000");
+
+    for _ in 0..3 {
+        run_ok(kakukuma().args(["transform", "rotate", file, "--cw"]));
+    }
+    run_ok(kakukuma().args(["transform", "rotate", file, "--ccw"]));
+    run_ok(kakukuma().args(["transform", "rotate", file, "--cw"]));
+    let cell = stdout_json(&run_ok(kakukuma().args(["inspect", file, "15,0"])));
+    assert_eq!(cell["fg"], "#FF0000");
+    let stats = stdout_json(&run_ok(kakukuma().args(["stats", file])));
+    assert_eq!(stats["fill"]["filled"], 1);
+
+    cleanup(&f);
+}
+
+#[test]
+fn rotate_is_undoable() {
+    let f = temp_file("rotate_undo");
+    let file = f.to_str().unwrap();
+    run_ok(kakukuma().args(["new", file, "--size", "16x8"]));
+    run_ok(kakukuma().args(["draw", "pencil", file, "15,0", "--fg", "#FF0000"]));
+    run_ok(kakukuma().args(["draw", "pencil", file, "2,5", "--fg", "#00FF00"]));
+    run_ok(kakukuma().args(["transform", "rotate", file, "--cw"]));
+
+    run_ok(kakukuma().args(["undo", file]));
+    let cell = stdout_json(&run_ok(kakukuma().args(["inspect", file, "15,0"])));
+    assert_eq!(cell["fg"], "#FF0000");
+    let cell = stdout_json(&run_ok(kakukuma().args(["inspect", file, "2,5"])));
+    assert_eq!(cell["fg"], "#00FF00");
+    let stats = stdout_json(&run_ok(kakukuma().args(["stats", file])));
+    assert_eq!(stats["fill"]["filled"], 2);
+
+    // Redo brings back cells that land outside the original 16x8 area
+    run_ok(kakukuma().args(["redo", file]));
+    let cell = stdout_json(&run_ok(kakukuma().args(["inspect", file, "7,15"])));
+    assert_eq!(cell["fg"], "#FF0000");
+    let cell = stdout_json(&run_ok(kakukuma().args(["inspect", file, "2,2"])));
+    assert_eq!(cell["fg"], "#00FF00");
+
+    cleanup(&f);
+}
+
+#[test]
+fn rotate_needs_exactly_one_direction() {
+    let f = temp_file("rotate_dir");
+    let file = f.to_str().unwrap();
+    run_ok(kakukuma().args(["new", file]));
+    assert!(!kakukuma().args(["transform", "rotate", file]).output().unwrap().status.success());
+    assert!(!kakukuma().args(["transform", "rotate", file, "--cw", "--ccw"]).output().unwrap().status.success());
+    cleanup(&f);
+}
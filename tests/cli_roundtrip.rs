@@ -66,3 +66,141 @@ fn roundtrip_draw_export_verify() {
     let _ = std::fs::remove_file(&export_path);
     cleanup(&f);
 }
+
+#[test]
+fn export_reset_each_cell_is_opt_in() {
+    let f = temp_file("roundtrip_reset_each");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap()]));
+    run_ok(kakukuma().args([
+        "draw", "rect", f.to_str().unwrap(), "0,0", "3,3", "--color", "#FF0000", "--filled",
+    ]));
+
+    let plain_path = f.with_extension("plain.ans");
+    let robust_path = f.with_extension("robust.ans");
+    let plain = stdout_json(&run_ok(kakukuma().args([
+        "export", f.to_str().unwrap(), plain_path.to_str().unwrap(),
+    ])));
+    assert!(plain.get("reset_each_cell").is_none());
+    let robust = stdout_json(&run_ok(kakukuma().args([
+        "export", f.to_str().unwrap(), robust_path.to_str().unwrap(), "--reset-each-cell",
+    ])));
+    assert_eq!(robust["reset_each_cell"], true);
+
+    let plain_content = std::fs::read_to_string(&plain_path).unwrap();
+    let robust_content = std::fs::read_to_string(&robust_path).unwrap();
+    assert!(robust_content.len() > plain_content.len());
+    // 4x4 cells, each preceded by a reset, plus one per line end
+    assert_eq!(robust_content.matches("\x1b[0m").count(), 16 + 4);
+
+    let _ = std::fs::remove_file(&plain_path);
+    let _ = std::fs::remove_file(&robust_path);
+    cleanup(&f);
+}
+
+#[test]
+fn export_no_overwrite_guards_existing_output() {
+    let f = temp_file("roundtrip_no_overwrite");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap()]));
+    let out_path = f.with_extension("guard.txt");
+    std::fs::write(&out_path, "keep me").unwrap();
+
+    let out = kakukuma()
+        .args(["export", f.to_str().unwrap(), out_path.to_str().unwrap(), "--no-overwrite"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains(out_path.to_str().unwrap()));
+    assert_eq!(std::fs::read_to_string(&out_path).unwrap(), "keep me");
+
+    // Default stays permissive
+    run_ok(kakukuma().args(["export", f.to_str().unwrap(), out_path.to_str().unwrap()]));
+    assert_ne!(std::fs::read_to_string(&out_path).unwrap(), "keep me");
+
+    let _ = std::fs::remove_file(&out_path);
+    cleanup(&f);
+}
+
+#[test]
+fn export_png_background_removes_transparency() {
+    let f = temp_file("export_bg");
+    let file = f.to_str().unwrap();
+    let png = f.with_extension("png");
+    run_ok(kakukuma().args(["new", file]));
+    run_ok(kakukuma().args(["draw", "pencil", file, "2,2", "--color", "#FF0000", "--char", "upper-half"]));
+    run_ok(kakukuma().args(["draw", "pencil", file, "5,5", "--color", "#00FF00"]));
+
+    let out = run_ok(kakukuma().args(["export", file, png.to_str().unwrap(), "--background", "#102030"]));
+    assert_eq!(stdout_json(&out)["background"], "#102030");
+    let img = image::open(&png).unwrap().to_rgba8();
+    assert!(img.pixels().all(|p| p[3] == 255));
+    // Cropped to 2,2..5,5 with 8x16 cells: the empty lower half of the
+    // half block is background, the full block keeps its color
+    assert_eq!(img.get_pixel(0, 0).0, [255, 0, 0, 255]);
+    assert_eq!(img.get_pixel(0, 15).0, [16, 32, 48, 255]);
+    assert_eq!(img.get_pixel(3 * 8, 3 * 16).0, [0, 255, 0, 255]);
+
+    let txt = f.with_extension("txt");
+    let out = kakukuma()
+        .args(["export", file, txt.to_str().unwrap(), "--background", "#102030"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(!txt.exists());
+
+    let _ = std::fs::remove_file(&png);
+    cleanup(&f);
+}
+
+#[test]
+fn binary_kakb_matches_json_kaku() {
+    let json_file = temp_file("roundtrip_fmt");
+    let bin_file = temp_file("roundtrip_fmt").with_extension("kakb");
+    for f in [&json_file, &bin_file] {
+        let file = f.to_str().unwrap();
+        run_ok(kakukuma().args(["new", file, "--width", "24", "--height", "12"]));
+        run_ok(kakukuma().args(["draw", "pencil", file, "3,4", "--fg", "#123456", "--bg", "#ABCDEF", "--char", "upper-half"]));
+        run_ok(kakukuma().args(["draw", "rect", file, "10,2", "20,8", "--color", "#00FF00"]));
+    }
+
+    // Format follows the extension: JSON stays the default
+    let json_bytes = std::fs::read(&json_file).unwrap();
+    let bin_bytes = std::fs::read(&bin_file).unwrap();
+    assert!(json_bytes.starts_with(b"{"));
+    assert!(bin_bytes.starts_with(b"KAKB"));
+    assert!(bin_bytes.len() < json_bytes.len());
+
+    // Loading is transparent and both hold the same canvas
+    let preview = |f: &std::path::PathBuf| {
+        stdout_json(&run_ok(kakukuma().args(["preview", f.to_str().unwrap(), "--format", "json"])))
+    };
+    assert_eq!(preview(&json_file), preview(&bin_file));
+
+    // Undo works through the binary file as well
+    run_ok(kakukuma().args(["undo", bin_file.to_str().unwrap()]));
+    let cell = stdout_json(&run_ok(kakukuma().args(["inspect", bin_file.to_str().unwrap(), "10,2"])));
+    assert_eq!(cell["empty"], true);
+    assert!(std::fs::read(&bin_file).unwrap().starts_with(b"KAKB"));
+
+    cleanup(&json_file);
+    cleanup(&bin_file);
+    let _ = std::fs::remove_file(bin_file.with_extension("kakb.log"));
+}
+
+#[test]
+fn cli_edits_preserve_project_settings() {
+    let f = temp_file("settings");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--width", "16", "--height", "16"]));
+    let mut project: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&f).unwrap()).unwrap();
+    assert!(project.get("settings").is_none(), "new files carry no settings block");
+    let settings = serde_json::json!({ "tool": "Fill", "block": "\u{2592}", "filled_rect": true });
+    project["settings"] = settings.clone();
+    std::fs::write(&f, serde_json::to_string_pretty(&project).unwrap()).unwrap();
+
+    run_ok(kakukuma().args(["draw", "pencil", f.to_str().unwrap(), "1,1", "--color", "#FF0000"]));
+
+    let after: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&f).unwrap()).unwrap();
+    assert_eq!(after["settings"], settings);
+    cleanup(&f);
+}
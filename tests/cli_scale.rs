@@ -0,0 +1,65 @@
+mod helpers;
+
+use helpers::*;
+
+fn fg_at(file: &str, coord: &str) -> serde_json::Value {
+    stdout_json(&run_ok(kakukuma().args(["inspect", file, coord])))["fg"].clone()
+}
+
+#[test]
+fn scale_nearest_is_default_and_keeps_colors() {
+    let f = temp_file("scale");
+    let file = f.to_str().unwrap();
+    run_ok(kakukuma().args(["new", file, "--size", "8x8"]));
+    run_ok(kakukuma().args(["draw", "rect", file, "0,0", "3,7", "--color", "#FF0000", "--filled"]));
+    run_ok(kakukuma().args(["draw", "rect", file, "4,0", "7,7", "--color", "#0000FF", "--filled"]));
+
+    let json = stdout_json(&run_ok(kakukuma().args(["scale", file, "16x16"])));
+    assert_eq!(json["interp"], "nearest");
+    assert_eq!(json["new_width"], 16);
+    assert_eq!(fg_at(file, "7,9"), "#FF0000");
+    assert_eq!(fg_at(file, "8,9"), "#0000FF");
+
+    cleanup(&f);
+}
+
+#[test]
+fn scale_bilinear_snaps_blends_to_palette() {
+    let f = temp_file("scale_bilinear");
+    let file = f.to_str().unwrap();
+    run_ok(kakukuma().args(["new", file, "--size", "8x8"]));
+    run_ok(kakukuma().args(["draw", "rect", file, "0,0", "3,7", "--color", "#FF0000", "--filled"]));
+    run_ok(kakukuma().args(["draw", "rect", file, "4,0", "7,7", "--color", "#0000FF", "--filled"]));
+
+    run_ok(kakukuma().args(["scale", file, "32x8", "--interp", "bilinear"]));
+    // Snapped back to the canvas's own two colors
+    for x in 0..32 {
+        let fg = fg_at(file, &format!("{},0", x));
+        assert!(fg == "#FF0000" || fg == "#0000FF", "x={} fg={}", x, fg);
+    }
+
+    let out = kakukuma().args(["scale", file, "4x4"]).output().unwrap();
+    assert!(!out.status.success());
+
+    cleanup(&f);
+}
+
+#[test]
+fn scale_is_undoable() {
+    let f = temp_file("scale_undo");
+    let file = f.to_str().unwrap();
+    run_ok(kakukuma().args(["new", file, "--size", "8x8"]));
+    run_ok(kakukuma().args(["draw", "pencil", file, "7,7", "--fg", "#FF0000"]));
+    run_ok(kakukuma().args(["scale", file, "16x16"]));
+    assert_eq!(fg_at(file, "15,15"), "#FF0000");
+
+    run_ok(kakukuma().args(["undo", file]));
+    let stats = stdout_json(&run_ok(kakukuma().args(["stats", file])));
+    assert_eq!(stats["fill"]["filled"], 1);
+    assert_eq!(fg_at(file, "7,7"), "#FF0000");
+
+    run_ok(kakukuma().args(["redo", file]));
+    assert_eq!(fg_at(file, "15,15"), "#FF0000");
+
+    cleanup(&f);
+}
@@ -0,0 +1,56 @@
+mod helpers;
+
+use helpers::*;
+
+fn inspect(f: &std::path::Path, coord: &str) -> serde_json::Value {
+    stdout_json(&run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), coord])))
+}
+
+#[test]
+fn script_applies_lines_as_one_undo_step() {
+    let f = temp_file("script");
+    let script = f.with_extension("txt");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--width", "16", "--height", "16"]));
+    std::fs::write(&script, "# three strokes\npencil 5,5 #FF0000\nline 0,0 2,0 #00FF00\n\nfill 10,10 #0000FF\n").unwrap();
+
+    let out = run_ok(kakukuma().args([
+        "script", f.to_str().unwrap(), "--commands", script.to_str().unwrap(),
+    ]));
+    let json = stdout_json(&out);
+    assert_eq!(json["operations"], 3);
+
+    assert_eq!(inspect(&f, "5,5")["fg"], "#FF0000");
+    assert_eq!(inspect(&f, "2,0")["fg"], "#00FF00");
+    assert_eq!(inspect(&f, "10,10")["fg"], "#0000FF");
+
+    let undo = stdout_json(&run_ok(kakukuma().args(["undo", f.to_str().unwrap()])));
+    assert_eq!(undo["undone"], 1);
+    for coord in ["5,5", "2,0", "10,10"] {
+        assert_eq!(inspect(&f, coord)["empty"], true, "{} should be cleared", coord);
+    }
+
+    let _ = std::fs::remove_file(&script);
+    cleanup(&f);
+}
+
+#[test]
+fn script_error_reports_line_and_saves_nothing() {
+    let f = temp_file("script_err");
+    let script = f.with_extension("txt");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--width", "16", "--height", "16"]));
+    std::fs::write(&script, "pencil 1,1 #FF0000\npencil 2,2 #nothex\n").unwrap();
+
+    let out = kakukuma()
+        .args(["script", f.to_str().unwrap(), "--commands", script.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert_eq!(out.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains(":2:"), "error should name line 2: {}", stderr);
+
+    // The valid first line was not saved either
+    assert_eq!(inspect(&f, "1,1")["empty"], true);
+
+    let _ = std::fs::remove_file(&script);
+    cleanup(&f);
+}
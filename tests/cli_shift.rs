@@ -0,0 +1,28 @@
+mod helpers;
+
+use helpers::*;
+
+#[test]
+fn shift_moves_drawing_and_wraps() {
+    let f = temp_file("shift");
+    let file = f.to_str().unwrap();
+    run_ok(kakukuma().args(["new", file, "--size", "8x8"]));
+    run_ok(kakukuma().args(["draw", "pencil", file, "0,0", "--fg", "#FF0000"]));
+    run_ok(kakukuma().args(["draw", "pencil", file, "7,7", "--fg", "#0000FF"]));
+
+    let json = stdout_json(&run_ok(kakukuma().args(["transform", "shift", file, "--dx", "2", "--dy", "-1", "--wrap"])));
+    assert_eq!(json["cells_modified"], 4);
+    let red = stdout_json(&run_ok(kakukuma().args(["inspect", file, "2,7"])));
+    assert_eq!(red["fg"], "#FF0000");
+    let blue = stdout_json(&run_ok(kakukuma().args(["inspect", file, "1,6"])));
+    assert_eq!(blue["fg"], "#0000FF");
+
+    run_ok(kakukuma().args(["undo", file]));
+    run_ok(kakukuma().args(["transform", "shift", file, "--dx", "1"]));
+    let stats = stdout_json(&run_ok(kakukuma().args(["stats", file])));
+    assert_eq!(stats["fill"]["filled"], 1, "blue dropped off the right edge");
+    let red = stdout_json(&run_ok(kakukuma().args(["inspect", file, "1,0"])));
+    assert_eq!(red["fg"], "#FF0000");
+
+    cleanup(&f);
+}
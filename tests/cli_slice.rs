@@ -0,0 +1,125 @@
+mod helpers;
+
+use helpers::*;
+
+#[test]
+fn slice_cuts_even_grid_of_tiles() {
+    let f = temp_file("slice_even");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--width", "16", "--height", "8"]));
+    run_ok(kakukuma().args([
+        "draw", "pencil", f.to_str().unwrap(), "9,1", "--color", "#FF0000",
+    ]));
+    let prefix = f.with_extension("");
+    let prefix = prefix.to_str().unwrap();
+
+    let out = run_ok(kakukuma().args(["slice", f.to_str().unwrap(), "8x8", "--prefix", prefix]));
+    let json = stdout_json(&out);
+    assert_eq!(json["rows"], 1);
+    assert_eq!(json["cols"], 2);
+    assert_eq!(json["count"], 2);
+
+    let tile = std::path::PathBuf::from(format!("{}_0_1.kaku", prefix));
+    let out = run_ok(kakukuma().args(["inspect", tile.to_str().unwrap(), "1,1"]));
+    assert_eq!(stdout_json(&out)["fg"], "#FF0000");
+
+    // Tiles are not overwritten without --force
+    let out = kakukuma()
+        .args(["slice", f.to_str().unwrap(), "8x8", "--prefix", prefix])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    run_ok(kakukuma().args(["slice", f.to_str().unwrap(), "8x8", "--prefix", prefix, "--force"]));
+
+    for i in 0..2 {
+        let _ = std::fs::remove_file(format!("{}_0_{}.kaku", prefix, i));
+    }
+    cleanup(&f);
+}
+
+#[test]
+fn slice_uneven_needs_partial() {
+    let f = temp_file("slice_uneven");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--width", "20", "--height", "8"]));
+    let prefix = f.with_extension("");
+    let prefix = prefix.to_str().unwrap();
+
+    let out = kakukuma()
+        .args(["slice", f.to_str().unwrap(), "8x8", "--prefix", prefix])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("--partial"));
+
+    let out = run_ok(kakukuma().args([
+        "slice", f.to_str().unwrap(), "8x8", "--prefix", prefix, "--partial",
+    ]));
+    let json = stdout_json(&out);
+    assert_eq!(json["count"], 3);
+    assert_eq!(json["tiles"][1]["padded"], false);
+    // The 4-wide edge is padded up to the minimum canvas width
+    assert_eq!(json["tiles"][2]["width"], 8);
+    assert_eq!(json["tiles"][2]["height"], 8);
+    assert_eq!(json["tiles"][2]["padded"], true);
+    let edge = format!("{}_0_2.kaku", prefix);
+    assert_eq!(stdout_json(&run_ok(kakukuma().args(["info", &edge])))["width"], 8);
+
+    for i in 0..3 {
+        let _ = std::fs::remove_file(format!("{}_0_{}.kaku", prefix, i));
+    }
+    cleanup(&f);
+}
+
+#[test]
+fn slice_rejects_tiles_below_minimum_size() {
+    let f = temp_file("slice_tiny");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--width", "16", "--height", "16"]));
+    let out = kakukuma().args(["slice", f.to_str().unwrap(), "4x4"]).output().unwrap();
+    assert_eq!(out.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&out.stderr).contains("at least 8x8"));
+    cleanup(&f);
+}
+
+#[test]
+fn assemble_rebuilds_sliced_canvas() {
+    let f = temp_file("assemble_src");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--width", "16", "--height", "16"]));
+    run_ok(kakukuma().args([
+        "draw", "pencil", f.to_str().unwrap(), "12,3", "--color", "#00FF00",
+    ]));
+    let prefix = f.with_extension("");
+    let prefix = prefix.to_str().unwrap();
+    run_ok(kakukuma().args(["slice", f.to_str().unwrap(), "8x8", "--prefix", prefix]));
+    let tiles: Vec<String> = ["0_0", "0_1", "1_0", "1_1"]
+        .iter()
+        .map(|rc| format!("{}_{}.kaku", prefix, rc))
+        .collect();
+
+    let out_file = temp_file("assemble_out");
+    let mut args = vec!["assemble", out_file.to_str().unwrap(), "--cols", "2"];
+    args.extend(tiles.iter().map(|t| t.as_str()));
+    let json = stdout_json(&run_ok(kakukuma().args(&args)));
+    assert_eq!(json["width"], 16);
+    assert_eq!(json["height"], 16);
+    assert_eq!(json["rows"], 2);
+
+    let out = run_ok(kakukuma().args(["diff", f.to_str().unwrap(), out_file.to_str().unwrap()]));
+    assert_eq!(stdout_json(&out)["changes"].as_array().unwrap().len(), 0);
+
+    // Mismatched tile sizes are rejected
+    let small = temp_file("assemble_small");
+    run_ok(kakukuma().args(["new", small.to_str().unwrap(), "--width", "9", "--height", "8"]));
+    let out = kakukuma()
+        .args(["assemble", out_file.to_str().unwrap(), "--cols", "2", "--force", &tiles[0]])
+        .arg(small.to_str().unwrap())
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("same size"));
+
+    for t in &tiles {
+        let _ = std::fs::remove_file(t);
+    }
+    cleanup(&small);
+    cleanup(&out_file);
+    cleanup(&f);
+}
@@ -0,0 +1,103 @@
+mod helpers;
+
+use std::io::Write;
+use std::process::{Output, Stdio};
+
+use helpers::*;
+
+/// Run kakukuma with `input` piped to stdin.
+fn run_piped(args: &[&str], input: &[u8]) -> Output {
+    let mut child = kakukuma()
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to execute");
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    let out = child.wait_with_output().unwrap();
+    assert!(
+        out.status.success(),
+        "command failed: {}\nstderr: {}",
+        out.status,
+        String::from_utf8_lossy(&out.stderr)
+    );
+    out
+}
+
+#[test]
+fn stdio_pipeline_composes() {
+    let created = run_ok(kakukuma().args(["new", "-", "--size", "10x6"]));
+    let status: serde_json::Value = serde_json::from_slice(&created.stderr).unwrap();
+    assert_eq!(status["created"], "-");
+    assert!(!std::path::Path::new("-").exists());
+
+    let drawn = run_piped(&["draw", "pencil", "-", "2,3", "--fg", "#FF0000"], &created.stdout);
+    let status: serde_json::Value = serde_json::from_slice(&drawn.stderr).unwrap();
+    assert_eq!(status["cells_modified"], 1);
+    assert!(!std::path::Path::new("-.log").exists());
+
+    // stdout carries only the project
+    let project: serde_json::Value = serde_json::from_slice(&drawn.stdout).unwrap();
+    assert_eq!(project["canvas"]["width"], 10);
+
+    let stats = stdout_json(&run_piped(&["stats", "-"], &drawn.stdout));
+    assert_eq!(stats["fill"]["filled"], 1);
+}
+
+#[test]
+fn stdio_project_can_be_saved_to_file() {
+    let f = temp_file("stdio");
+    let file = f.to_str().unwrap();
+    run_ok(kakukuma().args(["new", file, "--size", "8x8"]));
+    let data = std::fs::read(&f).unwrap();
+
+    let out = run_piped(&["draw", "rect", "-", "0,0", "3,3", "--color", "#00FF00"], &data);
+    std::fs::write(&f, &out.stdout).unwrap();
+    let stats = stdout_json(&run_ok(kakukuma().args(["stats", file])));
+    assert_eq!(stats["fill"]["filled"], 12);
+
+    cleanup(&f);
+}
+
+#[test]
+fn stdio_has_no_history() {
+    let out = kakukuma().args(["undo", "-"]).stdin(Stdio::null()).output().unwrap();
+    assert_eq!(out.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&out.stderr).contains("no history"));
+}
+
+#[test]
+fn stdio_read_only_commands_accept_stdin() {
+    let created = run_ok(kakukuma().args(["new", "-", "--size", "8x8"]));
+    let drawn = run_piped(&["draw", "pencil", "-", "1,1", "--fg", "#FF0000"], &created.stdout);
+    let project = drawn.stdout;
+
+    let preview = run_piped(&["preview", "-", "--format", "plain"], &project);
+    assert_eq!(String::from_utf8_lossy(&preview.stdout), "\u{2588}");
+
+    let inspect = stdout_json(&run_piped(&["inspect", "-", "1,1"], &project));
+    assert_eq!(inspect["fg"], "#FF0000");
+
+    let info = stdout_json(&run_piped(&["info", "-"], &project));
+    assert_eq!(info["format"], "json");
+    assert_eq!(info["size_bytes"], project.len());
+}
+
+#[test]
+fn stdio_export_writes_content_to_stdout() {
+    let created = run_ok(kakukuma().args(["new", "-", "--size", "8x8"]));
+    let drawn = run_piped(&["draw", "pencil", "-", "0,0", "--fg", "#FF0000"], &created.stdout);
+
+    // Content on stdout, status JSON on stderr
+    let out = run_piped(&["export", "-", "-", "--format", "plain"], &drawn.stdout);
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "\u{2588}");
+    let status: serde_json::Value = serde_json::from_slice(&out.stderr).unwrap();
+    assert_eq!(status["exported"], "-");
+    assert_eq!(status["format"], "plain");
+    assert!(!std::path::Path::new("-").exists());
+
+    // The deprecated --output flag takes `-` too, and PNG bytes stream as-is
+    let out = run_piped(&["export", "-", "--output", "-", "--format", "png"], &drawn.stdout);
+    assert!(out.stdout.starts_with(b"\x89PNG\r\n\x1a\n"));
+}
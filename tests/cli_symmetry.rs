@@ -78,3 +78,31 @@ fn symmetry_quad() {
 
     cleanup(&f);
 }
+
+#[test]
+fn symmetry_custom_center() {
+    let f = temp_file("sym_center");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--width", "16", "--height", "16"]));
+
+    // Mirror around column 4 instead of the canvas center
+    let out = run_ok(kakukuma().args([
+        "draw", "pencil", f.to_str().unwrap(), "2,5",
+        "--color", "#FF0000", "--symmetry", "horizontal", "--center", "4,8",
+    ]));
+    let json = stdout_json(&out);
+    assert_eq!(json["cells_modified"], 2);
+    assert_eq!(json["center"]["x"], 4);
+
+    let mirrored = stdout_json(&run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), "6,5"])));
+    assert_eq!(mirrored["fg"], "#FF0000");
+    let default_mirror = stdout_json(&run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), "13,5"])));
+    assert_eq!(default_mirror["empty"], true);
+
+    let out = kakukuma()
+        .args(["draw", "pencil", f.to_str().unwrap(), "2,5", "--symmetry", "horizontal", "--center", "16,0"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+
+    cleanup(&f);
+}
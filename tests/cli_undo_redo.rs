@@ -112,3 +112,83 @@ fn undo_on_empty_fails() {
     assert!(!out.status.success());
     cleanup(&f);
 }
+
+#[test]
+fn log_dir_keeps_sidecar_out_of_project_folder() {
+    let f = temp_file("undo_log_dir");
+    let log_dir = std::env::temp_dir().join(format!("kaku_integ_logs_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&log_dir);
+    let dir_arg = log_dir.to_str().unwrap();
+
+    run_ok(kakukuma().args(["--log-dir", dir_arg, "new", f.to_str().unwrap()]));
+    run_ok(kakukuma().args([
+        "draw", "pencil", f.to_str().unwrap(), "5,5", "--color", "#FF0000", "--log-dir", dir_arg,
+    ]));
+    assert!(!f.with_extension("kaku.log").exists(), "no sidecar next to the project");
+    assert_eq!(std::fs::read_dir(&log_dir).unwrap().count(), 1);
+
+    // The env var resolves to the same log as the flag
+    let out = run_ok(kakukuma().env("KAKUKUMA_LOG_DIR", &log_dir).args(["undo", f.to_str().unwrap()]));
+    assert_eq!(stdout_json(&out)["undone"], 1);
+    let after = stdout_json(&run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), "5,5"])));
+    assert_eq!(after["empty"], true);
+
+    cleanup(&f);
+    let _ = std::fs::remove_dir_all(&log_dir);
+}
+
+#[test]
+fn max_entries_prunes_oldest() {
+    let f = temp_file("undo_max_entries");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--width", "16", "--height", "16"]));
+    for x in 0..10 {
+        run_ok(kakukuma().args([
+            "draw", "pencil", f.to_str().unwrap(), &format!("{},0", x), "--color", "#FF0000",
+            "--max-entries", "8",
+        ]));
+    }
+
+    let info = stdout_json(&run_ok(kakukuma().args(["info", f.to_str().unwrap()])));
+    assert_eq!(info["log"]["total"], 8);
+
+    let out = run_ok(kakukuma().args(["undo", f.to_str().unwrap(), "--count", "20"]));
+    assert_eq!(stdout_json(&out)["undone"], 8);
+    // The two oldest draws fell out of the window and stay
+    let kept = stdout_json(&run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), "1,0"])));
+    assert_eq!(kept["empty"], false);
+    let undone = stdout_json(&run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), "2,0"])));
+    assert_eq!(undone["empty"], true);
+
+    let bad = kakukuma().args(["info", f.to_str().unwrap(), "--max-entries", "2"]).output().unwrap();
+    assert!(!bad.status.success());
+
+    cleanup(&f);
+}
+
+#[test]
+fn coalesced_draws_undo_in_one_step() {
+    let f = temp_file("undo_coalesce");
+    run_ok(kakukuma().args(["new", f.to_str().unwrap(), "--width", "16", "--height", "16"]));
+    for coord in ["1,1", "2,1", "3,1"] {
+        run_ok(kakukuma().args([
+            "--coalesce-secs", "60", "draw", "pencil", f.to_str().unwrap(), coord, "--color", "#FF0000",
+        ]));
+    }
+    let history = stdout_json(&run_ok(kakukuma().args(["info", f.to_str().unwrap()])));
+    assert_eq!(history["log"]["total"], 1);
+
+    let json = stdout_json(&run_ok(kakukuma().args(["undo", f.to_str().unwrap()])));
+    assert_eq!(json["undone"], 1);
+    for coord in ["1,1", "2,1", "3,1"] {
+        let cell = stdout_json(&run_ok(kakukuma().args(["inspect", f.to_str().unwrap(), coord])));
+        assert_eq!(cell["empty"], true, "{}", coord);
+    }
+
+    // Without the flag every draw stays its own entry
+    run_ok(kakukuma().args(["draw", "pencil", f.to_str().unwrap(), "1,1", "--color", "#FF0000"]));
+    run_ok(kakukuma().args(["draw", "pencil", f.to_str().unwrap(), "2,1", "--color", "#FF0000"]));
+    let history = stdout_json(&run_ok(kakukuma().args(["info", f.to_str().unwrap()])));
+    assert_eq!(history["log"]["total"], 2);
+
+    cleanup(&f);
+}